@@ -98,6 +98,8 @@ data class ClientCodegenConfig(
     /** If true, adds `endpoint_url`/`set_endpoint_url` methods to the service config */
     val includeEndpointUrlConfig: Boolean = DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG,
     val enableUserConfigurableRuntimePlugins: Boolean = DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS,
+    /** If true, generates functions that deserialize the items of output lists while the response body arrives */
+    val includeListItemStreams: Boolean = DEFAULT_INCLUDE_LIST_ITEM_STREAMS,
 ) : CoreCodegenConfig(
         formatTimeoutSeconds, debugMode, DEFAULT_FLATTEN_ACCESSORS,
     ) {
@@ -108,6 +110,7 @@ data class ClientCodegenConfig(
         private val DEFAULT_EVENT_STREAM_ALLOW_LIST: Set<String> = emptySet()
         private const val DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG = true
         private const val DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS = true
+        private const val DEFAULT_INCLUDE_LIST_ITEM_STREAMS = false
         private const val DEFAULT_NULLABILITY_CHECK_MODE = "CLIENT"
 
         // Note: only clients default to true, servers default to false
@@ -132,6 +135,7 @@ data class ClientCodegenConfig(
                 addMessageToErrors = node.get().getBooleanMemberOrDefault("addMessageToErrors", DEFAULT_ADD_MESSAGE_TO_ERRORS),
                 includeEndpointUrlConfig = node.get().getBooleanMemberOrDefault("includeEndpointUrlConfig", DEFAULT_INCLUDE_ENDPOINT_URL_CONFIG),
                 enableUserConfigurableRuntimePlugins = node.get().getBooleanMemberOrDefault("enableUserConfigurableRuntimePlugins", DEFAULT_ENABLE_USER_CONFIGURABLE_RUNTIME_PLUGINS),
+                includeListItemStreams = node.get().getBooleanMemberOrDefault("includeListItemStreams", DEFAULT_INCLUDE_LIST_ITEM_STREAMS),
                 nullabilityCheckMode = NullableIndex.CheckMode.valueOf(node.get().getStringMemberOrDefault("nullabilityCheckMode", DEFAULT_NULLABILITY_CHECK_MODE)),
             )
         } else {
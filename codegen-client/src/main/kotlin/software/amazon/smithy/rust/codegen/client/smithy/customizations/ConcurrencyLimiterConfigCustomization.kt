@@ -0,0 +1,88 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.configReexport
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+
+class ConcurrencyLimiterConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val moduleUseName = codegenContext.moduleUseName()
+
+    private val codegenScope =
+        codegenContext.runtimeConfig.let { rc ->
+            val api = RuntimeType.smithyRuntimeApiClient(rc)
+            arrayOf(
+                *preludeScope,
+                "LimitConcurrency" to configReexport(api.resolve("client::concurrency_limit::LimitConcurrency")),
+                "SharedConcurrencyLimiter" to configReexport(api.resolve("client::concurrency_limit::SharedConcurrencyLimiter")),
+            )
+        }
+
+    override fun section(section: ServiceConfig): Writable =
+        writable {
+            when (section) {
+                is ServiceConfig.BuilderImpl -> {
+                    val docs = """
+                        /// Set the concurrency limiter for this client.
+                        ///
+                        /// A concurrency limiter bounds the number of requests that can be in flight at once. A permit is
+                        /// acquired right before a request is sent, and released once the response body has been read or
+                        /// dropped. There is no limit by default.
+                        ///
+                        /// ## Examples
+                        ///
+                        /// ```no_run
+                        /// use aws_smithy_runtime::client::concurrency_limit::ConcurrencyLimiter;
+                        ///
+                        /// let config = $moduleUseName::Config::builder()
+                        ///     // allow at most 50 requests in flight at once
+                        ///     .concurrency_limiter(ConcurrencyLimiter::fixed(50))
+                        ///     // ...
+                        ///     .build();
+                        /// let client = $moduleUseName::Client::from_conf(config);
+                        /// ```
+                    """
+                    rustTemplate(
+                        """
+                        $docs
+                        pub fn concurrency_limiter(mut self, concurrency_limiter: impl #{LimitConcurrency} + 'static) -> Self {
+                            self.set_concurrency_limiter(#{Some}(concurrency_limiter));
+                            self
+                        }
+
+                        $docs
+                        pub fn set_concurrency_limiter(&mut self, concurrency_limiter: #{Option}<impl #{LimitConcurrency} + 'static>) -> &mut Self {
+                            self.runtime_components.set_concurrency_limiter(concurrency_limiter);
+                            self
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                is ServiceConfig.ConfigImpl -> {
+                    rustTemplate(
+                        """
+                        /// Returns the configured concurrency limiter.
+                        pub fn concurrency_limiter(&self) -> #{Option}<#{SharedConcurrencyLimiter}> {
+                            self.runtime_components.concurrency_limiter()
+                        }
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                else -> {}
+            }
+        }
+}
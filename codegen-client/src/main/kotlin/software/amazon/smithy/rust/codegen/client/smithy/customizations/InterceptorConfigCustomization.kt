@@ -21,6 +21,8 @@ class InterceptorConfigCustomization(codegenContext: ClientCodegenContext) : Con
         arrayOf(
             "Intercept" to configReexport(RuntimeType.intercept(runtimeConfig)),
             "SharedInterceptor" to configReexport(RuntimeType.sharedInterceptor(runtimeConfig)),
+            "AsyncIntercept" to configReexport(RuntimeType.asyncIntercept(runtimeConfig)),
+            "SharedAsyncInterceptor" to configReexport(RuntimeType.sharedAsyncInterceptor(runtimeConfig)),
         )
 
     override fun section(section: ServiceConfig) =
@@ -33,6 +35,11 @@ class InterceptorConfigCustomization(codegenContext: ClientCodegenContext) : Con
                         pub fn interceptors(&self) -> impl Iterator<Item = #{SharedInterceptor}> + '_ {
                             self.runtime_components.interceptors()
                         }
+
+                        /// Returns async interceptors currently registered by the user.
+                        pub fn async_interceptors(&self) -> impl Iterator<Item = #{SharedAsyncInterceptor}> + '_ {
+                            self.runtime_components.async_interceptors()
+                        }
                         """,
                         *codegenScope,
                     )
@@ -146,6 +153,29 @@ class InterceptorConfigCustomization(codegenContext: ClientCodegenContext) : Con
                             self.runtime_components.set_interceptors(interceptors.into_iter());
                             self
                         }
+
+                        /// Add an [async interceptor](#{AsyncIntercept}) whose "read/write" hooks can await, for example,
+                        /// to fetch a data key while modifying a request.
+                        ///
+                        /// Each async hook runs right after the synchronous interceptors for the same stage
+                        /// of the request execution pipeline. Time spent in async hooks counts towards the
+                        /// operation timeout and the operation attempt timeout.
+                        pub fn async_interceptor(mut self, interceptor: impl #{AsyncIntercept} + 'static) -> Self {
+                            self.push_async_interceptor(#{SharedAsyncInterceptor}::new(interceptor));
+                            self
+                        }
+
+                        /// Add a [`SharedAsyncInterceptor`](#{SharedAsyncInterceptor}) that runs at specific stages of the request execution pipeline.
+                        pub fn push_async_interceptor(&mut self, interceptor: #{SharedAsyncInterceptor}) -> &mut Self {
+                            self.runtime_components.push_async_interceptor(interceptor);
+                            self
+                        }
+
+                        /// Set [`SharedAsyncInterceptor`](#{SharedAsyncInterceptor})s for the builder.
+                        pub fn set_async_interceptors(&mut self, interceptors: impl IntoIterator<Item = #{SharedAsyncInterceptor}>) -> &mut Self {
+                            self.runtime_components.set_async_interceptors(interceptors.into_iter());
+                            self
+                        }
                         """,
                         *codegenScope,
                     )
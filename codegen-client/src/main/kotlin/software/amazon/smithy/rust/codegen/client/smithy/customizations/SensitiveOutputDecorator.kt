@@ -5,16 +5,22 @@
 
 package software.amazon.smithy.rust.codegen.client.smithy.customizations
 
+import software.amazon.smithy.model.knowledge.HttpBinding
+import software.amazon.smithy.model.knowledge.HttpBindingIndex
 import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.traits.HttpTrait
+import software.amazon.smithy.model.traits.SensitiveTrait
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
 import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
 import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
 import software.amazon.smithy.rust.codegen.client.smithy.generators.SensitiveIndex
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.util.dq
 
 class SensitiveOutputDecorator : ClientCodegenDecorator {
     override val name: String get() = "SensitiveOutputDecorator"
@@ -32,19 +38,102 @@ private class SensitiveOutputCustomization(
     private val codegenContext: ClientCodegenContext,
     private val operation: OperationShape,
 ) : OperationCustomization() {
-    private val sensitiveIndex = SensitiveIndex.of(codegenContext.model)
+    private val model = codegenContext.model
+    private val sensitiveIndex = SensitiveIndex.of(model)
+    private val orchestrator = RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig).resolve("client::orchestrator")
+
+    // Headers bound to `@sensitive` members, so that logging can redact their values
+    private val sensitiveHeaderBindings: List<HttpBinding> =
+        HttpBindingIndex.of(model).let { index ->
+            (index.getRequestBindings(operation).values + index.getResponseBindings(operation).values)
+                .filter { it.location == HttpBinding.Location.HEADER || it.location == HttpBinding.Location.PREFIX_HEADERS }
+                .filter { it.member.getMemberTrait(model, SensitiveTrait::class.java).isPresent }
+                .distinctBy { it.location to it.locationName.lowercase() }
+        }
+
+    private val sensitiveRequestBindings: List<HttpBinding> =
+        HttpBindingIndex.of(model).getRequestBindings(operation).values
+            .filter { it.member.getMemberTrait(model, SensitiveTrait::class.java).isPresent }
+
+    // Builder calls that mark the parts of the URI bound to `@sensitive` members, so that logging
+    // can redact them. Labels are located by counting from the end of the path, since the
+    // endpoint may prefix the modeled path.
+    private val sensitiveUriMarkers: List<String> =
+        operation.getTrait(HttpTrait::class.java).map { httpTrait ->
+            val segments = httpTrait.uri.segments
+            val greedyIndex = segments.indexOfFirst { it.isGreedyLabel }
+            sensitiveRequestBindings.mapNotNull { binding ->
+                when (binding.location) {
+                    HttpBinding.Location.LABEL -> {
+                        val index = segments.indexOfFirst { it.isLabel && it.content == binding.locationName }
+                        if (index < 0) {
+                            null
+                        } else if (greedyIndex >= index) {
+                            ".with_whole_path()"
+                        } else {
+                            ".with_path_segment_from_end(${segments.size - 1 - index})"
+                        }
+                    }
+                    HttpBinding.Location.QUERY -> ".with_query_key(${binding.locationName.dq()})"
+                    HttpBinding.Location.QUERY_PARAMS -> ".with_all_query_params()"
+                    else -> null
+                }
+            }.distinct()
+        }.orElse(emptyList())
 
     override fun section(section: OperationSection): Writable =
         writable {
-            if (section is OperationSection.AdditionalRuntimePluginConfig && sensitiveIndex.hasSensitiveOutput(operation)) {
-                rustTemplate(
-                    """
-                    ${section.newLayerName}.store_put(#{SensitiveOutput});
-                    """,
-                    "SensitiveOutput" to
-                        RuntimeType.smithyRuntimeApiClient(codegenContext.runtimeConfig)
-                            .resolve("client::orchestrator::SensitiveOutput"),
-                )
+            if (section is OperationSection.AdditionalRuntimePluginConfig) {
+                if (sensitiveIndex.hasSensitiveInput(operation)) {
+                    rustTemplate(
+                        """
+                        ${section.newLayerName}.store_put(#{SensitiveInput});
+                        """,
+                        "SensitiveInput" to orchestrator.resolve("SensitiveInput"),
+                    )
+                }
+                if (sensitiveIndex.hasSensitiveOutput(operation)) {
+                    rustTemplate(
+                        """
+                        ${section.newLayerName}.store_put(#{SensitiveOutput});
+                        """,
+                        "SensitiveOutput" to orchestrator.resolve("SensitiveOutput"),
+                    )
+                }
+                if (sensitiveHeaderBindings.isNotEmpty()) {
+                    rustTemplate(
+                        """
+                        ${section.newLayerName}.store_put(
+                            #{SensitiveHeaders}::new()
+                            #{headers}
+                        );
+                        """,
+                        "SensitiveHeaders" to orchestrator.resolve("SensitiveHeaders"),
+                        "headers" to
+                            writable {
+                                sensitiveHeaderBindings.forEach { binding ->
+                                    val method =
+                                        when (binding.location) {
+                                            HttpBinding.Location.PREFIX_HEADERS -> "with_prefix"
+                                            else -> "with_name"
+                                        }
+                                    rust(".$method(${binding.locationName.lowercase().dq()})")
+                                }
+                            },
+                    )
+                }
+                if (sensitiveUriMarkers.isNotEmpty()) {
+                    rustTemplate(
+                        """
+                        ${section.newLayerName}.store_put(
+                            #{SensitiveUri}::new()
+                            #{markers}
+                        );
+                        """,
+                        "SensitiveUri" to orchestrator.resolve("SensitiveUri"),
+                        "markers" to writable { sensitiveUriMarkers.forEach { rust(it) } },
+                    )
+                }
             }
         }
 }
@@ -45,7 +45,7 @@ fun Identifier.rustName(): String {
  * Endpoints standard library
  */
 object EndpointsLib {
-    val DiagnosticCollector = endpointsLib("diagnostic").toType().resolve("DiagnosticCollector")
+    val DiagnosticCollector = endpointsLib("diagnostic", CargoDependency.Tracing).toType().resolve("DiagnosticCollector")
 
     fun partitionResolver(runtimeConfig: RuntimeConfig) =
         endpointsLib("partition", CargoDependency.smithyJson(runtimeConfig), CargoDependency.RegexLite).toType()
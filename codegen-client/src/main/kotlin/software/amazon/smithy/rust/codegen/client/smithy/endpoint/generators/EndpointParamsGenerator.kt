@@ -66,7 +66,7 @@ val EndpointStdLib = RustModule.private("endpoint_lib")
  * The following snippet contains an example of what is generated (eliding the error):
  *  ```rust
  *  #[non_exhaustive]
- *  #[derive(std::clone::Clone, std::cmp::PartialEq, std::fmt::Debug)]
+ *  #[derive(std::clone::Clone, std::cmp::PartialEq, std::cmp::Eq, std::hash::Hash, std::fmt::Debug)]
  *  /// Configuration parameters for resolving the correct endpoint
  *  pub struct Params {
  *      pub(crate) region: std::option::Option<std::string::String>,
@@ -169,8 +169,8 @@ internal class EndpointParamsGenerator(
     private fun generateEndpointsStruct(writer: RustWriter) {
         // Ensure that fields can be added in the future
         Attribute.NonExhaustive.render(writer)
-        // Automatically implement standard Rust functionality
-        Attribute(derive(RuntimeType.Debug, RuntimeType.PartialEq, RuntimeType.Clone)).render(writer)
+        // Automatically implement standard Rust functionality. `Eq` and `Hash` allow params to be used as a cache key.
+        Attribute(derive(RuntimeType.Debug, RuntimeType.PartialEq, RuntimeType.Eq, RuntimeType.Hash, RuntimeType.Clone)).render(writer)
         // Generate the struct block:
         //    pub struct Params {
         //        ... members: pub(crate) field
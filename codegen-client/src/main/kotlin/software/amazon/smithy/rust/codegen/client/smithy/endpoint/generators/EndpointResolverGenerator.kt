@@ -107,8 +107,9 @@ class FunctionRegistry(private val functions: List<CustomRuntimeFunction>) {
  * impl aws_smithy_http::endpoint::ResolveEndpoint<crate::endpoint::Params> for DefaultResolver {
  *     fn resolve_endpoint(&self, params: &Params) -> aws_smithy_http::endpoint::Result {
  *         let mut diagnostic_collector = crate::endpoint_lib::diagnostic::DiagnosticCollector::new();
- *         crate::endpoint::internals::resolve_endpoint(params, &self.partition_resolver, &mut diagnostic_collector)
- *             .map_err(|err| err.with_source(diagnostic_collector.take_last_error()))
+ *         let result = crate::endpoint::internals::resolve_endpoint(params, &self.partition_resolver, &mut diagnostic_collector);
+ *         // when diagnostics are enabled, the trace of matched and failed conditions is emitted here
+ *         result.map_err(|err| err.with_source(diagnostic_collector.take_last_error()))
  *     }
  * }
  *
@@ -157,6 +158,10 @@ internal class EndpointResolverGenerator(
         )
     private val context = Context(registry, runtimeConfig)
 
+    // Every distinct rule condition, in the order it was first generated. Branches record the index of their
+    // condition, so each condition string is only embedded once, in the table rendered by [conditionTable].
+    private val conditionIndices = linkedMapOf<String, Int>()
+
     companion object {
         const val DIAGNOSTIC_COLLECTOR = "_diagnostic_collector"
         private const val PARAMS_NAME = "_params"
@@ -178,25 +183,43 @@ internal class EndpointResolverGenerator(
 
         // Now that we rendered the rules once (and then threw it away) we can see what functions we actually used!
         val fnsUsed = registry.fnsUsed()
+        val conditions = conditionTable()
         return RuntimeType.forInlineFun("DefaultResolver", ClientRustModule.Config.endpoint) {
             rustTemplate(
                 """
                 /// The default endpoint resolver
                 ##[derive(Debug, Default)]
                 pub struct DefaultResolver {
+                    diagnostics: bool,
                     #{custom_fields:W}
                 }
 
                 impl DefaultResolver {
                     /// Create a new endpoint resolver with default settings
                     pub fn new() -> Self {
-                        Self { #{custom_fields_init:W} }
+                        Self { diagnostics: false, #{custom_fields_init:W} }
+                    }
+
+                    /// Enable or disable diagnostics for endpoint rule evaluation
+                    ///
+                    /// When enabled, the resolver records which rule conditions matched and which failed,
+                    /// and emits them as a `DEBUG` level `tracing` event with the `aws_smithy_endpoint::diagnostic` target.
+                    pub fn with_diagnostics(mut self, enabled: bool) -> Self {
+                        self.diagnostics = enabled;
+                        self
                     }
 
                     fn resolve_endpoint(&self, params: &#{Params}) -> Result<#{SmithyEndpoint}, #{BoxError}> {
-                        let mut diagnostic_collector = #{DiagnosticCollector}::new();
-                        Ok(#{resolver_fn}(params, &mut diagnostic_collector, #{additional_args})
-                            .map_err(|err|err.with_source(diagnostic_collector.take_last_error()))?)
+                        let mut diagnostic_collector = if self.diagnostics {
+                            #{DiagnosticCollector}::with_trace(#{conditions})
+                        } else {
+                            #{DiagnosticCollector}::new()
+                        };
+                        let result = #{resolver_fn}(params, &mut diagnostic_collector, #{additional_args});
+                        if let Some(trace) = diagnostic_collector.take_trace() {
+                            trace.emit(params);
+                        }
+                        Ok(result.map_err(|err|err.with_source(diagnostic_collector.take_last_error()))?)
                     }
                 }
 
@@ -211,6 +234,7 @@ internal class EndpointResolverGenerator(
                 "Params" to EndpointParamsGenerator(codegenContext, endpointRuleSet.parameters).paramsStruct(),
                 "additional_args" to fnsUsed.mapNotNull { it.additionalArgsInvocation("self") }.join(","),
                 "resolver_fn" to resolverFn(endpointRuleSet, fnsUsed),
+                "conditions" to conditions,
                 *codegenScope,
             )
         }
@@ -237,6 +261,24 @@ internal class EndpointResolverGenerator(
         }
     }
 
+    /**
+     * The table of rule conditions that diagnostics look up recorded condition indices in. This must be created
+     * after the rules have been rendered once, so that every condition has been assigned an index.
+     */
+    private fun conditionTable(): RuntimeType =
+        RuntimeType.forInlineFun("CONDITIONS", endpointImplModule()) {
+            rustTemplate(
+                """
+                /// The conditions of the endpoint rules, indexed by the order they were generated in
+                pub(super) const CONDITIONS: &[&str] = &[#{conditions:W}];
+                """,
+                "conditions" to
+                    writable {
+                        conditionIndices.keys.forEach { rust("#L,", it.dq()) }
+                    },
+            )
+        }
+
     private fun resolverFnBody(endpointRuleSet: EndpointRuleSet) =
         writable {
             endpointRuleSet.parameters.toList().forEach {
@@ -316,9 +358,11 @@ internal class EndpointResolverGenerator(
                     fn.type() is OptionalType -> {
                         Attribute.AllowUnusedVariables.render(this)
                         rustTemplate(
-                            "if let Some($resultName) = #{target:W} { #{next:W} }",
+                            "if let Some($resultName) = #{target:W} { #{matched:W} #{next:W} } else { #{failed:W} }",
                             "target" to target,
                             "next" to next,
+                            "matched" to recordCondition(condition, true),
+                            "failed" to recordCondition(condition, false),
                         )
                     }
 
@@ -326,11 +370,16 @@ internal class EndpointResolverGenerator(
                         rustTemplate(
                             """
                             if #{target:W} {#{binding}
+                                #{matched:W}
                                 #{next:W}
+                            } else {
+                                #{failed:W}
                             }
                             """,
                             "target" to target,
                             "next" to next,
+                            "matched" to recordCondition(condition, true),
+                            "failed" to recordCondition(condition, false),
                             // handle the rare but possible case where we bound the name of a variable to a boolean condition
                             "binding" to
                                 writable {
@@ -357,6 +406,18 @@ internal class EndpointResolverGenerator(
         }
     }
 
+    /**
+     * Records the outcome of [condition] in the diagnostic collector, by its index in the condition table.
+     * Infallible conditions are not recorded since they always match.
+     */
+    private fun recordCondition(
+        condition: Condition,
+        matched: Boolean,
+    ): Writable {
+        val index = conditionIndices.getOrPut(condition.toString()) { conditionIndices.size }
+        return writable { rust("$DIAGNOSTIC_COLLECTOR.record_condition($index, $matched);") }
+    }
+
     inner class RuleVisitor : RuleValueVisitor<Writable> {
         override fun visitTreeRule(rules: List<Rule>) = generateRulesList(rules)
 
@@ -396,7 +457,14 @@ fun ClientCodegenContext.serviceSpecificEndpointResolver(): RuntimeType {
     val generator = EndpointTypesGenerator.fromContext(this)
     return RuntimeType.forInlineFun("ResolveEndpoint", ClientRustModule.Config.endpoint) {
         val ctx =
-            arrayOf(*preludeScope, "Params" to generator.paramsStruct(), *Types(runtimeConfig).toArray(), "Debug" to RuntimeType.Debug)
+            arrayOf(
+                *preludeScope,
+                "Params" to generator.paramsStruct(),
+                *Types(runtimeConfig).toArray(),
+                "Debug" to RuntimeType.Debug,
+                "CachingEndpointResolver" to
+                    RuntimeType.smithyRuntime(runtimeConfig).resolve("client::endpoint::cache::CachingEndpointResolver"),
+            )
         rustTemplate(
             """
             /// Endpoint resolver trait specific to ${serviceShape.serviceNameOrDefault("this service")}
@@ -413,6 +481,17 @@ fun ClientCodegenContext.serviceSpecificEndpointResolver(): RuntimeType {
                 {
                     #{SharedEndpointResolver}::new(DowncastParams(self))
                 }
+
+                /// Convert this service-specific resolver into a `SharedEndpointResolver` that caches resolved endpoints
+                ///
+                /// Endpoints are cached by `#{Params}`. At most `capacity` endpoints are kept, and the least recently
+                /// used endpoint is evicted first.
+                fn into_caching_shared_resolver(self, capacity: usize) -> #{SharedEndpointResolver}
+                where
+                    Self: Sized + 'static,
+                {
+                    #{SharedEndpointResolver}::new(#{CachingEndpointResolver}::<#{Params}>::with_capacity(DowncastParams(self), capacity))
+                }
             }
 
             ##[derive(Debug)]
@@ -5,6 +5,7 @@
 
 package software.amazon.smithy.rust.codegen.client.smithy.generators
 
+import software.amazon.smithy.model.shapes.CollectionShape
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
 import software.amazon.smithy.rust.codegen.client.smithy.customize.AuthSchemeOption
@@ -27,11 +28,13 @@ import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
 import software.amazon.smithy.rust.codegen.core.smithy.customize.writeCustomizations
 import software.amazon.smithy.rust.codegen.core.smithy.generators.protocol.ProtocolPayloadGenerator
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpLocation
 import software.amazon.smithy.rust.codegen.core.smithy.protocols.Protocol
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.inputShape
 import software.amazon.smithy.rust.codegen.core.util.outputShape
 import software.amazon.smithy.rust.codegen.core.util.sdkId
+import software.amazon.smithy.rust.codegen.core.util.toSnakeCase
 
 open class OperationGenerator(
     private val codegenContext: ClientCodegenContext,
@@ -61,6 +64,51 @@ open class OperationGenerator(
             codegenDecorator.authOptions(codegenContext, operationShape, emptyList()),
             operationCustomizations,
         )
+        if (codegenContext.settings.codegenConfig.includeListItemStreams) {
+            renderListItemStreams(operationWriter, operationShape)
+        }
+    }
+
+    /**
+     * Render a function per list in the output document that deserializes the list's items while the response body
+     * is still arriving, for protocols that support it.
+     */
+    private fun renderListItemStreams(
+        operationWriter: RustWriter,
+        operationShape: OperationShape,
+    ) {
+        val operationName = symbolProvider.toSymbol(operationShape).name
+        val parserGenerator = protocol.structuredDataParser()
+        for (member in protocol.httpBindingResolver.responseMembers(operationShape, HttpLocation.DOCUMENT)) {
+            val target = model.expectShape(member.target)
+            if (target !is CollectionShape) {
+                continue
+            }
+            val parser = parserGenerator.listItemsParser(member) ?: continue
+            val memberName = member.memberName.toSnakeCase()
+            operationWriter.rustTemplate(
+                """
+                /// Deserializes the items of `$memberName` from a successful `$operationName` response body as the body arrives.
+                ///
+                /// Unlike sending the request with the client, which buffers the whole response before deserializing it,
+                /// this yields the first items of a large list while the rest of the body is still being received. The
+                /// body can be taken from the response in an interceptor's `modify_before_deserialization` hook.
+                ///
+                /// The whole body is checked against `limits` as it's read, including the members after the list. Use
+                /// `DecodeLimits::new()` to read it without limits.
+                pub async fn stream_$memberName(body: #{ByteStream}, limits: #{DecodeLimits}) -> #{Result}<#{ListItems}<#{Item}>, #{DeserializeError}> {
+                    #{parser}(body, limits).await
+                }
+                """,
+                *preludeScope,
+                "ByteStream" to RuntimeType.byteStream(runtimeConfig),
+                "DecodeLimits" to RuntimeType.smithyTypes(runtimeConfig).resolve("decode_limits::DecodeLimits"),
+                "DeserializeError" to RuntimeType.smithyJson(runtimeConfig).resolve("deserialize::error::DeserializeError"),
+                "Item" to symbolProvider.toSymbol(target.member),
+                "ListItems" to RuntimeType.smithyJson(runtimeConfig).resolve("deserialize::incremental::ListItems"),
+                "parser" to parser,
+            )
+        }
     }
 
     private fun renderOperationStruct(
@@ -74,6 +74,9 @@ class PaginatorGenerator private constructor(
     private val outputShape = operation.outputShape(model)
     private val outputType = symbolProvider.toSymbol(outputShape)
     private val errorType = symbolProvider.symbolForOperationError(operation)
+    private val tokenType =
+        symbolProvider.toSymbol(paginationInfo.inputTokenMember).rustType().stripOuter<RustType.Option>()
+            .render(true)
 
     private val isTruncatedPaginator =
         codegenContext.model.getShape(outputShape.toShapeId()).orNull().let { shape ->
@@ -105,6 +108,11 @@ class PaginatorGenerator private constructor(
                     .resolve("client::orchestrator::HttpResponse"),
             "SdkError" to RuntimeType.sdkError(runtimeConfig),
             "pagination_stream" to RuntimeType.smithyAsync(runtimeConfig).resolve("future::pagination_stream"),
+            "Paginator" to
+                RuntimeType.smithyAsync(runtimeConfig).resolve("future::pagination_stream::paginator::Paginator"),
+            "CheckpointedPage" to
+                RuntimeType.smithyAsync(runtimeConfig)
+                    .resolve("future::pagination_stream::paginator::CheckpointedPage"),
             // External Types
             "Stream" to RuntimeType.TokioStream.resolve("Stream"),
         )
@@ -125,6 +133,7 @@ class PaginatorGenerator private constructor(
                     handle: std::sync::Arc<crate::client::Handle>,
                     builder: #{Builder},
                     stop_on_duplicate_token: bool,
+                    prefetch: usize,
                 }
 
                 impl $paginatorName {
@@ -134,6 +143,7 @@ class PaginatorGenerator private constructor(
                             handle,
                             builder,
                             stop_on_duplicate_token: true,
+                            prefetch: 0,
                         }
                     }
 
@@ -141,6 +151,17 @@ class PaginatorGenerator private constructor(
 
                     #{items_fn:W}
 
+                    /// Start paginating from the page for `token` instead of from the first page
+                    ///
+                    /// This is typically the [`next_token`](#{CheckpointedPage}::next_token) of the last page processed
+                    /// by an earlier stream created with [`send_checkpointed`](Self::send_checkpointed).
+                    ///
+                    /// _Note: this method will override any previously set value for `$inputTokenMember`_
+                    pub fn starting_token(mut self, token: $tokenType) -> Self {
+                        self.builder.$inputTokenMember = #{Some}(token);
+                        self
+                    }
+
                     /// Stop paginating when the service returns the same pagination token twice in a row.
                     ///
                     /// Defaults to true.
@@ -153,46 +174,59 @@ class PaginatorGenerator private constructor(
                         self
                     }
 
+                    /// Request up to `pages` pages ahead of the consumer of the stream
+                    ///
+                    /// Defaults to zero, so that each page is only requested when the stream is polled for it.
+                    /// See [`Paginator::prefetch`](#{Paginator}::prefetch) for details.
+                    pub fn prefetch(mut self, pages: usize) -> Self {
+                        self.prefetch = pages;
+                        self
+                    }
+
                     /// Create the pagination stream
                     ///
                     /// _Note:_ No requests will be dispatched until the stream is used
                     /// (e.g. with the [`.next().await`](aws_smithy_async::future::pagination_stream::PaginationStream::next) method).
                     pub fn send(self) -> #{pagination_stream}::PaginationStream<#{item_type}> {
+                        self.send_checkpointed().into_pages()
+                    }
+
+                    /// Create the pagination stream, yielding each page along with its pagination tokens
+                    ///
+                    /// To pick up where the stream left off later, persist the [`next_token`](#{CheckpointedPage}::next_token)
+                    /// of the last page that was processed, and pass it to [`starting_token`](Self::starting_token).
+                    ///
+                    /// _Note:_ No requests will be dispatched until the stream is used
+                    /// (e.g. with the [`.next().await`](aws_smithy_async::future::pagination_stream::PaginationStream::next) method).
+                    pub fn send_checkpointed(self) -> #{pagination_stream}::PaginationStream<#{checkpointed_item_type}> {
                         // Move individual fields out of self for the borrow checker
                         let builder = self.builder;
                         let handle = self.handle;
                         #{runtime_plugin_init}
-                        #{pagination_stream}::PaginationStream::new(#{pagination_stream}::fn_stream::FnStream::new(move |tx| #{Box}::pin(async move {
-                            // Build the input for the first time. If required fields are missing, this is where we'll produce an early error.
-                            let mut input = match builder.build().map_err(#{SdkError}::construction_failure) {
-                                #{Ok}(input) => input,
-                                #{Err}(e) => { let _ = tx.send(#{Err}(e)).await; return; }
-                            };
-                            loop {
-                                let resp = #{orchestrate};
-                                // If the input member is None or it was an error
-                                let done = match resp {
-                                    #{Ok}(ref resp) => {
-                                        let new_token = #{output_token}(resp);
-                                        #{is_empty_setter:W}
-                                        if !is_empty && new_token == input.$inputTokenMember.as_ref() && self.stop_on_duplicate_token {
-                                            true
-                                        } else {
-                                            input.$inputTokenMember = new_token.cloned();
-                                            is_empty
-                                        }
-                                    },
-                                    #{Err}(_) => true,
+                        let starting_token = builder.$inputTokenMember.clone();
+                        let mut paginator = #{Paginator}::new(move |token: #{Option}<$tokenType>| {
+                            let runtime_plugins = runtime_plugins.clone();
+                            let builder = builder.clone();
+                            async move {
+                                // Build the input for every page. If required fields are missing, this is where we'll produce an early error.
+                                let mut input = match builder.build() {
+                                    #{Ok}(input) => input,
+                                    #{Err}(e) => return #{Err}(#{SdkError}::construction_failure(e)),
                                 };
-                                if tx.send(resp).await.is_err() {
-                                    // receiving end was dropped
-                                    return
-                                }
-                                if done {
-                                    return
-                                }
+                                input.$inputTokenMember = token;
+                                let resp = #{orchestrate}?;
+                                let new_token = #{output_token}(&resp);
+                                #{is_empty_setter:W}
+                                let next_token = if is_empty { #{None} } else { new_token.cloned() };
+                                #{Ok}::<_, #{SdkError}<#{Error}, #{HttpResponse}>>((resp, next_token))
                             }
-                        })))
+                        })
+                        .stop_on_duplicate_token(self.stop_on_duplicate_token)
+                        .prefetch(self.prefetch);
+                        if let #{Some}(token) = starting_token {
+                            paginator = paginator.resume_from(token);
+                        }
+                        paginator.send()
                     }
                 }
                 """,
@@ -203,10 +237,17 @@ class PaginatorGenerator private constructor(
                     writable {
                         rustTemplate("#{Result}<#{Output}, #{SdkError}<#{Error}, #{HttpResponse}>>", *codegenScope)
                     },
+                "checkpointed_item_type" to
+                    writable {
+                        rustTemplate(
+                            "#{Result}<#{CheckpointedPage}<#{Output}, $tokenType>, #{SdkError}<#{Error}, #{HttpResponse}>>",
+                            *codegenScope,
+                        )
+                    },
                 "orchestrate" to
                     writable {
                         rustTemplate(
-                            "#{operation}::orchestrate(&runtime_plugins, input.clone()).await",
+                            "#{operation}::orchestrate(&runtime_plugins, input).await",
                             *codegenScope,
                         )
                     },
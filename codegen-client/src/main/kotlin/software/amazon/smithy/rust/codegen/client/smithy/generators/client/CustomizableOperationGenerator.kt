@@ -53,6 +53,9 @@ class CustomizableOperationGenerator(
                     RuntimeType.smithyRuntime(runtimeConfig)
                         .resolve("client::interceptors::MutateRequestInterceptor"),
                 "PhantomData" to RuntimeType.Phantom,
+                "RuntimeComponentsOverride" to
+                    RuntimeType.smithyRuntime(runtimeConfig)
+                        .resolve("client::config_override::RuntimeComponentsOverride"),
                 "RuntimePlugin" to RuntimeType.runtimePlugin(runtimeConfig),
                 "SharedRuntimePlugin" to RuntimeType.sharedRuntimePlugin(runtimeConfig),
                 "SendResult" to
@@ -173,6 +176,21 @@ class CustomizableOperationGenerator(
                         self
                     }
 
+                    /// Overrides runtime components, such as the HTTP client, an identity resolver, the retry
+                    /// strategy, or the endpoint resolver, for a single operation invocation.
+                    ///
+                    /// Unlike `config_override`, this can override any runtime component, including ones
+                    /// that aren't exposed by the config builder. The final set of runtime components is
+                    /// validated before the request is sent, and the operation fails with a construction
+                    /// failure if the override leaves it incomplete.
+                    pub fn runtime_components_override(
+                        mut self,
+                        runtime_components_override: #{RuntimeComponentsOverride},
+                    ) -> Self {
+                        self.runtime_plugins.push(#{SharedRuntimePlugin}::new(runtime_components_override));
+                        self
+                    }
+
                     /// Sends the request and returns the response.
                     pub async fn send(
                         self,
@@ -35,6 +35,7 @@ import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.expectMember
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.inputShape
+import software.amazon.smithy.rust.codegen.core.util.isBigNumber
 
 fun HttpTrait.uriFormatString(): String {
     return uri.rustFormatString("/", "/")
@@ -306,6 +307,11 @@ class RequestBindingGenerator(
                 throw IllegalArgumentException("lists should be handled at a higher level")
             }
 
+            // Big numbers aren't `Copy`, so they are encoded from the reference
+            target.isBigNumber() -> {
+                "${writer.format(encoder)}::from($targetName).encode()"
+            }
+
             else -> {
                 "${writer.format(encoder)}::from(${autoDeref(targetName)}).encode()"
             }
@@ -350,8 +356,9 @@ class RequestBindingGenerator(
             }
 
             else -> {
+                val encoderInput = if (target.isBigNumber()) input else autoDeref(input)
                 rust(
-                    "let mut ${outputVar}_encoder = #T::from(${autoDeref(input)}); let $outputVar = ${outputVar}_encoder.encode();",
+                    "let mut ${outputVar}_encoder = #T::from($encoderInput); let $outputVar = ${outputVar}_encoder.encode();",
                     encoder,
                 )
             }
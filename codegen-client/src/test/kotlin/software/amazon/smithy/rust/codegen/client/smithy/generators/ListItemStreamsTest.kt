@@ -0,0 +1,101 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.generators
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.testutil.ClientAdditionalSettings
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+internal class ListItemStreamsTest {
+    private val model =
+        """
+        namespace test
+        use aws.protocols#awsJson1_0
+
+        @awsJson1_0
+        service TestService {
+            operations: [ListThings]
+        }
+
+        operation ListThings {
+            output: ListThingsOutput
+        }
+
+        structure ListThingsOutput {
+            nextToken: String,
+            things: Things,
+            sparseNames: SparseNames,
+        }
+
+        list Things {
+            member: Thing
+        }
+
+        @sparse
+        list SparseNames {
+            member: String
+        }
+
+        structure Thing {
+            name: String
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `list items are deserialized from the response body`() {
+        clientIntegrationTest(
+            model,
+            IntegrationTestParams(
+                additionalSettings = ClientAdditionalSettings.builder().includeListItemStreams().toObjectNode(),
+                cargoCommand = "cargo test --features behavior-version-latest",
+            ),
+        ) { context, rustCrate ->
+            rustCrate.testModule {
+                tokioTest("stream_list_items") {
+                    rustTemplate(
+                        """
+                        use crate::operation::list_things::{stream_sparse_names, stream_things};
+                        use crate::types::Thing;
+
+                        let body = br##"{"nextToken": "abc", "things": [{"name": "a"}, null, {"name": "b"}]}"##;
+                        let mut things = stream_things(#{ByteStream}::from_static(body), #{DecodeLimits}::new()).await.unwrap();
+                        assert_eq!(Thing::builder().name("a").build(), things.next().await.unwrap().unwrap());
+                        assert_eq!(Thing::builder().name("b").build(), things.next().await.unwrap().unwrap());
+                        assert!(things.next().await.is_none());
+
+                        let body = br##"{"sparseNames": ["a", null]}"##;
+                        let mut names = stream_sparse_names(#{ByteStream}::from_static(body), #{DecodeLimits}::new()).await.unwrap();
+                        assert_eq!(#{Some}("a".to_string()), names.next().await.unwrap().unwrap());
+                        assert_eq!(#{None}, names.next().await.unwrap().unwrap());
+                        assert!(names.next().await.is_none());
+
+                        let mut things = stream_things(#{ByteStream}::from_static(b"{}"), #{DecodeLimits}::new()).await.unwrap();
+                        assert!(things.next().await.is_none());
+
+                        // The limits apply to the whole body, including the members after the list
+                        let body = br##"{"things": [{"name": "a"}], "nextToken": "abcdefghij"}"##;
+                        let limits = #{DecodeLimits}::new().with_max_string_length(9);
+                        let mut things = stream_things(#{ByteStream}::from_static(body), limits).await.unwrap();
+                        assert_eq!(Thing::builder().name("a").build(), things.next().await.unwrap().unwrap());
+                        let err = things.next().await.unwrap().unwrap_err();
+                        assert!(err.limit_exceeded().is_some(), "{err:?}");
+                        assert!(things.next().await.is_none());
+                        """,
+                        *RuntimeType.preludeScope,
+                        "ByteStream" to RuntimeType.byteStream(context.runtimeConfig),
+                        "DecodeLimits" to RuntimeType.smithyTypes(context.runtimeConfig).resolve("decode_limits::DecodeLimits"),
+                    )
+                }
+            }
+        }
+    }
+}
@@ -12,9 +12,14 @@ import software.amazon.smithy.model.transform.ModelTransformer
 import software.amazon.smithy.rust.codegen.client.smithy.traits.IsTruncatedPaginatorTrait
 import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.integrationTest
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
 import software.amazon.smithy.rust.codegen.core.util.letIf
 
 internal class PaginatorGeneratorTest {
@@ -103,4 +108,55 @@ internal class PaginatorGeneratorTest {
             }
         }
     }
+
+    @Test
+    fun `paginators resume from a starting token and expose page tokens`() {
+        clientIntegrationTest(model) { context, rustCrate ->
+            rustCrate.testModule {
+                tokioTest("checkpointed_pages") {
+                    rustTemplate(
+                        """
+                        // Responds with the token "2" to a request for the page with the token "1"
+                        let response = |request: http::Request<#{SdkBody}>| {
+                            let body = std::str::from_utf8(request.body().bytes().unwrap()).unwrap();
+                            let token = if body.contains(r##""nextToken":"1""##) { r##","token":"2""## } else { "" };
+                            http::Response::builder()
+                                .status(200)
+                                .body(#{SdkBody}::from(format!(
+                                    r##"{{"inner":{{"items":["a"],"mapItems":{{}}{token}}}}}"##
+                                )))
+                                .unwrap()
+                        };
+                        let client = crate::Client::from_conf(
+                            crate::Config::builder()
+                                .http_client(#{infallible_client_fn}(response))
+                                .endpoint_url("http://localhost:1234")
+                                .build()
+                        );
+                        let pages = client
+                            .paginated_list()
+                            .into_paginator()
+                            .starting_token("1".to_string())
+                            .prefetch(1)
+                            .send_checkpointed()
+                            .try_collect()
+                            .await
+                            .unwrap();
+                        let tokens: #{Vec}<_> = pages
+                            .iter()
+                            .map(|page| (page.token().map(#{String}::as_str), page.next_token().map(#{String}::as_str)))
+                            .collect();
+                        assert_eq!(vec![(#{Some}("1"), #{Some}("2")), (#{Some}("2"), #{None})], tokens);
+                        assert_eq!(#{Some}("2"), pages[0].page().inner().unwrap().token());
+                        """,
+                        *RuntimeType.preludeScope,
+                        "SdkBody" to RuntimeType.sdkBody(context.runtimeConfig),
+                        "infallible_client_fn" to
+                            CargoDependency.smithyRuntimeTestUtil(context.runtimeConfig)
+                                .toType().resolve("client::http::test_util::infallible_client_fn"),
+                    )
+                }
+            }
+        }
+    }
 }
@@ -16,6 +16,8 @@ import software.amazon.smithy.codegen.core.SymbolWriter
 import software.amazon.smithy.codegen.core.SymbolWriter.Factory
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.node.Node
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.CollectionShape
 import software.amazon.smithy.model.shapes.DoubleShape
@@ -805,6 +807,8 @@ class RustWriter private constructor(
             block: RustWriter.(field: ValueExpression) -> Unit,
         ) {
             when (shape) {
+                // Big numbers can't be compared against a literal, so they are always serialized
+                is BigIntegerShape, is BigDecimalShape -> block(variable)
                 is NumberShape, is BooleanShape -> {
                     if (memberSymbol.defaultValue() is Default.RustDefault) {
                         when (shape) {
@@ -372,6 +372,12 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
         fun sharedInterceptor(runtimeConfig: RuntimeConfig): RuntimeType =
             smithyRuntimeApiClient(runtimeConfig).resolve("client::interceptors::SharedInterceptor")
 
+        fun asyncIntercept(runtimeConfig: RuntimeConfig): RuntimeType =
+            smithyRuntimeApiClient(runtimeConfig).resolve("client::interceptors::AsyncIntercept")
+
+        fun sharedAsyncInterceptor(runtimeConfig: RuntimeConfig): RuntimeType =
+            smithyRuntimeApiClient(runtimeConfig).resolve("client::interceptors::SharedAsyncInterceptor")
+
         fun afterDeserializationInterceptorContextRef(runtimeConfig: RuntimeConfig): RuntimeType =
             smithyRuntimeApiClient(runtimeConfig).resolve("client::interceptors::context::AfterDeserializationInterceptorContextRef")
 
@@ -410,6 +416,10 @@ data class RuntimeType(val path: String, val dependency: RustDependency? = null)
 
         fun document(runtimeConfig: RuntimeConfig): RuntimeType = smithyTypes(runtimeConfig).resolve("Document")
 
+        fun bigInteger(runtimeConfig: RuntimeConfig): RuntimeType = smithyTypes(runtimeConfig).resolve("BigInteger")
+
+        fun bigDecimal(runtimeConfig: RuntimeConfig): RuntimeType = smithyTypes(runtimeConfig).resolve("BigDecimal")
+
         fun format(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("date_time::Format")
 
         fun retryErrorKind(runtimeConfig: RuntimeConfig) = smithyTypes(runtimeConfig).resolve("retry::ErrorKind")
@@ -14,6 +14,8 @@ import software.amazon.smithy.model.node.NullNode
 import software.amazon.smithy.model.node.NumberNode
 import software.amazon.smithy.model.node.ObjectNode
 import software.amazon.smithy.model.node.StringNode
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
 import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.CollectionShape
@@ -544,6 +546,15 @@ class PrimitiveInstantiator(
                     }
 
                 is StringShape -> renderString(shape, data as StringNode)(this)
+                is BigIntegerShape, is BigDecimalShape -> {
+                    val value = if (data is StringNode) data.value else (data as NumberNode).value.toString()
+                    rustTemplate(
+                        """<#{NumberSymbol} as #{SmithyTypes}::primitive::Parse>::parse_smithy_primitive(${value.dq()}).expect("invalid string for number")""",
+                        "NumberSymbol" to symbolProvider.toSymbol(shape),
+                        *codegenScope,
+                    )
+                }
+
                 is NumberShape ->
                     when (data) {
                         is StringNode -> {
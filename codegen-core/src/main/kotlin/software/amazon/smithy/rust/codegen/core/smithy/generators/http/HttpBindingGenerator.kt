@@ -63,6 +63,7 @@ import software.amazon.smithy.rust.codegen.core.util.UNREACHABLE
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.inputShape
+import software.amazon.smithy.rust.codegen.core.util.isBigNumber
 import software.amazon.smithy.rust.codegen.core.util.isPrimitive
 import software.amazon.smithy.rust.codegen.core.util.isStreaming
 import software.amazon.smithy.rust.codegen.core.util.outputShape
@@ -222,6 +223,8 @@ class HttpBindingGenerator(
         // Deserialize a single structure, union or document member marked as a payload
         payloadParser: RustWriter.(String) -> Unit,
         httpMessageType: HttpMessageType = HttpMessageType.RESPONSE,
+        // Take the `DecodeLimits` to check the payload against as `limits`, for [payloadParser] to use
+        withDecodeLimits: Boolean = false,
     ): RuntimeType {
         check(binding.location == HttpBinding.Location.PAYLOAD)
         return protocolFunctions.deserializeFn(binding.member, fnNameSuffix = "payload") { fnName ->
@@ -245,7 +248,13 @@ class HttpBindingGenerator(
                 // The output needs to be Optional when deserializing the payload body or the caller signature
                 // will not match.
                 val outputT = symbolProvider.toSymbol(binding.member).makeOptional()
-                rustBlock("pub(crate) fn $fnName(body: &[u8]) -> std::result::Result<#T, #T>", outputT, errorSymbol) {
+                val limitsParam = if (withDecodeLimits) ", limits: #{DecodeLimits}" else ""
+                rustBlockTemplate(
+                    "pub(crate) fn $fnName(body: &[u8]$limitsParam) -> std::result::Result<#{Output}, #{Error}>",
+                    "Output" to outputT,
+                    "Error" to errorSymbol,
+                    "DecodeLimits" to RuntimeType.smithyTypes(runtimeConfig).resolve("decode_limits::DecodeLimits"),
+                ) {
                     deserializePayloadBody(
                         binding,
                         errorSymbol,
@@ -679,7 +688,8 @@ class HttpBindingGenerator(
         val block: RustWriter.(value: ValueExpression) -> Unit = { variableName ->
             if (shape.isPrimitive()) {
                 val encoder = RuntimeType.smithyTypes(runtimeConfig).resolve("primitive::Encoder")
-                rust("let mut encoder = #T::from(${variableName.asValue()});", encoder)
+                val encoderInput = if (shape.isBigNumber()) variableName.asRef() else variableName.asValue()
+                rust("let mut encoder = #T::from($encoderInput);", encoder)
             }
             val formatted =
                 headerFmtFun(
@@ -6,10 +6,13 @@
 package software.amazon.smithy.rust.codegen.core.smithy.protocols.parse
 
 import software.amazon.smithy.codegen.core.Symbol
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
 import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.ByteShape
 import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.DocumentShape
 import software.amazon.smithy.model.shapes.DoubleShape
 import software.amazon.smithy.model.shapes.FloatShape
 import software.amazon.smithy.model.shapes.IntegerShape
@@ -88,6 +91,7 @@ class CborParserGenerator(
             "SmithyCbor" to smithyCbor,
             "Decoder" to smithyCbor.resolve("Decoder"),
             "Error" to smithyCbor.resolve("decode::DeserializeError"),
+            "DecodeLimits" to RuntimeType.smithyTypes(runtimeConfig).resolve("decode_limits::DecodeLimits"),
             "HashMap" to RuntimeType.HashMap,
             *preludeScope,
         )
@@ -413,20 +417,24 @@ class CborParserGenerator(
      * operation, error and structure shapes.
      * We still generate the parser symbol even if there are no included members because the server
      * generation requires parsers for all input structures.
+     * When [withLimits] is set, the parser takes the `DecodeLimits` to check the input against.
      */
     private fun structureParser(
         shape: Shape,
         builderSymbol: Symbol,
         includedMembers: List<MemberShape>,
         fnNameSuffix: String? = null,
+        withLimits: Boolean = false,
     ): RuntimeType {
         return protocolFunctions.deserializeFn(shape, fnNameSuffix) { fnName ->
+            val limitsParam = if (withLimits) ", limits: #{DecodeLimits}" else ""
+            val withLimitsCall = if (withLimits) ".with_limits(limits)" else ""
             rustTemplate(
                 """
-                pub(crate) fn $fnName(value: &[u8], mut builder: #{Builder}) -> #{Result}<#{Builder}, #{Error}> {
+                pub(crate) fn $fnName(value: &[u8], mut builder: #{Builder}$limitsParam) -> #{Result}<#{Builder}, #{Error}> {
                     #{StructurePairParserFn:W}
 
-                    let decoder = &mut #{Decoder}::new(value);
+                    let decoder = &mut #{Decoder}::new(value)$withLimitsCall;
 
                     #{DecodeStructureMapLoop:W}
 
@@ -445,17 +453,30 @@ class CborParserGenerator(
         }
     }
 
-    override fun payloadParser(member: MemberShape): RuntimeType {
+    override fun payloadParser(member: MemberShape): RuntimeType = payloadParser(member, withLimits = false)
+
+    override fun serverPayloadParser(member: MemberShape): RuntimeType = payloadParser(member, withLimits = true)
+
+    /**
+     * When [withLimits] is set, the parser takes the `DecodeLimits` to check the payload against.
+     */
+    private fun payloadParser(
+        member: MemberShape,
+        withLimits: Boolean,
+    ): RuntimeType {
         val shape = model.expectShape(member.target)
         val returnSymbol = returnSymbolToParse(shape)
         check(shape is UnionShape || shape is StructureShape) {
             "Payload parser should only be used on structure and union shapes."
         }
-        return protocolFunctions.deserializeFn(shape, fnNameSuffix = "payload") { fnName ->
+        val fnNameSuffix = if (withLimits) "payload_with_limits" else "payload"
+        return protocolFunctions.deserializeFn(shape, fnNameSuffix = fnNameSuffix) { fnName ->
+            val limitsParam = if (withLimits) ", limits: #{DecodeLimits}" else ""
+            val withLimitsCall = if (withLimits) ".with_limits(limits)" else ""
             rustTemplate(
                 """
-                pub(crate) fn $fnName(value: &[u8]) -> #{Result}<#{ReturnType}, #{Error}> {
-                    let decoder = &mut #{Decoder}::new(value);
+                pub(crate) fn $fnName(value: &[u8]$limitsParam) -> #{Result}<#{ReturnType}, #{Error}> {
+                    let decoder = &mut #{Decoder}::new(value)$withLimitsCall;
                     #{DeserializeMember}
                 }
                 """,
@@ -494,7 +515,12 @@ class CborParserGenerator(
             return null
         }
         val inputShape = operationShape.inputShape(model)
-        return structureParser(operationShape, symbolProvider.symbolForBuilder(inputShape), includedMembers)
+        return structureParser(
+            operationShape,
+            symbolProvider.symbolForBuilder(inputShape),
+            includedMembers,
+            withLimits = true,
+        )
     }
 
     private fun deserializeMember(memberShape: MemberShape) =
@@ -514,15 +540,19 @@ class CborParserGenerator(
                 is FloatShape -> rust("decoder.float()")
                 is DoubleShape -> rust("decoder.double()")
 
+                is BigIntegerShape -> rust("decoder.big_integer()")
+                is BigDecimalShape -> rust("decoder.big_decimal()")
+
                 is TimestampShape -> rust("decoder.timestamp()")
 
+                is DocumentShape -> rust("decoder.document()")
+
                 // Aggregate shapes: https://smithy.io/2.0/spec/aggregate-types.html
                 is StructureShape -> deserializeStruct(target)
                 is CollectionShape -> deserializeCollection(target)
                 is MapShape -> deserializeMap(target)
                 is UnionShape -> deserializeUnion(target)
 
-                // Note that no protocol using CBOR serialization supports `document` shapes.
                 else -> PANIC("unexpected shape: $target")
             }
         }
@@ -52,6 +52,7 @@ import software.amazon.smithy.rust.codegen.core.util.PANIC
 import software.amazon.smithy.rust.codegen.core.util.dq
 import software.amazon.smithy.rust.codegen.core.util.hasTrait
 import software.amazon.smithy.rust.codegen.core.util.inputShape
+import software.amazon.smithy.rust.codegen.core.util.isBigNumber
 import software.amazon.smithy.rust.codegen.core.util.isTargetUnit
 import software.amazon.smithy.rust.codegen.core.util.outputShape
 import software.amazon.smithy.utils.StringUtils
@@ -109,13 +110,19 @@ class JsonParserGenerator(
             "expect_blob_or_null" to smithyJson.resolve("deserialize::token::expect_blob_or_null"),
             "expect_bool_or_null" to smithyJson.resolve("deserialize::token::expect_bool_or_null"),
             "expect_document" to smithyJson.resolve("deserialize::token::expect_document"),
+            "expect_integer_or_null" to smithyJson.resolve("deserialize::token::expect_integer_or_null"),
             "expect_number_or_null" to smithyJson.resolve("deserialize::token::expect_number_or_null"),
             "expect_start_array" to smithyJson.resolve("deserialize::token::expect_start_array"),
             "expect_start_object" to smithyJson.resolve("deserialize::token::expect_start_object"),
             "expect_string_or_null" to smithyJson.resolve("deserialize::token::expect_string_or_null"),
             "expect_timestamp_or_null" to smithyJson.resolve("deserialize::token::expect_timestamp_or_null"),
             "json_token_iter" to smithyJson.resolve("deserialize::json_token_iter"),
+            "json_token_iter_with_limits" to smithyJson.resolve("deserialize::json_token_iter_with_limits"),
+            "DecodeLimits" to RuntimeType.smithyTypes(runtimeConfig).resolve("decode_limits::DecodeLimits"),
             "Peekable" to RuntimeType.std.resolve("iter::Peekable"),
+            "ByteStream" to RuntimeType.byteStream(runtimeConfig),
+            "ByteStreamTokenizer" to smithyJson.resolve("deserialize::incremental::ByteStreamTokenizer"),
+            "ListItems" to smithyJson.resolve("deserialize::incremental::ListItems"),
             "skip_value" to smithyJson.resolve("deserialize::token::skip_value"),
             "skip_to_end" to smithyJson.resolve("deserialize::token::skip_to_end"),
             "Token" to smithyJson.resolve("deserialize::Token"),
@@ -128,23 +135,32 @@ class JsonParserGenerator(
      * operation, error and structure shapes.
      * We still generate the parser symbol even if there are no included members because the server
      * generation requires parsers for all input structures.
+     * When [withLimits] is set, the parser takes the `DecodeLimits` to check the input against.
      */
     private fun structureParser(
         shape: Shape,
         builderSymbol: Symbol,
         includedMembers: List<MemberShape>,
         fnNameSuffix: String? = null,
+        withLimits: Boolean = false,
     ): RuntimeType {
         return protocolFunctions.deserializeFn(shape, fnNameSuffix) { fnName ->
             val unusedMut = if (includedMembers.isEmpty()) "##[allow(unused_mut)] " else ""
+            val limitsParam = if (withLimits) ", limits: #{DecodeLimits}" else ""
+            val tokenIter =
+                if (withLimits) {
+                    "#{json_token_iter_with_limits}(#{or_empty}(value), limits)"
+                } else {
+                    "#{json_token_iter}(#{or_empty}(value))"
+                }
             rustBlockTemplate(
-                "pub(crate) fn $fnName(value: &[u8], ${unusedMut}mut builder: #{Builder}) -> Result<#{Builder}, #{Error}>",
+                "pub(crate) fn $fnName(value: &[u8], ${unusedMut}mut builder: #{Builder}$limitsParam) -> Result<#{Builder}, #{Error}>",
                 "Builder" to builderSymbol,
                 *codegenScope,
             ) {
                 rustTemplate(
                     """
-                    let mut tokens_owned = #{json_token_iter}(#{or_empty}(value)).peekable();
+                    let mut tokens_owned = $tokenIter.peekable();
                     let tokens = &mut tokens_owned;
                     #{expect_start_object}(tokens.next())?;
                     """,
@@ -157,15 +173,27 @@ class JsonParserGenerator(
         }
     }
 
-    override fun payloadParser(member: MemberShape): RuntimeType {
+    override fun payloadParser(member: MemberShape): RuntimeType = payloadParser(member, withLimits = false)
+
+    override fun serverPayloadParser(member: MemberShape): RuntimeType = payloadParser(member, withLimits = true)
+
+    /**
+     * When [withLimits] is set, the parser takes the `DecodeLimits` to check the payload against.
+     */
+    private fun payloadParser(
+        member: MemberShape,
+        withLimits: Boolean,
+    ): RuntimeType {
         val shape = model.expectShape(member.target)
         val returnSymbolToParse = returnSymbolToParse(shape)
         check(shape is UnionShape || shape is StructureShape || shape is DocumentShape) {
             "Payload parser should only be used on structure shapes, union shapes, and document shapes."
         }
-        return protocolFunctions.deserializeFn(shape, fnNameSuffix = "payload") { fnName ->
+        val fnNameSuffix = if (withLimits) "payload_with_limits" else "payload"
+        return protocolFunctions.deserializeFn(shape, fnNameSuffix = fnNameSuffix) { fnName ->
+            val limitsParam = if (withLimits) ", limits: #{DecodeLimits}" else ""
             rustBlockTemplate(
-                "pub(crate) fn $fnName(input: &[u8]) -> Result<#{ReturnType}, #{Error}>",
+                "pub(crate) fn $fnName(input: &[u8]$limitsParam) -> Result<#{ReturnType}, #{Error}>",
                 *codegenScope,
                 "ReturnType" to returnSymbolToParse.symbol,
             ) {
@@ -176,9 +204,15 @@ class JsonParserGenerator(
                         "#{or_empty}(input)"
                     }
 
+                val tokenIter =
+                    if (withLimits) {
+                        "#{json_token_iter_with_limits}($input, limits)"
+                    } else {
+                        "#{json_token_iter}($input)"
+                    }
                 rustTemplate(
                     """
-                    let mut tokens_owned = #{json_token_iter}($input).peekable();
+                    let mut tokens_owned = $tokenIter.peekable();
                     let tokens = &mut tokens_owned;
                     """,
                     *codegenScope,
@@ -235,7 +269,46 @@ class JsonParserGenerator(
             return null
         }
         val inputShape = operationShape.inputShape(model)
-        return structureParser(operationShape, symbolProvider.symbolForBuilder(inputShape), includedMembers)
+        return structureParser(
+            operationShape,
+            symbolProvider.symbolForBuilder(inputShape),
+            includedMembers,
+            withLimits = true,
+        )
+    }
+
+    override fun listItemsParser(member: MemberShape): RuntimeType {
+        val shape = model.expectShape(member.target, CollectionShape::class.java)
+        val itemScope = arrayOf("Item" to symbolProvider.toSymbol(shape.member), *codegenScope)
+        return protocolFunctions.deserializeFn(member, fnNameSuffix = "items") { fnName ->
+            rustBlockTemplate(
+                "pub(crate) async fn $fnName(body: #{ByteStream}, limits: #{DecodeLimits}) -> Result<#{ListItems}<#{Item}>, #{Error}>",
+                *itemScope,
+            ) {
+                rustBlockTemplate("fn de_item(value: &[u8]) -> Result<Option<#{Item}>, #{Error}>", *itemScope) {
+                    rustTemplate(
+                        """
+                        let mut tokens_owned = #{json_token_iter}(value).peekable();
+                        let tokens = &mut tokens_owned;
+                        """,
+                        *codegenScope,
+                    )
+                    withBlock("let item =", ";") {
+                        deserializeMember(shape.member)
+                    }
+                    expectEndOfTokenStream()
+                    // Nulls are items of sparse lists, and are skipped in dense lists
+                    rust(if (shape.hasTrait<SparseTrait>()) "Ok(Some(item))" else "Ok(item)")
+                }
+                rustTemplate(
+                    """
+                    let tokens = #{ByteStreamTokenizer}::new(body).with_limits(limits);
+                    #{ListItems}::from_object_member(tokens, ${jsonName(member).dq()}, de_item).await
+                    """,
+                    *codegenScope,
+                )
+            }
+        }
     }
 
     private fun RustWriter.expectEndOfTokenStream() {
@@ -349,14 +422,17 @@ class JsonParserGenerator(
     }
 
     private fun RustWriter.deserializeNumber(target: NumberShape) {
-        if (target.isFloatShape) {
+        if (target.isBigNumber()) {
+            val expectFn = if (target.isBigIntegerShape) "expect_big_integer_or_null" else "expect_big_decimal_or_null"
+            rust("#T(tokens.next())?", smithyJson.resolve("deserialize::token::$expectFn"))
+        } else if (target.isFloatShape) {
             rustTemplate("#{expect_number_or_null}(tokens.next())?.map(|v| v.to_f32_lossy())", *codegenScope)
         } else if (target.isDoubleShape) {
             rustTemplate("#{expect_number_or_null}(tokens.next())?.map(|v| v.to_f64_lossy())", *codegenScope)
         } else {
             rustTemplate(
                 """
-                #{expect_number_or_null}(tokens.next())?
+                #{expect_integer_or_null}(tokens.next())?
                     .map(#{NumberType}::try_from)
                     .transpose()?
                 """,
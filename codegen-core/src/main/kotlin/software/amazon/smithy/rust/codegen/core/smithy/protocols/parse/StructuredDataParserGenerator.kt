@@ -24,6 +24,18 @@ interface StructuredDataParserGenerator {
      */
     fun payloadParser(member: MemberShape): RuntimeType
 
+    /**
+     * Generate a parse function for a server operation input member targeted with `@httpPayload`, which checks the
+     * payload against the given `DecodeLimits`
+     *
+     * ```rust
+     * fn parse_my_struct(input: &[u8], limits: DecodeLimits) -> Result<MyStruct, XmlDecodeError> {
+     *      ...
+     * }
+     * ```
+     */
+    fun serverPayloadParser(member: MemberShape): RuntimeType
+
     /**
      * Generate a parser for operation input
      * Because only a subset of fields of the operation may be impacted by the document, a builder is passed
@@ -49,15 +61,28 @@ interface StructuredDataParserGenerator {
     fun errorParser(errorShape: StructureShape): RuntimeType?
 
     /**
-     * Generate a parser for a server operation input structure
+     * Generate a parser for a server operation input structure, which checks the input against the given
+     * `DecodeLimits`
      *
      * ```rust
      * fn deser_operation_crate_operation_my_operation_input(
-     *    value: &[u8], builder: my_operation_input::Builder
+     *    value: &[u8], builder: my_operation_input::Builder, limits: DecodeLimits
      * ) -> Result<my_operation_input::Builder, Error> {
      *    ..
      * }
      * ```
      */
     fun serverInputParser(operationShape: OperationShape): RuntimeType?
+
+    /**
+     * Generate a parser that deserializes the items of the list [member] of an operation output one at a time, while
+     * the response body is still arriving. Returns `null` if the protocol doesn't support it.
+     *
+     * ```rust
+     * async fn de_items_items(body: ByteStream, limits: DecodeLimits) -> Result<ListItems<Item>, Error> {
+     *    ..
+     * }
+     * ```
+     */
+    fun listItemsParser(member: MemberShape): RuntimeType? = null
 }
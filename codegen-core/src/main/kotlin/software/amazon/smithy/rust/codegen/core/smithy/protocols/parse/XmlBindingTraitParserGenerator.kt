@@ -106,6 +106,7 @@ class XmlBindingTraitParserGenerator(
         arrayOf(
             "Blob" to RuntimeType.blob(runtimeConfig),
             "Document" to smithyXml.resolve("decode::Document"),
+            "DecodeLimits" to RuntimeType.smithyTypes(runtimeConfig).resolve("decode_limits::DecodeLimits"),
             "XmlDecodeError" to xmlDecodeError,
             "next_start_element" to smithyXml.resolve("decode::next_start_element"),
             "try_data" to smithyXml.resolve("decode::try_data"),
@@ -127,16 +128,29 @@ class XmlBindingTraitParserGenerator(
      * }
      * ```
      */
-    override fun payloadParser(member: MemberShape): RuntimeType {
+    override fun payloadParser(member: MemberShape): RuntimeType = payloadParser(member, withLimits = false)
+
+    override fun serverPayloadParser(member: MemberShape): RuntimeType = payloadParser(member, withLimits = true)
+
+    /**
+     * When [withLimits] is set, the parser takes the `DecodeLimits` to check the payload against.
+     */
+    private fun payloadParser(
+        member: MemberShape,
+        withLimits: Boolean,
+    ): RuntimeType {
         val shape = model.expectShape(member.target)
         check(shape is UnionShape || shape is StructureShape) {
             "payload parser should only be used on structures & unions"
         }
-        return protocolFunctions.deserializeFn(member) { fnName ->
-            rustBlock(
-                "pub fn $fnName(inp: &[u8]) -> Result<#1T, #2T>",
-                symbolProvider.toSymbol(shape),
-                xmlDecodeError,
+        val fnNameSuffix = if (withLimits) "with_limits" else null
+        return protocolFunctions.deserializeFn(member, fnNameSuffix) { fnName ->
+            val limitsParam = if (withLimits) ", limits: #{DecodeLimits}" else ""
+            val withLimitsCall = if (withLimits) ".with_limits(limits)" else ""
+            rustBlockTemplate(
+                "pub fn $fnName(inp: &[u8]$limitsParam) -> Result<#{Shape}, #{XmlDecodeError}>",
+                "Shape" to symbolProvider.toSymbol(shape),
+                *codegenScope,
             ) {
                 // for payloads, first look at the member trait
                 // next, look to see if this structure was renamed
@@ -144,7 +158,7 @@ class XmlBindingTraitParserGenerator(
                 val shapeName = XmlName(xmlIndex.payloadShapeName(member))
                 rustTemplate(
                     """
-                    let mut doc = #{Document}::try_from(inp)?;
+                    let mut doc = #{Document}::try_from(inp)?$withLimitsCall;
                     ##[allow(unused_mut)]
                     let mut decoder = doc.root_element()?;
                     let start_el = decoder.start_el();
@@ -250,14 +264,14 @@ class XmlBindingTraitParserGenerator(
         }
         return protocolFunctions.deserializeFn(operationShape) { fnName ->
             Attribute.AllowUnusedMut.render(this)
-            rustBlock(
-                "pub fn $fnName(inp: &[u8], mut builder: #1T) -> Result<#1T, #2T>",
-                symbolProvider.symbolForBuilder(inputShape),
-                xmlDecodeError,
+            rustBlockTemplate(
+                "pub fn $fnName(inp: &[u8], mut builder: #{Builder}, limits: #{DecodeLimits}) -> Result<#{Builder}, #{XmlDecodeError}>",
+                "Builder" to symbolProvider.symbolForBuilder(inputShape),
+                *codegenScope,
             ) {
                 rustTemplate(
                     """
-                    let mut doc = #{Document}::try_from(inp)?;
+                    let mut doc = #{Document}::try_from(inp)?.with_limits(limits);
 
                     ##[allow(unused_mut)]
                     let mut decoder = doc.root_element()?;
@@ -348,7 +362,7 @@ class XmlBindingTraitParserGenerator(
         ignoreUnexpected: Boolean = true,
         inner: RustWriter.(Ctx) -> Unit,
     ) {
-        rustBlock("while let Some(mut tag) = ${ctx.tag}.next_tag()") {
+        rustBlock("while let Some(mut tag) = ${ctx.tag}.next_tag()?") {
             rustBlock("match tag.start_el()") {
                 inner(ctx.copy(tag = "tag"))
                 if (ignoreUnexpected) {
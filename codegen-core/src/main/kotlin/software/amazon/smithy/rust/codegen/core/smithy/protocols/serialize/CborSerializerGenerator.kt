@@ -5,6 +5,8 @@
 
 package software.amazon.smithy.rust.codegen.core.smithy.protocols.serialize
 
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
 import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.ByteShape
@@ -258,8 +260,22 @@ class CborSerializerGenerator(
         }
     }
 
-    override fun documentSerializer(): RuntimeType =
-        UNREACHABLE("No protocol using CBOR supports `document` shapes, so we don't need to implement this")
+    override fun documentSerializer(): RuntimeType {
+        return ProtocolFunctions.crossOperationFn("serialize_document") { fnName ->
+            rustTemplate(
+                """
+                pub fn $fnName(input: &#{Document}) -> #{ByteSlab} {
+                    let mut encoder = #{Encoder}::new(#{Vec}::new());
+                    encoder.document(input);
+                    encoder.into_writer()
+                }
+                """,
+                "Document" to RuntimeType.document(runtimeConfig),
+                "ByteSlab" to RuntimeType.ByteSlab,
+                *codegenScope,
+            )
+        }
+    }
 
     override fun operationOutputSerializer(operationShape: OperationShape): RuntimeType? {
         // Don't generate an operation CBOR serializer if there was no operation output shape in the
@@ -388,9 +404,12 @@ class CborSerializerGenerator(
             is FloatShape -> rust("$encoder.float(${value.asValue()});")
             is DoubleShape -> rust("$encoder.double(${value.asValue()});")
 
+            is BigIntegerShape -> rust("$encoder.big_integer(${value.asRef()});")
+            is BigDecimalShape -> rust("$encoder.big_decimal(${value.asRef()});")
+
             is TimestampShape -> rust("$encoder.timestamp(${value.asRef()});")
 
-            is DocumentShape -> UNREACHABLE("Smithy RPC v2 CBOR does not support `document` shapes")
+            is DocumentShape -> rust("$encoder.document(${value.asRef()});")
 
             // Aggregate shapes: https://smithy.io/2.0/spec/aggregate-types.html
             else -> {
@@ -5,6 +5,8 @@
 
 package software.amazon.smithy.rust.codegen.core.smithy.protocols.serialize
 
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
 import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.ByteShape
@@ -418,6 +420,8 @@ class JsonSerializerGenerator(
         when (target) {
             is StringShape -> rust("$writer.string(${value.name}.as_str());")
             is BooleanShape -> rust("$writer.boolean(${value.asValue()});")
+            is BigIntegerShape -> rust("$writer.big_integer(${value.asRef()});")
+            is BigDecimalShape -> rust("$writer.big_decimal(${value.asRef()});")
             is NumberShape -> {
                 val numberType =
                     when (target) {
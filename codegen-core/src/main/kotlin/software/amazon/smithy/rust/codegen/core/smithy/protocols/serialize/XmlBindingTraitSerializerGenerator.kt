@@ -6,6 +6,8 @@
 package software.amazon.smithy.rust.codegen.core.smithy.protocols.serialize
 
 import software.amazon.smithy.codegen.core.CodegenException
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
 import software.amazon.smithy.model.shapes.BlobShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.CollectionShape
@@ -321,6 +323,14 @@ class XmlBindingTraitSerializerGenerator(
                 rust("$dereferenced.as_str()")
             }
 
+            is BigIntegerShape, is BigDecimalShape -> {
+                // Big numbers aren't `Copy`, so they are encoded from the reference
+                rust(
+                    "#T::from($input).encode()",
+                    RuntimeType.smithyTypes(runtimeConfig).resolve("primitive::Encoder"),
+                )
+            }
+
             is BooleanShape, is NumberShape -> {
                 rust(
                     "#T::from(${autoDeref(input)}).encode()",
@@ -87,10 +87,21 @@ sealed class AdditionalSettings {
 class ClientAdditionalSettings private constructor(settings: List<AdditionalSettings>) :
     AdditionalSettings.CoreAdditionalSettings(settings) {
         class Builder : CoreAdditionalSettings.Builder<ClientAdditionalSettings>() {
+            fun includeListItemStreams(enabled: Boolean = true): Builder {
+                settings.add(IncludeListItemStreams(enabled))
+                return this
+            }
+
             override fun build(): ClientAdditionalSettings = ClientAdditionalSettings(settings)
         }
 
         // Additional settings that are specific to client generation should be defined here.
+        private data class IncludeListItemStreams(val enabled: Boolean) : AdditionalSettings() {
+            override fun toObjectNode(): ObjectNode =
+                ObjectNode.builder()
+                    .withMember("includeListItemStreams", enabled)
+                    .build()
+        }
 
         companion object {
             fun builder() = Builder()
@@ -8,6 +8,8 @@ package software.amazon.smithy.rust.codegen.core.util
 import software.amazon.smithy.aws.traits.ServiceTrait
 import software.amazon.smithy.codegen.core.CodegenException
 import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.shapes.BigDecimalShape
+import software.amazon.smithy.model.shapes.BigIntegerShape
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.ListShape
 import software.amazon.smithy.model.shapes.MapShape
@@ -144,6 +146,9 @@ fun Shape.isPrimitive(): Boolean =
         else -> false
     }
 
+/** Returns true if the shape is a `bigInteger` or `bigDecimal`, which are not `Copy` in Rust */
+fun Shape.isBigNumber(): Boolean = this is BigIntegerShape || this is BigDecimalShape
+
 /** Convert a string to a ShapeId */
 fun String.shapeId() = ShapeId.from(this)
 
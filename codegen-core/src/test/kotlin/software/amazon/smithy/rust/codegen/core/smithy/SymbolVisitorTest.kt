@@ -234,6 +234,27 @@ class SymbolVisitorTest {
         sym.references[0].dependencies.shouldNotBeEmpty()
     }
 
+    @ParameterizedTest(name = "{0}")
+    @CsvSource("BigInteger", "BigDecimal")
+    fun `create big numbers`(primitiveType: String) {
+        val member = MemberShape.builder().id("foo.bar#MyStruct\$someField").target("smithy.api#$primitiveType").build()
+        val struct =
+            StructureShape.builder()
+                .id("foo.bar#MyStruct")
+                .addMember(member)
+                .build()
+        val model =
+            Model.assembler()
+                .addShapes(struct, member)
+                .assemble()
+                .unwrap()
+        val provider: SymbolProvider = testSymbolProvider(model)
+        val sym = provider.toSymbol(member)
+        sym.rustType().render(false) shouldBe "Option<$primitiveType>"
+        sym.referenceClosure().map { it.name } shouldContain primitiveType
+        sym.references[0].dependencies.shouldNotBeEmpty()
+    }
+
     @Test
     fun `creates operations`() {
         val model =
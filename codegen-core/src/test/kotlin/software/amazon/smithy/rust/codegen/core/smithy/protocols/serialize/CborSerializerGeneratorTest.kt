@@ -0,0 +1,129 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.core.smithy.protocols.serialize
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.HttpTraitHttpBindingResolver
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.ProtocolContentTypes
+import software.amazon.smithy.rust.codegen.core.smithy.protocols.parse.CborParserGenerator
+import software.amazon.smithy.rust.codegen.core.smithy.transformers.OperationNormalizer
+import software.amazon.smithy.rust.codegen.core.testutil.TestWorkspace
+import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
+import software.amazon.smithy.rust.codegen.core.testutil.compileAndTest
+import software.amazon.smithy.rust.codegen.core.testutil.renderWithModelBuilder
+import software.amazon.smithy.rust.codegen.core.testutil.testCodegenContext
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+import software.amazon.smithy.rust.codegen.core.util.inputShape
+import software.amazon.smithy.rust.codegen.core.util.lookup
+import software.amazon.smithy.rust.codegen.core.util.outputShape
+
+class CborSerializerGeneratorTest {
+    private val model =
+        """
+        namespace test
+
+        list DocumentList {
+            member: Document
+        }
+
+        @input
+        structure OpInput {
+            doc: Document,
+            docs: DocumentList,
+        }
+
+        @output
+        structure OpOutput {
+            doc: Document,
+            docs: DocumentList,
+        }
+
+        @http(uri: "/doc", method: "POST")
+        operation Op {
+            input: OpInput,
+            output: OpOutput,
+        }
+        """.asSmithyModel()
+
+    @Test
+    fun `round trips document members`() {
+        val model = OperationNormalizer.transform(model)
+        val codegenContext = testCodegenContext(model)
+        val symbolProvider = codegenContext.symbolProvider
+        val httpBindingResolver =
+            HttpTraitHttpBindingResolver(model, ProtocolContentTypes.consistent("application/cbor"))
+        val serializerGenerator = CborSerializerGenerator(codegenContext, httpBindingResolver)
+        val parserGenerator =
+            CborParserGenerator(
+                codegenContext, httpBindingResolver,
+                handleNullForNonSparseCollection = { collectionName ->
+                    writable {
+                        rustTemplate(
+                            """
+                            decoder.null()?;
+                            return #{Ok}($collectionName)
+                            """,
+                            *RuntimeType.preludeScope,
+                        )
+                    }
+                },
+            )
+        val operation = model.lookup<OperationShape>("test#Op")
+        val inputSerializer = serializerGenerator.operationInputSerializer(operation)
+        val outputParser = parserGenerator.operationParser(operation)
+        val documentSerializer = serializerGenerator.documentSerializer()
+
+        val project = TestWorkspace.testProject(symbolProvider)
+        project.lib {
+            unitTest(
+                "cbor_document_members",
+                """
+                use aws_smithy_types::{Document, Number};
+
+                let doc = Document::Object(
+                    [
+                        ("name".to_string(), Document::from("widget")),
+                        ("count".to_string(), Document::Number(Number::NegInt(-3))),
+                        (
+                            "tags".to_string(),
+                            Document::Array(vec![Document::Null, Document::Bool(true)]),
+                        ),
+                    ]
+                    .into_iter()
+                    .collect(),
+                );
+                let input = crate::test_input::OpInput::builder()
+                    .doc(doc.clone())
+                    .docs(Document::from(1.5))
+                    .build()
+                    .unwrap();
+                let serialized = ${format(inputSerializer!!)}(&input).unwrap();
+                let output = ${format(outputParser!!)}(
+                    serialized.bytes().unwrap(),
+                    crate::test_output::OpOutput::builder(),
+                )
+                .unwrap()
+                .build();
+                assert_eq!(Some(doc.clone()), output.doc);
+                assert_eq!(Some(vec![Document::from(1.5)]), output.docs);
+
+                let bytes = ${format(documentSerializer)}(&doc);
+                let mut decoder = aws_smithy_cbor::Decoder::new(&bytes);
+                assert_eq!(doc, decoder.document().unwrap());
+                """,
+            )
+        }
+        model.lookup<OperationShape>("test#Op").also { op ->
+            op.inputShape(model).renderWithModelBuilder(model, symbolProvider, project)
+            op.outputShape(model).renderWithModelBuilder(model, symbolProvider, project)
+        }
+        project.compileAndTest()
+    }
+}
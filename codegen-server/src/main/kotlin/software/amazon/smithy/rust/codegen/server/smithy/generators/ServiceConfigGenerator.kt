@@ -162,6 +162,8 @@ class ServiceConfigGenerator(
             "HttpMarker" to smithyHttpServer.resolve("plugin::HttpMarker"),
             "Tower" to RuntimeType.Tower,
             "Stack" to RuntimeType.Tower.resolve("layer::util::Stack"),
+            "DecodeLimits" to RuntimeType.smithyTypes(codegenContext.runtimeConfig).resolve("decode_limits::DecodeLimits"),
+            "DecodeLimitsLayer" to smithyHttpServer.resolve("layer::decode_limits::DecodeLimitsLayer"),
         )
     private val serviceName = codegenContext.serviceShape.id.name.toPascalCase()
 
@@ -247,6 +249,18 @@ class ServiceConfigGenerator(
                     }
                 }
 
+                /// Check the bodies of requests against the given limits while they're deserialized.
+                ///
+                /// Requests whose body exceeds one of the limits, such as one that's nested too deeply, are
+                /// rejected with a `400 Bad Request` before they're fully deserialized. By default, request
+                /// bodies aren't limited.
+                pub fn decode_limits(
+                    self,
+                    limits: #{DecodeLimits},
+                ) -> ${serviceName}ConfigBuilder<#{Stack}<#{DecodeLimitsLayer}, L>, H, M> {
+                    self.layer(#{DecodeLimitsLayer}::new(limits))
+                }
+
                 /// Add a HTTP [plugin] to the service.
                 ///
                 /// [plugin]: #{SmithyHttpServer}::plugin
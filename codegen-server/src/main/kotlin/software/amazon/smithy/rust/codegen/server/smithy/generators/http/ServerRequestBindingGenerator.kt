@@ -50,12 +50,14 @@ class ServerRequestBindingGenerator(
     fun generateDeserializePayloadFn(
         binding: HttpBindingDescriptor,
         structuredHandler: RustWriter.(String) -> Unit,
+        withDecodeLimits: Boolean = false,
     ): RuntimeType =
         httpBindingGenerator.generateDeserializePayloadFn(
             binding,
             protocol.deserializePayloadErrorType(binding).toSymbol(),
             structuredHandler,
             HttpMessageType.REQUEST,
+            withDecodeLimits,
         )
 
     fun generateDeserializePrefixHeadersFn(binding: HttpBindingDescriptor): RuntimeType =
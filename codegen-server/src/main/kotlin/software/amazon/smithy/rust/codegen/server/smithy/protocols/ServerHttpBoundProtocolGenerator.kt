@@ -11,12 +11,14 @@ import software.amazon.smithy.model.node.ExpectationNotMetException
 import software.amazon.smithy.model.pattern.UriPattern
 import software.amazon.smithy.model.shapes.BooleanShape
 import software.amazon.smithy.model.shapes.CollectionShape
+import software.amazon.smithy.model.shapes.DocumentShape
 import software.amazon.smithy.model.shapes.MapShape
 import software.amazon.smithy.model.shapes.MemberShape
 import software.amazon.smithy.model.shapes.NumberShape
 import software.amazon.smithy.model.shapes.OperationShape
 import software.amazon.smithy.model.shapes.Shape
 import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.shapes.UnionShape
 import software.amazon.smithy.model.traits.ErrorTrait
 import software.amazon.smithy.model.traits.HttpErrorTrait
 import software.amazon.smithy.model.traits.HttpPayloadTrait
@@ -703,6 +705,14 @@ class ServerHttpBoundProtocolTraitImplGenerator(
             "let mut input = #T::default();",
             inputShape.serverBuilderSymbol(codegenContext),
         )
+        val parser = structuredDataParser.serverInputParser(operationShape)
+        if (parser != null || bindings.any { payloadTakesDecodeLimits(it) }) {
+            // The limits are attached to the request's extensions, which aren't kept in its parts.
+            rustTemplate(
+                "let decode_limits = #{SmithyHttpServer}::layer::decode_limits::request_decode_limits(&request);",
+                *codegenScope,
+            )
+        }
         Attribute.AllowUnusedVariables.render(this)
         rustTemplate(
             """
@@ -713,7 +723,6 @@ class ServerHttpBoundProtocolTraitImplGenerator(
             "Request" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("http::Request"),
             "RequestParts" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("http::RequestParts"),
         )
-        val parser = structuredDataParser.serverInputParser(operationShape)
 
         if (parser != null) {
             // `null` is only returned by Smithy when there are no members, but we know there's at least one, since
@@ -749,7 +758,7 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                         &headers,
                         Some("$expectedRequestContentType"),
                     )?;
-                    input = #{parser}(bytes.as_ref(), input)?;
+                    input = #{parser}(bytes.as_ref(), input, decode_limits)?;
                     """,
                     *codegenScope,
                     "parser" to parser,
@@ -822,10 +831,16 @@ class ServerHttpBoundProtocolTraitImplGenerator(
             HttpLocation.PREFIX_HEADERS -> writable { serverRenderPrefixHeadersParser(this, binding, operationShape) }
             HttpLocation.PAYLOAD -> {
                 val structureShapeHandler: RustWriter.(String) -> Unit = { body ->
-                    rust("#T($body)", structuredDataParser.payloadParser(binding.member))
+                    rust("#T($body, limits)", structuredDataParser.serverPayloadParser(binding.member))
                 }
+                val withDecodeLimits = payloadTakesDecodeLimits(binding)
                 val deserializer =
-                    httpBindingGenerator.generateDeserializePayloadFn(binding, structuredHandler = structureShapeHandler)
+                    httpBindingGenerator.generateDeserializePayloadFn(
+                        binding,
+                        structuredHandler = structureShapeHandler,
+                        withDecodeLimits = withDecodeLimits,
+                    )
+                val decodeLimitsArg = if (withDecodeLimits) ", decode_limits" else ""
                 return writable {
                     if (binding.member.isStreaming(model)) {
                         rustTemplate(
@@ -875,7 +890,7 @@ class ServerHttpBoundProtocolTraitImplGenerator(
                             {
                                 let bytes = #{Hyper}::body::to_bytes(body).await?;
                                 #{VerifyRequestContentTypeHeader:W}
-                                #{Deserializer}(&bytes)?
+                                #{Deserializer}(&bytes$decodeLimitsArg)?
                             }
                             """,
                             "Deserializer" to deserializer,
@@ -896,6 +911,18 @@ class ServerHttpBoundProtocolTraitImplGenerator(
         }
     }
 
+    /**
+     * Whether the deserializer of [binding] checks the payload against the request's `DecodeLimits`, which is the case
+     * for non-streaming `@httpPayload` members that are parsed as structured data.
+     */
+    private fun payloadTakesDecodeLimits(binding: HttpBindingDescriptor): Boolean {
+        if (binding.location != HttpLocation.PAYLOAD || binding.member.isStreaming(model)) {
+            return false
+        }
+        val target = model.expectShape(binding.member.target)
+        return target is StructureShape || target is UnionShape || target is DocumentShape
+    }
+
     private fun serverRenderUriPathParser(
         writer: RustWriter,
         operationShape: OperationShape,
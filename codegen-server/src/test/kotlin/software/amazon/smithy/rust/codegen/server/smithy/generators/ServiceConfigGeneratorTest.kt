@@ -15,6 +15,7 @@ import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
 import software.amazon.smithy.rust.codegen.core.testutil.asSmithyModel
 import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
 import software.amazon.smithy.rust.codegen.core.testutil.unitTest
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCargoDependency
 import software.amazon.smithy.rust.codegen.server.smithy.ServerCodegenContext
@@ -290,4 +291,117 @@ internal class ServiceConfigGeneratorTest {
 
         codegenException.message.shouldContain("Injected config method `invalid_generic_bindings` has generic bindings that use `L`, `H`, or `M` to refer to the generic types. This is not allowed. Invalid generic bindings:")
     }
+
+    @Test
+    fun `decode_limits should reject request bodies that exceed the limits`() {
+        val model = File("../codegen-core/common-test-models/simple.smithy").readText().asSmithyModel()
+
+        serverIntegrationTest(model) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                tokioTest("request_bodies_exceeding_the_decode_limits_are_rejected") {
+                    rustTemplate(
+                        """
+                        use #{Tower}::ServiceExt;
+
+                        let config = crate::SimpleServiceConfig::builder()
+                            .decode_limits(#{DecodeLimits}::new().with_max_string_length(5))
+                            .build();
+                        let service = crate::SimpleService::builder(config)
+                            .operation(|input: crate::input::OperationInput| async move {
+                                crate::output::OperationOutput { message: input.message }
+                            })
+                            .build_unchecked();
+                        let request = |message: &str| {
+                            #{Http}::Request::builder()
+                                .method("POST")
+                                .uri("/operation")
+                                .header("content-type", "application/json")
+                                .body(#{SmithyHttpServer}::body::Body::from(format!(r##"{{"message":"{message}"}}"##)))
+                                .unwrap()
+                        };
+
+                        let response = service.clone().oneshot(request("hello")).await.unwrap();
+                        assert_eq!(200, response.status().as_u16());
+                        let response = service.oneshot(request("hello world")).await.unwrap();
+                        assert_eq!(400, response.status().as_u16());
+                        """,
+                        "DecodeLimits" to
+                            RuntimeType.smithyTypes(codegenContext.runtimeConfig)
+                                .resolve("decode_limits::DecodeLimits"),
+                        "Http" to RuntimeType.Http,
+                        "SmithyHttpServer" to ServerCargoDependency.smithyHttpServer(codegenContext.runtimeConfig).toType(),
+                        "Tower" to RuntimeType.Tower,
+                    )
+                }
+            }
+        }
+    }
+
+    @Test
+    fun `decode_limits should reject httpPayload bodies that exceed the limits`() {
+        val model =
+            """
+            namespace test
+
+            use aws.protocols#restJson1
+
+            @restJson1
+            service PayloadService {
+                operations: [PutPayload]
+            }
+
+            @http(method: "POST", uri: "/payload")
+            operation PutPayload {
+                input := {
+                    @httpPayload
+                    payload: Payload
+                }
+                output := {}
+            }
+
+            structure Payload {
+                message: String
+            }
+            """.asSmithyModel(smithyVersion = "2")
+
+        serverIntegrationTest(model) { codegenContext, rustCrate ->
+            rustCrate.testModule {
+                tokioTest("http_payload_bodies_exceeding_the_decode_limits_are_rejected") {
+                    rustTemplate(
+                        """
+                        use #{Tower}::ServiceExt;
+
+                        let config = crate::PayloadServiceConfig::builder()
+                            .decode_limits(#{DecodeLimits}::new().with_max_string_length(5))
+                            .build();
+                        let service = crate::PayloadService::builder(config)
+                            .put_payload(|_input: crate::input::PutPayloadInput| async move {
+                                crate::output::PutPayloadOutput {}
+                            })
+                            .build_unchecked();
+                        let request = |message: &str| {
+                            #{Http}::Request::builder()
+                                .method("POST")
+                                .uri("/payload")
+                                .header("content-type", "application/json")
+                                .body(#{SmithyHttpServer}::body::Body::from(format!(r##"{{"message":"{message}"}}"##)))
+                                .unwrap()
+                        };
+
+                        let response = service.clone().oneshot(request("hello")).await.unwrap();
+                        assert_eq!(200, response.status().as_u16());
+                        let response = service.oneshot(request("hello world")).await.unwrap();
+                        assert_eq!(400, response.status().as_u16());
+                        """,
+                        "DecodeLimits" to
+                            RuntimeType.smithyTypes(codegenContext.runtimeConfig)
+                                .resolve("decode_limits::DecodeLimits"),
+                        "Http" to RuntimeType.Http,
+                        "SmithyHttpServer" to ServerCargoDependency.smithyHttpServer(codegenContext.runtimeConfig).toType(),
+                        "Tower" to RuntimeType.Tower,
+                    )
+                }
+            }
+        }
+    }
 }
@@ -338,7 +338,7 @@ internal class CborSerializerAndParserGeneratorSerdeRoundTripIntegrationTest {
                                         #{ciborium}::into_writer(&expected, &mut bytes)
                                             .expect("failed to serialize to `Vec<u8>` with `ciborium`");
                                         let input = #{InputBuilder}::default();
-                                        let input = #{DeserializeFn}(&bytes, input)
+                                        let input = #{DeserializeFn}(&bytes, input, Default::default())
                                            .expect("our generated CBOR deserializer failed");
                                         let actual = input.build()$err;
                                         #{AssertEq}(expected, actual);
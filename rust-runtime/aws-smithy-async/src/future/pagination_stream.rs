@@ -12,6 +12,7 @@ use std::task::{Context, Poll};
 
 pub mod collect;
 pub mod fn_stream;
+pub mod paginator;
 use fn_stream::FnStream;
 
 /// Stream specifically made to support paginators.
@@ -0,0 +1,456 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A token-driven paginator with checkpoints, resumption and prefetching.
+
+use crate::future::pagination_stream::fn_stream::FnStream;
+use crate::future::pagination_stream::{PaginationStream, TryFlatMap};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+/// A page yielded by a [`Paginator`], along with the continuation tokens around it.
+///
+/// To checkpoint a stream, persist the [`next_token`](CheckpointedPage::next_token) of the last
+/// page that was fully processed, and pass it to [`Paginator::resume_from`] to pick up where
+/// the stream left off.
+#[derive(Clone, Debug)]
+pub struct CheckpointedPage<Page, Token> {
+    page: Page,
+    token: Option<Token>,
+    next_token: Option<Token>,
+}
+
+impl<Page, Token> CheckpointedPage<Page, Token> {
+    /// Returns the page.
+    pub fn page(&self) -> &Page {
+        &self.page
+    }
+
+    /// Consumes this and returns the page.
+    pub fn into_page(self) -> Page {
+        self.page
+    }
+
+    /// Returns the token this page was requested with, or `None` for the first page.
+    pub fn token(&self) -> Option<&Token> {
+        self.token.as_ref()
+    }
+
+    /// Returns the token to request the page after this one, or `None` if this is the last page.
+    pub fn next_token(&self) -> Option<&Token> {
+        self.next_token.as_ref()
+    }
+}
+
+/// Creates a [`PaginationStream`] of [`CheckpointedPage`]s from a function that fetches one page.
+///
+/// The fetch function is called with the continuation token of the page to fetch (`None` for the
+/// first page), and returns the page along with the token for the next one. The stream ends after
+/// a page without a next token, or after the first error. The fetch function should return `None`
+/// for an empty token.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn docs() -> Result<(), &'static str> {
+/// use aws_smithy_async::future::pagination_stream::paginator::Paginator;
+/// use aws_smithy_async::future::pagination_stream::TryFlatMap;
+/// # struct Output { items: Vec<String>, next_token: Option<String> }
+/// # async fn list_items(_token: Option<String>) -> Result<Output, &'static str> { todo!() }
+/// # fn load_checkpoint() -> Option<String> { todo!() }
+/// # fn save_checkpoint(_token: Option<&String>) { todo!() }
+///
+/// let mut paginator = Paginator::new(|token| async move {
+///     let output = list_items(token).await?;
+///     Ok((output.items, output.next_token))
+/// });
+/// if let Some(token) = load_checkpoint() {
+///     paginator = paginator.resume_from(token);
+/// }
+/// let mut pages = paginator.send();
+/// while let Some(page) = pages.try_next().await? {
+///     // process `page.page()`
+///     save_checkpoint(page.next_token());
+/// }
+///
+/// // Or, to stream the items instead of the pages:
+/// let items: Vec<String> = TryFlatMap::new(Paginator::new(|token| async move {
+///     let output = list_items(token).await?;
+///     Ok((output.items, output.next_token))
+/// })
+/// .send())
+/// .flat_map(|page| page.into_page())
+/// .try_collect()
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Paginator<Token, F> {
+    fetch: F,
+    start_token: Option<Token>,
+    stop_on_duplicate_token: bool,
+    prefetch: usize,
+}
+
+impl<Token: fmt::Debug, F> fmt::Debug for Paginator<Token, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Paginator");
+        debug
+            .field("start_token", &self.start_token)
+            .field("stop_on_duplicate_token", &self.stop_on_duplicate_token)
+            .field("prefetch", &self.prefetch)
+            .finish()
+    }
+}
+
+impl<Token, F> Paginator<Token, F> {
+    /// Creates a paginator that fetches pages with `fetch`.
+    pub fn new<Fut, Page, Err>(fetch: F) -> Self
+    where
+        F: Fn(Option<Token>) -> Fut,
+        Fut: Future<Output = Result<(Page, Option<Token>), Err>>,
+    {
+        Self {
+            fetch,
+            start_token: None,
+            stop_on_duplicate_token: true,
+            prefetch: 0,
+        }
+    }
+
+    /// Starts the stream at the page for `token` instead of at the first page.
+    ///
+    /// This is typically the [`next_token`](CheckpointedPage::next_token) of the last page
+    /// processed before the previous stream was interrupted.
+    pub fn resume_from(mut self, token: Token) -> Self {
+        self.start_token = Some(token);
+        self
+    }
+
+    /// Stop paginating when a page returns the token it was requested with.
+    ///
+    /// _By default this is set to true._
+    ///
+    /// Some APIs can return the same token while still producing new results, e.g. when tailing
+    /// a log. This option can be set to `false` to accommodate these use cases. The last page
+    /// of a stopped stream has no [`next_token`](CheckpointedPage::next_token).
+    pub fn stop_on_duplicate_token(mut self, stop_on_duplicate_token: bool) -> Self {
+        self.stop_on_duplicate_token = stop_on_duplicate_token;
+        self
+    }
+
+    /// Fetch up to `pages` pages ahead of the consumer of the stream.
+    ///
+    /// _By default this is set to zero_, and each page is only fetched when it is requested.
+    ///
+    /// Pages are still fetched one at a time, since each request needs the token of the page
+    /// before it. With the `rt-tokio` feature, they're fetched by a background task while the
+    /// consumer is busy with earlier pages. The task is spawned onto the current Tokio runtime
+    /// when the stream is first polled, and is aborted when the stream is dropped. Without a Tokio
+    /// runtime, pages are only fetched ahead while the stream is being polled.
+    pub fn prefetch(mut self, pages: usize) -> Self {
+        self.prefetch = pages;
+        self
+    }
+
+    /// Creates the pagination stream.
+    ///
+    /// _Note:_ No pages will be fetched until the stream is used
+    /// (e.g. with the [`.next().await`](PaginationStream::next) method).
+    pub fn send<Fut, Page, Err>(
+        self,
+    ) -> PaginationStream<Result<CheckpointedPage<Page, Token>, Err>>
+    where
+        Token: Clone + PartialEq + Send + 'static,
+        F: Fn(Option<Token>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<(Page, Option<Token>), Err>> + Send + 'static,
+        Page: Send + 'static,
+        Err: Send + 'static,
+    {
+        let mut pages = Pages {
+            fetch: self.fetch,
+            token: self.start_token,
+            stop_on_duplicate_token: self.stop_on_duplicate_token,
+            done: false,
+        };
+        if self.prefetch > 0 {
+            return prefetching(pages, self.prefetch);
+        }
+        PaginationStream::new(FnStream::new(|tx| {
+            Box::pin(async move {
+                while let Some(page) = pages.next().await {
+                    if tx.send(page).await.is_err() {
+                        // receiving end was dropped
+                        return;
+                    }
+                }
+            }) as Pin<Box<dyn Future<Output = ()> + Send>>
+        }))
+    }
+}
+
+/// The state of a paginator between pages.
+struct Pages<Token, F> {
+    fetch: F,
+    token: Option<Token>,
+    stop_on_duplicate_token: bool,
+    done: bool,
+}
+
+impl<Token, F> Pages<Token, F>
+where
+    Token: Clone + PartialEq,
+{
+    async fn next<Fut, Page, Err>(&mut self) -> Option<Result<CheckpointedPage<Page, Token>, Err>>
+    where
+        F: Fn(Option<Token>) -> Fut,
+        Fut: Future<Output = Result<(Page, Option<Token>), Err>>,
+    {
+        if self.done {
+            return None;
+        }
+        let token = self.token.take();
+        match (self.fetch)(token.clone()).await {
+            Ok((page, mut next_token)) => {
+                if self.stop_on_duplicate_token && next_token.is_some() && next_token == token {
+                    next_token = None;
+                }
+                self.done = next_token.is_none();
+                self.token = next_token.clone();
+                Some(Ok(CheckpointedPage {
+                    page,
+                    token,
+                    next_token,
+                }))
+            }
+            Err(err) => {
+                self.done = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl<Page, Token, Err> PaginationStream<Result<CheckpointedPage<Page, Token>, Err>> {
+    /// Produces a stream of the pages alone, without their continuation tokens.
+    pub fn into_pages(self) -> PaginationStream<Result<Page, Err>>
+    where
+        Page: Send + 'static,
+        Token: Send + 'static,
+        Err: Send + 'static,
+    {
+        TryFlatMap::new(self).flat_map(|page| Some(page.into_page()))
+    }
+}
+
+fn prefetching<Token, F, Fut, Page, Err>(
+    mut pages: Pages<Token, F>,
+    prefetch: usize,
+) -> PaginationStream<Result<CheckpointedPage<Page, Token>, Err>>
+where
+    Token: Clone + PartialEq + Send + 'static,
+    F: Fn(Option<Token>) -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(Page, Option<Token>), Err>> + Send + 'static,
+    Page: Send + 'static,
+    Err: Send + 'static,
+{
+    /// Aborts the prefetching task when the stream is dropped
+    #[cfg(feature = "rt-tokio")]
+    struct AbortOnDrop(tokio::task::JoinHandle<()>);
+
+    #[cfg(feature = "rt-tokio")]
+    impl Drop for AbortOnDrop {
+        fn drop(&mut self) {
+            self.0.abort();
+        }
+    }
+
+    PaginationStream::new(FnStream::new(move |tx| {
+        Box::pin(async move {
+            let (prefetched_tx, mut prefetched_rx) = tokio::sync::mpsc::channel(prefetch);
+            let fetch = async move {
+                // Reserve room for a page before fetching it, so that no more than `prefetch`
+                // pages are ever waiting for the consumer
+                while let Ok(permit) = prefetched_tx.reserve().await {
+                    match pages.next().await {
+                        Some(page) => permit.send(page),
+                        None => return,
+                    }
+                }
+            };
+            let forward = async move {
+                while let Some(page) = prefetched_rx.recv().await {
+                    if tx.send(page).await.is_err() {
+                        // receiving end was dropped
+                        return;
+                    }
+                }
+            };
+            #[cfg(feature = "rt-tokio")]
+            if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                let _task = AbortOnDrop(runtime.spawn(fetch));
+                forward.await;
+                return;
+            }
+            futures_util::future::join(fetch, forward).await;
+        }) as Pin<Box<dyn Future<Output = ()> + Send>>
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CheckpointedPage, Paginator};
+    use crate::future::pagination_stream::TryFlatMap;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio_test::{assert_pending, assert_ready};
+
+    /// The result of fetching a page in tests
+    type Fetched<Page, Token> = std::future::Ready<Result<(Page, Option<Token>), &'static str>>;
+
+    /// Lists the numbers `0..10`, three at a time, with the next index as the token
+    fn list_numbers(
+        calls: Arc<AtomicUsize>,
+    ) -> impl Fn(Option<usize>) -> Fetched<Vec<usize>, usize> {
+        move |token| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let start = token.unwrap_or(0);
+            let end = (start + 3).min(10);
+            std::future::ready(Ok(((start..end).collect(), (end < 10).then_some(end))))
+        }
+    }
+
+    fn tokens<P, T: Copy>(pages: &[CheckpointedPage<P, T>]) -> Vec<(Option<T>, Option<T>)> {
+        pages
+            .iter()
+            .map(|page| (page.token().copied(), page.next_token().copied()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn yields_pages_with_their_tokens() {
+        let pages = Paginator::new(list_numbers(Default::default()))
+            .send()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![
+                (None, Some(3)),
+                (Some(3), Some(6)),
+                (Some(6), Some(9)),
+                (Some(9), None)
+            ],
+            tokens(&pages)
+        );
+        assert_eq!(&vec![9], pages[3].page());
+    }
+
+    #[tokio::test]
+    async fn resumes_from_a_checkpoint() {
+        let mut stream = Paginator::new(list_numbers(Default::default())).send();
+        stream.try_next().await.unwrap();
+        let checkpoint = *stream
+            .try_next()
+            .await
+            .unwrap()
+            .unwrap()
+            .next_token()
+            .unwrap();
+        drop(stream);
+
+        let items = TryFlatMap::new(
+            Paginator::new(list_numbers(Default::default()))
+                .resume_from(checkpoint)
+                .send(),
+        )
+        .flat_map(CheckpointedPage::into_page)
+        .try_collect()
+        .await;
+        assert_eq!(Ok(vec![6, 7, 8, 9]), items);
+    }
+
+    #[tokio::test]
+    async fn stops_on_duplicate_token_and_errors() {
+        fn repeating(result: Result<(), &'static str>) -> impl Fn(Option<u8>) -> Fetched<(), u8> {
+            move |_| std::future::ready(result.map(|_| ((), Some(1))))
+        }
+
+        let pages = Paginator::new(repeating(Ok(())))
+            .send()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(vec![(None, Some(1)), (Some(1), None)], tokens(&pages));
+
+        let mut stream = Paginator::new(repeating(Err("bummer"))).send();
+        assert_eq!(
+            Some(Err("bummer")),
+            stream.next().await.map(|r| r.map(drop))
+        );
+        assert!(stream.next().await.is_none());
+    }
+
+    /// Lists the numbers `0..30`, one at a time, with the next number as the token
+    fn count_to_thirty(calls: Arc<AtomicUsize>) -> impl Fn(Option<usize>) -> Fetched<usize, usize> {
+        move |token| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            let n = token.unwrap_or(0);
+            std::future::ready(Ok((n, (n < 29).then_some(n + 1))))
+        }
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn prefetches_pages_in_the_background() {
+        /// With the clock paused, the runtime only advances it once every task is blocked
+        async fn wait_until_idle() {
+            tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        }
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut stream = Paginator::new(count_to_thirty(calls.clone()))
+            .prefetch(2)
+            .send();
+        wait_until_idle().await;
+        assert_eq!(0, calls.load(Ordering::SeqCst));
+
+        let first = stream.try_next().await.unwrap().unwrap();
+        assert_eq!(&0, first.page());
+        wait_until_idle().await;
+        // The first page, plus two pages waiting for the consumer
+        assert_eq!(3, calls.load(Ordering::SeqCst));
+
+        let second = stream.try_next().await.unwrap().unwrap();
+        assert_eq!(&1, second.page());
+        wait_until_idle().await;
+        assert_eq!(4, calls.load(Ordering::SeqCst));
+
+        let rest = stream.into_pages().try_collect().await.unwrap();
+        assert_eq!((2..30).collect::<Vec<_>>(), rest);
+        assert_eq!(30, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn prefetches_pages_while_polled_without_a_runtime() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut stream = Paginator::new(count_to_thirty(calls.clone()))
+            .prefetch(2)
+            .send();
+
+        let mut next = tokio_test::task::spawn(stream.next());
+        assert_pending!(next.poll());
+        let first = assert_ready!(next.poll()).unwrap().unwrap();
+        assert_eq!(0, first.into_page());
+        drop(next);
+        // Nothing is fetched while the stream isn't polled
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+
+        let rest = tokio_test::block_on(stream.into_pages().try_collect()).unwrap();
+        assert_eq!((1..30).collect::<Vec<_>>(), rest);
+        assert_eq!(30, calls.load(Ordering::SeqCst));
+    }
+}
@@ -14,4 +14,7 @@ pub use instant_sleep::{instant_time_and_sleep, InstantSleep};
 mod manual_time;
 pub use manual_time::ManualTimeSource;
 
+mod simulated_runtime;
+pub use simulated_runtime::{SimulatedRuntime, SleepRecord};
+
 pub mod tick_advance_sleep;
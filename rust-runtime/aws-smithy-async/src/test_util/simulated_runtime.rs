@@ -0,0 +1,220 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+use crate::time::{SharedTimeSource, TimeSource};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::runtime::{Handle, Runtime};
+use tokio::time::Instant;
+
+/// A sleep that was started on a [`SimulatedRuntime`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SleepRecord {
+    started_at: SystemTime,
+    duration: Duration,
+}
+
+impl SleepRecord {
+    /// Returns the simulated time at which the sleep was started.
+    pub fn started_at(&self) -> SystemTime {
+        self.started_at
+    }
+
+    /// Returns the requested duration of the sleep.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Returns the simulated time at which the sleep is (or was) due to complete.
+    pub fn ends_at(&self) -> SystemTime {
+        self.started_at + self.duration
+    }
+}
+
+/// A runtime that runs async code under simulated time.
+///
+/// The runtime supplies a time source and a sleep implementation that share the same clock. That
+/// clock only moves when every task on the runtime is idle, at which point it jumps straight to
+/// the end of the earliest pending sleep. This lets a whole client, including its retries,
+/// timeouts, identity cache refreshes and rate limiting, run to completion instantly and
+/// deterministically. Every sleep is recorded in a [timeline](SimulatedRuntime::timeline).
+///
+/// This is built on a current-thread Tokio runtime with its clock paused, so it can't be used
+/// from within another Tokio runtime (e.g. a `#[tokio::test]`). Tasks spawned with
+/// `tokio::spawn` from [`block_on`](SimulatedRuntime::block_on) also run under simulated time.
+/// Note that the clock is advanced even while tasks are waiting on I/O, so the client should use
+/// a simulated HTTP client as well.
+///
+/// # Examples
+///
+/// ```
+/// use aws_smithy_async::rt::sleep::AsyncSleep;
+/// use aws_smithy_async::test_util::SimulatedRuntime;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let runtime = SimulatedRuntime::new(UNIX_EPOCH);
+/// let sleep_impl = runtime.sleep_impl();
+/// runtime.block_on(async move {
+///     // Completes instantly, with the clock ending one minute later
+///     sleep_impl.sleep(Duration::from_secs(60)).await;
+/// });
+/// assert_eq!(UNIX_EPOCH + Duration::from_secs(60), runtime.now());
+/// assert_eq!(1, runtime.timeline().len());
+/// ```
+#[derive(Debug)]
+pub struct SimulatedRuntime {
+    runtime: Runtime,
+    clock: Clock,
+}
+
+impl SimulatedRuntime {
+    /// Creates a runtime whose clock starts at `start_time`.
+    pub fn new(start_time: SystemTime) -> Self {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .start_paused(true)
+            .build()
+            .expect("failed to build a current-thread runtime");
+        let start = {
+            let _guard = runtime.enter();
+            Instant::now()
+        };
+        let clock = Clock {
+            handle: runtime.handle().clone(),
+            start_time,
+            start,
+            timeline: Default::default(),
+        };
+        Self { runtime, clock }
+    }
+
+    /// Runs `future` to completion, advancing the clock whenever all tasks are idle.
+    pub fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Returns a time source that reads the simulated clock.
+    pub fn time_source(&self) -> SharedTimeSource {
+        SharedTimeSource::new(SimulatedTimeSource {
+            clock: self.clock.clone(),
+        })
+    }
+
+    /// Returns a sleep implementation that waits on the simulated clock.
+    pub fn sleep_impl(&self) -> SharedAsyncSleep {
+        SharedAsyncSleep::new(SimulatedSleep {
+            clock: self.clock.clone(),
+        })
+    }
+
+    /// Returns the current simulated time.
+    pub fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+
+    /// Returns every sleep started on this runtime so far, in the order they were started.
+    pub fn timeline(&self) -> Vec<SleepRecord> {
+        self.clock.timeline.lock().unwrap().clone()
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Clock {
+    handle: Handle,
+    start_time: SystemTime,
+    start: Instant,
+    timeline: Arc<Mutex<Vec<SleepRecord>>>,
+}
+
+impl Clock {
+    fn now(&self) -> SystemTime {
+        // Reading the paused clock requires the runtime's context
+        let _guard = self.handle.enter();
+        self.start_time + self.start.elapsed()
+    }
+}
+
+#[derive(Debug)]
+struct SimulatedTimeSource {
+    clock: Clock,
+}
+
+impl TimeSource for SimulatedTimeSource {
+    fn now(&self) -> SystemTime {
+        self.clock.now()
+    }
+}
+
+#[derive(Debug)]
+struct SimulatedSleep {
+    clock: Clock,
+}
+
+impl AsyncSleep for SimulatedSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let started_at = self.clock.now();
+        self.clock.timeline.lock().unwrap().push(SleepRecord {
+            started_at,
+            duration,
+        });
+        let sleep = {
+            let _guard = self.clock.handle.enter();
+            tokio::time::sleep(duration)
+        };
+        Sleep::new(sleep)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SimulatedRuntime;
+    use crate::future::timeout::Timeout;
+    use crate::rt::sleep::AsyncSleep;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn advances_time_when_idle_and_records_sleeps() {
+        let runtime = SimulatedRuntime::new(UNIX_EPOCH);
+        let sleep_impl = runtime.sleep_impl();
+        runtime.block_on(async move {
+            let first = tokio::spawn({
+                let sleep_impl = sleep_impl.clone();
+                async move { sleep_impl.sleep(Duration::from_secs(3)).await }
+            });
+            sleep_impl.sleep(Duration::from_secs(1)).await;
+            sleep_impl.sleep(Duration::from_secs(5)).await;
+            first.await.unwrap();
+        });
+
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(6), runtime.now());
+        let timeline: Vec<_> = runtime
+            .timeline()
+            .iter()
+            .map(|sleep| (sleep.started_at(), sleep.ends_at()))
+            .collect();
+        let secs = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        assert_eq!(
+            vec![(secs(0), secs(1)), (secs(0), secs(3)), (secs(1), secs(6))],
+            timeline
+        );
+    }
+
+    #[test]
+    fn time_source_and_sleep_share_a_clock() {
+        let runtime = SimulatedRuntime::new(UNIX_EPOCH + Duration::from_secs(100));
+        let time_source = runtime.time_source();
+        let sleep_impl = runtime.sleep_impl();
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(100), time_source.now());
+
+        let result = runtime.block_on(async move {
+            let never = std::future::pending::<()>();
+            Timeout::new(never, sleep_impl.sleep(Duration::from_secs(30))).await
+        });
+        assert!(result.is_err());
+        assert_eq!(UNIX_EPOCH + Duration::from_secs(130), time_source.now());
+    }
+}
@@ -5,11 +5,20 @@
 
 use std::borrow::Cow;
 
-use aws_smithy_types::{Blob, DateTime};
+use aws_smithy_types::decode_limits::{DecodeLimits, LimitExceeded};
+use aws_smithy_types::{BigDecimal, BigInteger, Blob, DateTime, Document, Number};
 use minicbor::decode::Error;
 
 use crate::data::Type;
 
+/// The maximum nesting depth of arrays, maps and tags when decoding a [`Document`].
+const MAX_DOCUMENT_RECURSION: usize = 256;
+
+/// The maximum length in bytes of a bignum's magnitude when decoding a [`BigInteger`], which is
+/// enough for numbers with about 2,400 decimal digits. Converting the magnitude to decimal takes
+/// time quadratic in its length, so it is bounded regardless of the decoder's limits.
+const MAX_BIGNUM_LENGTH: usize = 1024;
+
 /// Provides functions for decoding a CBOR object with a known schema.
 ///
 /// Although CBOR is a self-describing format, this decoder is tailored for cases where the schema
@@ -20,6 +29,8 @@ use crate::data::Type;
 #[derive(Debug, Clone)]
 pub struct Decoder<'b> {
     decoder: minicbor::Decoder<'b>,
+    limits: DecodeLimits,
+    token_count: usize,
 }
 
 /// When any of the decode methods are called they look for that particular data type at the current
@@ -28,6 +39,7 @@ pub struct Decoder<'b> {
 pub struct DeserializeError {
     #[allow(dead_code)]
     _inner: Error,
+    limit: Option<LimitExceeded>,
 }
 
 impl std::fmt::Display for DeserializeError {
@@ -40,53 +52,59 @@ impl std::error::Error for DeserializeError {}
 
 impl DeserializeError {
     pub(crate) fn new(inner: Error) -> Self {
-        Self { _inner: inner }
+        Self {
+            _inner: inner,
+            limit: None,
+        }
+    }
+
+    pub(crate) fn from_limit(limit: LimitExceeded, at: usize) -> Self {
+        Self {
+            _inner: Error::message(format!("CBOR input {limit}")).at(at),
+            limit: Some(limit),
+        }
     }
 
     /// More than one union variant was detected: `unexpected_type` was unexpected.
     pub fn unexpected_union_variant(unexpected_type: Type, at: usize) -> Self {
-        Self {
-            _inner: Error::type_mismatch(unexpected_type.into_minicbor_type())
+        Self::new(
+            Error::type_mismatch(unexpected_type.into_minicbor_type())
                 .with_message("encountered unexpected union variant; expected end of union")
                 .at(at),
-        }
+        )
     }
 
     /// Unknown union variant was detected. Servers reject unknown union varaints.
     pub fn unknown_union_variant(variant_name: &str, at: usize) -> Self {
-        Self {
-            _inner: Error::message(format!(
+        Self::new(
+            Error::message(format!(
                 "encountered unknown union variant {}",
                 variant_name
             ))
             .at(at),
-        }
+        )
     }
 
     /// More than one union variant was detected, but we never even got to parse the first one.
     /// We immediately raise this error when detecting a union serialized as a fixed-length CBOR
     /// map whose length (specified upfront) is a value different than 1.
     pub fn mixed_union_variants(at: usize) -> Self {
-        Self {
-            _inner: Error::message(
+        Self::new(
+            Error::message(
                 "encountered mixed variants in union; expected a single union variant to be set",
             )
             .at(at),
-        }
+        )
     }
 
     /// Expected end of stream but more data is available.
     pub fn expected_end_of_stream(at: usize) -> Self {
-        Self {
-            _inner: Error::message("encountered additional data; expected end of stream").at(at),
-        }
+        Self::new(Error::message("encountered additional data; expected end of stream").at(at))
     }
 
     /// Returns a custom error with an offset.
     pub fn custom(message: impl Into<Cow<'static, str>>, at: usize) -> Self {
-        Self {
-            _inner: Error::message(message.into()).at(at),
-        }
+        Self::new(Error::message(message.into()).at(at))
     }
 
     /// An unexpected type was encountered.
@@ -95,6 +113,12 @@ impl DeserializeError {
     pub fn is_type_mismatch(&self) -> bool {
         self._inner.is_type_mismatch()
     }
+
+    /// Returns the limit that was exceeded if this error was caused by the input exceeding the
+    /// configured [`DecodeLimits`].
+    pub fn limit_exceeded(&self) -> Option<&LimitExceeded> {
+        self.limit.as_ref()
+    }
 }
 
 /// Macro for delegating method calls to the decoder.
@@ -116,6 +140,7 @@ macro_rules! delegate_method {
     ($($(#[$meta:meta])* $wrapper_name:ident => $encoder_name:ident($result_type:ty);)+) => {
         $(
             pub fn $wrapper_name(&mut self) -> Result<$result_type, DeserializeError> {
+                self.count_token()?;
                 self.decoder.$encoder_name().map_err(DeserializeError::new)
             }
         )+
@@ -126,6 +151,51 @@ impl<'b> Decoder<'b> {
     pub fn new(bytes: &'b [u8]) -> Self {
         Self {
             decoder: minicbor::Decoder::new(bytes),
+            limits: DecodeLimits::new(),
+            token_count: 0,
+        }
+    }
+
+    /// Enforces the given [`DecodeLimits`] while decoding.
+    ///
+    /// Every data item read counts as a token, including tags and the break that ends an
+    /// indefinite-length item. Skipping an item counts as a single token.
+    ///
+    /// The length of definite-length arrays and maps is checked as soon as it is read, before
+    /// any of their items are. Nesting depth and the size of indefinite-length arrays and maps can
+    /// only be tracked by the decoder while reading a [`Document`]; elsewhere they are bounded by
+    /// the schema driving the decoder.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    fn count_token(&mut self) -> Result<(), DeserializeError> {
+        self.token_count += 1;
+        self.limits
+            .check_tokens(self.token_count)
+            .map_err(|limit| DeserializeError::from_limit(limit, self.decoder.position()))
+    }
+
+    fn check_string_length(&self, length: usize, at: usize) -> Result<(), DeserializeError> {
+        self.limits
+            .check_string_length(length)
+            .map_err(|limit| DeserializeError::from_limit(limit, at))
+    }
+
+    fn check_depth(&self, depth: usize, at: usize) -> Result<(), DeserializeError> {
+        self.limits
+            .check_depth(depth)
+            .map_err(|limit| DeserializeError::from_limit(limit, at))
+    }
+
+    fn check_collection_size(&self, size: Option<u64>, at: usize) -> Result<(), DeserializeError> {
+        match size {
+            Some(size) => self
+                .limits
+                .check_collection_size(usize::try_from(size).unwrap_or(usize::MAX))
+                .map_err(|limit| DeserializeError::from_limit(limit, at)),
+            None => Ok(()),
         }
     }
 
@@ -155,10 +225,46 @@ impl<'b> Decoder<'b> {
         double => f64(f64);
         /// Reads a null CBOR element at the current position.
         null => null(());
-        /// Returns the number of elements in a definite list. For indefinite lists it returns a `None`.
-        list => array(Option<u64>);
-        /// Returns the number of elements in a definite map. For indefinite map it returns a `None`.
-        map => map(Option<u64>);
+    }
+
+    /// Returns the number of elements in a definite list. For indefinite lists it returns a `None`.
+    pub fn list(&mut self) -> Result<Option<u64>, DeserializeError> {
+        self.count_token()?;
+        let at = self.decoder.position();
+        let len = self.decoder.array().map_err(DeserializeError::new)?;
+        self.check_collection_size(len, at)?;
+        Ok(len)
+    }
+
+    /// Returns the number of elements in a definite map. For indefinite map it returns a `None`.
+    pub fn map(&mut self) -> Result<Option<u64>, DeserializeError> {
+        self.count_token()?;
+        let at = self.decoder.position();
+        let len = self.decoder.map().map_err(DeserializeError::new)?;
+        self.check_collection_size(len, at)?;
+        Ok(len)
+    }
+
+    /// Checks that the input, from the current position to the end, uses core deterministic
+    /// encoding ([RFC 8949 §4.2.1]). This doesn't move the decoder.
+    ///
+    /// Integers, lengths and tags must use their shortest form, and floats the shortest precision
+    /// that represents them exactly, with NaN as `0xf97e00`. Arrays, maps and strings must have a
+    /// definite length. Map keys must be unique and sorted by the bytewise lexicographic order of
+    /// their encoding. The error's offset is that of the first byte that differs from the
+    /// deterministic encoding.
+    ///
+    /// [RFC 8949 §4.2.1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1
+    pub fn check_deterministic(&self) -> Result<(), DeserializeError> {
+        match crate::deterministic::find_non_deterministic(self.decoder.clone())
+            .map_err(DeserializeError::new)?
+        {
+            Some(at) => Err(DeserializeError::custom(
+                "CBOR input is not deterministically encoded",
+                at,
+            )),
+            None => Ok(()),
+        }
     }
 
     /// Returns the current position of the buffer, which will be decoded when any of the methods is called.
@@ -171,14 +277,18 @@ impl<'b> Decoder<'b> {
     /// indefinite-length string. An error is returned if the element is neither a definite length nor an
     /// indefinite-length string.
     pub fn str(&mut self) -> Result<Cow<'b, str>, DeserializeError> {
+        self.count_token()?;
         let bookmark = self.decoder.position();
         match self.decoder.str() {
-            Ok(str_value) => Ok(Cow::Borrowed(str_value)),
+            Ok(str_value) => {
+                self.check_string_length(str_value.len(), bookmark)?;
+                Ok(Cow::Borrowed(str_value))
+            }
             Err(e) if e.is_type_mismatch() => {
                 // Move the position back to the start of the CBOR element and then try
                 // decoding it as an indefinite length string.
                 self.decoder.set_position(bookmark);
-                Ok(Cow::Owned(self.string()?))
+                Ok(Cow::Owned(self.read_string()?))
             }
             Err(e) => Err(DeserializeError::new(e)),
         }
@@ -187,6 +297,13 @@ impl<'b> Decoder<'b> {
     /// Allocates and returns a `String` if the element at the current position in the buffer is either a
     /// definite-length or an indefinite-length string. Otherwise, an error is returned if the element is not a string type.
     pub fn string(&mut self) -> Result<String, DeserializeError> {
+        self.count_token()?;
+        self.read_string()
+    }
+
+    fn read_string(&mut self) -> Result<String, DeserializeError> {
+        let at = self.decoder.position();
+        let limits = self.limits;
         let mut iter = self.decoder.str_iter().map_err(DeserializeError::new)?;
         let head = iter.next();
 
@@ -195,11 +312,16 @@ impl<'b> Decoder<'b> {
             Some(head) => {
                 let mut combined_chunks = String::from(head.map_err(DeserializeError::new)?);
                 for chunk in iter {
-                    combined_chunks.push_str(chunk.map_err(DeserializeError::new)?);
+                    let chunk = chunk.map_err(DeserializeError::new)?;
+                    limits
+                        .check_string_length(combined_chunks.len() + chunk.len())
+                        .map_err(|limit| DeserializeError::from_limit(limit, at))?;
+                    combined_chunks.push_str(chunk);
                 }
                 combined_chunks
             }
         };
+        self.check_string_length(decoded_string.len(), at)?;
 
         Ok(decoded_string)
     }
@@ -207,10 +329,21 @@ impl<'b> Decoder<'b> {
     /// Returns a `blob` if the element at the current position in the buffer is a byte string. Otherwise,
     /// a `DeserializeError` error is returned.
     pub fn blob(&mut self) -> Result<Blob, DeserializeError> {
+        self.count_token()?;
+        let at = self.decoder.position();
+        let limits = self.limits;
         let iter = self.decoder.bytes_iter().map_err(DeserializeError::new)?;
+        let mut length = 0;
         let parts: Vec<&[u8]> = iter
-            .collect::<Result<_, _>>()
-            .map_err(DeserializeError::new)?;
+            .map(|part| -> Result<_, DeserializeError> {
+                let part = part.map_err(DeserializeError::new)?;
+                length += part.len();
+                limits
+                    .check_string_length(length)
+                    .map_err(|limit| DeserializeError::from_limit(limit, at))?;
+                Ok(part)
+            })
+            .collect::<Result<_, _>>()?;
 
         Ok(if parts.len() == 1 {
             Blob::new(parts[0]) // Directly convert &[u8] to Blob if there's only one part.
@@ -222,6 +355,7 @@ impl<'b> Decoder<'b> {
     /// Returns a `DateTime` if the element at the current position in the buffer is a `timestamp`. Otherwise,
     /// a `DeserializeError` error is returned.
     pub fn timestamp(&mut self) -> Result<DateTime, DeserializeError> {
+        self.count_token()?;
         let tag = self.decoder.tag().map_err(DeserializeError::new)?;
         let timestamp_tag = minicbor::data::Tag::from(minicbor::data::IanaTag::Timestamp);
 
@@ -238,6 +372,7 @@ impl<'b> Decoder<'b> {
             // fail since the upstream test expect `123000000` in subsec but the decoded actual
             // subsec would be `123000025`.
             // https://github.com/smithy-lang/smithy/blob/6466fe77c65b8a17b219f0b0a60c767915205f95/smithy-protocol-tests/model/rpcv2Cbor/fractional-seconds.smithy#L17
+            self.count_token()?;
             let epoch_seconds = self.decoder.f64().map_err(DeserializeError::new)?;
             let mut result = DateTime::from_secs_f64(epoch_seconds);
             let subsec_nanos = result.subsec_nanos();
@@ -245,6 +380,324 @@ impl<'b> Decoder<'b> {
             Ok(result)
         }
     }
+
+    /// Returns a `BigInteger` if the element at the current position in the buffer is an integer or a
+    /// bignum (tag 2 or 3). Otherwise, a `DeserializeError` error is returned.
+    pub fn big_integer(&mut self) -> Result<BigInteger, DeserializeError> {
+        use minicbor::data::{IanaTag, Tag};
+
+        let at = self.position();
+        match self.datatype()? {
+            Type::U8 | Type::U16 | Type::U32 | Type::U64 => {
+                self.count_token()?;
+                Ok(BigInteger::from(
+                    self.decoder.u64().map_err(DeserializeError::new)?,
+                ))
+            }
+            Type::I8 | Type::I16 | Type::I32 | Type::I64 => Ok(BigInteger::from(self.long()?)),
+            Type::Int => {
+                self.count_token()?;
+                Ok(BigInteger::from(i128::from(
+                    self.decoder.int().map_err(DeserializeError::new)?,
+                )))
+            }
+            Type::Tag => {
+                self.count_token()?;
+                let tag = self.decoder.tag().map_err(DeserializeError::new)?;
+                if tag == Tag::from(IanaTag::PosBignum) {
+                    Ok(BigInteger::from_magnitude_be_bytes(
+                        false,
+                        &self.bignum_magnitude()?,
+                    ))
+                } else if tag == Tag::from(IanaTag::NegBignum) {
+                    // Negative bignums encode `-1 - n`, so the magnitude is `n + 1`
+                    let mut magnitude = self.bignum_magnitude()?;
+                    let mut carry = true;
+                    for byte in magnitude.iter_mut().rev() {
+                        let (value, overflow) = byte.overflowing_add(1);
+                        *byte = value;
+                        carry = overflow;
+                        if !carry {
+                            break;
+                        }
+                    }
+                    if carry {
+                        magnitude.insert(0, 1);
+                    }
+                    Ok(BigInteger::from_magnitude_be_bytes(true, &magnitude))
+                } else {
+                    Err(DeserializeError::custom("expected bignum tag", at))
+                }
+            }
+            other => Err(DeserializeError::custom(
+                format!("expected a big integer, found {:?}", other),
+                at,
+            )),
+        }
+    }
+
+    /// Reads the byte string of a bignum, without its leading zeros.
+    fn bignum_magnitude(&mut self) -> Result<Vec<u8>, DeserializeError> {
+        let at = self.position();
+        let mut magnitude = self.blob()?.into_inner();
+        let leading_zeros = magnitude.iter().take_while(|&&byte| byte == 0).count();
+        magnitude.drain(..leading_zeros);
+        if magnitude.len() > MAX_BIGNUM_LENGTH {
+            return Err(DeserializeError::custom(
+                format!("bignums longer than {MAX_BIGNUM_LENGTH} bytes are not supported"),
+                at,
+            ));
+        }
+        Ok(magnitude)
+    }
+
+    /// Returns a `BigDecimal` if the element at the current position in the buffer is a decimal
+    /// fraction (tag 4), an integer, a bignum or a finite float. Otherwise, a `DeserializeError`
+    /// error is returned.
+    pub fn big_decimal(&mut self) -> Result<BigDecimal, DeserializeError> {
+        let at = self.position();
+        match self.datatype()? {
+            Type::F16 | Type::F32 | Type::F64 => {
+                let value = self.double()?;
+                value
+                    .to_string()
+                    .parse()
+                    .map_err(|_| DeserializeError::custom("expected a finite float", at))
+            }
+            Type::Tag => {
+                self.count_token()?;
+                let tag = self.decoder.tag().map_err(DeserializeError::new)?;
+                let decimal_tag = minicbor::data::Tag::from(minicbor::data::IanaTag::Decimal);
+                if tag != decimal_tag {
+                    // Move the position back to the tag and decode it as a bignum.
+                    self.decoder.set_position(at);
+                    return Ok(self.big_integer()?.into());
+                }
+                if self.list()? != Some(2) {
+                    return Err(DeserializeError::custom(
+                        "expected a decimal fraction with an exponent and a mantissa",
+                        at,
+                    ));
+                }
+                let exponent = self.long()?;
+                let mantissa = self.big_integer()?;
+                BigDecimal::from_mantissa_and_exponent(&mantissa, exponent)
+                    .map_err(|err| DeserializeError::custom(err.to_string(), at))
+            }
+            _ => Ok(self.big_integer()?.into()),
+        }
+    }
+
+    /// Returns a [`Document`] for the CBOR data item at the current position.
+    ///
+    /// CBOR can express values that a document cannot, so some of them are mapped lossily:
+    /// - Byte strings become base64-encoded strings.
+    /// - Epoch-based timestamps (tag 1) become their number of seconds since the Unix epoch.
+    /// - Bignums (tags 2 and 3) become integers when they fit in an `i64` or `u64`, and floats
+    ///   otherwise.
+    /// - Decimal fractions (tag 4) become floats.
+    /// - `undefined` becomes null, and other tags are ignored in favor of the value they enclose.
+    ///
+    /// Map keys must be strings. Maps and arrays may be either definite or indefinite length.
+    pub fn document(&mut self) -> Result<Document, DeserializeError> {
+        self.document_inner(0)
+    }
+
+    // Only the array, map and tag arms recurse. Everything else lives in out-of-line helpers so
+    // that each level of nesting uses as little stack as possible.
+    fn document_inner(&mut self, depth: usize) -> Result<Document, DeserializeError> {
+        if depth >= MAX_DOCUMENT_RECURSION {
+            return Err(self.recursion_limit_error());
+        }
+        match self.datatype()? {
+            Type::Array | Type::ArrayIndef => self.array_document(depth),
+            Type::Map | Type::MapIndef => self.map_document(depth),
+            Type::Tag => self.tagged_document(depth),
+            other => self.scalar_document(other),
+        }
+    }
+
+    #[inline(never)]
+    fn recursion_limit_error(&self) -> DeserializeError {
+        DeserializeError::custom(
+            "exceeded max recursion depth while decoding a document",
+            self.position(),
+        )
+    }
+
+    #[inline(never)]
+    fn scalar_document(&mut self, datatype: Type) -> Result<Document, DeserializeError> {
+        let document = match datatype {
+            Type::Bool => Document::Bool(self.boolean()?),
+            Type::Null | Type::Undefined => {
+                self.skip()?;
+                Document::Null
+            }
+            Type::U8 | Type::U16 | Type::U32 | Type::U64 => {
+                self.count_token()?;
+                Document::Number(Number::PosInt(
+                    self.decoder.u64().map_err(DeserializeError::new)?,
+                ))
+            }
+            Type::I8 | Type::I16 | Type::I32 | Type::I64 => {
+                let n = self.long()?;
+                Document::Number(if n < 0 {
+                    Number::NegInt(n)
+                } else {
+                    Number::PosInt(n as u64)
+                })
+            }
+            // Negative integers that don't fit in an `i64`
+            Type::Int => {
+                self.count_token()?;
+                let n = self.decoder.int().map_err(DeserializeError::new)?;
+                Document::Number(Number::Float(i128::from(n) as f64))
+            }
+            Type::F16 | Type::F32 | Type::F64 => Document::Number(Number::Float(self.double()?)),
+            Type::Bytes | Type::BytesIndef => {
+                Document::String(aws_smithy_types::base64::encode(self.blob()?))
+            }
+            Type::String | Type::StringIndef => Document::String(self.string()?),
+            other => {
+                return Err(DeserializeError::custom(
+                    format!("cannot decode a document from CBOR type {:?}", other),
+                    self.position(),
+                ))
+            }
+        };
+        Ok(document)
+    }
+
+    /// Reads the header of an array or map whose items are nested at `depth + 1`.
+    ///
+    /// Returns the position of the header along with its length.
+    #[inline(never)]
+    fn collection_header(
+        &mut self,
+        depth: usize,
+        map: bool,
+    ) -> Result<(usize, Option<u64>), DeserializeError> {
+        let at = self.position();
+        self.check_depth(depth + 1, at)?;
+        let length = if map { self.map()? } else { self.list()? };
+        Ok((at, length))
+    }
+
+    /// Returns true if the indefinite-length collection at `at` has ended, consuming the break.
+    /// Otherwise, checks that there is room for item `count` (1-based) of the collection.
+    #[inline(never)]
+    fn collection_next(&mut self, count: usize, at: usize) -> Result<bool, DeserializeError> {
+        if self.datatype()? == Type::Break {
+            self.skip()?;
+            return Ok(true);
+        }
+        self.check_collection_size(Some(count as u64), at)?;
+        Ok(false)
+    }
+
+    #[inline(never)]
+    fn array_document(&mut self, depth: usize) -> Result<Document, DeserializeError> {
+        let (at, length) = self.collection_header(depth, false)?;
+        let mut items = Vec::new();
+        match length {
+            None => {
+                while !self.collection_next(items.len() + 1, at)? {
+                    items.push(self.document_inner(depth + 1)?);
+                }
+            }
+            Some(n) => {
+                for _ in 0..n {
+                    items.push(self.document_inner(depth + 1)?);
+                }
+            }
+        }
+        Ok(Document::Array(items))
+    }
+
+    #[inline(never)]
+    fn map_document(&mut self, depth: usize) -> Result<Document, DeserializeError> {
+        let (at, length) = self.collection_header(depth, true)?;
+        let mut members = Vec::new();
+        match length {
+            None => {
+                while !self.collection_next(members.len() + 1, at)? {
+                    let key = self.string()?;
+                    members.push((key, self.document_inner(depth + 1)?));
+                }
+            }
+            Some(n) => {
+                for _ in 0..n {
+                    let key = self.string()?;
+                    members.push((key, self.document_inner(depth + 1)?));
+                }
+            }
+        }
+        Ok(members.into_iter().collect())
+    }
+
+    #[inline(never)]
+    fn tagged_document(&mut self, depth: usize) -> Result<Document, DeserializeError> {
+        use minicbor::data::{IanaTag, Tag};
+
+        let at = self.position();
+        self.count_token()?;
+        let tag = self.decoder.tag().map_err(DeserializeError::new)?;
+        if tag == Tag::from(IanaTag::PosBignum) || tag == Tag::from(IanaTag::NegBignum) {
+            let magnitude = self.blob()?;
+            Ok(Document::Number(bignum(
+                magnitude.as_ref(),
+                tag == Tag::from(IanaTag::NegBignum),
+            )))
+        } else if tag == Tag::from(IanaTag::Decimal) {
+            // A decimal fraction is an array of an exponent and a mantissa: `mantissa * 10^exponent`
+            let fraction = match self.document_inner(depth + 1)? {
+                Document::Array(parts) => match parts.as_slice() {
+                    [Document::Number(exponent), Document::Number(mantissa)] => {
+                        Some(mantissa.to_f64_lossy() * 10f64.powf(exponent.to_f64_lossy()))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            fraction
+                .map(|n| Document::Number(Number::Float(n)))
+                .ok_or_else(|| DeserializeError::custom("invalid decimal fraction", at))
+        } else if tag == Tag::from(IanaTag::Timestamp) {
+            match self.document_inner(depth + 1)? {
+                number @ Document::Number(_) => Ok(number),
+                _ => Err(DeserializeError::custom(
+                    "invalid epoch-based timestamp",
+                    at,
+                )),
+            }
+        } else {
+            self.document_inner(depth + 1)
+        }
+    }
+}
+
+/// Converts the big-endian magnitude of a bignum into a number. Negative bignums encode `-1 - n`.
+fn bignum(magnitude: &[u8], negative: bool) -> Number {
+    let start = magnitude
+        .iter()
+        .position(|&b| b != 0)
+        .unwrap_or(magnitude.len());
+    let magnitude = &magnitude[start..];
+    if magnitude.len() <= 8 {
+        let n = magnitude
+            .iter()
+            .fold(0u64, |acc, &b| (acc << 8) | u64::from(b));
+        match (negative, i64::try_from(n)) {
+            (false, _) => Number::PosInt(n),
+            (true, Ok(n)) => Number::NegInt(-1 - n),
+            (true, Err(_)) => Number::Float(-1.0 - n as f64),
+        }
+    } else {
+        let n = magnitude
+            .iter()
+            .fold(0f64, |acc, &b| acc * 256.0 + f64::from(b));
+        Number::Float(if negative { -1.0 - n } else { n })
+    }
 }
 
 #[allow(dead_code)] // to avoid `never constructed` warning
@@ -300,6 +753,8 @@ where
 mod tests {
     use crate::Decoder;
     use aws_smithy_types::date_time::Format;
+    use aws_smithy_types::{Document, Number};
+    use std::collections::HashMap;
 
     #[test]
     fn test_definite_str_is_cow_borrowed() {
@@ -334,6 +789,37 @@ mod tests {
         assert_eq!(member, "");
     }
 
+    #[test]
+    fn test_bignum_length_is_bounded() {
+        let bignum = |magnitude: &[u8]| {
+            // Tag 2 (positive bignum) around a byte string with a 2-byte length
+            let mut bytes = vec![0xc2, 0x59];
+            bytes.extend((magnitude.len() as u16).to_be_bytes());
+            bytes.extend(magnitude);
+            bytes
+        };
+
+        let mut magnitude = vec![0xff; super::MAX_BIGNUM_LENGTH];
+        let bytes = bignum(&magnitude);
+        let value = Decoder::new(&bytes)
+            .big_integer()
+            .expect("within the limit");
+        assert_eq!(magnitude, value.magnitude_to_be_bytes());
+
+        // Leading zeros don't count towards the limit
+        magnitude.insert(0, 0);
+        let bytes = bignum(&magnitude);
+        Decoder::new(&bytes)
+            .big_integer()
+            .expect("within the limit");
+
+        magnitude[0] = 1;
+        let bytes = bignum(&magnitude);
+        Decoder::new(&bytes)
+            .big_integer()
+            .expect_err("bignum is too long");
+    }
+
     #[test]
     fn test_empty_blob_works() {
         let bytes = [0x40];
@@ -360,6 +846,289 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_indefinite_length_document() {
+        // `{_ "tags": [_ "a", h'0102'], "n": -1}`, with an indefinite-length map and array.
+        let bytes = [
+            0xbf, 0x64, 0x74, 0x61, 0x67, 0x73, 0x9f, 0x61, 0x61, 0x42, 0x01, 0x02, 0xff, 0x61,
+            0x6e, 0x20, 0xff,
+        ];
+        let mut decoder = Decoder::new(&bytes);
+        let document = decoder.document().expect("should decode document");
+        assert_eq!(
+            document,
+            Document::Object(HashMap::from([
+                (
+                    "tags".to_string(),
+                    Document::Array(vec!["a".into(), "AQI=".into()])
+                ),
+                ("n".to_string(), Document::Number(Number::NegInt(-1))),
+            ]))
+        );
+        assert_eq!(bytes.len(), decoder.position());
+    }
+
+    /// Encodes an item with `encode`, which flushes the encoder between its pieces, and returns
+    /// the concatenated chunks
+    fn flushed_chunks(encode: impl FnOnce(&mut crate::Encoder, &mut Vec<Vec<u8>>)) -> Vec<u8> {
+        let mut encoder = crate::Encoder::new(Vec::new());
+        let mut chunks = Vec::new();
+        encode(&mut encoder, &mut chunks);
+        chunks.push(encoder.into_writer());
+        assert!(chunks.len() > 2, "the item should be split across chunks");
+        chunks.concat()
+    }
+
+    fn flush(encoder: &mut crate::Encoder, chunks: &mut Vec<Vec<u8>>) {
+        let mut chunk = Vec::new();
+        encoder.flush_into(&mut chunk);
+        chunks.push(chunk);
+    }
+
+    #[test]
+    fn test_indefinite_items_split_across_chunks() {
+        let bytes = flushed_chunks(|encoder, chunks| {
+            encoder.begin_str();
+            flush(encoder, chunks);
+            for part in ["caf", "é", "", "!"] {
+                encoder.str(part);
+                flush(encoder, chunks);
+            }
+            encoder.end();
+        });
+        assert_eq!("café!", Decoder::new(&bytes).str().unwrap());
+
+        let bytes = flushed_chunks(|encoder, chunks| {
+            encoder.begin_blob();
+            for part in [&b"ab"[..], b"", b"c"] {
+                encoder.bytes(part);
+                flush(encoder, chunks);
+            }
+            encoder.end();
+        });
+        assert_eq!(b"abc", Decoder::new(&bytes).blob().unwrap().as_ref());
+
+        let bytes = flushed_chunks(|encoder, chunks| {
+            encoder.begin_array();
+            flush(encoder, chunks);
+            encoder.integer(1);
+            flush(encoder, chunks);
+            encoder.begin_array().end();
+            flush(encoder, chunks);
+            encoder.begin_str().str("x");
+            flush(encoder, chunks);
+            encoder.end().end();
+        });
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(None, decoder.list().unwrap());
+        assert_eq!(
+            Document::Array(vec![
+                Document::from(1u64),
+                Document::Array(vec![]),
+                Document::from("x")
+            ]),
+            Decoder::new(&bytes).document().unwrap()
+        );
+
+        let bytes = flushed_chunks(|encoder, chunks| {
+            encoder.begin_map();
+            flush(encoder, chunks);
+            encoder.begin_str().str("k");
+            flush(encoder, chunks);
+            encoder.str("ey").end();
+            flush(encoder, chunks);
+            encoder.begin_map().str("inner").boolean(true);
+            flush(encoder, chunks);
+            encoder.end();
+            flush(encoder, chunks);
+            encoder.str("n").null().end();
+        });
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(None, decoder.map().unwrap());
+        assert_eq!("key", decoder.str().unwrap());
+        assert_eq!(
+            Document::from(HashMap::from([
+                (
+                    "key".to_string(),
+                    Document::from(HashMap::from([("inner".to_string(), Document::Bool(true))]))
+                ),
+                ("n".to_string(), Document::Null),
+            ])),
+            Decoder::new(&bytes).document().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_tagged_document_values() {
+        let cases: &[(&[u8], Document)] = &[
+            // 1(1000): epoch-based timestamp
+            (
+                &[0xc1, 0x19, 0x03, 0xe8],
+                Document::Number(Number::PosInt(1000)),
+            ),
+            // 2(h'010000000000000000'): 2^64, too big for a `u64`
+            (
+                &[0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+                Document::Number(Number::Float(18446744073709551616.0)),
+            ),
+            // 2(h'00ff'): leading zeros are ignored
+            (
+                &[0xc2, 0x42, 0x00, 0xff],
+                Document::Number(Number::PosInt(255)),
+            ),
+            // 3(h'ff'): -1 - 255
+            (&[0xc3, 0x41, 0xff], Document::Number(Number::NegInt(-256))),
+            // 4([-2, 27315]): 273.15
+            (
+                &[0xc4, 0x82, 0x21, 0x19, 0x6a, 0xb3],
+                Document::Number(Number::Float(27315.0 * 10f64.powf(-2.0))),
+            ),
+            // 32("http://a"): other tags are ignored
+            (
+                &[
+                    0xd8, 0x20, 0x68, 0x68, 0x74, 0x74, 0x70, 0x3a, 0x2f, 0x2f, 0x61,
+                ],
+                Document::from("http://a"),
+            ),
+        ];
+        for (bytes, expected) in cases {
+            let mut decoder = Decoder::new(bytes);
+            assert_eq!(
+                *expected,
+                decoder.document().expect("should decode document"),
+                "{bytes:02x?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_document_recursion_limit() {
+        let bytes = [0x81; 300];
+        let mut decoder = Decoder::new(&bytes);
+        assert!(decoder.document().is_err());
+
+        // Nesting right up to the limit must fit on a test thread's stack
+        let mut bytes = vec![0x81; super::MAX_DOCUMENT_RECURSION - 1];
+        bytes.push(0x80);
+        let mut decoder = Decoder::new(&bytes);
+        assert!(decoder.document().is_ok());
+    }
+
+    #[test]
+    fn test_limits() {
+        use aws_smithy_types::decode_limits::{DecodeLimit, DecodeLimits};
+
+        let limit_of =
+            |err: crate::decode::DeserializeError| err.limit_exceeded().map(|l| l.limit());
+
+        // [1, 2, 3]
+        let list = [0x83, 0x01, 0x02, 0x03];
+        let limits = DecodeLimits::new().with_max_collection_size(2);
+        let mut decoder = Decoder::new(&list).with_limits(limits);
+        assert_eq!(
+            Some(DecodeLimit::CollectionSize),
+            limit_of(decoder.list().unwrap_err())
+        );
+        let limits = DecodeLimits::new().with_max_tokens(3);
+        let mut decoder = Decoder::new(&list).with_limits(limits);
+        assert_eq!(Some(3), decoder.list().unwrap());
+        assert_eq!(1, decoder.integer().unwrap());
+        assert_eq!(2, decoder.integer().unwrap());
+        assert_eq!(
+            Some(DecodeLimit::Tokens),
+            limit_of(decoder.integer().unwrap_err())
+        );
+
+        let limits = DecodeLimits::new().with_max_string_length(2);
+        // "abc"
+        let mut decoder = Decoder::new(&[0x63, 0x61, 0x62, 0x63]).with_limits(limits);
+        assert_eq!(
+            Some(DecodeLimit::StringLength),
+            limit_of(decoder.str().unwrap_err())
+        );
+        // (_ "a", "bc")
+        let indefinite = [0x7f, 0x61, 0x61, 0x62, 0x62, 0x63, 0xff];
+        let mut decoder = Decoder::new(&indefinite).with_limits(limits);
+        assert_eq!(
+            Some(DecodeLimit::StringLength),
+            limit_of(decoder.str().unwrap_err())
+        );
+        // h'0102' is within the limit
+        let mut decoder = Decoder::new(&[0x42, 0x01, 0x02]).with_limits(limits);
+        assert_eq!(&[1, 2], decoder.blob().unwrap().as_ref());
+
+        // [[1]]
+        let nested = [0x81, 0x81, 0x01];
+        let limits = DecodeLimits::new().with_max_depth(1);
+        let mut decoder = Decoder::new(&nested).with_limits(limits);
+        assert_eq!(
+            Some(DecodeLimit::Depth),
+            limit_of(decoder.document().unwrap_err())
+        );
+        let limits = DecodeLimits::new().with_max_depth(2);
+        let mut decoder = Decoder::new(&nested).with_limits(limits);
+        assert!(decoder.document().is_ok());
+        // [_ 1, 2, 3]
+        let indefinite = [0x9f, 0x01, 0x02, 0x03, 0xff];
+        let limits = DecodeLimits::new().with_max_collection_size(2);
+        let mut decoder = Decoder::new(&indefinite).with_limits(limits);
+        assert_eq!(
+            Some(DecodeLimit::CollectionSize),
+            limit_of(decoder.document().unwrap_err())
+        );
+    }
+
+    #[test]
+    fn test_check_deterministic() {
+        let valid: &[&[u8]] = &[
+            // {"a": 1.5, "b": [100000.0, 0.1], "aa": NaN}
+            &[
+                0xa3, 0x61, 0x61, 0xf9, 0x3e, 0x00, 0x61, 0x62, 0x82, 0xfa, 0x47, 0xc3, 0x50, 0x00,
+                0xfb, 0x3f, 0xb9, 0x99, 0x99, 0x99, 0x99, 0x99, 0x9a, 0x62, 0x61, 0x61, 0xf9, 0x7e,
+                0x00,
+            ],
+            // 1(1000000), -500
+            &[0xc1, 0x1a, 0x00, 0x0f, 0x42, 0x40, 0x39, 0x01, 0xf3],
+        ];
+        for bytes in valid {
+            let decoder = Decoder::new(bytes);
+            decoder.check_deterministic().expect("deterministic");
+            assert_eq!(0, decoder.position());
+        }
+
+        let invalid: &[(&[u8], usize)] = &[
+            // 1 as a one byte integer
+            (&[0x18, 0x01], 0),
+            // 1.5 as a single precision float
+            (&[0xfa, 0x3f, 0xc0, 0x00, 0x00], 0),
+            // NaN with a payload
+            (&[0xf9, 0x7e, 0x01], 2),
+            // ["a"] with an indefinite length
+            (&[0x9f, 0x61, 0x61, 0xff], 0),
+            // "ab" in chunks, in a definite array
+            (&[0x81, 0x7f, 0x61, 0x61, 0x61, 0x62, 0xff], 1),
+            // {"b": 1, "a": 2}
+            (&[0xa2, 0x61, 0x62, 0x01, 0x61, 0x61, 0x02], 2),
+            // {"aa": 1, "b": 2}: shorter keys sort first
+            (&[0xa2, 0x62, 0x61, 0x61, 0x01, 0x61, 0x62, 0x02], 1),
+        ];
+        for (bytes, offset) in invalid {
+            assert!(Decoder::new(bytes).check_deterministic().is_err());
+            assert_eq!(
+                Some(*offset),
+                crate::deterministic::find_non_deterministic(minicbor::Decoder::new(bytes))
+                    .unwrap(),
+                "{bytes:02x?}"
+            );
+        }
+
+        // Duplicate keys and truncated input are errors regardless
+        assert!(Decoder::new(&[0xa2, 0x61, 0x61, 0x01, 0x61, 0x61, 0x02])
+            .check_deterministic()
+            .is_err());
+        assert!(Decoder::new(&[0x82, 0x01]).check_deterministic().is_err());
+    }
+
     #[test]
     fn test_timestamp_should_be_truncated_to_fit_millisecond_precision() {
         // Input bytes are derived from the `RpcV2CborDateTimeWithFractionalSeconds` protocol test,
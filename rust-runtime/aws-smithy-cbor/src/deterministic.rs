@@ -0,0 +1,422 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Core deterministic encoding, as defined in [RFC 8949 §4.2.1].
+//!
+//! Rather than tracking the structure of the items while they are written, CBOR is made
+//! deterministic by decoding it and writing it back out:
+//! - Integers, lengths and tags use their shortest form. The encoder already does this.
+//! - Floats use the shortest of half, single and double precision that represents them exactly.
+//!   NaN is always written as the half precision quiet NaN `0xf97e00`.
+//! - Indefinite-length items are written with a definite length.
+//! - Map entries are sorted by the bytewise lexicographic order of their encoded keys. Duplicate
+//!   keys are an error.
+//!
+//! [RFC 8949 §4.2.1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1
+
+use crate::data::Type;
+use minicbor::decode::Error;
+
+/// The maximum nesting depth of arrays, maps and tags.
+const MAX_DEPTH: usize = 256;
+
+/// We always write to a `Vec<u8>`, which is infallible in `minicbor`.
+const INFALLIBLE_WRITE: &str = "write failed";
+
+/// Writes the items read by `decoder`, up to the end of its input, in deterministic form.
+pub(crate) fn canonicalize(mut decoder: minicbor::Decoder<'_>) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::with_capacity(decoder.input().len() - decoder.position());
+    while decoder.position() < decoder.input().len() {
+        write_item(&mut decoder, &mut out, 0)?;
+    }
+    Ok(out)
+}
+
+/// Finds the end of the last complete top-level item in a buffer that CBOR is being written to.
+///
+/// Only the item headers are read, and each byte is only scanned once however many times the
+/// buffer grows, so that an item that stays open across many flushes isn't read again each time.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ItemBoundaries {
+    /// Position up to which the buffer has been scanned
+    scanned: usize,
+    /// Position just after the last complete top-level item
+    complete: usize,
+    /// Number of nested items left to read for each open array, map or tag, or `None` for an
+    /// indefinite-length item, which ends with a break
+    open: Vec<Option<u64>>,
+}
+
+impl ItemBoundaries {
+    /// Scans the bytes of `buffer` that haven't been scanned yet, and returns the position just
+    /// after the last complete top-level item.
+    pub(crate) fn scan(&mut self, buffer: &[u8]) -> Result<usize, Error> {
+        while let Some(&initial) = buffer.get(self.scanned) {
+            let (major, info) = (initial >> 5, initial & 0x1f);
+            let argument_len = match info {
+                0..=23 | 31 => 0,
+                24..=27 => 1 << (info - 24),
+                _ => return Err(Error::message("invalid CBOR item header").at(self.scanned)),
+            };
+            let Some(argument) = buffer.get(self.scanned + 1..self.scanned + 1 + argument_len)
+            else {
+                break;
+            };
+            let argument = match info {
+                0..=23 => u64::from(info),
+                _ => argument.iter().fold(0, |acc, &b| acc << 8 | u64::from(b)),
+            };
+            let mut next = self.scanned + 1 + argument_len;
+            let complete = match (major, info) {
+                // Indefinite-length byte string, string, array or map
+                (2..=5, 31) => {
+                    self.open.push(None);
+                    false
+                }
+                // Byte string or string
+                (2 | 3, _) => match usize::try_from(argument)
+                    .ok()
+                    .and_then(|len| next.checked_add(len))
+                {
+                    Some(end) if end <= buffer.len() => {
+                        next = end;
+                        true
+                    }
+                    _ => break,
+                },
+                (4..=6, _) => {
+                    let items = match major {
+                        4 => argument,
+                        5 => argument.saturating_mul(2),
+                        _ => 1,
+                    };
+                    if items > 0 {
+                        self.open.push(Some(items));
+                    }
+                    items == 0
+                }
+                (7, 31) => {
+                    if self.open.pop() != Some(None) {
+                        return Err(Error::message("unexpected break").at(self.scanned));
+                    }
+                    true
+                }
+                (0 | 1 | 7, _) => true,
+                _ => unreachable!("the major type has three bits"),
+            };
+            self.scanned = next;
+            if complete {
+                self.complete_item();
+            }
+        }
+        Ok(self.complete)
+    }
+
+    /// Records that an item ended at `scanned`, which may complete the items it's nested in.
+    fn complete_item(&mut self) {
+        loop {
+            match self.open.last_mut() {
+                None => {
+                    self.complete = self.scanned;
+                    return;
+                }
+                Some(None) => return,
+                Some(Some(left)) => {
+                    *left -= 1;
+                    if *left > 0 {
+                        return;
+                    }
+                    self.open.pop();
+                }
+            }
+        }
+    }
+
+    /// Accounts for the first `len` bytes, which must be complete items, being removed from the
+    /// buffer.
+    pub(crate) fn discard(&mut self, len: usize) {
+        self.scanned -= len;
+        self.complete -= len;
+    }
+}
+
+/// Returns the position of the first byte, from the decoder's position to the end of its input,
+/// that differs from the deterministic encoding of the same items.
+pub(crate) fn find_non_deterministic(
+    decoder: minicbor::Decoder<'_>,
+) -> Result<Option<usize>, Error> {
+    let start = decoder.position();
+    let input = &decoder.input()[start..];
+    let expected = canonicalize(decoder)?;
+    let mismatch = input
+        .iter()
+        .zip(&expected)
+        .position(|(actual, expected)| actual != expected)
+        .or_else(|| (input.len() != expected.len()).then_some(input.len().min(expected.len())));
+    Ok(mismatch.map(|offset| start + offset))
+}
+
+fn encoder(out: &mut Vec<u8>) -> minicbor::Encoder<&mut Vec<u8>> {
+    minicbor::Encoder::new(out)
+}
+
+fn write_item(
+    decoder: &mut minicbor::Decoder<'_>,
+    out: &mut Vec<u8>,
+    depth: usize,
+) -> Result<(), Error> {
+    if depth >= MAX_DEPTH {
+        return Err(
+            Error::message("exceeded max recursion depth while decoding CBOR")
+                .at(decoder.position()),
+        );
+    }
+    let at = decoder.position();
+    match Type::new(decoder.datatype()?) {
+        Type::Bool => {
+            encoder(out).bool(decoder.bool()?).expect(INFALLIBLE_WRITE);
+        }
+        Type::Null => {
+            decoder.null()?;
+            encoder(out).null().expect(INFALLIBLE_WRITE);
+        }
+        Type::Undefined => {
+            decoder.undefined()?;
+            encoder(out).undefined().expect(INFALLIBLE_WRITE);
+        }
+        Type::Simple => {
+            encoder(out)
+                .simple(decoder.simple()?)
+                .expect(INFALLIBLE_WRITE);
+        }
+        Type::U8 | Type::U16 | Type::U32 | Type::U64 => {
+            encoder(out).u64(decoder.u64()?).expect(INFALLIBLE_WRITE);
+        }
+        Type::I8 | Type::I16 | Type::I32 | Type::I64 => {
+            encoder(out).i64(decoder.i64()?).expect(INFALLIBLE_WRITE);
+        }
+        Type::Int => {
+            encoder(out).int(decoder.int()?).expect(INFALLIBLE_WRITE);
+        }
+        Type::F16 | Type::F32 | Type::F64 => write_float(out, decoder.f64()?),
+        Type::Bytes | Type::BytesIndef => {
+            let mut bytes = Vec::new();
+            for chunk in decoder.bytes_iter()? {
+                bytes.extend_from_slice(chunk?);
+            }
+            encoder(out).bytes(&bytes).expect(INFALLIBLE_WRITE);
+        }
+        Type::String | Type::StringIndef => {
+            let mut string = String::new();
+            for chunk in decoder.str_iter()? {
+                string.push_str(chunk?);
+            }
+            encoder(out).str(&string).expect(INFALLIBLE_WRITE);
+        }
+        Type::Array | Type::ArrayIndef => {
+            let mut items = Vec::new();
+            let len = match decoder.array()? {
+                Some(len) => {
+                    for _ in 0..len {
+                        write_item(decoder, &mut items, depth + 1)?;
+                    }
+                    len
+                }
+                None => {
+                    let mut len = 0;
+                    while !at_break(decoder)? {
+                        write_item(decoder, &mut items, depth + 1)?;
+                        len += 1;
+                    }
+                    len
+                }
+            };
+            encoder(out).array(len).expect(INFALLIBLE_WRITE);
+            out.extend_from_slice(&items);
+        }
+        Type::Map | Type::MapIndef => {
+            let mut entries = Vec::new();
+            let mut read_entry = |decoder: &mut minicbor::Decoder<'_>| -> Result<(), Error> {
+                let (mut key, mut value) = (Vec::new(), Vec::new());
+                write_item(decoder, &mut key, depth + 1)?;
+                write_item(decoder, &mut value, depth + 1)?;
+                entries.push((key, value));
+                Ok(())
+            };
+            match decoder.map()? {
+                Some(len) => {
+                    for _ in 0..len {
+                        read_entry(decoder)?;
+                    }
+                }
+                None => {
+                    while !at_break(decoder)? {
+                        read_entry(decoder)?;
+                    }
+                }
+            }
+            entries.sort();
+            if entries.windows(2).any(|pair| pair[0].0 == pair[1].0) {
+                return Err(Error::message("duplicate map key").at(at));
+            }
+            encoder(out)
+                .map(entries.len() as u64)
+                .expect(INFALLIBLE_WRITE);
+            for (key, value) in entries {
+                out.extend_from_slice(&key);
+                out.extend_from_slice(&value);
+            }
+        }
+        Type::Tag => {
+            encoder(out).tag(decoder.tag()?).expect(INFALLIBLE_WRITE);
+            write_item(decoder, out, depth + 1)?;
+        }
+        Type::Break | Type::Unknown(_) => {
+            return Err(Error::message("unexpected CBOR data item").at(at));
+        }
+    }
+    Ok(())
+}
+
+/// Returns true, and consumes the break, if the decoder is at the end of an indefinite-length item.
+fn at_break(decoder: &mut minicbor::Decoder<'_>) -> Result<bool, Error> {
+    if Type::new(decoder.datatype()?) == Type::Break {
+        decoder.skip()?;
+        Ok(true)
+    } else {
+        Ok(false)
+    }
+}
+
+/// Writes a float with the shortest encoding that represents it exactly.
+fn write_float(out: &mut Vec<u8>, value: f64) {
+    if value.is_nan() {
+        out.extend_from_slice(&[0xf9, 0x7e, 0x00]);
+        return;
+    }
+    let single = value as f32;
+    if f64::from(single) != value {
+        encoder(out).f64(value).expect(INFALLIBLE_WRITE);
+    } else if let Some(half) = to_f16_exact(single) {
+        out.push(0xf9);
+        out.extend_from_slice(&half.to_be_bytes());
+    } else {
+        encoder(out).f32(single).expect(INFALLIBLE_WRITE);
+    }
+}
+
+/// Returns the half precision encoding of a non-NaN `value` if it represents it exactly.
+fn to_f16_exact(value: f32) -> Option<u16> {
+    let bits = value.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    match exponent {
+        // Zero. Single precision subnormals are all too small for half precision.
+        0 if mantissa == 0 => return Some(sign),
+        0 => return None,
+        // Infinity
+        0xff => return Some(sign | 0x7c00),
+        _ => {}
+    }
+    let exponent = exponent - 127;
+    match exponent {
+        // Normal half precision numbers have 10 bits of mantissa
+        -14..=15 if mantissa & 0x1fff == 0 => {
+            Some(sign | (((exponent + 15) as u16) << 10) | (mantissa >> 13) as u16)
+        }
+        // Subnormal half precision numbers are multiples of 2^-24
+        -24..=-15 => {
+            let significand = mantissa | 0x80_0000;
+            let shift = -(exponent + 1);
+            if significand & ((1 << shift) - 1) == 0 {
+                Some(sign | (significand >> shift) as u16)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_f16_exact, ItemBoundaries};
+    use crate::Encoder;
+
+    #[test]
+    fn item_boundaries() {
+        let mut encoder = Encoder::new(Vec::new());
+        let mut expected = Vec::new();
+        let mut push = |encoder: &mut Encoder, complete: bool| {
+            let len = encoder.buffered().len();
+            expected.push((len, complete));
+        };
+        encoder.integer(1);
+        push(&mut encoder, true);
+        encoder.begin_map().str("a");
+        push(&mut encoder, false);
+        encoder.array(2).str("long string").begin_str().str("x");
+        push(&mut encoder, false);
+        encoder.end().bytes(b"ab").end();
+        push(&mut encoder, true);
+        encoder.map(1).null();
+        push(&mut encoder, false);
+        encoder.timestamp(&aws_smithy_types::DateTime::from_secs(0));
+        push(&mut encoder, true);
+        encoder.array(0).double(1.5);
+        push(&mut encoder, true);
+        let buffer = encoder.into_writer();
+
+        assert_eq!(
+            buffer.len(),
+            ItemBoundaries::default().scan(&buffer).unwrap()
+        );
+        // Scanning a byte at a time finds the same boundaries
+        let mut boundaries = ItemBoundaries::default();
+        let mut scanned = 0;
+        for (len, complete) in expected {
+            for end in scanned..len {
+                boundaries.scan(&buffer[..end]).unwrap();
+            }
+            let found = boundaries.scan(&buffer[..len]).unwrap();
+            if complete {
+                assert_eq!(len, found);
+            } else {
+                assert!(found < len, "{found} should be before {len}");
+            }
+            scanned = len;
+        }
+
+        let mut boundaries = ItemBoundaries::default();
+        assert!(boundaries.scan(&[0x01, 0xff]).is_err());
+    }
+
+    #[test]
+    fn f16_exact() {
+        let cases: &[(f32, Option<u16>)] = &[
+            (0.0, Some(0x0000)),
+            (-0.0, Some(0x8000)),
+            (1.0, Some(0x3c00)),
+            (1.5, Some(0x3e00)),
+            (-4.0, Some(0xc400)),
+            (65504.0, Some(0x7bff)),
+            (65536.0, None),
+            (0.1, None),
+            (100000.0, None),
+            (f32::INFINITY, Some(0x7c00)),
+            (f32::NEG_INFINITY, Some(0xfc00)),
+            // Smallest normal and subnormal half precision numbers
+            (2f32.powi(-14), Some(0x0400)),
+            (2f32.powi(-15), Some(0x0200)),
+            (2f32.powi(-24), Some(0x0001)),
+            (3.0 * 2f32.powi(-24), Some(0x0003)),
+            (2f32.powi(-25), None),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(*expected, to_f16_exact(*value), "{value}");
+        }
+    }
+}
@@ -3,7 +3,11 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use aws_smithy_types::{Blob, DateTime};
+use crate::deterministic::ItemBoundaries;
+use aws_smithy_types::{BigDecimal, BigInteger, Blob, DateTime, Document, Number};
+use bytes::{BufMut, Bytes};
+use std::error::Error;
+use std::fmt;
 
 /// Macro for delegating method calls to the encoder.
 ///
@@ -31,11 +35,78 @@ macro_rules! delegate_method {
     };
 }
 
+/// Macro for forwarding writer methods from a [`DeterministicEncoder`] to its [`Encoder`].
+///
+/// The generated methods return the `DeterministicEncoder` rather than the `Encoder`, so that a
+/// chain of writes ends in [`DeterministicEncoder::flush_into`], which reports errors.
+macro_rules! forward_method {
+    ($($method_name:ident($($param_name:ident : $param_type:ty),*);)+) => {
+        $(
+            #[doc = concat!("See [`Encoder::", stringify!($method_name), "`].")]
+            pub fn $method_name(&mut self, $($param_name: $param_type),*) -> &mut Self {
+                self.encoder.$method_name($($param_name),*);
+                self
+            }
+        )+
+    };
+}
+
+/// Provides functions for encoding CBOR.
+///
+/// The encoder writes into an in-memory buffer, so writes never fail. To stream a large payload,
+/// the bytes written so far can be moved out of the buffer at any point with
+/// [`flush_into`](Encoder::flush_into) or, with the `rt-tokio` feature,
+/// [`flush_to`](Encoder::flush_to), and encoding then continues where it left off. This pairs with
+/// the indefinite-length items started by [`begin_array`](Encoder::begin_array),
+/// [`begin_map`](Encoder::begin_map), [`begin_blob`](Encoder::begin_blob) and
+/// [`begin_str`](Encoder::begin_str), whose length doesn't need to be known up front.
+///
+/// The encoder can also produce [core deterministic encoding], see
+/// [`deterministic`](Encoder::deterministic).
+///
+/// [core deterministic encoding]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1
 #[derive(Debug, Clone)]
 pub struct Encoder {
     encoder: minicbor::Encoder<Vec<u8>>,
+    /// Set when the encoder belongs to a [`DeterministicEncoder`]
+    deterministic: Option<DeterministicState>,
+    /// Output of a [`flush_to`](Encoder::flush_to) that didn't complete, which comes before
+    /// everything in `encoder`
+    unflushed: Bytes,
+}
+
+#[derive(Debug, Clone, Default)]
+struct DeterministicState {
+    boundaries: ItemBoundaries,
+    /// Once an item can't be encoded deterministically, nothing more is written out
+    error: Option<EncodeError>,
+}
+
+/// An error from writing out CBOR that can't be encoded deterministically.
+///
+/// This happens when a map has duplicate keys, an [`end`](Encoder::end) has no matching
+/// `begin_*`, or an item is still incomplete when the encoder is finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodeError {
+    message: String,
 }
 
+impl EncodeError {
+    fn new(message: impl fmt::Display) -> Self {
+        Self {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot encode deterministically: {}", self.message)
+    }
+}
+
+impl Error for EncodeError {}
+
 /// We always write to a `Vec<u8>`, which is infallible in `minicbor`.
 /// <https://docs.rs/minicbor/latest/minicbor/encode/write/trait.Write.html#impl-Write-for-Vec%3Cu8%3E>
 const INFALLIBLE_WRITE: &str = "write failed";
@@ -44,15 +115,43 @@ impl Encoder {
     pub fn new(writer: Vec<u8>) -> Self {
         Self {
             encoder: minicbor::Encoder::new(writer),
+            deterministic: None,
+            unflushed: Bytes::new(),
         }
     }
 
+    /// Makes the encoder produce core deterministic encoding ([RFC 8949 §4.2.1]), so that equal
+    /// values are always encoded to the same bytes.
+    ///
+    /// Items are written as usual and made deterministic when they leave the encoder, through
+    /// [`DeterministicEncoder::into_writer`] or a flush: floats are shortened to the smallest
+    /// precision that represents them exactly, indefinite-length items are given a definite
+    /// length, and map entries are sorted by their encoded keys. Flushing only writes out
+    /// complete top-level items; an item that is still open stays buffered.
+    ///
+    /// [RFC 8949 §4.2.1]: https://www.rfc-editor.org/rfc/rfc8949.html#section-4.2.1
+    pub fn deterministic(mut self) -> DeterministicEncoder {
+        self.deterministic = Some(DeterministicState::default());
+        DeterministicEncoder { encoder: self }
+    }
+
     delegate_method! {
         /// Used when it's not cheap to calculate the size, i.e. when the struct has one or more
         /// `Option`al members.
         begin_map => begin_map();
+        /// Begins an indefinite-length array. Write its items, then close it with
+        /// [`end`](Encoder::end).
+        begin_array => begin_array();
+        /// Begins an indefinite-length byte string. Write its chunks with
+        /// [`bytes`](Encoder::bytes), then close it with [`end`](Encoder::end).
+        begin_blob => begin_bytes();
+        /// Begins an indefinite-length string. Write its chunks with [`str`](Encoder::str), then
+        /// close it with [`end`](Encoder::end).
+        begin_str => begin_str();
         /// Writes a definite length string.
         str => str(x: &str);
+        /// Writes a definite length byte string.
+        bytes => bytes(x: &[u8]);
         /// Writes a boolean value.
         boolean => bool(x: bool);
         /// Writes a byte value.
@@ -69,7 +168,7 @@ impl Encoder {
         double => f64(x: f64);
         /// Writes a null tag.
         null => null();
-        /// Writes an end tag.
+        /// Writes the break that ends an indefinite-length array, map, byte string or string.
         end => end();
     }
 
@@ -111,7 +210,600 @@ impl Encoder {
         self
     }
 
-    pub fn into_writer(self) -> Vec<u8> {
-        self.encoder.into_writer()
+    /// Writes a big integer as a CBOR integer (major type 0 or 1) if it is in the range
+    /// `-2^64..2^64`, and as a bignum (tag 2 or 3) otherwise.
+    pub fn big_integer(&mut self, x: &BigInteger) -> &mut Self {
+        use minicbor::data::{IanaTag, Int, Tag};
+
+        if let Some(n) = x
+            .as_str()
+            .parse::<i128>()
+            .ok()
+            .and_then(|n| Int::try_from(n).ok())
+        {
+            self.encoder.int(n).expect(INFALLIBLE_WRITE);
+        } else if x.is_negative() {
+            // Negative bignums encode `-1 - n`, so write the magnitude minus one
+            let mut magnitude = x.magnitude_to_be_bytes();
+            for byte in magnitude.iter_mut().rev() {
+                let (value, borrow) = byte.overflowing_sub(1);
+                *byte = value;
+                if !borrow {
+                    break;
+                }
+            }
+            let start = magnitude.iter().take_while(|&&b| b == 0).count();
+            self.encoder
+                .tag(Tag::from(IanaTag::NegBignum))
+                .expect(INFALLIBLE_WRITE)
+                .bytes(&magnitude[start..])
+                .expect(INFALLIBLE_WRITE);
+        } else {
+            self.encoder
+                .tag(Tag::from(IanaTag::PosBignum))
+                .expect(INFALLIBLE_WRITE)
+                .bytes(&x.magnitude_to_be_bytes())
+                .expect(INFALLIBLE_WRITE);
+        }
+        self
+    }
+
+    /// Writes a big decimal as a decimal fraction (tag 4): an array of a base 10 exponent and an
+    /// integer mantissa.
+    pub fn big_decimal(&mut self, x: &BigDecimal) -> &mut Self {
+        let (mantissa, exponent) = x.mantissa_and_exponent();
+        self.encoder
+            .tag(minicbor::data::Tag::from(minicbor::data::IanaTag::Decimal))
+            .expect(INFALLIBLE_WRITE);
+        self.array(2).long(exponent).big_integer(&mantissa)
+    }
+
+    /// Writes a [`Document`].
+    ///
+    /// Objects are written as definite length maps with string keys, in the document's iteration
+    /// order. Integers are written with the smallest CBOR integer encoding that fits them, and
+    /// floats as double precision floats.
+    pub fn document(&mut self, x: &Document) -> &mut Self {
+        match x {
+            Document::Array(items) => {
+                self.array(items.len());
+                for item in items {
+                    self.document(item);
+                }
+            }
+            Document::Number(Number::PosInt(n)) => {
+                self.encoder.u64(*n).expect(INFALLIBLE_WRITE);
+            }
+            Document::Number(Number::NegInt(n)) => {
+                self.long(*n);
+            }
+            Document::Number(Number::Float(n)) => {
+                self.double(*n);
+            }
+            Document::String(s) => {
+                self.str(s);
+            }
+            Document::Bool(b) => {
+                self.boolean(*b);
+            }
+            Document::Null => {
+                self.null();
+            }
+            object => {
+                let members: Vec<_> = object
+                    .members()
+                    .expect("all other variants are objects")
+                    .collect();
+                self.map(members.len());
+                for (key, value) in members {
+                    self.str(key).document(value);
+                }
+            }
+        }
+        self
+    }
+
+    /// Returns the bytes written since the encoder was created or last flushed.
+    ///
+    /// In [deterministic](Encoder::deterministic) mode, these are the bytes as they were written,
+    /// before being made deterministic. Bytes left over from a [`flush_to`](Encoder::flush_to)
+    /// that didn't complete aren't included.
+    pub fn buffered(&self) -> &[u8] {
+        self.encoder.writer()
+    }
+
+    /// Removes the bytes that are ready to be written out from the buffer.
+    ///
+    /// In deterministic mode, these are the complete top-level items, which are only read once
+    /// they're complete. If they can't be encoded deterministically, the error is recorded and
+    /// nothing is removed.
+    fn take_output(&mut self) -> Vec<u8> {
+        let buffer = self.encoder.writer_mut();
+        let Some(state) = &mut self.deterministic else {
+            return std::mem::take(buffer);
+        };
+        if state.error.is_some() {
+            return Vec::new();
+        }
+        let result = state.boundaries.scan(buffer).and_then(|complete| {
+            let decoder = minicbor::Decoder::new(&buffer[..complete]);
+            crate::deterministic::canonicalize(decoder).map(|output| (output, complete))
+        });
+        match result {
+            Ok((output, complete)) => {
+                buffer.drain(..complete);
+                state.boundaries.discard(complete);
+                output
+            }
+            Err(err) => {
+                state.error = Some(EncodeError::new(err));
+                Vec::new()
+            }
+        }
+    }
+
+    /// Returns the error that stopped deterministic encoding, if there was one.
+    fn deterministic_error(&self) -> Result<(), EncodeError> {
+        match self
+            .deterministic
+            .as_ref()
+            .and_then(|state| state.error.as_ref())
+        {
+            Some(err) => Err(err.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Moves the bytes written so far into `dst`.
+    ///
+    /// # Panics
+    /// Panics if `dst` doesn't have enough capacity for the buffered bytes, as
+    /// [`BufMut::put_slice`] does.
+    pub fn flush_into<B: BufMut>(&mut self, dst: &mut B) -> &mut Self {
+        dst.put(std::mem::take(&mut self.unflushed));
+        dst.put_slice(&self.take_output());
+        self
+    }
+
+    /// Writes the bytes written so far into `writer`.
+    ///
+    /// This is cancel safe: bytes are discarded from the encoder as soon as they have been
+    /// written, so if this future is dropped before it completes, the next flush continues with
+    /// the first byte that wasn't written.
+    ///
+    /// In [deterministic](Encoder::deterministic) mode, an item that can't be encoded
+    /// deterministically is reported as an [`InvalidData`](std::io::ErrorKind::InvalidData)
+    /// error, see [`DeterministicEncoder::flush_to`].
+    #[cfg(feature = "rt-tokio")]
+    pub async fn flush_to<W>(&mut self, mut writer: &mut W) -> std::io::Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        use bytes::Buf;
+        use tokio::io::AsyncWriteExt;
+
+        loop {
+            if !self.unflushed.has_remaining() {
+                let output = self.take_output();
+                if output.is_empty() {
+                    return self
+                        .deterministic_error()
+                        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err));
+                }
+                self.unflushed = output.into();
+            }
+            // `write_buf` advances `unflushed` past each write as soon as it completes. It's
+            // called on `&mut W` since it needs a sized writer.
+            if AsyncWriteExt::write_buf(&mut writer, &mut self.unflushed).await? == 0 {
+                return Err(std::io::ErrorKind::WriteZero.into());
+            }
+        }
+    }
+
+    pub fn into_writer(mut self) -> Vec<u8> {
+        let mut output = std::mem::take(&mut self.unflushed).to_vec();
+        if output.is_empty() {
+            return self.encoder.into_writer();
+        }
+        output.extend_from_slice(self.encoder.writer());
+        output
+    }
+}
+
+/// An [`Encoder`] that produces core deterministic encoding, created by
+/// [`Encoder::deterministic`].
+///
+/// Items are written with the same methods as on [`Encoder`]. Writing them out can fail, since
+/// not all CBOR can be encoded deterministically, so [`flush_into`](DeterministicEncoder::flush_into)
+/// and [`into_writer`](DeterministicEncoder::into_writer) return an [`EncodeError`].
+#[derive(Debug, Clone)]
+pub struct DeterministicEncoder {
+    encoder: Encoder,
+}
+
+impl DeterministicEncoder {
+    forward_method! {
+        begin_map();
+        begin_array();
+        begin_blob();
+        begin_str();
+        str(x: &str);
+        bytes(x: &[u8]);
+        boolean(x: bool);
+        byte(x: i8);
+        short(x: i16);
+        integer(x: i32);
+        long(x: i64);
+        float(x: f32);
+        double(x: f64);
+        null();
+        end();
+        blob(x: &Blob);
+        array(len: usize);
+        map(len: usize);
+        timestamp(x: &DateTime);
+        big_integer(x: &BigInteger);
+        big_decimal(x: &BigDecimal);
+        document(x: &Document);
+    }
+
+    /// Returns the bytes written since the encoder was created or last flushed, before being made
+    /// deterministic.
+    ///
+    /// See [`Encoder::buffered`].
+    pub fn buffered(&self) -> &[u8] {
+        self.encoder.buffered()
+    }
+
+    /// Moves the complete top-level items written so far into `dst`.
+    ///
+    /// Returns an error if one of them can't be encoded deterministically, in which case it,
+    /// and everything written after it, is never written out.
+    ///
+    /// # Panics
+    /// Panics if `dst` doesn't have enough capacity for the flushed bytes, as
+    /// [`BufMut::put_slice`] does.
+    pub fn flush_into<B: BufMut>(&mut self, dst: &mut B) -> Result<&mut Self, EncodeError> {
+        self.encoder.flush_into(dst);
+        self.encoder.deterministic_error()?;
+        Ok(self)
+    }
+
+    /// Writes the complete top-level items written so far into `writer`.
+    ///
+    /// This is cancel safe, like [`Encoder::flush_to`]. An item that can't be encoded
+    /// deterministically is reported as an [`InvalidData`](std::io::ErrorKind::InvalidData)
+    /// error.
+    #[cfg(feature = "rt-tokio")]
+    pub async fn flush_to<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+    where
+        W: tokio::io::AsyncWrite + Unpin + ?Sized,
+    {
+        self.encoder.flush_to(writer).await
+    }
+
+    /// Returns everything that hasn't been flushed yet.
+    ///
+    /// Returns an error if an item can't be encoded deterministically, or is incomplete.
+    pub fn into_writer(mut self) -> Result<Vec<u8>, EncodeError> {
+        let mut output = std::mem::take(&mut self.encoder.unflushed).to_vec();
+        output.extend_from_slice(&self.encoder.take_output());
+        self.encoder.deterministic_error()?;
+        if !self.encoder.buffered().is_empty() {
+            return Err(EncodeError::new("the last item is incomplete"));
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::Type;
+    use crate::{Decoder, Encoder};
+    use aws_smithy_types::{BigDecimal, BigInteger, DateTime, Document, Number};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_document_round_trip() {
+        let document = Document::Object(HashMap::from([
+            ("name".to_string(), Document::from("widget")),
+            ("count".to_string(), Document::from(3u64)),
+            ("offset".to_string(), Document::from(-7i64)),
+            ("ratio".to_string(), Document::from(0.5f64)),
+            ("enabled".to_string(), Document::from(true)),
+            ("parent".to_string(), Document::Null),
+            (
+                "tags".to_string(),
+                Document::Array(vec!["a".into(), Document::Array(vec![])]),
+            ),
+        ]));
+        let mut encoder = Encoder::new(Vec::new());
+        encoder.document(&document);
+        let bytes = encoder.into_writer();
+
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(document, decoder.document().expect("valid document"));
+        assert_eq!(bytes.len(), decoder.position());
+    }
+
+    #[test]
+    fn test_big_numbers() {
+        let cases: &[(&str, &[u8])] = &[
+            // Values that fit in 64 bits are plain integers
+            (
+                "18446744073709551615",
+                &[0x1b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            ),
+            (
+                "-9223372036854775808",
+                &[0x3b, 0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            ),
+            // 2(h'010000000000000000')
+            (
+                "18446744073709551616",
+                &[0xc2, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+            ),
+            // 3(h'010000000000000000'): -1 - 2^64
+            (
+                "-18446744073709551617",
+                &[0xc3, 0x49, 0x01, 0, 0, 0, 0, 0, 0, 0, 0],
+            ),
+            // Major type 1 covers magnitudes up to 2^64: -1 - 2^63
+            ("-9223372036854775809", &[0x3b, 0x80, 0, 0, 0, 0, 0, 0, 0]),
+            // -1 - (2^64 - 1)
+            (
+                "-18446744073709551616",
+                &[0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+            ),
+        ];
+        for (value, expected) in cases {
+            let value: BigInteger = value.parse().unwrap();
+            let mut encoder = Encoder::new(Vec::new());
+            encoder.big_integer(&value);
+            let bytes = encoder.into_writer();
+            assert_eq!(*expected, bytes.as_slice(), "{value}");
+            let decoded = Decoder::new(&bytes)
+                .big_integer()
+                .expect("valid big integer");
+            assert_eq!(value, decoded);
+        }
+
+        // 4([-2, 27315])
+        let value: BigDecimal = "273.15".parse().unwrap();
+        let mut encoder = Encoder::new(Vec::new());
+        encoder.big_decimal(&value);
+        let bytes = encoder.into_writer();
+        assert_eq!(vec![0xc4, 0x82, 0x21, 0x19, 0x6a, 0xb3], bytes);
+        let decoded = Decoder::new(&bytes)
+            .big_decimal()
+            .expect("valid big decimal");
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    fn test_document_integers_use_smallest_encoding() {
+        let mut encoder = Encoder::new(Vec::new());
+        encoder
+            .document(&Document::Number(Number::PosInt(10)))
+            .document(&Document::Number(Number::NegInt(-500)));
+        assert_eq!(vec![0x0a, 0x39, 0x01, 0xf3], encoder.into_writer());
+    }
+
+    #[test]
+    fn test_indefinite_length_items() {
+        let mut encoder = Encoder::new(Vec::new());
+        encoder
+            .begin_map()
+            .str("list")
+            .begin_array()
+            .integer(1)
+            .begin_array()
+            .end()
+            .end()
+            .str("blob")
+            .begin_blob()
+            .bytes(b"ab")
+            .bytes(b"")
+            .bytes(b"c")
+            .end()
+            .begin_str()
+            .str("st")
+            .str("r")
+            .end()
+            .begin_str()
+            .end()
+            .end();
+        let bytes = encoder.into_writer();
+        assert_eq!(
+            vec![
+                0xbf, 0x64, b'l', b'i', b's', b't', 0x9f, 0x01, 0x9f, 0xff, 0xff, 0x64, b'b', b'l',
+                b'o', b'b', 0x5f, 0x42, b'a', b'b', 0x40, 0x41, b'c', 0xff, 0x7f, 0x62, b's', b't',
+                0x61, b'r', 0xff, 0x7f, 0xff, 0xff,
+            ],
+            bytes
+        );
+
+        let mut decoder = Decoder::new(&bytes);
+        assert_eq!(None, decoder.map().unwrap());
+        assert_eq!("list", decoder.str().unwrap());
+        assert_eq!(None, decoder.list().unwrap());
+        assert_eq!(1, decoder.integer().unwrap());
+        assert_eq!(None, decoder.list().unwrap());
+        assert_eq!(Type::Break, decoder.datatype().unwrap());
+        decoder.skip().unwrap();
+        assert_eq!(Type::Break, decoder.datatype().unwrap());
+        decoder.skip().unwrap();
+        assert_eq!("blob", decoder.str().unwrap());
+        assert_eq!(b"abc", decoder.blob().unwrap().as_ref());
+        assert_eq!("str", decoder.str().unwrap());
+        assert_eq!("", decoder.string().unwrap());
+        assert_eq!(Type::Break, decoder.datatype().unwrap());
+        decoder.skip().unwrap();
+        assert_eq!(bytes.len(), decoder.position());
+
+        let document = Decoder::new(&bytes).document().expect("valid document");
+        assert_eq!(
+            Document::Object(HashMap::from([
+                (
+                    "list".to_string(),
+                    Document::Array(vec![Document::from(1u64), Document::Array(vec![])])
+                ),
+                ("blob".to_string(), Document::from("YWJj")),
+                ("str".to_string(), Document::from("")),
+            ])),
+            document
+        );
+    }
+
+    #[test]
+    fn test_flush_into() {
+        let mut encoder = Encoder::new(Vec::new());
+        let mut out = bytes::BytesMut::new();
+        encoder.begin_array().integer(1);
+        assert_eq!(&[0x9f, 0x01], encoder.buffered());
+        encoder.flush_into(&mut out);
+        assert!(encoder.buffered().is_empty());
+        encoder.integer(2).end().flush_into(&mut out);
+        assert_eq!(&[0x9f, 0x01, 0x02, 0xff], &out[..]);
+        assert!(encoder.into_writer().is_empty());
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn test_flush_to() {
+        let mut encoder = Encoder::new(Vec::new());
+        let mut out = Vec::new();
+        encoder.begin_str().str("a");
+        encoder.flush_to(&mut out).await.unwrap();
+        encoder.str("b").end();
+        encoder.flush_to(&mut out).await.unwrap();
+        assert_eq!(vec![0x7f, 0x61, b'a', 0x61, b'b', 0xff], out);
+        assert_eq!("ab", Decoder::new(&out).str().unwrap());
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn test_flush_to_is_cancel_safe() {
+        use tokio::io::AsyncReadExt;
+
+        let mut encoder = Encoder::new(Vec::new());
+        let (mut tx, mut rx) = tokio::io::duplex(2);
+        encoder.begin_str().str("abc");
+        // Only two bytes fit in the pipe, so the flush is dropped part way through
+        tokio::select! {
+            biased;
+            _ = encoder.flush_to(&mut tx) => panic!("the flush should not complete"),
+            _ = std::future::ready(()) => {}
+        }
+        encoder.end();
+
+        let read = async {
+            let mut out = Vec::new();
+            rx.read_to_end(&mut out).await.unwrap();
+            out
+        };
+        let flush = async {
+            encoder.flush_to(&mut tx).await.unwrap();
+            drop(tx);
+        };
+        let (out, ()) = tokio::join!(read, flush);
+        assert_eq!(vec![0x7f, 0x63, b'a', b'b', b'c', 0xff], out);
+        assert!(encoder.into_writer().is_empty());
+    }
+
+    #[test]
+    fn test_deterministic() {
+        // The same writes on an `Encoder` and a `DeterministicEncoder`
+        macro_rules! encode {
+            ($encoder:expr) => {
+                $encoder
+                    .begin_map()
+                    .str("b")
+                    .begin_array()
+                    .double(100000.0)
+                    .float(0.1)
+                    .end()
+                    .str("aa")
+                    .double(f64::NAN)
+                    .str("a")
+                    .double(1.5)
+                    .end()
+                    .timestamp(&DateTime::from_secs(1_000_000))
+            };
+        }
+        let mut encoder = Encoder::new(Vec::new()).deterministic();
+        encode!(encoder);
+        let deterministic = encoder.into_writer().unwrap();
+        assert_eq!(
+            vec![
+                0xa3, 0x61, 0x61, 0xf9, 0x3e, 0x00, 0x61, 0x62, 0x82, 0xfa, 0x47, 0xc3, 0x50, 0x00,
+                0xfa, 0x3d, 0xcc, 0xcc, 0xcd, 0x62, 0x61, 0x61, 0xf9, 0x7e, 0x00, 0xc1, 0xfa, 0x49,
+                0x74, 0x24, 0x00,
+            ],
+            deterministic
+        );
+        Decoder::new(&deterministic)
+            .check_deterministic()
+            .expect("deterministic");
+
+        let mut encoder = Encoder::new(Vec::new());
+        encode!(encoder);
+        let regular = encoder.into_writer();
+        assert!(Decoder::new(&regular).check_deterministic().is_err());
+
+        // Flushing only writes out complete items
+        let mut encoder = Encoder::new(Vec::new()).deterministic();
+        let mut out = bytes::BytesMut::new();
+        encoder.integer(1).begin_array().integer(2);
+        encoder.flush_into(&mut out).unwrap();
+        assert_eq!(&[0x01], &out[..]);
+        assert_eq!(&[0x9f, 0x02], encoder.buffered());
+        encoder.end().flush_into(&mut out).unwrap();
+        assert_eq!(&[0x01, 0x81, 0x02], &out[..]);
+        assert!(encoder.into_writer().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_deterministic_errors() {
+        let mut encoder = Encoder::new(Vec::new()).deterministic();
+        encoder.map(2).str("a").null().str("a").null();
+        let err = encoder.clone().into_writer().unwrap_err();
+        assert!(err.to_string().contains("duplicate map key"), "{err}");
+        let mut out = Vec::new();
+        assert_eq!(err, encoder.flush_into(&mut out).unwrap_err());
+        assert!(out.is_empty());
+
+        let mut encoder = Encoder::new(Vec::new()).deterministic();
+        encoder.integer(1).end();
+        let mut out = Vec::new();
+        assert!(encoder.flush_into(&mut out).is_err());
+        assert!(out.is_empty());
+
+        let mut encoder = Encoder::new(Vec::new()).deterministic();
+        encoder.integer(1).begin_array();
+        assert!(encoder.into_writer().is_err());
+
+        // Writes chained into a flush still report the error
+        let mut encoder = Encoder::new(Vec::new()).deterministic();
+        let mut out = Vec::new();
+        let err = encoder
+            .map(2)
+            .str("a")
+            .null()
+            .str("a")
+            .null()
+            .flush_into(&mut out)
+            .unwrap_err();
+        assert!(err.to_string().contains("duplicate map key"), "{err}");
+        assert!(out.is_empty());
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn test_deterministic_flush_to_error() {
+        let mut encoder = Encoder::new(Vec::new()).deterministic();
+        encoder.map(2).str("a").null().str("a").null();
+        let err = encoder.flush_to(&mut Vec::new()).await.unwrap_err();
+        assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
     }
 }
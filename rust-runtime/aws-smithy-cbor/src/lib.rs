@@ -11,6 +11,7 @@
 
 pub mod data;
 pub mod decode;
+mod deterministic;
 pub mod encode;
 
 pub use decode::Decoder;
@@ -0,0 +1,111 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Prints each Event Stream frame in a captured byte stream.
+//!
+//! ```text
+//! eventstream-inspect [--json] [--unwrap-signed] [--preview <bytes>] [FILE]
+//! ```
+//!
+//! The stream is read from standard input when no file is given.
+
+use aws_smithy_eventstream::inspect::{frames_to_json, inspect_frames, InspectOptions};
+use std::io::Read;
+use std::process::ExitCode;
+
+const USAGE: &str =
+    "usage: eventstream-inspect [--json] [--unwrap-signed] [--preview <bytes>] [FILE]
+
+Prints each Event Stream frame in a captured byte stream, read from FILE or standard input.
+
+options:
+    --json             print the frames as a JSON array
+    --unwrap-signed    decode the payloads of frames with a :chunk-signature header as frames
+    --preview <bytes>  limit payload previews to this many bytes (default 64)";
+
+struct Args {
+    json: bool,
+    options: InspectOptions,
+    path: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut parsed = Args {
+        json: false,
+        options: InspectOptions::new(),
+        path: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => parsed.json = true,
+            "--unwrap-signed" => parsed.options = parsed.options.with_unwrap_signed(true),
+            "--preview" => {
+                let length = args
+                    .next()
+                    .and_then(|length| length.parse().ok())
+                    .ok_or("--preview requires a number of bytes")?;
+                parsed.options = parsed.options.with_payload_preview_length(length);
+            }
+            "-h" | "--help" => return Err(USAGE.into()),
+            _ if arg.starts_with('-') => return Err(format!("unknown option: {arg}\n\n{USAGE}")),
+            _ if parsed.path.is_none() => parsed.path = Some(arg),
+            _ => return Err(format!("unexpected argument: {arg}\n\n{USAGE}")),
+        }
+    }
+    Ok(parsed)
+}
+
+fn read_input(path: Option<&str>) -> std::io::Result<Vec<u8>> {
+    match path {
+        Some(path) => std::fs::read(path),
+        None => {
+            let mut input = Vec::new();
+            std::io::stdin().read_to_end(&mut input)?;
+            Ok(input)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let input = match read_input(args.path.as_deref()) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("failed to read input: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.json {
+        return match frames_to_json(&input, args.options) {
+            Ok(json) => {
+                println!("{json}");
+                ExitCode::SUCCESS
+            }
+            Err(err) => {
+                eprintln!("failed to decode frame: {err}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    for frame in inspect_frames(&input, args.options) {
+        match frame {
+            Ok(frame) => println!("{frame}"),
+            Err(err) => {
+                eprintln!("failed to decode frame: {err}");
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
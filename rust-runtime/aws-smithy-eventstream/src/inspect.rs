@@ -0,0 +1,705 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Inspection of captured Event Stream frames.
+//!
+//! This decodes a byte stream one frame at a time and reports everything about each frame,
+//! including its prelude and checksums, even when the checksums don't match. Frames can be
+//! printed in a human readable form with their [`Display`](fmt::Display) implementation, or
+//! rendered to JSON with [`InspectedFrame::to_json`] for use in test assertions.
+//!
+//! This module and the `eventstream-inspect` binary, a thin command line wrapper around it, require
+//! the `inspect` feature.
+//!
+//! ```rust
+//! use aws_smithy_eventstream::frame::write_message_to;
+//! use aws_smithy_eventstream::inspect::{inspect_frames, InspectOptions};
+//! use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+//!
+//! let message = Message::new(&b"hello"[..])
+//!     .add_header(Header::new(":event-type", HeaderValue::String("greeting".into())));
+//! let mut captured = Vec::new();
+//! write_message_to(&message, &mut captured).unwrap();
+//!
+//! let frames = inspect_frames(&captured, InspectOptions::new())
+//!     .collect::<Result<Vec<_>, _>>()
+//!     .unwrap();
+//! assert_eq!(1, frames.len());
+//! assert!(frames[0].checksums_valid());
+//! assert_eq!(b"hello", &frames[0].message().payload()[..]);
+//! ```
+
+use crate::error::{Error, ErrorKind};
+use crate::frame::{
+    read_message_from, DecodedFrame, MessageFrameDecoder, MESSAGE_CRC_LENGTH_BYTES,
+    PRELUDE_LENGTH_BYTES_USIZE,
+};
+use aws_smithy_json::serialize::{JsonArrayWriter, JsonObjectWriter, JsonValueWriter};
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::event_stream::{HeaderValue, Message};
+use aws_smithy_types::Number;
+use std::fmt;
+use std::fmt::Write as _;
+
+const CRC_LENGTH: usize = MESSAGE_CRC_LENGTH_BYTES as usize;
+const MIN_FRAME_LENGTH: usize = PRELUDE_LENGTH_BYTES_USIZE + CRC_LENGTH;
+const CHUNK_SIGNATURE_HEADER: &str = ":chunk-signature";
+// Signed envelopes are only ever nested one level deep, so anything deeper is malformed input
+const MAX_UNWRAP_DEPTH: usize = 4;
+
+/// Options for [`inspect_frames`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InspectOptions {
+    unwrap_signed: bool,
+    payload_preview_length: usize,
+}
+
+impl Default for InspectOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InspectOptions {
+    /// Creates the default options: signed envelopes are left wrapped, and payload previews are
+    /// limited to 64 bytes.
+    pub const fn new() -> Self {
+        Self {
+            unwrap_signed: false,
+            payload_preview_length: 64,
+        }
+    }
+
+    /// Decodes the payload of signed frames, which carry a `:chunk-signature` header, as the
+    /// frame that was signed.
+    pub const fn with_unwrap_signed(mut self, unwrap_signed: bool) -> Self {
+        self.unwrap_signed = unwrap_signed;
+        self
+    }
+
+    /// Limits how many bytes of each payload are included in its preview.
+    pub const fn with_payload_preview_length(mut self, payload_preview_length: usize) -> Self {
+        self.payload_preview_length = payload_preview_length;
+        self
+    }
+
+    /// Returns true if signed envelopes are unwrapped.
+    pub const fn unwrap_signed(&self) -> bool {
+        self.unwrap_signed
+    }
+
+    /// Returns the maximum number of payload bytes included in a preview.
+    pub const fn payload_preview_length(&self) -> usize {
+        self.payload_preview_length
+    }
+}
+
+/// A CRC-32 checksum read from a frame, along with the checksum calculated for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checksum {
+    expected: u32,
+    actual: u32,
+}
+
+impl Checksum {
+    /// Returns the checksum calculated from the frame's bytes.
+    pub fn expected(&self) -> u32 {
+        self.expected
+    }
+
+    /// Returns the checksum written in the frame.
+    pub fn actual(&self) -> u32 {
+        self.actual
+    }
+
+    /// Returns true if the checksums match.
+    pub fn is_valid(&self) -> bool {
+        self.expected == self.actual
+    }
+
+    fn write_json(&self, mut object: JsonObjectWriter<'_>) {
+        object.key("valid").boolean(self.is_valid());
+        object
+            .key("expected")
+            .number(Number::PosInt(self.expected.into()));
+        object
+            .key("actual")
+            .number(Number::PosInt(self.actual.into()));
+        object.finish();
+    }
+}
+
+impl fmt::Display for Checksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_valid() {
+            write!(f, "0x{:08X} (valid)", self.actual)
+        } else {
+            write!(
+                f,
+                "0x{:08X} (mismatch, expected 0x{:08X})",
+                self.actual, self.expected
+            )
+        }
+    }
+}
+
+/// A decoded frame, along with details of its encoding.
+#[derive(Debug)]
+pub struct InspectedFrame {
+    offset: usize,
+    total_length: u32,
+    headers_length: u32,
+    prelude_crc: Checksum,
+    message_crc: Checksum,
+    message: Message,
+    unwrapped: Option<Result<Box<InspectedFrame>, Error>>,
+    payload_preview_length: usize,
+}
+
+impl InspectedFrame {
+    /// Returns the offset of the frame in the inspected input.
+    ///
+    /// The offset of an unwrapped frame is relative to the payload of its envelope.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the total length of the frame from its prelude.
+    pub fn total_length(&self) -> u32 {
+        self.total_length
+    }
+
+    /// Returns the length of the frame's headers from its prelude.
+    pub fn headers_length(&self) -> u32 {
+        self.headers_length
+    }
+
+    /// Returns the checksum of the prelude.
+    pub fn prelude_crc(&self) -> Checksum {
+        self.prelude_crc
+    }
+
+    /// Returns the checksum of the whole frame.
+    pub fn message_crc(&self) -> Checksum {
+        self.message_crc
+    }
+
+    /// Returns true if both checksums of the frame match.
+    pub fn checksums_valid(&self) -> bool {
+        self.prelude_crc.is_valid() && self.message_crc.is_valid()
+    }
+
+    /// Returns the decoded message.
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
+
+    /// Returns the frame carried in the payload of a signed envelope.
+    ///
+    /// This is `None` unless [`InspectOptions::with_unwrap_signed`] was set, and the frame has a
+    /// `:chunk-signature` header and a non-empty payload. The empty signed frame that ends a
+    /// signed stream has nothing to unwrap.
+    pub fn unwrapped(&self) -> Option<Result<&InspectedFrame, &Error>> {
+        self.unwrapped
+            .as_ref()
+            .map(|unwrapped| unwrapped.as_deref())
+    }
+
+    /// Renders the frame as a JSON object.
+    ///
+    /// Header values are rendered along with their type. Byte arrays and UUIDs are rendered as
+    /// hex, and timestamps as RFC 3339 date-times. Payloads are previewed as text when they are
+    /// valid UTF-8, and as hex otherwise.
+    pub fn to_json(&self) -> String {
+        let mut output = String::new();
+        self.write_json(JsonObjectWriter::new(&mut output));
+        output
+    }
+
+    fn write_json(&self, mut object: JsonObjectWriter<'_>) {
+        object
+            .key("offset")
+            .number(Number::PosInt(self.offset as u64));
+        let mut prelude = object.key("prelude").start_object();
+        prelude
+            .key("total_length")
+            .number(Number::PosInt(self.total_length.into()));
+        prelude
+            .key("headers_length")
+            .number(Number::PosInt(self.headers_length.into()));
+        self.prelude_crc
+            .write_json(prelude.key("crc").start_object());
+        prelude.finish();
+
+        let mut headers = object.key("headers").start_array();
+        for header in self.message.headers() {
+            let mut entry = headers.value().start_object();
+            entry.key("name").string(header.name().as_str());
+            entry.key("type").string(type_name(header.value()));
+            write_header_value_json(header.value(), entry.key("value"));
+            entry.finish();
+        }
+        headers.finish();
+
+        let payload = self.message.payload();
+        let preview = Preview::new(payload, self.payload_preview_length);
+        let mut payload_object = object.key("payload").start_object();
+        payload_object
+            .key("length")
+            .number(Number::PosInt(payload.len() as u64));
+        payload_object.key("encoding").string(preview.encoding());
+        payload_object.key("preview").string(&preview.text());
+        payload_object.key("truncated").boolean(preview.truncated);
+        payload_object.finish();
+
+        self.message_crc
+            .write_json(object.key("message_crc").start_object());
+        match &self.unwrapped {
+            Some(Ok(unwrapped)) => unwrapped.write_json(object.key("unwrapped").start_object()),
+            Some(Err(err)) => {
+                let mut error = object.key("unwrapped").start_object();
+                error.key("error").string(&err.to_string());
+                error.finish();
+            }
+            None => {}
+        }
+        object.finish();
+    }
+
+    fn write_text(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        let pad = "    ".repeat(indent);
+        writeln!(
+            f,
+            "{pad}frame at offset {}: total_length={}, headers_length={}, prelude_crc={}",
+            self.offset, self.total_length, self.headers_length, self.prelude_crc
+        )?;
+        for header in self.message.headers() {
+            write!(
+                f,
+                "{pad}  header {} ({}): ",
+                header.name().as_str(),
+                type_name(header.value())
+            )?;
+            write_header_value_text(header.value(), f)?;
+            writeln!(f)?;
+        }
+        let payload = self.message.payload();
+        let preview = Preview::new(payload, self.payload_preview_length);
+        writeln!(
+            f,
+            "{pad}  payload ({} bytes, {}): {:?}{}",
+            payload.len(),
+            preview.encoding(),
+            preview.text(),
+            if preview.truncated { "..." } else { "" }
+        )?;
+        write!(f, "{pad}  message_crc={}", self.message_crc)?;
+        match &self.unwrapped {
+            Some(Ok(unwrapped)) => {
+                writeln!(f)?;
+                writeln!(f, "{pad}  signed payload:")?;
+                unwrapped.write_text(f, indent + 1)
+            }
+            Some(Err(err)) => {
+                writeln!(f)?;
+                write!(f, "{pad}  signed payload: {err}")
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for InspectedFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_text(f, 0)
+    }
+}
+
+/// Iterator over the frames in a captured byte stream, returned by [`inspect_frames`].
+#[derive(Debug)]
+pub struct InspectFrames<'a> {
+    input: &'a [u8],
+    offset: usize,
+    options: InspectOptions,
+    failed: bool,
+}
+
+impl Iterator for InspectFrames<'_> {
+    type Item = Result<InspectedFrame, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = &self.input[self.offset..];
+        if self.failed || remaining.is_empty() {
+            return None;
+        }
+        let result = inspect_frame(remaining, self.offset, &self.options, 0);
+        match &result {
+            Ok(frame) => self.offset += frame.total_length as usize,
+            Err(_) => self.failed = true,
+        }
+        Some(result)
+    }
+}
+
+/// Inspects each frame in `input`, which should hold a sequence of whole frames.
+///
+/// A frame whose checksums don't match is still decoded, and its [`Checksum`]s report the
+/// mismatch. Iteration stops after the first frame that can't be decoded at all, such as a
+/// truncated frame, since the start of the next frame can't be known.
+pub fn inspect_frames(input: &[u8], options: InspectOptions) -> InspectFrames<'_> {
+    InspectFrames {
+        input,
+        offset: 0,
+        options,
+        failed: false,
+    }
+}
+
+/// Inspects each frame in `input` and renders them as a JSON array.
+///
+/// See [`InspectedFrame::to_json`] for the format of each frame.
+pub fn frames_to_json(input: &[u8], options: InspectOptions) -> Result<String, Error> {
+    let mut output = String::new();
+    let mut array = JsonArrayWriter::new(&mut output);
+    for frame in inspect_frames(input, options) {
+        frame?.write_json(array.value().start_object());
+    }
+    array.finish();
+    Ok(output)
+}
+
+fn read_u32(input: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes(input[at..at + 4].try_into().expect("four bytes"))
+}
+
+fn inspect_frame(
+    input: &[u8],
+    offset: usize,
+    options: &InspectOptions,
+    depth: usize,
+) -> Result<InspectedFrame, Error> {
+    if input.len() < MIN_FRAME_LENGTH {
+        return Err(ErrorKind::InvalidMessageLength.into());
+    }
+    let total_length = read_u32(input, 0);
+    let headers_length = read_u32(input, 4);
+    if (total_length as usize) < MIN_FRAME_LENGTH || input.len() < total_length as usize {
+        return Err(ErrorKind::InvalidMessageLength.into());
+    }
+    let frame = &input[..total_length as usize];
+    let crc_at = frame.len() - CRC_LENGTH;
+    let prelude_crc = Checksum {
+        expected: crc32fast::hash(&frame[..8]),
+        actual: read_u32(frame, 8),
+    };
+    let message_crc = Checksum {
+        expected: crc32fast::hash(&frame[..crc_at]),
+        actual: read_u32(frame, crc_at),
+    };
+
+    let message = if prelude_crc.is_valid() && message_crc.is_valid() {
+        // Decode the frame the same way a client would
+        match MessageFrameDecoder::new().decode_frame(frame)? {
+            DecodedFrame::Complete(message) => message,
+            DecodedFrame::Incomplete => return Err(ErrorKind::InvalidMessageLength.into()),
+        }
+    } else {
+        // Correct the checksums so that the rest of the frame can still be read
+        let mut corrected = frame.to_vec();
+        corrected[8..12].copy_from_slice(&prelude_crc.expected.to_be_bytes());
+        let crc = crc32fast::hash(&corrected[..crc_at]);
+        corrected[crc_at..].copy_from_slice(&crc.to_be_bytes());
+        read_message_from(&corrected[..])?
+    };
+
+    let signed = message
+        .headers()
+        .iter()
+        .any(|header| header.name().as_str() == CHUNK_SIGNATURE_HEADER);
+    let unwrapped = (options.unwrap_signed && signed && !message.payload().is_empty()).then(|| {
+        if depth >= MAX_UNWRAP_DEPTH {
+            return Err(Error::unmarshalling(format!(
+                "signed envelopes are nested more than {MAX_UNWRAP_DEPTH} deep"
+            )));
+        }
+        let payload = message.payload();
+        let inner = inspect_frame(payload, 0, options, depth + 1)?;
+        if inner.total_length as usize != payload.len() {
+            return Err(ErrorKind::InvalidMessageLength.into());
+        }
+        Ok(Box::new(inner))
+    });
+
+    Ok(InspectedFrame {
+        offset,
+        total_length,
+        headers_length,
+        prelude_crc,
+        message_crc,
+        message,
+        unwrapped,
+        payload_preview_length: options.payload_preview_length,
+    })
+}
+
+fn type_name(value: &HeaderValue) -> &'static str {
+    match value {
+        HeaderValue::Bool(_) => "bool",
+        HeaderValue::Byte(_) => "byte",
+        HeaderValue::Int16(_) => "int16",
+        HeaderValue::Int32(_) => "int32",
+        HeaderValue::Int64(_) => "int64",
+        HeaderValue::ByteArray(_) => "byte_array",
+        HeaderValue::String(_) => "string",
+        HeaderValue::Timestamp(_) => "timestamp",
+        HeaderValue::Uuid(_) => "uuid",
+        _ => "unknown",
+    }
+}
+
+fn int_number(value: i64) -> Number {
+    if value < 0 {
+        Number::NegInt(value)
+    } else {
+        Number::PosInt(value as u64)
+    }
+}
+
+fn write_header_value_json(value: &HeaderValue, writer: JsonValueWriter<'_>) {
+    match value {
+        HeaderValue::Bool(value) => writer.boolean(*value),
+        HeaderValue::Byte(value) => writer.number(int_number((*value).into())),
+        HeaderValue::Int16(value) => writer.number(int_number((*value).into())),
+        HeaderValue::Int32(value) => writer.number(int_number((*value).into())),
+        HeaderValue::Int64(value) => writer.number(int_number(*value)),
+        HeaderValue::ByteArray(value) => writer.string(&hex(value)),
+        HeaderValue::String(value) => writer.string(value.as_str()),
+        HeaderValue::Timestamp(value) => match value.fmt(Format::DateTime) {
+            Ok(formatted) => writer.string(&formatted),
+            Err(_) => writer.number(int_number(value.secs())),
+        },
+        HeaderValue::Uuid(value) => writer.string(&format!("{value:032x}")),
+        _ => writer.null(),
+    }
+}
+
+fn write_header_value_text(value: &HeaderValue, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match value {
+        HeaderValue::Bool(value) => write!(f, "{value}"),
+        HeaderValue::Byte(value) => write!(f, "{value}"),
+        HeaderValue::Int16(value) => write!(f, "{value}"),
+        HeaderValue::Int32(value) => write!(f, "{value}"),
+        HeaderValue::Int64(value) => write!(f, "{value}"),
+        HeaderValue::ByteArray(value) => write!(f, "{}", hex(value)),
+        HeaderValue::String(value) => write!(f, "{:?}", value.as_str()),
+        HeaderValue::Timestamp(value) => match value.fmt(Format::DateTime) {
+            Ok(formatted) => write!(f, "{formatted}"),
+            Err(_) => write!(f, "{value:?}"),
+        },
+        HeaderValue::Uuid(value) => write!(f, "{value:032x}"),
+        _ => write!(f, "{value:?}"),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut output = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(output, "{byte:02x}").expect("writing to a String is infallible");
+    }
+    output
+}
+
+/// The start of a payload, as text if it's valid UTF-8, or as hex otherwise.
+struct Preview<'a> {
+    bytes: &'a [u8],
+    utf8: bool,
+    truncated: bool,
+}
+
+impl<'a> Preview<'a> {
+    fn new(payload: &'a [u8], max_length: usize) -> Self {
+        let utf8 = std::str::from_utf8(payload).is_ok();
+        let mut length = payload.len().min(max_length);
+        if utf8 {
+            // Don't split a character
+            while std::str::from_utf8(&payload[..length]).is_err() {
+                length -= 1;
+            }
+        }
+        Self {
+            bytes: &payload[..length],
+            utf8,
+            truncated: length < payload.len(),
+        }
+    }
+
+    fn encoding(&self) -> &'static str {
+        if self.utf8 {
+            "utf-8"
+        } else {
+            "hex"
+        }
+    }
+
+    fn text(&self) -> String {
+        if self.utf8 {
+            std::str::from_utf8(self.bytes)
+                .expect("checked in new")
+                .to_string()
+        } else {
+            hex(self.bytes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frames_to_json, inspect_frames, InspectOptions, MAX_UNWRAP_DEPTH};
+    use crate::frame::write_message_to;
+    use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+    use aws_smithy_types::DateTime;
+    use bytes::Bytes;
+
+    fn encode(message: &Message) -> Vec<u8> {
+        let mut output = Vec::new();
+        write_message_to(message, &mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn renders_frames_to_json() {
+        let message = Message::new(&b"hello"[..])
+            .add_header(Header::new(":event-type", HeaderValue::String("hi".into())))
+            .add_header(Header::new("count", HeaderValue::Int32(-3)));
+        let mut input = encode(&message);
+        input.extend(encode(&Message::new(vec![0xff, 0x00, 0x01])));
+
+        let options = InspectOptions::new().with_payload_preview_length(2);
+        assert_eq!(
+            concat!(
+                r#"[{"offset":0,"prelude":{"total_length":49,"headers_length":28,"#,
+                r#""crc":{"valid":true,"expected":3982627344,"actual":3982627344}},"#,
+                r#""headers":[{"name":":event-type","type":"string","value":"hi"},"#,
+                r#"{"name":"count","type":"int32","value":-3}],"#,
+                r#""payload":{"length":5,"encoding":"utf-8","preview":"he","truncated":true},"#,
+                r#""message_crc":{"valid":true,"expected":553809812,"actual":553809812}},"#,
+                r#"{"offset":49,"prelude":{"total_length":19,"headers_length":0,"#,
+                r#""crc":{"valid":true,"expected":1113731643,"actual":1113731643}},"#,
+                r#""headers":[],"#,
+                r#""payload":{"length":3,"encoding":"hex","preview":"ff00","truncated":true},"#,
+                r#""message_crc":{"valid":true,"expected":262760151,"actual":262760151}}]"#,
+            ),
+            frames_to_json(&input, options).unwrap()
+        );
+    }
+
+    #[test]
+    fn reports_checksum_mismatches() {
+        let input = include_bytes!("../test_data/invalid_message_checksum");
+        let frames: Vec<_> = inspect_frames(input, InspectOptions::new()).collect();
+        assert_eq!(1, frames.len());
+        let frame = frames[0].as_ref().unwrap();
+        assert!(frame.prelude_crc().is_valid());
+        assert!(!frame.message_crc().is_valid());
+        assert!(!frame.checksums_valid());
+        assert!(frame.to_string().contains("mismatch"), "{frame}");
+
+        let input = include_bytes!("../test_data/invalid_prelude_checksum");
+        let frame = inspect_frames(input, InspectOptions::new())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(!frame.prelude_crc().is_valid());
+    }
+
+    #[test]
+    fn stops_at_truncated_frame() {
+        let input = include_bytes!("../test_data/valid_no_headers");
+        let mut truncated = input.to_vec();
+        truncated.extend_from_slice(&input[..input.len() - 1]);
+        let frames: Vec<_> = inspect_frames(&truncated, InspectOptions::new()).collect();
+        assert_eq!(2, frames.len());
+        assert!(frames[0].is_ok());
+        assert!(frames[1].is_err());
+        assert!(frames_to_json(&truncated, InspectOptions::new()).is_err());
+    }
+
+    #[test]
+    fn unwraps_signed_envelopes() {
+        let inner = Message::new(&b"{}"[..])
+            .add_header(Header::new(":event-type", HeaderValue::String("e".into())));
+        let envelope = Message::new(Bytes::from(encode(&inner)))
+            .add_header(Header::new(
+                ":date",
+                HeaderValue::Timestamp(DateTime::from_secs(1_700_000_000)),
+            ))
+            .add_header(Header::new(
+                ":chunk-signature",
+                HeaderValue::ByteArray(Bytes::from_static(&[0xab, 0xcd])),
+            ));
+        let mut input = encode(&envelope);
+        // The empty signed frame that ends the stream
+        input.extend(encode(&Message::new(Bytes::new()).add_header(Header::new(
+            ":chunk-signature",
+            HeaderValue::ByteArray(Bytes::from_static(&[0x01])),
+        ))));
+
+        let frames = inspect_frames(&input, InspectOptions::new())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert!(frames[0].unwrapped().is_none());
+
+        let options = InspectOptions::new().with_unwrap_signed(true);
+        let frames = inspect_frames(&input, options)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(2, frames.len());
+        let unwrapped = frames[0].unwrapped().unwrap().unwrap();
+        assert_eq!(inner.headers(), unwrapped.message().headers());
+        assert!(frames[1].unwrapped().is_none());
+
+        let json = frames[0].to_json();
+        assert!(
+            json.contains(r#"{"name":":date","type":"timestamp","value":"2023-11-14T22:13:20Z"}"#),
+            "{json}"
+        );
+        assert!(
+            json.contains(r#"{"name":":chunk-signature","type":"byte_array","value":"abcd"}"#),
+            "{json}"
+        );
+        assert!(json.contains(r#""unwrapped":{"offset":0,"#), "{json}");
+        let text = frames[0].to_string();
+        assert!(text.contains("signed payload:"), "{text}");
+        assert!(
+            text.contains("header :event-type (string): \"e\""),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn limits_how_deep_signed_envelopes_are_unwrapped() {
+        let signed = |payload: Vec<u8>| {
+            Message::new(Bytes::from(payload)).add_header(Header::new(
+                ":chunk-signature",
+                HeaderValue::ByteArray(Bytes::from_static(&[0x01])),
+            ))
+        };
+        let mut input = encode(&Message::new(&b"{}"[..]));
+        for _ in 0..=MAX_UNWRAP_DEPTH {
+            input = encode(&signed(input));
+        }
+
+        let options = InspectOptions::new().with_unwrap_signed(true);
+        let frames = inspect_frames(&input, options)
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        let mut frame = &frames[0];
+        for _ in 0..MAX_UNWRAP_DEPTH {
+            frame = frame.unwrapped().unwrap().unwrap();
+        }
+        let err = frame.unwrapped().unwrap().unwrap_err();
+        assert!(err.to_string().contains("nested"), "{err}");
+    }
+}
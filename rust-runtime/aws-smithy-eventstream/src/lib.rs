@@ -21,4 +21,6 @@ pub mod arbitrary;
 mod buf;
 pub mod error;
 pub mod frame;
+#[cfg(feature = "inspect")]
+pub mod inspect;
 pub mod smithy;
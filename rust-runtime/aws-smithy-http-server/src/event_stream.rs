@@ -0,0 +1,592 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Runtime for long-lived, bidirectional event streams.
+//!
+//! The generated code for an operation with event streams gives its handler an
+//! [`aws_smithy_http::event_stream::Receiver`] for the events sent by the client, and expects an
+//! [`aws_smithy_http::event_stream::EventStreamSender`] for the events sent back. This module
+//! manages the lifecycle of such a conversation:
+//!
+//! - [`channel`] creates an [`EventStreamSender`] that handlers send typed events with, and an
+//!   [`EventStreamOutput`] that is converted into the operation output's event stream.
+//! - Events are buffered in a bounded channel. When the client stops reading, sends wait for room,
+//!   putting backpressure on the handler.
+//! - The client's events are only read from the request body when the handler asks for them with
+//!   [`Receiver::recv`], so a slow handler puts backpressure on the client through the
+//!   transport's flow control.
+//! - Keepalive events can be sent periodically while the handler has nothing to send, see
+//!   [`EventStreamOutput::with_keepalive`].
+//! - The stream is closed when no event has been sent or received for the idle timeout,
+//!   optionally with a terminal exception event, see [`EventStreamOutput::with_idle_timeout_error`].
+//! - [`EventStreamSender::close_with_error`] gracefully closes the stream with a terminal exception
+//!   event.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # use std::time::Duration;
+//! use aws_smithy_http_server::event_stream::{channel, EventStreamConfig};
+//! # #[derive(Debug)] struct Error;
+//! # impl std::fmt::Display for Error {
+//! #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "error") }
+//! # }
+//! # impl std::error::Error for Error {}
+//! # #[derive(Debug)] enum Event { Heartbeat, Message(String) }
+//! # type Input = aws_smithy_http::event_stream::Receiver<Event, Error>;
+//! # type Output = aws_smithy_http::event_stream::EventStreamSender<Event, Error>;
+//!
+//! async fn handler(input: Input) -> Output {
+//!     let config = EventStreamConfig::new()
+//!         .with_keepalive_interval(Duration::from_secs(30))
+//!         .with_idle_timeout(Duration::from_secs(300));
+//!     let (sender, output) = channel::<Event, Error>(&config);
+//!     let mut receiver = sender.receiver(input);
+//!     tokio::spawn(async move {
+//!         // Echo each event back to the client
+//!         while let Ok(Some(event)) = receiver.recv().await {
+//!             if sender.send(event).await.is_err() {
+//!                 break;
+//!             }
+//!         }
+//!     });
+//!     output.with_keepalive(|| Event::Heartbeat).into()
+//! }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::event_stream::RawMessage;
+use futures_util::Stream;
+use tokio::sync::{mpsc, Notify};
+use tokio::time::{sleep_until, Instant, Sleep};
+
+/// The default number of events that can be buffered before [`EventStreamSender::send`] waits.
+const DEFAULT_BUFFER_CAPACITY: usize = 16;
+
+/// Connection-level settings for a server event stream.
+///
+/// By default, up to 16 events are buffered, no keepalives are sent and the stream never times
+/// out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EventStreamConfig {
+    buffer_capacity: usize,
+    keepalive_interval: Option<Duration>,
+    idle_timeout: Option<Duration>,
+}
+
+impl Default for EventStreamConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventStreamConfig {
+    /// Creates the default configuration.
+    pub fn new() -> Self {
+        Self {
+            buffer_capacity: DEFAULT_BUFFER_CAPACITY,
+            keepalive_interval: None,
+            idle_timeout: None,
+        }
+    }
+
+    /// Sets how many events can be buffered before [`EventStreamSender::send`] waits for the client
+    /// to read them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer_capacity` is zero.
+    pub fn with_buffer_capacity(mut self, buffer_capacity: usize) -> Self {
+        assert!(buffer_capacity > 0, "the event stream buffer capacity must be non-zero");
+        self.buffer_capacity = buffer_capacity;
+        self
+    }
+
+    /// Sends a keepalive event whenever no other event has been sent for `keepalive_interval`.
+    ///
+    /// The keepalive event is provided with [`EventStreamOutput::with_keepalive`].
+    pub fn with_keepalive_interval(mut self, keepalive_interval: Duration) -> Self {
+        self.keepalive_interval = Some(keepalive_interval);
+        self
+    }
+
+    /// Closes the stream when no event has been sent or received for `idle_timeout`.
+    ///
+    /// Keepalive events don't count as activity.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    /// Returns the number of events that can be buffered.
+    pub fn buffer_capacity(&self) -> usize {
+        self.buffer_capacity
+    }
+
+    /// Returns the keepalive interval, if keepalives are enabled.
+    pub fn keepalive_interval(&self) -> Option<Duration> {
+        self.keepalive_interval
+    }
+
+    /// Returns the idle timeout, if enabled.
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+}
+
+/// State shared between the sender, the output and the receiver of a stream.
+#[derive(Debug)]
+struct Shared {
+    last_activity: Mutex<Instant>,
+    closed: AtomicBool,
+    close_notify: Notify,
+}
+
+impl Shared {
+    fn touch(&self) {
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
+    fn last_activity(&self) -> Instant {
+        *self.last_activity.lock().unwrap()
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.close_notify.notify_waiters();
+    }
+
+    fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::SeqCst)
+    }
+}
+
+/// Creates a bounded event stream.
+///
+/// Events sent with the returned [`EventStreamSender`] are yielded by the returned
+/// [`EventStreamOutput`], which should be converted into the operation output's event stream.
+pub fn channel<T, E>(config: &EventStreamConfig) -> (EventStreamSender<T, E>, EventStreamOutput<T, E>) {
+    let (tx, rx) = mpsc::channel(config.buffer_capacity);
+    let shared = Arc::new(Shared {
+        last_activity: Mutex::new(Instant::now()),
+        closed: AtomicBool::new(false),
+        close_notify: Notify::new(),
+    });
+    let sender = EventStreamSender {
+        tx,
+        shared: shared.clone(),
+    };
+    let output = EventStreamOutput {
+        rx,
+        shared,
+        keepalive_interval: config.keepalive_interval,
+        idle_timeout: config.idle_timeout,
+        keepalive: None,
+        idle_timeout_error: None,
+        keepalive_sleep: None,
+        idle_sleep: None,
+        done: false,
+    };
+    (sender, output)
+}
+
+/// An error returned when sending to an event stream that has been closed.
+///
+/// The stream is closed once the client disconnects, the idle timeout elapses, or a terminal
+/// exception event has been sent. The unsent value can be recovered with
+/// [`SendError::into_inner`].
+pub struct SendError<T>(T);
+
+impl<T> SendError<T> {
+    /// Returns the value that couldn't be sent.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SendError").finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the event stream is closed")
+    }
+}
+
+impl<T> std::error::Error for SendError<T> {}
+
+/// Sends events to the client of an event stream created with [`channel`].
+///
+/// The sender can be cloned to send from several tasks. The stream ends once every clone has
+/// been dropped or closed and the buffered events have been sent.
+pub struct EventStreamSender<T, E> {
+    tx: mpsc::Sender<Result<T, E>>,
+    shared: Arc<Shared>,
+}
+
+impl<T, E> Clone for EventStreamSender<T, E> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T, E> fmt::Debug for EventStreamSender<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStreamSender")
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E> EventStreamSender<T, E> {
+    /// Sends an event, waiting for room in the buffer if the client isn't keeping up.
+    pub async fn send(&self, event: T) -> Result<(), SendError<T>> {
+        if self.is_closed() {
+            return Err(SendError(event));
+        }
+        self.shared.touch();
+        self.tx.send(Ok(event)).await.map_err(|err| match err.0 {
+            Ok(event) => SendError(event),
+            Err(_) => unreachable!("an event was sent"),
+        })
+    }
+
+    /// Closes the stream after the events that have already been sent.
+    ///
+    /// Dropping the sender has the same effect. If the sender has been cloned, the stream stays
+    /// open until every clone has been closed or dropped.
+    pub fn close(self) {}
+
+    /// Sends a terminal exception event to the client and closes the stream.
+    ///
+    /// Events that were sent before the exception are still delivered, but no event can be sent
+    /// after it from any clone of this sender.
+    pub async fn close_with_error(self, error: E) -> Result<(), SendError<E>> {
+        if self.is_closed() {
+            return Err(SendError(error));
+        }
+        let result = self.tx.send(Err(error)).await.map_err(|err| match err.0 {
+            Err(error) => SendError(error),
+            Ok(_) => unreachable!("an error was sent"),
+        });
+        self.shared.close();
+        result
+    }
+
+    /// Returns true if events can no longer be sent.
+    pub fn is_closed(&self) -> bool {
+        self.shared.is_closed() || self.tx.is_closed()
+    }
+
+    /// Waits until the stream has been closed, either by the client disconnecting, the idle
+    /// timeout elapsing, or a terminal exception event being sent.
+    pub async fn closed(&self) {
+        let notified = self.shared.close_notify.notified();
+        if self.shared.is_closed() {
+            return;
+        }
+        tokio::select! {
+            _ = notified => {}
+            _ = self.tx.closed() => {}
+        }
+    }
+
+    /// Wraps the operation's input event stream so that the events received from the client count
+    /// as activity for the idle timeout, and so that receiving stops once this stream is closed.
+    pub fn receiver<I, IE>(&self, input: aws_smithy_http::event_stream::Receiver<I, IE>) -> Receiver<I, IE> {
+        Receiver {
+            inner: input,
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+/// Receives events from the client of an event stream.
+///
+/// Created with [`EventStreamSender::receiver`]. Events are only read from the request when
+/// [`Receiver::recv`] is called, so at most one partially read frame is buffered at a time.
+#[derive(Debug)]
+pub struct Receiver<T, E> {
+    inner: aws_smithy_http::event_stream::Receiver<T, E>,
+    shared: Arc<Shared>,
+}
+
+impl<T, E> Receiver<T, E> {
+    /// Receives the next event from the client.
+    ///
+    /// Returns `Ok(None)` when the client has finished sending, or when the stream has been closed
+    /// by a terminal exception event or the idle timeout.
+    pub async fn recv(&mut self) -> Result<Option<T>, SdkError<E, RawMessage>> {
+        let notified = self.shared.close_notify.notified();
+        if self.shared.is_closed() {
+            return Ok(None);
+        }
+        tokio::select! {
+            result = self.inner.recv() => {
+                if let Ok(Some(_)) = &result {
+                    self.shared.touch();
+                }
+                result
+            }
+            _ = notified => Ok(None),
+        }
+    }
+
+    /// Returns the wrapped input event stream.
+    pub fn into_inner(self) -> aws_smithy_http::event_stream::Receiver<T, E> {
+        self.inner
+    }
+}
+
+type Factory<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+/// The events sent with an [`EventStreamSender`].
+///
+/// This is a [`Stream`] of `Result<T, E>` that ends after the first error, which is what the
+/// generated code marshals into the response. Convert it into the operation output's
+/// [`aws_smithy_http::event_stream::EventStreamSender`] with `.into()`.
+pub struct EventStreamOutput<T, E> {
+    rx: mpsc::Receiver<Result<T, E>>,
+    shared: Arc<Shared>,
+    keepalive_interval: Option<Duration>,
+    idle_timeout: Option<Duration>,
+    keepalive: Option<Factory<T>>,
+    idle_timeout_error: Option<Factory<E>>,
+    keepalive_sleep: Option<Pin<Box<Sleep>>>,
+    idle_sleep: Option<Pin<Box<Sleep>>>,
+    done: bool,
+}
+
+impl<T, E> fmt::Debug for EventStreamOutput<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStreamOutput")
+            .field("keepalive_interval", &self.keepalive_interval)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E> EventStreamOutput<T, E> {
+    /// Sets the event sent when no other event has been sent for the configured
+    /// [keepalive interval](EventStreamConfig::with_keepalive_interval).
+    ///
+    /// Keepalives are only sent if both the interval and the event are set. The event is typically
+    /// an empty member of the operation's event stream union that clients ignore.
+    pub fn with_keepalive(mut self, keepalive: impl Fn() -> T + Send + Sync + 'static) -> Self {
+        self.keepalive = Some(Box::new(keepalive));
+        self
+    }
+
+    /// Sets the terminal exception event sent when the stream is closed by the configured
+    /// [idle timeout](EventStreamConfig::with_idle_timeout).
+    ///
+    /// Without it, the stream ends without an exception.
+    pub fn with_idle_timeout_error(mut self, idle_timeout_error: impl Fn() -> E + Send + Sync + 'static) -> Self {
+        self.idle_timeout_error = Some(Box::new(idle_timeout_error));
+        self
+    }
+
+    fn finish(&mut self) {
+        self.done = true;
+        self.rx.close();
+        self.shared.close();
+    }
+
+    /// Polls the idle timeout, returning `Ready` once the stream has been idle for long enough.
+    fn poll_idle(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let Some(idle_timeout) = self.idle_timeout else {
+            return Poll::Pending;
+        };
+        loop {
+            let deadline = self.shared.last_activity() + idle_timeout;
+            let sleep = self.idle_sleep.get_or_insert_with(|| Box::pin(sleep_until(deadline)));
+            if sleep.deadline() != deadline {
+                sleep.as_mut().reset(deadline);
+            }
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            // Activity may have been recorded since the deadline was calculated
+            if self.shared.last_activity() + idle_timeout <= Instant::now() {
+                return Poll::Ready(());
+            }
+        }
+    }
+
+    /// Polls the keepalive timer, returning `Ready` when a keepalive event is due.
+    fn poll_keepalive(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let (Some(interval), Some(_)) = (self.keepalive_interval, &self.keepalive) else {
+            return Poll::Pending;
+        };
+        let sleep = self
+            .keepalive_sleep
+            .get_or_insert_with(|| Box::pin(sleep_until(Instant::now() + interval)));
+        if sleep.as_mut().poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+        sleep.as_mut().reset(Instant::now() + interval);
+        Poll::Ready(())
+    }
+
+    fn reset_keepalive(&mut self) {
+        if let (Some(interval), Some(sleep)) = (self.keepalive_interval, self.keepalive_sleep.as_mut()) {
+            sleep.as_mut().reset(Instant::now() + interval);
+        }
+    }
+}
+
+impl<T, E> Stream for EventStreamOutput<T, E> {
+    type Item = Result<T, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.done {
+            return Poll::Ready(None);
+        }
+        match this.rx.poll_recv(cx) {
+            Poll::Ready(Some(Ok(event))) => {
+                this.reset_keepalive();
+                return Poll::Ready(Some(Ok(event)));
+            }
+            Poll::Ready(Some(Err(error))) => {
+                this.finish();
+                return Poll::Ready(Some(Err(error)));
+            }
+            Poll::Ready(None) => {
+                this.finish();
+                return Poll::Ready(None);
+            }
+            Poll::Pending => {}
+        }
+        if this.poll_idle(cx).is_ready() {
+            tracing::debug!("closing idle event stream");
+            this.finish();
+            return Poll::Ready(this.idle_timeout_error.as_ref().map(|error| Err(error())));
+        }
+        if this.poll_keepalive(cx).is_ready() {
+            let keepalive = this.keepalive.as_ref().expect("checked in poll_keepalive");
+            return Poll::Ready(Some(Ok(keepalive())));
+        }
+        Poll::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::{poll, StreamExt};
+
+    #[derive(Debug, PartialEq)]
+    enum Event {
+        Keepalive,
+        Message(u32),
+    }
+
+    #[derive(Debug, PartialEq)]
+    struct Error(&'static str);
+
+    #[tokio::test]
+    async fn events_are_sent_in_order_until_closed() {
+        let (sender, output) = channel::<Event, Error>(&EventStreamConfig::new());
+        sender.send(Event::Message(1)).await.unwrap();
+        sender.send(Event::Message(2)).await.unwrap();
+        sender.close();
+        let events: Vec<_> = output.collect().await;
+        assert_eq!(vec![Ok(Event::Message(1)), Ok(Event::Message(2))], events);
+    }
+
+    #[tokio::test]
+    async fn close_with_error_sends_a_terminal_exception() {
+        let (sender, output) = channel::<Event, Error>(&EventStreamConfig::new());
+        let other = sender.clone();
+        sender.send(Event::Message(1)).await.unwrap();
+        sender.close_with_error(Error("done")).await.unwrap();
+        assert!(other.is_closed());
+        assert!(other.send(Event::Message(2)).await.is_err());
+        let events: Vec<_> = output.collect().await;
+        assert_eq!(vec![Ok(Event::Message(1)), Err(Error("done"))], events);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn sends_wait_for_room_in_the_buffer() {
+        let (sender, mut output) = channel::<Event, Error>(&EventStreamConfig::new().with_buffer_capacity(1));
+        sender.send(Event::Message(1)).await.unwrap();
+        let blocked = sender.send(Event::Message(2));
+        tokio::pin!(blocked);
+        assert!(
+            poll!(&mut blocked).is_pending(),
+            "send should wait while the buffer is full"
+        );
+        tokio::time::advance(Duration::from_secs(60)).await;
+        assert!(
+            poll!(&mut blocked).is_pending(),
+            "send should wait while the buffer is full"
+        );
+
+        assert_eq!(Some(Ok(Event::Message(1))), output.next().await);
+        blocked.await.unwrap();
+        assert_eq!(Some(Ok(Event::Message(2))), output.next().await);
+
+        drop(output);
+        assert!(sender.is_closed());
+        assert_eq!(
+            Event::Message(3),
+            sender.send(Event::Message(3)).await.unwrap_err().into_inner()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn keepalives_are_sent_while_there_are_no_events() {
+        let config = EventStreamConfig::new().with_keepalive_interval(Duration::from_millis(20));
+        let (sender, output) = channel::<Event, Error>(&config);
+        let mut output = output.with_keepalive(|| Event::Keepalive);
+        for _ in 0..2 {
+            assert!(poll!(output.next()).is_pending());
+            tokio::time::advance(Duration::from_millis(20)).await;
+            assert_eq!(Some(Ok(Event::Keepalive)), output.next().await);
+        }
+        sender.send(Event::Message(1)).await.unwrap();
+        assert_eq!(Some(Ok(Event::Message(1))), output.next().await);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn idle_streams_are_closed() {
+        let config = EventStreamConfig::new()
+            .with_keepalive_interval(Duration::from_millis(10))
+            .with_idle_timeout(Duration::from_millis(50));
+        let (sender, output) = channel::<Event, Error>(&config);
+        let mut output = output
+            .with_keepalive(|| Event::Keepalive)
+            .with_idle_timeout_error(|| Error("idle"));
+        // Keepalives don't count as activity
+        for _ in 0..4 {
+            assert!(poll!(output.next()).is_pending());
+            tokio::time::advance(Duration::from_millis(10)).await;
+            assert_eq!(Some(Ok(Event::Keepalive)), output.next().await);
+        }
+        assert!(poll!(output.next()).is_pending());
+        tokio::time::advance(Duration::from_millis(10)).await;
+        assert_eq!(Some(Err(Error("idle"))), output.next().await);
+        assert_eq!(None, output.next().await);
+
+        assert!(sender.is_closed());
+        sender.closed().await;
+    }
+}
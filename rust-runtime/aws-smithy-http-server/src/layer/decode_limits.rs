@@ -0,0 +1,139 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Middleware for bounding the resources used to deserialize request bodies.
+//!
+//! [`DecodeLimitsLayer`] attaches [`DecodeLimits`] to every request. Generated operation
+//! deserializers read them with [`request_decode_limits`] and check the JSON, XML or CBOR request
+//! body against them, so a deeply nested or oversized body is rejected with a `400 Bad Request`
+//! before it's fully deserialized.
+//!
+//! Requests without limits attached are deserialized without limits.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aws_smithy_http_server::layer::decode_limits::DecodeLimitsLayer;
+//! use aws_smithy_types::decode_limits::DecodeLimits;
+//! use tower::Layer;
+//!
+//! let limits = DecodeLimits::new()
+//!     .with_max_depth(32)
+//!     .with_max_string_length(1024 * 1024)
+//!     .with_max_collection_size(10_000);
+//! # async fn handle() { }
+//! let app = tower::service_fn(handle);
+//! let app = DecodeLimitsLayer::new(limits).layer(app);
+//! ```
+
+use std::task::{Context, Poll};
+
+use aws_smithy_types::decode_limits::DecodeLimits;
+use http::Request;
+use tower::{Layer, Service};
+
+/// Returns the limits attached to `request` by a [`DecodeLimitsLayer`], or no limits if there
+/// aren't any.
+pub fn request_decode_limits<B>(request: &Request<B>) -> DecodeLimits {
+    request.extensions().get::<DecodeLimits>().copied().unwrap_or_default()
+}
+
+/// A [`tower::Layer`] used to apply [`DecodeLimitsService`].
+#[derive(Clone, Debug)]
+pub struct DecodeLimitsLayer {
+    limits: DecodeLimits,
+}
+
+impl DecodeLimitsLayer {
+    /// Checks the bodies of requests against `limits` while they're deserialized.
+    pub fn new(limits: DecodeLimits) -> Self {
+        Self { limits }
+    }
+}
+
+impl<S> Layer<S> for DecodeLimitsLayer {
+    type Service = DecodeLimitsService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DecodeLimitsService {
+            inner,
+            limits: self.limits,
+        }
+    }
+}
+
+/// A middleware [`Service`] that attaches [`DecodeLimits`] to requests.
+#[derive(Clone, Debug)]
+pub struct DecodeLimitsService<S> {
+    inner: S,
+    limits: DecodeLimits,
+}
+
+impl<S, B> Service<Request<B>> for DecodeLimitsService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        req.extensions_mut().insert(self.limits);
+        self.inner.call(req)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{request_decode_limits, DecodeLimitsLayer};
+    use crate::protocol::{rest_json_1, rest_xml, rpc_v2_cbor};
+    use aws_smithy_types::decode_limits::DecodeLimits;
+    use http::{Request, StatusCode};
+    use std::convert::Infallible;
+    use tower::{service_fn, Layer, ServiceExt};
+
+    #[tokio::test]
+    async fn attaches_limits_to_requests() {
+        let limits = DecodeLimits::new().with_max_depth(4);
+        let svc = DecodeLimitsLayer::new(limits).layer(service_fn(|req: Request<()>| async move {
+            Ok::<_, Infallible>(request_decode_limits(&req))
+        }));
+        assert_eq!(limits, svc.oneshot(Request::new(())).await.unwrap());
+
+        assert!(request_decode_limits(&Request::new(())).is_unlimited());
+    }
+
+    #[test]
+    fn exceeded_limits_are_rejected_as_bad_requests() {
+        let limits = DecodeLimits::new().with_max_depth(1);
+
+        let err = aws_smithy_json::deserialize::json_token_iter_with_limits(b"[[]]", limits)
+            .find_map(Result::err)
+            .unwrap();
+        assert!(err.limit_exceeded().is_some());
+        let err = rest_json_1::runtime_error::RuntimeError::from(rest_json_1::rejection::RequestRejection::from(err));
+        assert_eq!(StatusCode::BAD_REQUEST, err.status_code());
+
+        let mut doc = aws_smithy_xml::decode::Document::new("<a><b/></a>").with_limits(limits);
+        let mut root = doc.root_element().unwrap();
+        let err = root.next_tag().err().unwrap();
+        assert!(err.limit_exceeded().is_some());
+        let err = rest_xml::runtime_error::RuntimeError::from(rest_xml::rejection::RequestRejection::from(err));
+        assert_eq!(StatusCode::BAD_REQUEST, err.status_code());
+
+        let limits = DecodeLimits::new().with_max_string_length(1);
+        let err = aws_smithy_cbor::Decoder::new(&[0x62, b'a', b'b'])
+            .with_limits(limits)
+            .string()
+            .unwrap_err();
+        assert!(err.limit_exceeded().is_some());
+        let err = rpc_v2_cbor::runtime_error::RuntimeError::from(rpc_v2_cbor::rejection::RequestRejection::from(err));
+        assert_eq!(StatusCode::BAD_REQUEST, err.status_code());
+    }
+}
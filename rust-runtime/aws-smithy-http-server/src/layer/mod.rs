@@ -7,3 +7,4 @@
 //! [`Router`](crate::routing::Router), so they are enacted before a request is routed.
 
 pub mod alb_health_check;
+pub mod decode_limits;
@@ -17,6 +17,9 @@ pub(crate) mod macros;
 
 pub mod body;
 pub(crate) mod error;
+#[cfg(feature = "event-stream")]
+#[cfg_attr(docsrs, doc(cfg(feature = "event-stream")))]
+pub mod event_stream;
 pub mod extension;
 pub mod instrumentation;
 pub mod layer;
@@ -122,7 +122,10 @@ pub enum RequestRejection {
     MissingContentType(#[from] MissingContentTypeReason),
 
     /// Used when failing to deserialize the HTTP body's bytes into a JSON document conforming to
-    /// the modeled input it should represent.
+    /// the modeled input it should represent. This includes bodies exceeding the configured
+    /// [`DecodeLimits`](aws_smithy_types::decode_limits::DecodeLimits), in which case
+    /// [`DeserializeError::limit_exceeded`](aws_smithy_json::deserialize::error::DeserializeError::limit_exceeded)
+    /// describes the exceeded limit.
     #[error("error deserializing request HTTP body as JSON: {0}")]
     JsonDeserialize(#[from] aws_smithy_json::deserialize::error::DeserializeError),
 
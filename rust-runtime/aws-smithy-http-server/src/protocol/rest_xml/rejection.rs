@@ -36,7 +36,8 @@ pub enum RequestRejection {
     MissingContentType(#[from] MissingContentTypeReason),
 
     /// Used when failing to deserialize the HTTP body's bytes into a XML conforming to the modeled
-    /// input it should represent.
+    /// input it should represent. This includes bodies exceeding the configured
+    /// [`DecodeLimits`](aws_smithy_types::decode_limits::DecodeLimits).
     #[error("error deserializing request HTTP body as XML: {0}")]
     XmlDeserialize(#[from] aws_smithy_xml::decode::XmlDecodeError),
 
@@ -4,10 +4,12 @@
  */
 
 use crate::deserialize::error::{DeserializeError as Error, DeserializeErrorKind as ErrorKind};
+use aws_smithy_types::decode_limits::DecodeLimits;
 use aws_smithy_types::Number;
 use ErrorKind::*;
 
 pub mod error;
+pub mod incremental;
 pub mod token;
 
 pub use token::{EscapeError, EscapedStr, Offset, Token};
@@ -26,10 +28,26 @@ pub use token::{EscapeError, EscapedStr, Offset, Token};
 /// yield `ValueNull` and `ValueTrue`. It is the responsibility of the caller to handle this for
 /// their use-case.
 pub fn json_token_iter(input: &[u8]) -> JsonTokenIterator<'_> {
+    json_token_iter_with_limits(input, DecodeLimits::new())
+}
+
+/// JSON token parser that enforces the given [`DecodeLimits`]
+///
+/// This behaves exactly like [`json_token_iter`], except that it yields an error, and then stops,
+/// as soon as the input exceeds one of the `limits`. The error's
+/// [`limit_exceeded`](Error::limit_exceeded) describes which limit was exceeded.
+///
+/// Nesting depth counts arrays and objects, collection size counts array items and object
+/// members, and string length is measured on the escaped string, including object keys.
+pub fn json_token_iter_with_limits(input: &[u8], limits: DecodeLimits) -> JsonTokenIterator<'_> {
     JsonTokenIterator {
         input,
         index: 0,
         state_stack: vec![State::Initial],
+        limits,
+        token_count: 0,
+        collection_sizes: Vec::new(),
+        partial_string: None,
     }
 }
 
@@ -56,6 +74,13 @@ pub struct JsonTokenIterator<'a> {
     input: &'a [u8],
     index: usize,
     state_stack: Vec<State>,
+    limits: DecodeLimits,
+    token_count: usize,
+    /// Number of items or members read so far in each open array or object
+    collection_sizes: Vec<usize>,
+    /// Index of the first byte of a string that was cut off by the end of the input, and the
+    /// index up to which it was scanned. Reading that string again resumes from there.
+    partial_string: Option<(usize, usize)>,
 }
 
 impl<'a> JsonTokenIterator<'a> {
@@ -180,28 +205,55 @@ impl<'a> JsonTokenIterator<'a> {
 
         // Read bytes until a non-escaped end-quote, unescaping sequences as needed on the fly
         let start = self.index;
+        if let Some((partial_start, scanned)) = self.partial_string.take() {
+            if partial_start == start {
+                self.index = scanned;
+            }
+        }
         loop {
-            match self.peek_expect()? {
-                b'"' => {
-                    let value = std::str::from_utf8(&self.input[start..self.index])
-                        .map_err(|_| self.error(InvalidUtf8))?;
-                    self.advance();
-                    return Ok(value);
+            let scanned = self.index;
+            match self.scan_string(start) {
+                Ok(Some(value)) => return Ok(value),
+                Ok(None) => {}
+                Err(err) => {
+                    if matches!(err.kind, UnexpectedEos) {
+                        self.partial_string = Some((start, scanned));
+                    }
+                    return Err(err);
                 }
-                b'\\' => match self.next_expect()? {
-                    b'\\' | b'/' | b'"' | b'b' | b'f' | b'n' | b'r' | b't' => self.advance(),
+            }
+        }
+    }
+
+    /// Scans the next character or escape sequence of the string starting at `start`,
+    /// returning the string once its end-quote is reached.
+    fn scan_string(&mut self, start: usize) -> Result<Option<&'a str>, Error> {
+        match self.peek_expect()? {
+            b'"' => {
+                let value = std::str::from_utf8(&self.input[start..self.index])
+                    .map_err(|_| self.error(InvalidUtf8))?;
+                self.advance();
+                return Ok(Some(value));
+            }
+            // Skip the escape sequence, checking that it's valid. The hex digits of `\u` escapes
+            // are validated when the string is unescaped.
+            b'\\' => {
+                self.advance();
+                match self.next_expect()? {
+                    b'\\' | b'/' | b'"' | b'b' | b'f' | b'n' | b'r' | b't' => {}
                     b'u' => {
                         if self.index + 4 > self.input.len() {
                             return Err(self.error_at(self.input.len(), UnexpectedEos));
                         }
                         self.index += 4;
                     }
-                    byte => return Err(self.error(InvalidEscape(byte.into()))),
-                },
-                byte @ 0x00..=0x1F => return Err(self.error(UnexpectedControlCharacter(byte))),
-                _ => self.advance(),
+                    byte => return Err(self.error_at(self.index - 1, InvalidEscape(byte.into()))),
+                }
             }
+            byte @ 0x00..=0x1F => return Err(self.error(UnexpectedControlCharacter(byte))),
+            _ => self.advance(),
         }
+        Ok(None)
     }
 
     /// Expects the given literal to be next in the stream.
@@ -321,6 +373,7 @@ impl<'a> JsonTokenIterator<'a> {
         use std::str::FromStr;
         Ok(Token::ValueNumber {
             offset,
+            raw: number_str,
             value: if floating {
                 Number::Float(
                     f64::from_str(number_str)
@@ -329,20 +382,21 @@ impl<'a> JsonTokenIterator<'a> {
                             must_be_finite(f).map_err(|_| self.error_at(start, InvalidNumber))
                         })?,
                 )
-            } else if negative {
-                // If the negative value overflows, then stuff it into an f64
-                let positive = u64::from_str(&number_str[1..])
-                    .map_err(|_| self.error_at(start, InvalidNumber))?;
-                let negative = positive.wrapping_neg() as i64;
-                if negative > 0 {
-                    Number::Float(-(positive as f64))
+            } else {
+                let integer = if negative {
+                    i64::from_str(number_str).map(Number::NegInt)
                 } else {
-                    Number::NegInt(negative)
+                    u64::from_str(number_str).map(Number::PosInt)
+                };
+                match integer {
+                    Ok(integer) => integer,
+                    // If the value overflows, then stuff it into an f64. Its exact value is still
+                    // available from `raw`.
+                    Err(_) => Number::Float(
+                        f64::from_str(number_str)
+                            .map_err(|_| self.error_at(start, InvalidNumber))?,
+                    ),
                 }
-            } else {
-                Number::PosInt(
-                    u64::from_str(number_str).map_err(|_| self.error_at(start, InvalidNumber))?,
-                )
             },
         })
     }
@@ -457,6 +511,52 @@ impl<'a> JsonTokenIterator<'a> {
             byte => Err(self.error(UnexpectedToken(byte.into(), "':'"))),
         }
     }
+
+    /// Checks a token read in the given `state` against the configured limits.
+    fn check_limits(&mut self, state: State, token: Token<'a>) -> Result<Token<'a>, Error> {
+        let offset = token.offset().0;
+        let exceeded = |err| Error::new(LimitExceeded(err), Some(offset));
+
+        self.token_count += 1;
+        self.limits
+            .check_tokens(self.token_count)
+            .map_err(exceeded)?;
+
+        let is_item = match state {
+            State::ArrayFirstValueOrEnd | State::ArrayNextValueOrEnd => {
+                !matches!(token, Token::EndArray { .. })
+            }
+            State::ObjectFirstKeyOrEnd | State::ObjectNextKeyOrEnd => {
+                matches!(token, Token::ObjectKey { .. })
+            }
+            State::Initial | State::ObjectFieldValue => false,
+        };
+        if is_item {
+            if let Some(size) = self.collection_sizes.last_mut() {
+                *size += 1;
+                self.limits.check_collection_size(*size).map_err(exceeded)?;
+            }
+        }
+
+        match &token {
+            Token::StartArray { .. } | Token::StartObject { .. } => {
+                self.limits
+                    .check_depth(self.state_stack.len() - 1)
+                    .map_err(exceeded)?;
+                self.collection_sizes.push(0);
+            }
+            Token::EndArray { .. } | Token::EndObject { .. } => {
+                self.collection_sizes.pop();
+            }
+            Token::ObjectKey { key: value, .. } | Token::ValueString { value, .. } => {
+                self.limits
+                    .check_string_length(value.as_escaped_str().len())
+                    .map_err(exceeded)?;
+            }
+            Token::ValueBool { .. } | Token::ValueNull { .. } | Token::ValueNumber { .. } => {}
+        }
+        Ok(token)
+    }
 }
 
 impl<'a> Iterator for JsonTokenIterator<'a> {
@@ -469,7 +569,8 @@ impl<'a> Iterator for JsonTokenIterator<'a> {
         }
 
         self.discard_whitespace();
-        let result = match self.state() {
+        let state = self.state();
+        let result = match state {
             State::Initial => self.peek_byte().map(|_| self.read_value()),
             State::ArrayFirstValueOrEnd => Some(self.state_array_first_value_or_end()),
             State::ArrayNextValueOrEnd => Some(self.state_array_next_value_or_end()),
@@ -477,6 +578,10 @@ impl<'a> Iterator for JsonTokenIterator<'a> {
             State::ObjectNextKeyOrEnd => Some(self.state_object_next_key_or_end()),
             State::ObjectFieldValue => Some(self.state_object_field_value()),
         };
+        let result = match result {
+            Some(Ok(token)) if !self.limits.is_unlimited() => Some(self.check_limits(state, token)),
+            result => result,
+        };
         // Invalidate the stream if we encountered an error
         if result.as_ref().map(|r| r.is_err()).unwrap_or(false) {
             self.index = self.input.len();
@@ -508,7 +613,8 @@ mod tests {
         end_array, end_object, object_key, start_array, start_object, value_bool, value_null,
         value_number, value_string,
     };
-    use crate::deserialize::{json_token_iter, EscapedStr, Token};
+    use crate::deserialize::{json_token_iter, json_token_iter_with_limits, EscapedStr, Token};
+    use aws_smithy_types::decode_limits::{DecodeLimit, DecodeLimits};
     use aws_smithy_types::Number;
     use proptest::prelude::*;
 
@@ -618,7 +724,7 @@ mod tests {
             } else {
                 Number::PosInt(input as u64)
             };
-            expect_token(value_number(0, expected), iter.next());
+            expect_token(value_number(0, &json, expected), iter.next());
             expect_token(None, iter.next());
         }
 
@@ -626,7 +732,7 @@ mod tests {
         fn float_prop_test(input: f64) {
             let json = serde_json::to_string(&input).unwrap();
             let mut iter = json_token_iter(json.as_bytes());
-            expect_token(value_number(0, Number::Float(input)), iter.next());
+            expect_token(value_number(0, &json, Number::Float(input)), iter.next());
             expect_token(None, iter.next());
         }
     }
@@ -634,7 +740,8 @@ mod tests {
     #[test]
     fn valid_numbers() {
         let expect = |number, input| {
-            expect_token(value_number(0, number), json_token_iter(input).next());
+            let raw = std::str::from_utf8(input).unwrap();
+            expect_token(value_number(0, raw, number), json_token_iter(input).next());
         };
         expect(Number::Float(0.0), b"0.");
         expect(Number::Float(0.0), b"0e0");
@@ -648,6 +755,10 @@ mod tests {
             Number::Float(-18446744073709551615.0),
             b"-18446744073709551615",
         );
+        expect(
+            Number::Float(18446744073709551616.0),
+            b"18446744073709551616",
+        );
     }
 
     // These cases actually shouldn't parse according to the spec, but it's easier
@@ -655,7 +766,8 @@ mod tests {
     #[test]
     fn invalid_numbers_we_are_intentionally_accepting() {
         let expect = |number, input| {
-            expect_token(value_number(0, number), json_token_iter(input).next());
+            let raw = std::str::from_utf8(input).unwrap();
+            expect_token(value_number(0, raw, number), json_token_iter(input).next());
         };
 
         expect(Number::NegInt(-1), b"-01");
@@ -758,13 +870,16 @@ mod tests {
         );
         expect_token(start_object(0), tokens.next());
         expect_token(object_key(2, "some_int"), tokens.next());
-        expect_token(value_number(14, Number::PosInt(5)), tokens.next());
+        expect_token(value_number(14, "5", Number::PosInt(5)), tokens.next());
         expect_token(object_key(35, "some_float"), tokens.next());
-        expect_token(value_number(49, Number::Float(5.2)), tokens.next());
+        expect_token(value_number(49, "5.2", Number::Float(5.2)), tokens.next());
         expect_token(object_key(72, "some_negative"), tokens.next());
-        expect_token(value_number(89, Number::NegInt(-5)), tokens.next());
+        expect_token(value_number(89, "-5", Number::NegInt(-5)), tokens.next());
         expect_token(object_key(111, "some_negative_float"), tokens.next());
-        expect_token(value_number(134, Number::Float(-2.4)), tokens.next());
+        expect_token(
+            value_number(134, "-2.4", Number::Float(-2.4)),
+            tokens.next(),
+        );
         expect_token(object_key(158, "some_string"), tokens.next());
         expect_token(value_string(173, "test"), tokens.next());
         expect_token(object_key(199, "some_struct"), tokens.next());
@@ -814,10 +929,90 @@ mod tests {
         assert!(json_token_iter(b"\"test\ttest\"").next().unwrap().is_err());
     }
 
+    #[test]
+    fn invalid_escapes() {
+        expect_err!(
+            ErrorKind::InvalidEscape('x'),
+            Some(6),
+            json_token_iter(br#""test\xtest""#).next()
+        );
+        expect_err!(
+            ErrorKind::UnexpectedEos,
+            Some(9),
+            json_token_iter(br#""test\u12"#).next()
+        );
+        expect_token(
+            value_string(0, r#"\"\\\/\b\f\n\r\t\u00e9"#),
+            json_token_iter(br#""\"\\\/\b\f\n\r\t\u00e9""#).next(),
+        );
+    }
+
     #[test]
     fn escaped_str() {
         let escaped = EscapedStr::new("foo\\nbar");
         assert_eq!("foo\\nbar", escaped.as_escaped_str());
         assert_eq!("foo\nbar", escaped.to_unescaped().unwrap());
     }
+
+    #[track_caller]
+    fn expect_limit(input: &[u8], limits: DecodeLimits, limit: DecodeLimit, offset: usize) {
+        let mut iter = json_token_iter_with_limits(input, limits);
+        let err = loop {
+            match iter.next() {
+                Some(Ok(_)) => {}
+                Some(Err(err)) => break err,
+                None => panic!("expected {limit:?} to be exceeded"),
+            }
+        };
+        assert_eq!(Some(limit), err.limit_exceeded().map(|l| l.limit()));
+        assert_eq!(Some(offset), err.offset, "{err}");
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn limits() {
+        let input = br#"{"a": [1, "bc", {"d": []}], "e": null}"#;
+        assert_eq!(
+            json_token_iter(input).count(),
+            json_token_iter_with_limits(
+                input,
+                DecodeLimits::new()
+                    .with_max_depth(4)
+                    .with_max_string_length(2)
+                    .with_max_collection_size(3)
+                    .with_max_tokens(14)
+            )
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .len()
+        );
+
+        let limits = DecodeLimits::new().with_max_depth(3);
+        expect_limit(input, limits, DecodeLimit::Depth, 22);
+        let limits = DecodeLimits::new().with_max_string_length(1);
+        expect_limit(input, limits, DecodeLimit::StringLength, 10);
+        let limits = DecodeLimits::new().with_max_collection_size(2);
+        expect_limit(input, limits, DecodeLimit::CollectionSize, 16);
+        let limits = DecodeLimits::new().with_max_collection_size(1);
+        expect_limit(input, limits, DecodeLimit::CollectionSize, 10);
+        let limits = DecodeLimits::new().with_max_tokens(13);
+        expect_limit(input, limits, DecodeLimit::Tokens, 37);
+
+        // Object members count once, however many tokens they take
+        let limits = DecodeLimits::new().with_max_collection_size(1);
+        expect_limit(
+            br#"{"a": 1, "b": 2}"#,
+            limits,
+            DecodeLimit::CollectionSize,
+            9,
+        );
+        let err = json_token_iter_with_limits(br#""abc""#, limits.with_max_string_length(2))
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert_eq!(
+            "Error at offset 0: JSON input exceeded the maximum string length of 2",
+            err.to_string()
+        );
+    }
 }
@@ -4,6 +4,7 @@
  */
 
 use crate::escape::EscapeError;
+use aws_smithy_types::decode_limits::LimitExceeded;
 use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt;
@@ -19,6 +20,7 @@ pub(in crate::deserialize) enum DeserializeErrorKind {
     InvalidEscape(char),
     InvalidNumber,
     InvalidUtf8,
+    LimitExceeded(LimitExceeded),
     UnescapeFailed(EscapeError),
     UnexpectedControlCharacter(u8),
     UnexpectedEos,
@@ -66,6 +68,15 @@ impl DeserializeError {
         self.offset = Some(offset);
         self
     }
+
+    /// Returns the limit that was exceeded if this error was caused by the input exceeding the
+    /// configured [`DecodeLimits`](aws_smithy_types::decode_limits::DecodeLimits).
+    pub fn limit_exceeded(&self) -> Option<&LimitExceeded> {
+        match &self.kind {
+            DeserializeErrorKind::LimitExceeded(limit) => Some(limit),
+            _ => None,
+        }
+    }
 }
 
 impl StdError for DeserializeError {
@@ -73,6 +84,7 @@ impl StdError for DeserializeError {
         use DeserializeErrorKind::*;
         match &self.kind {
             UnescapeFailed(source) => Some(source),
+            LimitExceeded(source) => Some(source),
             Custom {
                 source: Some(source),
                 ..
@@ -101,6 +113,7 @@ impl fmt::Display for DeserializeError {
             InvalidEscape(escape) => write!(f, "invalid JSON escape: \\{escape}"),
             InvalidNumber => write!(f, "invalid number"),
             InvalidUtf8 => write!(f, "invalid UTF-8 codepoint in JSON stream"),
+            LimitExceeded(limit) => write!(f, "JSON input {limit}"),
             UnescapeFailed(_) => write!(f, "failed to unescape JSON string"),
             UnexpectedControlCharacter(value) => write!(
                 f,
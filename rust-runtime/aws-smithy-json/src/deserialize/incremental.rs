@@ -0,0 +1,987 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Incremental JSON tokenizing for input that arrives in chunks.
+//!
+//! [`json_token_iter`](crate::deserialize::json_token_iter) needs the whole JSON document in a
+//! single slice. [`IncrementalTokenizer`] instead accepts the document chunk by chunk, and yields
+//! tokens as soon as they are complete. Only the bytes of the token currently being read are
+//! buffered, so a large list can be deserialized item by item while the rest of it is still
+//! being received.
+//!
+//! [`ByteStreamTokenizer`] drives an [`IncrementalTokenizer`] from a [`ByteStream`], such as a
+//! response body, and [`ListItems`] uses it to deserialize the items of a list in that body one
+//! at a time.
+
+use crate::deserialize::error::{DeserializeError as Error, DeserializeErrorKind as ErrorKind};
+use crate::deserialize::token::expect_start_object;
+use crate::deserialize::{EscapedStr, JsonTokenIterator, Offset, State, Token};
+use aws_smithy_types::byte_stream::ByteStream;
+use aws_smithy_types::decode_limits::DecodeLimits;
+use aws_smithy_types::Number;
+
+/// Result of reading the next token from an [`IncrementalTokenizer`].
+#[derive(Debug, PartialEq)]
+pub enum NextToken<'a> {
+    /// There wasn't enough input buffered to read a full token.
+    ///
+    /// Call [`IncrementalTokenizer::feed`] with more input, or [`IncrementalTokenizer::finish`]
+    /// if there is none left, and try again.
+    Incomplete,
+    /// A complete token was read.
+    Complete(Token<'a>),
+    /// All of the input has been read.
+    End,
+}
+
+/// JSON tokenizer that accepts its input in chunks.
+///
+/// Tokens are validated and yielded exactly as [`json_token_iter`](crate::deserialize::json_token_iter)
+/// would for the same input concatenated together, including their offsets, regardless of
+/// where the chunk boundaries fall. Tokens borrow from the tokenizer's buffer, so each token
+/// must be dropped before more input is fed in.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_json::deserialize::incremental::{IncrementalTokenizer, NextToken};
+/// use aws_smithy_json::deserialize::Token;
+///
+/// let mut tokenizer = IncrementalTokenizer::new();
+/// tokenizer.feed(b"[\"hel");
+/// assert!(matches!(tokenizer.next_token(), Ok(NextToken::Complete(Token::StartArray { .. }))));
+/// assert!(matches!(tokenizer.next_token(), Ok(NextToken::Incomplete)));
+///
+/// tokenizer.feed(b"lo\"]");
+/// tokenizer.finish();
+/// match tokenizer.next_token() {
+///     Ok(NextToken::Complete(Token::ValueString { value, .. })) => {
+///         assert_eq!("hello", value.as_escaped_str())
+///     }
+///     other => panic!("unexpected: {:?}", other),
+/// }
+/// assert!(matches!(tokenizer.next_token(), Ok(NextToken::Complete(Token::EndArray { .. }))));
+/// assert!(matches!(tokenizer.next_token(), Ok(NextToken::End)));
+/// ```
+#[derive(Debug)]
+pub struct IncrementalTokenizer {
+    /// Input that hasn't been discarded yet
+    buffer: Vec<u8>,
+    /// Index into `buffer` of the first byte that hasn't been tokenized
+    index: usize,
+    /// Offset of `buffer[0]` in the overall input
+    base_offset: usize,
+    state_stack: Vec<State>,
+    /// Copy of `state_stack` to restore when a token turns out to be incomplete
+    saved_state_stack: Vec<State>,
+    limits: DecodeLimits,
+    token_count: usize,
+    collection_sizes: Vec<usize>,
+    /// Copy of `collection_sizes` to restore when a token turns out to be incomplete
+    saved_collection_sizes: Vec<usize>,
+    /// Start of the string at the end of the buffer, and how far it has been scanned, so that
+    /// each chunk of a long string is only scanned once
+    partial_string: Option<(usize, usize)>,
+    /// Index into `buffer` of the start of a value that is being read by
+    /// [`ByteStreamTokenizer::read_value`], which must be kept until the value is complete
+    retain_from: Option<usize>,
+    /// The most recently read token
+    current: Option<BufferedToken>,
+    finished: bool,
+    failed: bool,
+}
+
+/// A token that refers to its string data by position in the buffer instead of by reference.
+#[derive(Debug)]
+enum BufferedToken {
+    StartArray(Offset),
+    EndArray(Offset),
+    ObjectKey(Offset, usize),
+    StartObject(Offset),
+    EndObject(Offset),
+    ValueBool(Offset, bool),
+    ValueNull(Offset),
+    ValueNumber(Offset, usize, Number),
+    ValueString(Offset, usize),
+}
+
+impl BufferedToken {
+    fn offset(&self) -> Offset {
+        match *self {
+            BufferedToken::StartArray(offset)
+            | BufferedToken::EndArray(offset)
+            | BufferedToken::ObjectKey(offset, _)
+            | BufferedToken::StartObject(offset)
+            | BufferedToken::EndObject(offset)
+            | BufferedToken::ValueBool(offset, _)
+            | BufferedToken::ValueNull(offset)
+            | BufferedToken::ValueNumber(offset, _, _)
+            | BufferedToken::ValueString(offset, _) => offset,
+        }
+    }
+}
+
+/// Result of trying to read a token into `IncrementalTokenizer::current`
+#[derive(Debug, Eq, PartialEq)]
+enum Progress {
+    Complete,
+    Incomplete,
+    End,
+}
+
+impl Default for IncrementalTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalTokenizer {
+    /// Creates a new tokenizer without any input.
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            index: 0,
+            base_offset: 0,
+            state_stack: vec![State::Initial],
+            saved_state_stack: Vec::new(),
+            limits: DecodeLimits::new(),
+            token_count: 0,
+            collection_sizes: Vec::new(),
+            saved_collection_sizes: Vec::new(),
+            partial_string: None,
+            retain_from: None,
+            current: None,
+            finished: false,
+            failed: false,
+        }
+    }
+
+    /// Sets the limits the input is checked against as it's tokenized.
+    ///
+    /// A [`LimitExceeded`](aws_smithy_types::decode_limits::LimitExceeded) error is returned
+    /// as soon as the input exceeds one of them, exactly as with
+    /// [`json_token_iter_with_limits`](crate::deserialize::json_token_iter_with_limits).
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Appends the next chunk of input.
+    ///
+    /// # Panics
+    /// This function will panic if called after [`finish`](IncrementalTokenizer::finish).
+    pub fn feed(&mut self, chunk: &[u8]) {
+        assert!(
+            !self.finished,
+            "input can't be fed after `finish` was called"
+        );
+        // Discard input that has already been tokenized so the buffer only ever holds
+        // the partial token at the end of the input, and the value being read if there is one
+        let discard = self
+            .retain_from
+            .map_or(self.index, |start| start.min(self.index));
+        self.buffer.drain(..discard);
+        self.base_offset += discard;
+        self.partial_string = self
+            .partial_string
+            .map(|(start, scanned)| (start - discard, scanned - discard));
+        self.retain_from = self.retain_from.map(|start| start - discard);
+        self.index -= discard;
+        self.current = None;
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Indicates that there is no more input.
+    ///
+    /// Once finished, tokens at the end of the input are yielded and incomplete tokens
+    /// are reported as errors.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Reads the next token.
+    ///
+    /// Once an error is returned, all subsequent calls return [`NextToken::End`].
+    pub fn next_token(&mut self) -> Result<NextToken<'_>, Error> {
+        Ok(match self.advance()? {
+            Progress::Complete => {
+                NextToken::Complete(self.current_token().expect("a token was just read"))
+            }
+            Progress::Incomplete => NextToken::Incomplete,
+            Progress::End => NextToken::End,
+        })
+    }
+
+    /// Tries to read the next token into `self.current`.
+    fn advance(&mut self) -> Result<Progress, Error> {
+        self.current = None;
+        if self.failed {
+            return Ok(Progress::End);
+        }
+        self.saved_state_stack.clear();
+        self.saved_state_stack.extend_from_slice(&self.state_stack);
+        self.saved_collection_sizes.clear();
+        self.saved_collection_sizes
+            .extend_from_slice(&self.collection_sizes);
+
+        let mut iter = JsonTokenIterator {
+            input: &self.buffer,
+            index: self.index,
+            state_stack: std::mem::take(&mut self.state_stack),
+            limits: self.limits,
+            token_count: self.token_count,
+            collection_sizes: std::mem::take(&mut self.collection_sizes),
+            partial_string: self.partial_string.take(),
+        };
+        let result = iter.next();
+        let end = iter.index;
+        self.state_stack = iter.state_stack;
+        self.collection_sizes = iter.collection_sizes;
+        let token_count = iter.token_count;
+        let partial_string = iter.partial_string;
+
+        let token = match result {
+            None => {
+                // Only whitespace was left, which doesn't need to be read again
+                self.index = end;
+                return Ok(if self.finished {
+                    Progress::End
+                } else {
+                    Progress::Incomplete
+                });
+            }
+            Some(Err(err)) if !self.finished && self.more_input_could_fix(&err) => {
+                self.restore_saved_state();
+                if let Some((start, scanned)) = partial_string {
+                    // Don't buffer more of a string than it's allowed to be long
+                    if let Err(limit) = self.limits.check_string_length(scanned - start) {
+                        self.failed = true;
+                        let offset = self.base_offset + start - 1;
+                        return Err(Error::new(ErrorKind::LimitExceeded(limit), Some(offset)));
+                    }
+                    self.partial_string = partial_string;
+                }
+                return Ok(Progress::Incomplete);
+            }
+            Some(Err(mut err)) => {
+                self.failed = true;
+                err.offset = err.offset.map(|offset| offset + self.base_offset);
+                return Err(err);
+            }
+            Some(Ok(token)) => token,
+        };
+
+        // Numbers, booleans, and null aren't delimited, so they're only complete once the byte
+        // after them is known. More digits could follow a number, and a literal can't be
+        // followed by arbitrary characters.
+        let undelimited = matches!(
+            token,
+            Token::ValueNumber { .. } | Token::ValueBool { .. } | Token::ValueNull { .. }
+        );
+        if undelimited && end == self.buffer.len() && !self.finished {
+            self.restore_saved_state();
+            return Ok(Progress::Incomplete);
+        }
+        self.token_count = token_count;
+
+        let shift = |offset: Offset| Offset(offset.0 + self.base_offset);
+        let current = match token {
+            Token::StartArray { offset } => BufferedToken::StartArray(shift(offset)),
+            Token::EndArray { offset } => BufferedToken::EndArray(shift(offset)),
+            Token::ObjectKey { offset, .. } => BufferedToken::ObjectKey(shift(offset), end),
+            Token::StartObject { offset } => BufferedToken::StartObject(shift(offset)),
+            Token::EndObject { offset } => BufferedToken::EndObject(shift(offset)),
+            Token::ValueBool { offset, value } => BufferedToken::ValueBool(shift(offset), value),
+            Token::ValueNull { offset } => BufferedToken::ValueNull(shift(offset)),
+            Token::ValueNumber { offset, value, .. } => {
+                BufferedToken::ValueNumber(shift(offset), end, value)
+            }
+            Token::ValueString { offset, .. } => BufferedToken::ValueString(shift(offset), end),
+        };
+        self.current = Some(current);
+        self.index = end;
+        Ok(Progress::Complete)
+    }
+
+    /// Undoes the state changes made while reading a token that turned out to be incomplete.
+    fn restore_saved_state(&mut self) {
+        std::mem::swap(&mut self.state_stack, &mut self.saved_state_stack);
+        std::mem::swap(&mut self.collection_sizes, &mut self.saved_collection_sizes);
+    }
+
+    /// Returns true if `err` was caused by a token being cut off at the end of the buffer.
+    fn more_input_could_fix(&self, err: &Error) -> bool {
+        match err.kind {
+            ErrorKind::UnexpectedEos => true,
+            // A number like `-` or `1e` is invalid by itself, but more digits could complete it
+            ErrorKind::InvalidNumber => err.offset.is_some_and(|start| {
+                self.buffer[start..]
+                    .iter()
+                    .all(|b| matches!(b, b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E'))
+            }),
+            _ => false,
+        }
+    }
+
+    /// Returns the most recently read token.
+    fn current_token(&self) -> Option<Token<'_>> {
+        // Strings start after the opening quote at `offset` and end before the closing quote at `end`
+        let string = |offset: Offset, end: usize| {
+            let start = offset.0 - self.base_offset + 1;
+            EscapedStr::new(
+                std::str::from_utf8(&self.buffer[start..end - 1])
+                    .expect("validated when the token was read"),
+            )
+        };
+        Some(match *self.current.as_ref()? {
+            BufferedToken::StartArray(offset) => Token::StartArray { offset },
+            BufferedToken::EndArray(offset) => Token::EndArray { offset },
+            BufferedToken::ObjectKey(offset, end) => Token::ObjectKey {
+                offset,
+                key: string(offset, end),
+            },
+            BufferedToken::StartObject(offset) => Token::StartObject { offset },
+            BufferedToken::EndObject(offset) => Token::EndObject { offset },
+            BufferedToken::ValueBool(offset, value) => Token::ValueBool { offset, value },
+            BufferedToken::ValueNull(offset) => Token::ValueNull { offset },
+            BufferedToken::ValueNumber(offset, end, value) => Token::ValueNumber {
+                offset,
+                value,
+                raw: std::str::from_utf8(&self.buffer[offset.0 - self.base_offset..end])
+                    .expect("validated when the token was read"),
+            },
+            BufferedToken::ValueString(offset, end) => Token::ValueString {
+                offset,
+                value: string(offset, end),
+            },
+        })
+    }
+}
+
+/// Reads JSON tokens from a [`ByteStream`] as its data arrives.
+///
+/// Tokens are returned in the same form as the items of
+/// [`JsonTokenIterator`](crate::deserialize::JsonTokenIterator), so they can be passed to the
+/// functions in [`token`](crate::deserialize::token) such as
+/// [`expect_string_or_null`](crate::deserialize::token::expect_string_or_null).
+///
+/// # Examples
+/// ```rust
+/// # async fn example() {
+/// use aws_smithy_json::deserialize::incremental::ByteStreamTokenizer;
+/// use aws_smithy_json::deserialize::token::expect_start_array;
+/// use aws_smithy_json::deserialize::Token;
+/// use aws_smithy_types::byte_stream::ByteStream;
+///
+/// let mut tokens = ByteStreamTokenizer::new(ByteStream::from_static(b"[1, 2, 3]"));
+/// expect_start_array(tokens.next_token().await).unwrap();
+/// while let Some(token) = tokens.next_token().await {
+///     match token.unwrap() {
+///         Token::ValueNumber { value, .. } => println!("item: {value:?}"),
+///         Token::EndArray { .. } => break,
+///         other => panic!("unexpected: {other:?}"),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ByteStreamTokenizer {
+    body: ByteStream,
+    tokenizer: IncrementalTokenizer,
+}
+
+impl ByteStreamTokenizer {
+    /// Creates a tokenizer that reads from `body`.
+    pub fn new(body: ByteStream) -> Self {
+        Self {
+            body,
+            tokenizer: IncrementalTokenizer::new(),
+        }
+    }
+
+    /// Sets the limits the body is checked against as it's tokenized.
+    ///
+    /// See [`IncrementalTokenizer::with_limits`].
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.tokenizer = self.tokenizer.with_limits(limits);
+        self
+    }
+
+    /// Reads the next token, waiting for more data from the body if needed.
+    ///
+    /// Returns `None` once the body has been fully read, or after an error.
+    pub async fn next_token(&mut self) -> Option<Result<Token<'_>, Error>> {
+        match self.advance().await {
+            Ok(true) => self.tokenizer.current_token().map(Ok),
+            Ok(false) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    /// Skips the next value, including everything nested in it.
+    pub async fn skip_value(&mut self) -> Result<(), Error> {
+        self.expect_value_start().await?;
+        self.read_to_end_of_value().await
+    }
+
+    /// Reads the next value and returns its JSON text, or `None` if the end of the enclosing
+    /// array or object comes first.
+    async fn read_value(&mut self) -> Result<Option<&[u8]>, Error> {
+        if !self.expect_value_start().await? {
+            return Ok(None);
+        }
+        let current = self
+            .tokenizer
+            .current
+            .as_ref()
+            .expect("a token was just read");
+        self.tokenizer.retain_from = Some(current.offset().0 - self.tokenizer.base_offset);
+        let result = self.read_to_end_of_value().await;
+        let start = self.tokenizer.retain_from.take().expect("set above");
+        result?;
+        Ok(Some(&self.tokenizer.buffer[start..self.tokenizer.index]))
+    }
+
+    /// Reads the first token of a value. Returns false if it's the end of an array or object instead.
+    async fn expect_value_start(&mut self) -> Result<bool, Error> {
+        if !self.advance().await? {
+            return Err(Error::new(ErrorKind::UnexpectedEos, None));
+        }
+        match self.tokenizer.current {
+            Some(BufferedToken::EndArray(_)) | Some(BufferedToken::EndObject(_)) => Ok(false),
+            Some(BufferedToken::ObjectKey(offset, _)) => {
+                Err(Error::custom("expected value").with_offset(offset.0))
+            }
+            _ => Ok(true),
+        }
+    }
+
+    /// Reads tokens until the value started by the current token is complete.
+    async fn read_to_end_of_value(&mut self) -> Result<(), Error> {
+        let mut depth = 0usize;
+        loop {
+            match self.tokenizer.current {
+                Some(BufferedToken::StartArray(_)) | Some(BufferedToken::StartObject(_)) => {
+                    depth += 1
+                }
+                Some(BufferedToken::EndArray(_)) | Some(BufferedToken::EndObject(_)) => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                return Ok(());
+            }
+            if !self.advance().await? {
+                return Err(Error::new(ErrorKind::UnexpectedEos, None));
+            }
+        }
+    }
+
+    /// Reads the next token into `self.tokenizer.current`, waiting for more data from the body
+    /// if needed. Returns false once the body has been fully read.
+    async fn advance(&mut self) -> Result<bool, Error> {
+        loop {
+            match self.tokenizer.advance()? {
+                Progress::Complete => return Ok(true),
+                Progress::End => return Ok(false),
+                Progress::Incomplete => match self.body.next().await {
+                    Some(Ok(chunk)) => self.tokenizer.feed(&chunk),
+                    Some(Err(err)) => {
+                        self.tokenizer.failed = true;
+                        return Err(Error::custom_source("failed to read the JSON body", err));
+                    }
+                    None => self.tokenizer.finish(),
+                },
+            }
+        }
+    }
+}
+
+/// Deserializes the items of a JSON list in a [`ByteStream`] one at a time, as the body arrives.
+///
+/// Only the item currently being deserialized is buffered, so the first items of a large list
+/// are available before the rest of the body has been received. Generated clients use this for
+/// list members of operation outputs.
+///
+/// # Examples
+/// ```rust
+/// # async fn example() {
+/// use aws_smithy_json::deserialize::error::DeserializeError;
+/// use aws_smithy_json::deserialize::incremental::{ByteStreamTokenizer, ListItems};
+/// use aws_smithy_json::deserialize::json_token_iter;
+/// use aws_smithy_json::deserialize::token::expect_number_or_null;
+/// use aws_smithy_types::byte_stream::ByteStream;
+///
+/// fn de_item(value: &[u8]) -> Result<Option<u64>, DeserializeError> {
+///     let number = expect_number_or_null(json_token_iter(value).next())?;
+///     number.map(|n| n.try_into().map_err(|_| DeserializeError::custom("expected u64"))).transpose()
+/// }
+///
+/// let body = ByteStream::from_static(br#"{"Items": [1, 2, 3], "NextToken": null}"#);
+/// let mut items = ListItems::from_object_member(ByteStreamTokenizer::new(body), "Items", de_item)
+///     .await
+///     .unwrap();
+/// while let Some(item) = items.next().await {
+///     println!("item: {}", item.unwrap());
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ListItems<T> {
+    tokens: ByteStreamTokenizer,
+    de_item: fn(&[u8]) -> Result<Option<T>, Error>,
+    done: bool,
+}
+
+impl<T> ListItems<T> {
+    /// Reads the items of the list that is the `key` member of the object at the top level of
+    /// `tokens`.
+    ///
+    /// Each item is passed to `de_item` as JSON text. Items it returns `None` for, such as nulls
+    /// in a dense list, are skipped. Members of the object before `key` are skipped. If the object
+    /// has no `key` member, or it's null, there are no items.
+    ///
+    /// Once the list ends, the members of the object after it are read and skipped, so that a
+    /// malformed or truncated body, or trailing data after the object, is reported as an error by
+    /// [`ListItems::next`].
+    pub async fn from_object_member(
+        mut tokens: ByteStreamTokenizer,
+        key: &str,
+        de_item: fn(&[u8]) -> Result<Option<T>, Error>,
+    ) -> Result<Self, Error> {
+        expect_start_object(tokens.next_token().await)?;
+        let found = loop {
+            match tokens.next_token().await.transpose()? {
+                Some(Token::ObjectKey { key: member, .. }) => {
+                    if member.to_unescaped()? == key {
+                        break true;
+                    }
+                    tokens.skip_value().await?;
+                }
+                Some(Token::EndObject { .. }) => break false,
+                _ => return Err(Error::custom("expected object key or end of object")),
+            }
+        };
+        let done = if found {
+            match tokens.next_token().await.transpose()? {
+                Some(Token::StartArray { .. }) => false,
+                Some(Token::ValueNull { .. }) => {
+                    skip_to_end_of_object(&mut tokens).await?;
+                    true
+                }
+                _ => return Err(Error::custom("expected start array or null")),
+            }
+        } else {
+            expect_end_of_body(&mut tokens).await?;
+            true
+        };
+        Ok(Self {
+            tokens,
+            de_item,
+            done,
+        })
+    }
+
+    /// Returns the next item, or `None` once the end of the list has been reached.
+    ///
+    /// Once an error is returned, all subsequent calls return `None`.
+    pub async fn next(&mut self) -> Option<Result<T, Error>> {
+        while !self.done {
+            let item = match self.tokens.read_value().await {
+                Ok(Some(value)) => (self.de_item)(value),
+                Ok(None) => {
+                    // The list has ended, so check the rest of the body
+                    self.done = true;
+                    return skip_to_end_of_object(&mut self.tokens).await.err().map(Err);
+                }
+                Err(err) => Err(err),
+            };
+            match item {
+                Ok(Some(item)) => return Some(Ok(item)),
+                Ok(None) => {}
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+/// Skips the remaining members of the object at the top level of `tokens`, and then expects the
+/// end of the body.
+async fn skip_to_end_of_object(tokens: &mut ByteStreamTokenizer) -> Result<(), Error> {
+    loop {
+        match tokens.next_token().await.transpose()? {
+            Some(Token::ObjectKey { .. }) => tokens.skip_value().await?,
+            Some(Token::EndObject { .. }) => break,
+            _ => return Err(Error::custom("expected object key or end of object")),
+        }
+    }
+    expect_end_of_body(tokens).await
+}
+
+async fn expect_end_of_body(tokens: &mut ByteStreamTokenizer) -> Result<(), Error> {
+    match tokens.next_token().await.transpose()? {
+        None => Ok(()),
+        Some(_) => Err(Error::custom(
+            "found more JSON tokens after completing parsing",
+        )),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::deserialize::error::DeserializeError;
+    use crate::deserialize::incremental::{
+        ByteStreamTokenizer, IncrementalTokenizer, ListItems, NextToken,
+    };
+    use crate::deserialize::{json_token_iter, json_token_iter_with_limits, Token};
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use aws_smithy_types::decode_limits::DecodeLimits;
+    use bytes::Bytes;
+    use std::collections::VecDeque;
+    use std::convert::Infallible;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    const INPUT: &str = r#" {
+        "Items": [
+            {"Name": "caf\u00e9 \"quoted\"", "Size": 12345, "Ratio": -1.5e-3},
+            {"Name": "🦀 crab", "Tags": [true, false, null]},
+            []
+        ],
+        "Next": null
+    } "#;
+
+    /// Body that yields its data in the given chunks
+    struct ChunkedBody(VecDeque<Bytes>);
+
+    impl http_body::Body for ChunkedBody {
+        type Data = Bytes;
+        type Error = Infallible;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Bytes, Infallible>>> {
+            Poll::Ready(self.0.pop_front().map(Ok))
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Infallible>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn chunked_body(input: &[u8], size: usize) -> ByteStream {
+        let chunks = input.chunks(size).map(Bytes::copy_from_slice).collect();
+        ByteStream::new(SdkBody::from_body_0_4(ChunkedBody(chunks)))
+    }
+
+    /// Deserializes an item by returning its JSON text, or `None` for null
+    fn raw_item(value: &[u8]) -> Result<Option<String>, DeserializeError> {
+        Ok(Some(std::str::from_utf8(value).unwrap().to_string()).filter(|item| item != "null"))
+    }
+
+    async fn collect_items(mut items: ListItems<String>) -> Vec<String> {
+        let mut out = Vec::new();
+        while let Some(item) = items.next().await {
+            out.push(item.unwrap_or_else(|err| format!("{err:?}")));
+        }
+        out
+    }
+
+    /// Tokenizes `chunks` incrementally, returning the `Debug` output of each token or error
+    fn tokenize_chunks<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Vec<String> {
+        tokenize_chunks_with_limits(chunks, DecodeLimits::new())
+    }
+
+    fn tokenize_chunks_with_limits<'a>(
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+        limits: DecodeLimits,
+    ) -> Vec<String> {
+        let mut tokenizer = IncrementalTokenizer::new().with_limits(limits);
+        let mut chunks = chunks.into_iter();
+        let mut out = Vec::new();
+        loop {
+            match tokenizer.next_token() {
+                Ok(NextToken::Complete(token)) => out.push(format!("{:?}", token)),
+                Ok(NextToken::Incomplete) => match chunks.next() {
+                    Some(chunk) => tokenizer.feed(chunk),
+                    None => tokenizer.finish(),
+                },
+                Ok(NextToken::End) => return out,
+                Err(err) => out.push(format!("{:?}", err)),
+            }
+        }
+    }
+
+    fn tokenize_all(input: &[u8]) -> Vec<String> {
+        collect_tokens(json_token_iter(input))
+    }
+
+    fn collect_tokens<'a>(
+        tokens: impl Iterator<Item = Result<Token<'a>, DeserializeError>>,
+    ) -> Vec<String> {
+        tokens
+            .map(|token| match token {
+                Ok(token) => format!("{:?}", token),
+                Err(err) => format!("{:?}", err),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_split_point() {
+        let input = INPUT.as_bytes();
+        let expected = tokenize_all(input);
+        for split in 0..=input.len() {
+            let (head, tail) = input.split_at(split);
+            assert_eq!(expected, tokenize_chunks([head, tail]), "split at {split}");
+        }
+    }
+
+    #[test]
+    fn byte_at_a_time() {
+        let input = INPUT.as_bytes();
+        assert_eq!(tokenize_all(input), tokenize_chunks(input.chunks(1)));
+    }
+
+    #[test]
+    fn top_level_values() {
+        for input in ["123", "12 3", "true", "null ", "\"abc\"", "", "  "] {
+            let input = input.as_bytes();
+            assert_eq!(tokenize_all(input), tokenize_chunks(input.chunks(1)));
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let inputs: [&[u8]; 11] = [
+            b"[1, 2",
+            b"[1, -",
+            b"[1.e5]",
+            b"[\"abc",
+            b"[\"\\u00",
+            b"[tru",
+            b"[truex]",
+            b"[1, 2x]",
+            b"{\"a\" 1}",
+            b"[\"\\q\"]",
+            b"[\"\xff\"]",
+        ];
+        for input in inputs {
+            let expected = tokenize_all(input);
+            for size in 1..=input.len() {
+                assert_eq!(
+                    expected,
+                    tokenize_chunks(input.chunks(size)),
+                    "{input:?} in chunks of {size}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn limits() {
+        let input = INPUT.as_bytes();
+        let all_limits = [
+            DecodeLimits::new().with_max_depth(2),
+            DecodeLimits::new().with_max_string_length(5),
+            DecodeLimits::new().with_max_collection_size(2),
+            DecodeLimits::new().with_max_tokens(10),
+        ];
+        for limits in all_limits {
+            let expected = collect_tokens(json_token_iter_with_limits(input, limits));
+            assert!(expected.last().unwrap().contains("LimitExceeded"));
+            for size in [1, 2, 7] {
+                assert_eq!(
+                    expected,
+                    tokenize_chunks_with_limits(input.chunks(size), limits),
+                    "{limits:?} in chunks of {size}"
+                );
+            }
+        }
+        // A number at the end of a chunk is read again once more input arrives, but must only
+        // be counted once
+        let input = b"[12345, 6789]";
+        let limits = DecodeLimits::new().with_max_tokens(3);
+        assert_eq!(
+            collect_tokens(json_token_iter_with_limits(input, limits)),
+            tokenize_chunks_with_limits(input.chunks(1), limits)
+        );
+    }
+
+    #[test]
+    fn no_tokens_after_error() {
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.feed(b"[}");
+        assert!(matches!(tokenizer.next_token(), Ok(NextToken::Complete(_))));
+        assert!(tokenizer.next_token().is_err());
+        tokenizer.feed(b"]");
+        assert!(matches!(tokenizer.next_token(), Ok(NextToken::End)));
+    }
+
+    #[test]
+    fn buffer_only_holds_partial_token() {
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.feed(b"[\"first\", \"sec");
+        for _ in 0..2 {
+            assert!(matches!(tokenizer.next_token(), Ok(NextToken::Complete(_))));
+        }
+        assert!(matches!(tokenizer.next_token(), Ok(NextToken::Incomplete)));
+        tokenizer.feed(b"ond\"]");
+        assert_eq!(b", \"second\"]", &tokenizer.buffer[..]);
+    }
+
+    #[test]
+    fn long_strings_are_scanned_once() {
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.feed(b"[");
+        assert!(matches!(tokenizer.next_token(), Ok(NextToken::Complete(_))));
+        // Scanning stops before an escape sequence that was cut off, and otherwise at the end of the buffer
+        let chunks: [(&[u8], usize); 5] = [
+            (b"\"abc", 4),
+            (b"def\\", 7),
+            (b"u00", 7),
+            (b"e9", 13),
+            (b"ghi", 16),
+        ];
+        for (chunk, scanned) in chunks {
+            tokenizer.feed(chunk);
+            assert!(matches!(tokenizer.next_token(), Ok(NextToken::Incomplete)));
+            assert_eq!(Some((1, scanned)), tokenizer.partial_string);
+        }
+        tokenizer.feed(b"\"]");
+        match tokenizer.next_token() {
+            Ok(NextToken::Complete(Token::ValueString { value, .. })) => {
+                assert_eq!("abcdef\u{e9}ghi", value.to_unescaped().unwrap())
+            }
+            other => panic!("unexpected: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn partial_strings_are_limited() {
+        let limits = DecodeLimits::new().with_max_string_length(4);
+        let mut tokenizer = IncrementalTokenizer::new().with_limits(limits);
+        tokenizer.feed(b"[\"abcd");
+        assert!(matches!(tokenizer.next_token(), Ok(NextToken::Complete(_))));
+        assert!(matches!(tokenizer.next_token(), Ok(NextToken::Incomplete)));
+        tokenizer.feed(b"e");
+        let err = tokenizer.next_token().unwrap_err();
+        assert!(err.limit_exceeded().is_some());
+        assert_eq!(Some(1), err.offset);
+    }
+
+    #[tokio::test]
+    async fn byte_stream() {
+        let mut tokens = ByteStreamTokenizer::new(ByteStream::from_static(INPUT.as_bytes()));
+        let mut actual = Vec::new();
+        while let Some(token) = tokens.next_token().await {
+            actual.push(format!("{:?}", token.unwrap()));
+        }
+        assert_eq!(tokenize_all(INPUT.as_bytes()), actual);
+    }
+
+    #[tokio::test]
+    async fn list_items() {
+        let expected = [
+            r#"{"Name": "caf\u00e9 \"quoted\"", "Size": 12345, "Ratio": -1.5e-3}"#,
+            r#"{"Name": "🦀 crab", "Tags": [true, false, null]}"#,
+            "[]",
+        ];
+        for size in [1, 2, 7, INPUT.len()] {
+            let tokens = ByteStreamTokenizer::new(chunked_body(INPUT.as_bytes(), size));
+            let items = ListItems::from_object_member(tokens, "Items", raw_item)
+                .await
+                .unwrap();
+            assert_eq!(
+                expected.to_vec(),
+                collect_items(items).await,
+                "chunks of {size}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn list_items_skip_members_and_nulls() {
+        let input =
+            br#"{"Skipped": {"Items": [1]}, "It\u0065ms": [null, 1, [2, {}], null], "After": {}}"#;
+        let tokens = ByteStreamTokenizer::new(chunked_body(input, 3));
+        let items = ListItems::from_object_member(tokens, "Items", raw_item)
+            .await
+            .unwrap();
+        assert_eq!(vec!["1", "[2, {}]"], collect_items(items).await);
+
+        for input in [&br#"{"Other": [1]}"#[..], br#"{"Items": null}"#] {
+            let tokens = ByteStreamTokenizer::new(ByteStream::from_static(input));
+            let items = ListItems::from_object_member(tokens, "Items", raw_item)
+                .await
+                .unwrap();
+            assert!(collect_items(items).await.is_empty());
+        }
+
+        let tokens = ByteStreamTokenizer::new(ByteStream::from_static(br#"{"Items": 1}"#));
+        assert!(ListItems::from_object_member(tokens, "Items", raw_item)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn list_items_stop_after_error() {
+        let input = br#"{"Items": [1, {"a" 2}, 3]}"#;
+        let tokens = ByteStreamTokenizer::new(chunked_body(input, 4));
+        let mut items = ListItems::from_object_member(tokens, "Items", raw_item)
+            .await
+            .unwrap();
+        assert_eq!("1", items.next().await.unwrap().unwrap());
+        assert!(items.next().await.unwrap().is_err());
+        assert!(items.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn list_items_check_the_rest_of_the_body() {
+        for input in [
+            &br#"{"Items": [1], "After": }"#[..],
+            br#"{"Items": [1], "After": [2"#,
+            br#"{"Items": [1]} {}"#,
+        ] {
+            let tokens = ByteStreamTokenizer::new(chunked_body(input, 3));
+            let mut items = ListItems::from_object_member(tokens, "Items", raw_item)
+                .await
+                .unwrap();
+            assert_eq!("1", items.next().await.unwrap().unwrap());
+            assert!(items.next().await.unwrap().is_err());
+            assert!(items.next().await.is_none());
+        }
+
+        for input in [
+            &br#"{"Items": null, "After": }"#[..],
+            br#"{"Other": [1]} {}"#,
+        ] {
+            let tokens = ByteStreamTokenizer::new(ByteStream::from_static(input));
+            assert!(ListItems::from_object_member(tokens, "Items", raw_item)
+                .await
+                .is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn list_items_only_buffer_the_current_item() {
+        let input = br#"{"Items": ["aaaaaaaa", "bbbbbbbb", "cccccccc"]}"#;
+        let tokens = ByteStreamTokenizer::new(chunked_body(input, 4));
+        let mut items = ListItems::from_object_member(tokens, "Items", raw_item)
+            .await
+            .unwrap();
+        assert_eq!("\"aaaaaaaa\"", items.next().await.unwrap().unwrap());
+        assert_eq!("\"bbbbbbbb\"", items.next().await.unwrap().unwrap());
+        assert!(!items.tokens.tokenizer.buffer.contains(&b'a'));
+    }
+}
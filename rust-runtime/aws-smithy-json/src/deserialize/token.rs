@@ -3,15 +3,14 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::deserialize::error::DeserializeError as Error;
+use crate::deserialize::error::{DeserializeError as Error, DeserializeErrorKind as ErrorKind};
 use crate::deserialize::must_not_be_finite;
 use crate::escape::unescape_string;
 pub use crate::escape::EscapeError;
 use aws_smithy_types::date_time::Format;
 use aws_smithy_types::primitive::Parse;
-use aws_smithy_types::{base64, Blob, DateTime, Document, Number};
+use aws_smithy_types::{base64, BigDecimal, BigInteger, Blob, DateTime, Document, Number};
 use std::borrow::Cow;
-use std::collections::HashMap;
 use std::iter::Peekable;
 
 /// New-type around `&str` that indicates the string is an escaped JSON string.
@@ -77,6 +76,8 @@ pub enum Token<'a> {
     ValueNumber {
         offset: Offset,
         value: Number,
+        /// The number exactly as it appears in the JSON input.
+        raw: &'a str,
     },
     ValueString {
         offset: Offset,
@@ -185,6 +186,46 @@ pub fn expect_number_or_null(
     }
 }
 
+/// Expects a [Token::ValueString], [Token::ValueNumber] or [Token::ValueNull] for an integer-typed value.
+///
+/// This behaves like [`expect_number_or_null`], except that integer literals that don't fit in
+/// 64 bits are rejected as invalid numbers instead of being returned as a [`Number::Float`].
+pub fn expect_integer_or_null(
+    token: Option<Result<Token<'_>, Error>>,
+) -> Result<Option<Number>, Error> {
+    match token.transpose()? {
+        Some(Token::ValueNumber {
+            value: Number::Float(_),
+            raw,
+            offset,
+        }) if !raw.contains(['.', 'e', 'E']) => {
+            Err(Error::new(ErrorKind::InvalidNumber, Some(offset.0)))
+        }
+        token => expect_number_or_null(token.map(Ok)),
+    }
+}
+
+macro_rules! expect_big_number_or_null_fn {
+    ($name:ident, $typ:ident, $doc:tt) => {
+        #[doc=$doc]
+        pub fn $name(token: Option<Result<Token<'_>, Error>>) -> Result<Option<$typ>, Error> {
+            match token.transpose()? {
+                Some(Token::ValueNull { .. }) => Ok(None),
+                Some(Token::ValueNumber { raw, offset, .. }) => {
+                    raw.parse().map(Some).map_err(|err| {
+                        Error::custom_source(concat!("expected a valid ", stringify!($typ)), err)
+                            .with_offset(offset.0)
+                    })
+                }
+                _ => Err(Error::custom("expected ValueNumber or ValueNull")),
+            }
+        }
+    };
+}
+
+expect_big_number_or_null_fn!(expect_big_integer_or_null, BigInteger, "Expects a [Token::ValueNumber] or [Token::ValueNull], and returns the exact [BigInteger] value if it's not null.");
+expect_big_number_or_null_fn!(expect_big_decimal_or_null, BigDecimal, "Expects a [Token::ValueNumber] or [Token::ValueNull], and returns the exact [BigDecimal] value if it's not null.");
+
 /// Expects a [Token::ValueString] or [Token::ValueNull]. If the value is a string, it interprets it as a base64 encoded [Blob] value.
 pub fn expect_blob_or_null(token: Option<Result<Token<'_>, Error>>) -> Result<Option<Blob>, Error> {
     Ok(match expect_string_or_null(token)? {
@@ -230,12 +271,25 @@ pub fn expect_document<'a, I>(tokens: &mut Peekable<I>) -> Result<Document, Erro
 where
     I: Iterator<Item = Result<Token<'a>, Error>>,
 {
-    expect_document_inner(tokens, 0)
+    expect_document_inner(tokens, 0, false)
+}
+
+/// Expects and parses a complete document value, creating [`Document::OrderedObject`]s for objects
+/// so that their members keep the order they appear in.
+pub fn expect_ordered_document<'a, I>(tokens: &mut Peekable<I>) -> Result<Document, Error>
+where
+    I: Iterator<Item = Result<Token<'a>, Error>>,
+{
+    expect_document_inner(tokens, 0, true)
 }
 
 const MAX_DOCUMENT_RECURSION: usize = 256;
 
-fn expect_document_inner<'a, I>(tokens: &mut Peekable<I>, depth: usize) -> Result<Document, Error>
+fn expect_document_inner<'a, I>(
+    tokens: &mut Peekable<I>,
+    depth: usize,
+    ordered: bool,
+) -> Result<Document, Error>
 where
     I: Iterator<Item = Result<Token<'a>, Error>>,
 {
@@ -252,19 +306,23 @@ where
             Ok(Document::String(value.to_unescaped()?.into_owned()))
         }
         Some(Token::StartObject { .. }) => {
-            let mut object = HashMap::new();
+            let mut members = Vec::new();
             loop {
                 match tokens.next().transpose()? {
                     Some(Token::EndObject { .. }) => break,
                     Some(Token::ObjectKey { key, .. }) => {
                         let key = key.to_unescaped()?.into_owned();
-                        let value = expect_document_inner(tokens, depth + 1)?;
-                        object.insert(key, value);
+                        let value = expect_document_inner(tokens, depth + 1, ordered)?;
+                        members.push((key, value));
                     }
                     _ => return Err(Error::custom("expected object key or end object")),
                 }
             }
-            Ok(Document::Object(object))
+            if ordered {
+                Ok(Document::OrderedObject(members.into_iter().collect()))
+            } else {
+                Ok(Document::Object(members.into_iter().collect()))
+            }
         }
         Some(Token::StartArray { .. }) => {
             let mut array = Vec::new();
@@ -274,7 +332,7 @@ where
                         tokens.next().transpose().unwrap();
                         break;
                     }
-                    _ => array.push(expect_document_inner(tokens, depth + 1)?),
+                    _ => array.push(expect_document_inner(tokens, depth + 1, ordered)?),
                 }
             }
             Ok(Document::Array(array))
@@ -336,9 +394,9 @@ fn skip_inner<'a>(
 #[cfg(test)]
 pub mod test {
     use super::*;
-    use crate::deserialize::error::DeserializeErrorKind as ErrorKind;
     use crate::deserialize::error::DeserializeErrorKind::UnexpectedToken;
     use crate::deserialize::json_token_iter;
+    use std::collections::HashMap;
 
     pub fn start_array<'a>(offset: usize) -> Option<Result<Token<'a>, Error>> {
         Some(Ok(Token::StartArray {
@@ -378,10 +436,15 @@ pub mod test {
         }))
     }
 
-    pub fn value_number<'a>(offset: usize, number: Number) -> Option<Result<Token<'a>, Error>> {
+    pub fn value_number(
+        offset: usize,
+        raw: &str,
+        number: Number,
+    ) -> Option<Result<Token<'_>, Error>> {
         Some(Ok(Token::ValueNumber {
             offset: Offset(offset),
             value: number,
+            raw,
         }))
     }
 
@@ -544,7 +607,7 @@ pub mod test {
         assert_eq!(None, expect_number_or_null(value_null(0)).unwrap());
         assert_eq!(
             Some(Number::PosInt(5)),
-            expect_number_or_null(value_number(0, Number::PosInt(5))).unwrap()
+            expect_number_or_null(value_number(0, "5", Number::PosInt(5))).unwrap()
         );
         expect_err_custom(
             "expected ValueString, ValueNumber, or ValueNull",
@@ -570,6 +633,26 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn test_expect_integer_or_null() {
+        let parse = |input: &str| expect_integer_or_null(json_token_iter(input.as_bytes()).next());
+        assert_eq!(None, parse("null").unwrap());
+        assert_eq!(
+            Some(Number::PosInt(u64::MAX)),
+            parse("18446744073709551615").unwrap()
+        );
+        assert_eq!(
+            Some(Number::NegInt(i64::MIN)),
+            parse("-9223372036854775808").unwrap()
+        );
+        assert_eq!(Some(Number::Float(1e20)), parse("1e20").unwrap());
+        for overflowing in ["18446744073709551616", "-9223372036854775809"] {
+            let err = parse(overflowing).expect_err("integer overflows");
+            assert!(matches!(err.kind, ErrorKind::InvalidNumber), "{err:?}");
+            assert_eq!(Some(0), err.offset);
+        }
+    }
+
     #[test]
     fn test_expect_blob_or_null() {
         assert_eq!(None, expect_blob_or_null(value_null(0)).unwrap());
@@ -603,8 +686,11 @@ pub mod test {
         }
         assert_eq!(
             Some(DateTime::from_secs_f64(2048.0)),
-            expect_timestamp_or_null(value_number(0, Number::Float(2048.0)), Format::EpochSeconds)
-                .unwrap()
+            expect_timestamp_or_null(
+                value_number(0, "2048.0", Number::Float(2048.0)),
+                Format::EpochSeconds
+            )
+            .unwrap()
         );
         assert_eq!(
             Some(DateTime::from_secs_f64(1445412480.0)),
@@ -627,7 +713,7 @@ pub mod test {
         expect_err_custom(
             "expected ValueString or ValueNull",
             None,
-            expect_timestamp_or_null(value_number(0, Number::Float(0.0)), Format::DateTime),
+            expect_timestamp_or_null(value_number(0, "0.0", Number::Float(0.0)), Format::DateTime),
         );
     }
 
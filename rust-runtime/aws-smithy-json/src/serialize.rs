@@ -6,7 +6,7 @@
 use crate::escape::escape_string;
 use aws_smithy_types::date_time::{DateTimeFormatError, Format};
 use aws_smithy_types::primitive::Encoder;
-use aws_smithy_types::{DateTime, Document, Number};
+use aws_smithy_types::{BigDecimal, BigInteger, DateTime, Document, Number};
 use std::borrow::Cow;
 
 pub struct JsonValueWriter<'a> {
@@ -33,25 +33,44 @@ impl<'a> JsonValueWriter<'a> {
 
     /// Writes a document `value`.
     pub fn document(self, value: &Document) {
+        self.write_document(value, false)
+    }
+
+    /// Writes a document `value` in canonical form, with the members of every object sorted by key.
+    ///
+    /// Equal documents are always written identically, regardless of the order of their object members,
+    /// which makes the output suitable for hashing or signing.
+    pub fn canonical_document(self, value: &Document) {
+        self.write_document(value, true)
+    }
+
+    fn write_document(self, value: &Document, sort_keys: bool) {
         match value {
             Document::Array(values) => {
                 let mut array = self.start_array();
                 for value in values {
-                    array.value().document(value);
+                    array.value().write_document(value, sort_keys);
                 }
                 array.finish();
             }
             Document::Bool(value) => self.boolean(*value),
             Document::Null => self.null(),
             Document::Number(value) => self.number(*value),
-            Document::Object(values) => {
+            Document::String(value) => self.string(value),
+            // Objects may be either a `Document::Object` or a `Document::OrderedObject`
+            object => {
+                let mut members = object.members().expect("all other variants are objects");
+                if sort_keys {
+                    let mut sorted: Vec<_> = members.collect();
+                    sorted.sort_unstable_by_key(|(key, _)| *key);
+                    members = Box::new(sorted.into_iter());
+                }
                 let mut object = self.start_object();
-                for (key, value) in values {
-                    object.key(key).document(value);
+                for (key, value) in members {
+                    object.key(key).write_document(value, sort_keys);
                 }
                 object.finish();
             }
-            Document::String(value) => self.string(value),
         }
     }
 
@@ -94,6 +113,16 @@ impl<'a> JsonValueWriter<'a> {
         }
     }
 
+    /// Writes a big integer `value` exactly.
+    pub fn big_integer(self, value: &BigInteger) {
+        self.output.push_str(value.as_str());
+    }
+
+    /// Writes a big decimal `value` exactly.
+    pub fn big_decimal(self, value: &BigDecimal) {
+        self.output.push_str(value.as_str());
+    }
+
     /// Writes a date-time `value` with the given `format`.
     pub fn date_time(
         self,
@@ -398,6 +427,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn canonical_document() {
+        let document = Document::Object(
+            [
+                (
+                    "b",
+                    Document::Array(vec![Document::Object(
+                        [("y", Document::Null), ("x", Document::Bool(true))]
+                            .into_iter()
+                            .map(|(k, v)| (k.to_string(), v))
+                            .collect(),
+                    )]),
+                ),
+                ("a", Document::Number(Number::PosInt(1))),
+                ("c", Document::String("c".into())),
+            ]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+        );
+        let mut output = String::new();
+        JsonValueWriter::new(&mut output).canonical_document(&document);
+        assert_eq!(r#"{"a":1,"b":[{"x":true,"y":null}],"c":"c"}"#, output);
+    }
+
+    #[test]
+    fn document_round_trip_preserves_order() {
+        use crate::deserialize::{json_token_iter, token::expect_ordered_document};
+
+        let input = r#"{"z":1,"a":{"y":[{"c":null,"b":false}],"x":"x"},"m":-2.5}"#;
+        let document =
+            expect_ordered_document(&mut json_token_iter(input.as_bytes()).peekable()).unwrap();
+        assert_eq!(input, format_document(document.clone()));
+
+        let mut canonical = String::new();
+        JsonValueWriter::new(&mut canonical).canonical_document(&document);
+        assert_eq!(
+            r#"{"a":{"x":"x","y":[{"b":false,"c":null}]},"m":-2.5,"z":1}"#,
+            canonical
+        );
+    }
+
     fn format_test_number(number: Number) -> String {
         let mut formatted = String::new();
         JsonValueWriter::new(&mut formatted).number(number);
@@ -426,6 +497,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn big_number_round_trip() {
+        use crate::deserialize::json_token_iter;
+        use crate::deserialize::token::{expect_big_decimal_or_null, expect_big_integer_or_null};
+        use aws_smithy_types::{BigDecimal, BigInteger};
+
+        let integer: BigInteger = "-123456789012345678901234567890".parse().unwrap();
+        let decimal: BigDecimal = "3.14159265358979323846264338327950288".parse().unwrap();
+        let mut output = String::new();
+        let mut array = JsonArrayWriter::new(&mut output);
+        array.value().big_integer(&integer);
+        array.value().big_decimal(&decimal);
+        array.value().null();
+        array.finish();
+        assert_eq!(
+            "[-123456789012345678901234567890,3.14159265358979323846264338327950288,null]",
+            output
+        );
+
+        let mut tokens = json_token_iter(output.as_bytes());
+        tokens.next();
+        assert_eq!(
+            Some(integer),
+            expect_big_integer_or_null(tokens.next()).unwrap()
+        );
+        assert_eq!(
+            Some(decimal),
+            expect_big_decimal_or_null(tokens.next()).unwrap()
+        );
+        assert_eq!(None, expect_big_decimal_or_null(tokens.next()).unwrap());
+        assert!(expect_big_integer_or_null(json_token_iter(b"1.5").next()).is_err());
+    }
+
     proptest! {
         #[test]
         fn matches_serde_json_pos_int_format(value: u64) {
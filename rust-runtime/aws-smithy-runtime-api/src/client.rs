@@ -93,6 +93,8 @@ macro_rules! new_type_future {
 
 pub mod auth;
 
+pub mod concurrency_limit;
+
 pub mod connection;
 
 pub mod connector_metadata;
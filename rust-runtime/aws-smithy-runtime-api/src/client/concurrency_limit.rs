@@ -0,0 +1,121 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Client-side concurrency limiting.
+//!
+//! A concurrency limiter bounds the number of requests a client has in flight at once.
+//! The orchestrator acquires a [`ConcurrencyPermit`] right before transmitting a request,
+//! and the permit is held until the response body has been fully read or dropped.
+
+use crate::box_error::BoxError;
+use crate::client::runtime_components::sealed::ValidateConfig;
+use crate::client::runtime_components::RuntimeComponents;
+use crate::impl_shared_conversions;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::fmt;
+use std::sync::Arc;
+
+new_type_future! {
+    #[doc = "Future for [`LimitConcurrency::acquire_permit`]."]
+    pub struct ConcurrencyPermitFuture<'a, ConcurrencyPermit, BoxError>;
+}
+
+/// Limits the number of requests that can be in flight at the same time.
+///
+/// The orchestrator calls [`acquire_permit`](LimitConcurrency::acquire_permit) once per request
+/// attempt, after signing and right before the request is handed to the HTTP client. The
+/// returned future may wait until capacity becomes available.
+pub trait LimitConcurrency: Send + Sync + fmt::Debug {
+    /// Acquires a permit to send a single request attempt.
+    fn acquire_permit<'a>(
+        &'a self,
+        runtime_components: &'a RuntimeComponents,
+        cfg: &'a ConfigBag,
+    ) -> ConcurrencyPermitFuture<'a>;
+}
+
+/// Shared concurrency limiter.
+///
+/// This is a simple shared ownership wrapper type for the [`LimitConcurrency`] trait.
+#[derive(Clone, Debug)]
+pub struct SharedConcurrencyLimiter(Arc<dyn LimitConcurrency>);
+
+impl SharedConcurrencyLimiter {
+    /// Creates a new [`SharedConcurrencyLimiter`].
+    pub fn new(limiter: impl LimitConcurrency + 'static) -> Self {
+        Self(Arc::new(limiter))
+    }
+}
+
+impl LimitConcurrency for SharedConcurrencyLimiter {
+    fn acquire_permit<'a>(
+        &'a self,
+        runtime_components: &'a RuntimeComponents,
+        cfg: &'a ConfigBag,
+    ) -> ConcurrencyPermitFuture<'a> {
+        self.0.acquire_permit(runtime_components, cfg)
+    }
+}
+
+impl ValidateConfig for SharedConcurrencyLimiter {}
+
+impl_shared_conversions!(convert SharedConcurrencyLimiter from LimitConcurrency using SharedConcurrencyLimiter::new);
+
+/// The outcome of a request attempt, as reported to the concurrency limiter that issued its permit.
+///
+/// Adaptive limiters use this to grow or shrink the concurrency limit.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PermitOutcome {
+    /// The service responded without indicating that it was overloaded.
+    Success,
+    /// The service responded, but indicated that it was overloaded (for example, with a 429 or 503 status).
+    Overloaded,
+    /// No response was received, for example, because of a timeout or an IO error.
+    Dropped,
+}
+
+/// The permit-specific half of a concurrency limiter.
+///
+/// Implementations of [`LimitConcurrency`] return one of these (wrapped in a [`ConcurrencyPermit`])
+/// for every acquired permit. Dropping it releases the permit.
+pub trait ReleasePermit: Send + Sync + fmt::Debug {
+    /// Records the outcome of the request attempt this permit was acquired for.
+    ///
+    /// The orchestrator calls this once, as soon as the response headers arrive or the attempt
+    /// fails to get a response. The permit itself is released later, when it is dropped.
+    fn record_outcome(&mut self, outcome: PermitOutcome) {
+        let _ = outcome;
+    }
+}
+
+/// A permit for a single in-flight request attempt.
+///
+/// The permit is returned to its concurrency limiter when this is dropped.
+#[derive(Debug)]
+pub struct ConcurrencyPermit {
+    inner: Option<Box<dyn ReleasePermit>>,
+}
+
+impl ConcurrencyPermit {
+    /// Creates a new permit that will be released by dropping `inner`.
+    pub fn new(inner: impl ReleasePermit + 'static) -> Self {
+        Self {
+            inner: Some(Box::new(inner)),
+        }
+    }
+
+    /// Creates a permit that isn't tied to any limiter.
+    pub fn unlimited() -> Self {
+        Self { inner: None }
+    }
+
+    /// Records the outcome of the request attempt this permit was acquired for.
+    pub fn record_outcome(&mut self, outcome: PermitOutcome) {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.record_outcome(outcome);
+        }
+    }
+}
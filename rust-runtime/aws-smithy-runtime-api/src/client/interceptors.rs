@@ -55,6 +55,21 @@ macro_rules! interceptor_trait_fn {
     };
 }
 
+macro_rules! async_interceptor_trait_fn {
+    ($name:ident, $phase:ident, $docs:tt) => {
+        #[doc = $docs]
+        fn $name<'a>(
+            &'a self,
+            context: &'a mut $phase<'_>,
+            runtime_components: &'a RuntimeComponents,
+            cfg: &'a mut ConfigBag,
+        ) -> InterceptorFuture<'a> {
+            let (_ctx, _rc, _cfg) = (context, runtime_components, cfg);
+            InterceptorFuture::ready(Ok(()))
+        }
+    };
+}
+
 /// An interceptor allows injecting code into the SDK ’s request execution pipeline.
 ///
 /// ## Terminology:
@@ -817,6 +832,143 @@ impl Intercept for SharedInterceptor {
 
 impl_shared_conversions!(convert SharedInterceptor from Intercept using SharedInterceptor::new);
 
+new_type_future! {
+    #[doc = "Future for the hooks of [`AsyncIntercept`]."]
+    pub struct InterceptorFuture<'a, (), BoxError>;
+}
+
+/// An interceptor with asynchronous "read/write" hooks.
+///
+/// This is the async counterpart to [`Intercept`], for interceptors that need to await something
+/// (such as fetching a data key or a remote signature) while modifying an in-flight request or
+/// response. Only the "read/write" hooks have async variants.
+///
+/// Each hook runs at the same point in the request execution pipeline as the [`Intercept`] hook
+/// of the same name, right after every synchronous interceptor has run that hook, and follows
+/// the same error behavior. Time spent in hooks counts towards the operation timeout, and for
+/// hooks that run as part of an attempt, towards the operation attempt timeout as well.
+pub trait AsyncIntercept: fmt::Debug + Send + Sync {
+    /// The name of this interceptor, used in error messages for debugging.
+    fn name(&self) -> &'static str;
+
+    async_interceptor_trait_fn!(
+        modify_before_serialization,
+        BeforeSerializationInterceptorContextMut,
+        "Async variant of [`Intercept::modify_before_serialization`]."
+    );
+
+    async_interceptor_trait_fn!(
+        modify_before_retry_loop,
+        BeforeTransmitInterceptorContextMut,
+        "Async variant of [`Intercept::modify_before_retry_loop`]."
+    );
+
+    async_interceptor_trait_fn!(
+        modify_before_signing,
+        BeforeTransmitInterceptorContextMut,
+        "Async variant of [`Intercept::modify_before_signing`]."
+    );
+
+    async_interceptor_trait_fn!(
+        modify_before_transmit,
+        BeforeTransmitInterceptorContextMut,
+        "Async variant of [`Intercept::modify_before_transmit`]."
+    );
+
+    async_interceptor_trait_fn!(
+        modify_before_deserialization,
+        BeforeDeserializationInterceptorContextMut,
+        "Async variant of [`Intercept::modify_before_deserialization`]."
+    );
+
+    async_interceptor_trait_fn!(
+        modify_before_attempt_completion,
+        FinalizerInterceptorContextMut,
+        "Async variant of [`Intercept::modify_before_attempt_completion`]."
+    );
+
+    async_interceptor_trait_fn!(
+        modify_before_completion,
+        FinalizerInterceptorContextMut,
+        "Async variant of [`Intercept::modify_before_completion`]."
+    );
+}
+
+macro_rules! shared_async_interceptor_fn {
+    ($name:ident, $phase:ident) => {
+        fn $name<'a>(
+            &'a self,
+            context: &'a mut $phase<'_>,
+            runtime_components: &'a RuntimeComponents,
+            cfg: &'a mut ConfigBag,
+        ) -> InterceptorFuture<'a> {
+            self.interceptor.$name(context, runtime_components, cfg)
+        }
+    };
+}
+
+/// Async interceptor wrapper that may be shared
+#[derive(Clone)]
+pub struct SharedAsyncInterceptor {
+    interceptor: Arc<dyn AsyncIntercept>,
+    check_enabled: Arc<dyn Fn(&ConfigBag) -> bool + Send + Sync>,
+}
+
+impl fmt::Debug for SharedAsyncInterceptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedAsyncInterceptor")
+            .field("interceptor", &self.interceptor)
+            .finish()
+    }
+}
+
+impl SharedAsyncInterceptor {
+    /// Create a new `SharedAsyncInterceptor` from `AsyncIntercept`.
+    pub fn new<T: AsyncIntercept + 'static>(interceptor: T) -> Self {
+        Self {
+            interceptor: Arc::new(interceptor),
+            check_enabled: Arc::new(|conf: &ConfigBag| {
+                conf.load::<DisableInterceptor<T>>().is_none()
+            }),
+        }
+    }
+
+    /// Checks if this interceptor is enabled in the given config.
+    pub fn enabled(&self, conf: &ConfigBag) -> bool {
+        (self.check_enabled)(conf)
+    }
+}
+
+impl ValidateConfig for SharedAsyncInterceptor {}
+
+impl AsyncIntercept for SharedAsyncInterceptor {
+    fn name(&self) -> &'static str {
+        self.interceptor.name()
+    }
+
+    shared_async_interceptor_fn!(
+        modify_before_serialization,
+        BeforeSerializationInterceptorContextMut
+    );
+    shared_async_interceptor_fn!(
+        modify_before_retry_loop,
+        BeforeTransmitInterceptorContextMut
+    );
+    shared_async_interceptor_fn!(modify_before_signing, BeforeTransmitInterceptorContextMut);
+    shared_async_interceptor_fn!(modify_before_transmit, BeforeTransmitInterceptorContextMut);
+    shared_async_interceptor_fn!(
+        modify_before_deserialization,
+        BeforeDeserializationInterceptorContextMut
+    );
+    shared_async_interceptor_fn!(
+        modify_before_attempt_completion,
+        FinalizerInterceptorContextMut
+    );
+    shared_async_interceptor_fn!(modify_before_completion, FinalizerInterceptorContextMut);
+}
+
+impl_shared_conversions!(convert SharedAsyncInterceptor from AsyncIntercept using SharedAsyncInterceptor::new);
+
 /// Generalized interceptor disabling interface
 ///
 /// RuntimePlugins can disable interceptors by inserting [`DisableInterceptor<T>`](DisableInterceptor) into the config bag
@@ -842,3 +994,11 @@ pub fn disable_interceptor<T: Intercept>(cause: &'static str) -> DisableIntercep
         cause,
     }
 }
+
+/// Disable an async interceptor with a given cause
+pub fn disable_async_interceptor<T: AsyncIntercept>(cause: &'static str) -> DisableInterceptor<T> {
+    DisableInterceptor {
+        _t: PhantomData,
+        cause,
+    }
+}
@@ -65,6 +65,132 @@ impl Storable for SensitiveOutput {
     type Storer = StoreReplace<Self>;
 }
 
+/// Marker type stored in the config bag to indicate that a request body should be redacted.
+#[derive(Debug)]
+pub struct SensitiveInput;
+
+impl Storable for SensitiveInput {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Names of HTTP headers that are bound to sensitive members of an operation's input or output.
+///
+/// This is stored in the config bag so that anything logging requests or responses
+/// can redact the values of these headers.
+#[derive(Clone, Debug, Default)]
+pub struct SensitiveHeaders {
+    names: Vec<Cow<'static, str>>,
+    prefixes: Vec<Cow<'static, str>>,
+}
+
+impl SensitiveHeaders {
+    /// Creates an empty set of sensitive headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the header with the given name as sensitive.
+    pub fn with_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.names.push(name.into());
+        self
+    }
+
+    /// Marks every header whose name starts with `prefix` as sensitive.
+    pub fn with_prefix(mut self, prefix: impl Into<Cow<'static, str>>) -> Self {
+        self.prefixes.push(prefix.into());
+        self
+    }
+
+    /// Returns true if the header with the given name is sensitive.
+    ///
+    /// Header names are compared case-insensitively.
+    pub fn is_sensitive(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n.eq_ignore_ascii_case(name))
+            || self.prefixes.iter().any(|p| {
+                name.get(..p.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(p))
+            })
+    }
+}
+
+impl Storable for SensitiveHeaders {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Parts of a request URI that are bound to sensitive members of an operation's input.
+///
+/// This is stored in the config bag so that anything logging requests can redact `@httpLabel`
+/// path segments and `@httpQuery` values of sensitive members.
+#[derive(Clone, Debug, Default)]
+pub struct SensitiveUri {
+    whole_path: bool,
+    path_segments_from_end: Vec<usize>,
+    query_keys: Vec<Cow<'static, str>>,
+    all_query_params: bool,
+}
+
+impl SensitiveUri {
+    /// Creates a URI with nothing marked as sensitive.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the whole path as sensitive.
+    ///
+    /// This is needed for sensitive greedy labels, or labels followed by a greedy label, since
+    /// the position of their segments can't be known up front.
+    pub fn with_whole_path(mut self) -> Self {
+        self.whole_path = true;
+        self
+    }
+
+    /// Marks a path segment as sensitive, counting from the last segment (at index 0).
+    ///
+    /// Segments are counted from the end because the endpoint may prefix the modeled path.
+    pub fn with_path_segment_from_end(mut self, index: usize) -> Self {
+        self.path_segments_from_end.push(index);
+        self
+    }
+
+    /// Marks the value of the query parameter with the given name as sensitive.
+    pub fn with_query_key(mut self, key: impl Into<Cow<'static, str>>) -> Self {
+        self.query_keys.push(key.into());
+        self
+    }
+
+    /// Marks every query parameter, key and value, as sensitive.
+    ///
+    /// This is needed for sensitive `@httpQueryParams` maps, since their keys are not modeled.
+    pub fn with_all_query_params(mut self) -> Self {
+        self.all_query_params = true;
+        self
+    }
+
+    /// Returns true if the whole path is sensitive.
+    pub fn is_whole_path_sensitive(&self) -> bool {
+        self.whole_path
+    }
+
+    /// Returns true if the path segment at `index`, counting from the last segment, is sensitive.
+    pub fn is_path_segment_sensitive(&self, index_from_end: usize) -> bool {
+        self.whole_path || self.path_segments_from_end.contains(&index_from_end)
+    }
+
+    /// Returns true if every query parameter, key and value, is sensitive.
+    pub fn are_all_query_params_sensitive(&self) -> bool {
+        self.all_query_params
+    }
+
+    /// Returns true if the value of the query parameter with the given name is sensitive.
+    pub fn is_query_value_sensitive(&self, key: &str) -> bool {
+        self.all_query_params || self.query_keys.iter().any(|k| k == key)
+    }
+}
+
+impl Storable for SensitiveUri {
+    type Storer = StoreReplace<Self>;
+}
+
 #[derive(Debug)]
 enum ErrorKind<E> {
     /// An error occurred within an interceptor.
@@ -15,12 +15,15 @@ use crate::client::auth::{
     AuthScheme, AuthSchemeId, ResolveAuthSchemeOptions, SharedAuthScheme,
     SharedAuthSchemeOptionResolver,
 };
+use crate::client::concurrency_limit::{LimitConcurrency, SharedConcurrencyLimiter};
 use crate::client::endpoint::{ResolveEndpoint, SharedEndpointResolver};
 use crate::client::http::{HttpClient, SharedHttpClient};
 use crate::client::identity::{
     ResolveCachedIdentity, ResolveIdentity, SharedIdentityCache, SharedIdentityResolver,
 };
-use crate::client::interceptors::{Intercept, SharedInterceptor};
+use crate::client::interceptors::{
+    AsyncIntercept, Intercept, SharedAsyncInterceptor, SharedInterceptor,
+};
 use crate::client::retries::classifiers::{ClassifyRetry, SharedRetryClassifier};
 use crate::client::retries::{RetryStrategy, SharedRetryStrategy};
 use crate::impl_shared_conversions;
@@ -66,9 +69,12 @@ pub(crate) mod sealed {
 }
 use sealed::ValidateConfig;
 
+type FinalConfigFn = dyn Fn(&RuntimeComponents, &ConfigBag) -> Result<(), BoxError> + Send + Sync;
+
 #[derive(Clone)]
 enum ValidatorInner {
     BaseConfigStaticFn(fn(&RuntimeComponentsBuilder, &ConfigBag) -> Result<(), BoxError>),
+    FinalConfigFn(Arc<FinalConfigFn>),
     Shared(Arc<dyn ValidateConfig>),
 }
 
@@ -76,6 +82,7 @@ impl fmt::Debug for ValidatorInner {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::BaseConfigStaticFn(_) => f.debug_tuple("StaticFn").finish(),
+            Self::FinalConfigFn(_) => f.debug_tuple("FinalConfigFn").finish(),
             Self::Shared(_) => f.debug_tuple("Shared").finish(),
         }
     }
@@ -133,6 +140,35 @@ impl SharedConfigValidator {
             inner: ValidatorInner::BaseConfigStaticFn(validator),
         }
     }
+
+    /// Creates a final config validator from a function.
+    ///
+    /// A final config validator gets called right before an operation is sent, once the runtime
+    /// components from every runtime plugin (including per-operation overrides) have been merged.
+    /// Any error returned from the validator function fails the operation with a construction failure.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use aws_smithy_runtime_api::client::runtime_components::SharedConfigValidator;
+    ///
+    /// let validator = SharedConfigValidator::final_config_fn(|components, _cfg| {
+    ///     if components.time_source().is_none() {
+    ///         return Err("I need a time_source!".into());
+    ///     }
+    ///     Ok(())
+    /// });
+    /// ```
+    pub fn final_config_fn(
+        validator: impl Fn(&RuntimeComponents, &ConfigBag) -> Result<(), BoxError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        Self {
+            inner: ValidatorInner::FinalConfigFn(Arc::new(validator)),
+        }
+    }
 }
 
 impl ValidateConfig for SharedConfigValidator {
@@ -146,6 +182,7 @@ impl ValidateConfig for SharedConfigValidator {
             ValidatorInner::Shared(validator) => {
                 validator.validate_base_client_config(runtime_components, cfg)
             }
+            ValidatorInner::FinalConfigFn(_) => Ok(()),
         }
     }
 
@@ -158,6 +195,7 @@ impl ValidateConfig for SharedConfigValidator {
             ValidatorInner::Shared(validator) => {
                 validator.validate_final_config(runtime_components, cfg)
             }
+            ValidatorInner::FinalConfigFn(validator) => validator(runtime_components, cfg),
             _ => Ok(()),
         }
     }
@@ -367,6 +405,8 @@ declare_runtime_components! {
 
         interceptors: Vec<SharedInterceptor>,
 
+        async_interceptors: Vec<SharedAsyncInterceptor>,
+
         retry_classifiers: Vec<SharedRetryClassifier>,
 
         #[required]
@@ -377,6 +417,8 @@ declare_runtime_components! {
         sleep_impl: Option<SharedAsyncSleep>,
 
         config_validators: Vec<SharedConfigValidator>,
+
+        concurrency_limiter: Option<SharedConcurrencyLimiter>,
     }
 }
 
@@ -427,6 +469,11 @@ impl RuntimeComponents {
         self.interceptors.iter().map(|s| s.value.clone())
     }
 
+    /// Returns an iterator over the async interceptors.
+    pub fn async_interceptors(&self) -> impl Iterator<Item = SharedAsyncInterceptor> + '_ {
+        self.async_interceptors.iter().map(|s| s.value.clone())
+    }
+
     /// Returns an iterator over the retry classifiers.
     pub fn retry_classifiers(&self) -> impl Iterator<Item = SharedRetryClassifier> + '_ {
         self.retry_classifiers.iter().map(|s| s.value.clone())
@@ -458,6 +505,11 @@ impl RuntimeComponents {
         self.config_validators.iter().map(|s| s.value.clone())
     }
 
+    /// Returns the concurrency limiter.
+    pub fn concurrency_limiter(&self) -> Option<SharedConcurrencyLimiter> {
+        self.concurrency_limiter.as_ref().map(|s| s.value.clone())
+    }
+
     /// Validate the final client configuration.
     ///
     /// This is intended to be called internally by the client.
@@ -493,8 +545,10 @@ impl RuntimeComponents {
         validate!(Required: self.identity_cache);
         validate!(Map: self.identity_resolvers);
         validate!(Vec: &self.interceptors);
+        validate!(Vec: &self.async_interceptors);
         validate!(Required: self.retry_strategy);
         validate!(Vec: &self.retry_classifiers);
+        validate!(Option: self.concurrency_limiter);
 
         Ok(())
     }
@@ -517,11 +571,13 @@ impl RuntimeComponentsBuilder {
             identity_cache: Some(rc.identity_cache),
             identity_resolvers: Some(rc.identity_resolvers),
             interceptors: rc.interceptors,
+            async_interceptors: rc.async_interceptors,
             retry_classifiers: rc.retry_classifiers,
             retry_strategy: Some(rc.retry_strategy),
             time_source: rc.time_source,
             sleep_impl: rc.sleep_impl,
             config_validators: rc.config_validators,
+            concurrency_limiter: rc.concurrency_limiter,
         }
     }
 
@@ -723,6 +779,48 @@ impl RuntimeComponentsBuilder {
         self
     }
 
+    /// Returns the async interceptors.
+    pub fn async_interceptors(&self) -> impl Iterator<Item = SharedAsyncInterceptor> + '_ {
+        self.async_interceptors.iter().map(|s| s.value.clone())
+    }
+
+    /// Adds all the given async interceptors.
+    pub fn extend_async_interceptors(
+        &mut self,
+        interceptors: impl Iterator<Item = SharedAsyncInterceptor>,
+    ) -> &mut Self {
+        self.async_interceptors
+            .extend(interceptors.map(|s| Tracked::new(self.builder_name, s)));
+        self
+    }
+
+    /// Adds an async interceptor.
+    pub fn push_async_interceptor(
+        &mut self,
+        interceptor: impl AsyncIntercept + 'static,
+    ) -> &mut Self {
+        self.async_interceptors
+            .push(Tracked::new(self.builder_name, interceptor.into_shared()));
+        self
+    }
+
+    /// Adds an async interceptor.
+    pub fn with_async_interceptor(mut self, interceptor: impl AsyncIntercept + 'static) -> Self {
+        self.push_async_interceptor(interceptor);
+        self
+    }
+
+    /// Directly sets the async interceptors and clears out any that were previously pushed.
+    pub fn set_async_interceptors(
+        &mut self,
+        interceptors: impl Iterator<Item = SharedAsyncInterceptor>,
+    ) -> &mut Self {
+        self.async_interceptors.clear();
+        self.async_interceptors
+            .extend(interceptors.map(|s| Tracked::new(self.builder_name, s)));
+        self
+    }
+
     /// Returns the retry classifiers.
     pub fn retry_classifiers(&self) -> impl Iterator<Item = SharedRetryClassifier> + '_ {
         self.retry_classifiers.iter().map(|s| s.value.clone())
@@ -862,6 +960,29 @@ impl RuntimeComponentsBuilder {
         self
     }
 
+    /// Returns the concurrency limiter.
+    pub fn concurrency_limiter(&self) -> Option<SharedConcurrencyLimiter> {
+        self.concurrency_limiter.as_ref().map(|s| s.value.clone())
+    }
+
+    /// Sets the concurrency limiter.
+    pub fn set_concurrency_limiter(
+        &mut self,
+        concurrency_limiter: Option<impl LimitConcurrency + 'static>,
+    ) -> &mut Self {
+        self.concurrency_limiter = self.tracked(concurrency_limiter.map(IntoShared::into_shared));
+        self
+    }
+
+    /// Sets the concurrency limiter.
+    pub fn with_concurrency_limiter(
+        mut self,
+        concurrency_limiter: Option<impl LimitConcurrency + 'static>,
+    ) -> Self {
+        self.set_concurrency_limiter(concurrency_limiter);
+        self
+    }
+
     /// Validate the base client configuration.
     ///
     /// This is intended to be called internally by the client.
@@ -886,7 +1007,9 @@ impl RuntimeComponentsBuilder {
             validate!(resolvers.values())
         }
         validate!(&self.interceptors);
+        validate!(&self.async_interceptors);
         validate!(&self.retry_strategy);
+        validate!(&self.concurrency_limiter);
         Ok(())
     }
 
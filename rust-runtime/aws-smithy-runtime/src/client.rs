@@ -6,12 +6,22 @@
 /// Smithy auth scheme implementations.
 pub mod auth;
 
+/// Client-side concurrency limiting.
+pub mod concurrency_limit;
+
 pub mod defaults;
 
 pub mod dns;
 
 pub mod endpoint;
 
+/// Structured, machine-readable records of failed requests.
+pub mod error_record;
+
+/// Reconnecting event stream receivers.
+#[cfg(feature = "event-stream")]
+pub mod event_stream;
+
 /// Built-in Smithy HTTP clients and connectors.
 ///
 /// See the [module docs in `aws-smithy-runtime-api`](aws_smithy_runtime_api::client::http)
@@ -53,3 +63,6 @@ pub mod sdk_feature;
 
 /// Smithy support-code for code generated waiters.
 pub mod waiters;
+
+/// Structured logging of requests and responses on the wire.
+pub mod wire_logging;
@@ -0,0 +1,896 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A concurrency limiter that bounds the number of in-flight requests.
+//!
+//! The limit can either be fixed, or adjusted at runtime based on how the service responds
+//! (additive-increase/multiplicative-decrease, or a latency-based Vegas-style algorithm).
+
+use crate::client::retries::RetryPartition;
+use crate::static_partition_map::StaticPartitionMap;
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::client::concurrency_limit::{
+    ConcurrencyPermit, ConcurrencyPermitFuture, LimitConcurrency, PermitOutcome, ReleasePermit,
+};
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::ConfigBag;
+use http_body_1x::{Frame, SizeHint};
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, SystemTime};
+use tokio::sync::Notify;
+use tracing::debug;
+
+static CONCURRENCY_LIMITS: StaticPartitionMap<ConcurrencyLimiterPartition, LimitState> =
+    StaticPartitionMap::new();
+
+/// Represents a partition for the concurrency limiter, e.g. an endpoint, a region
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+struct ConcurrencyLimiterPartition {
+    retry_partition: RetryPartition,
+}
+
+const DEFAULT_INITIAL_LIMIT: usize = 20;
+const DEFAULT_MIN_LIMIT: usize = 1;
+const DEFAULT_MAX_LIMIT: usize = 200;
+const DEFAULT_BACKOFF_RATIO: f64 = 0.9;
+const DEFAULT_VEGAS_ALPHA: usize = 3;
+const DEFAULT_VEGAS_BETA: usize = 6;
+
+/// Configuration for the additive-increase/multiplicative-decrease concurrency limit.
+///
+/// The limit grows by one for every successful request made while the limiter was at least half
+/// utilized, and is multiplied by the backoff ratio whenever a request is throttled or dropped.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AimdConfig {
+    initial_limit: usize,
+    min_limit: usize,
+    max_limit: usize,
+    backoff_ratio: f64,
+}
+
+impl Default for AimdConfig {
+    fn default() -> Self {
+        Self {
+            initial_limit: DEFAULT_INITIAL_LIMIT,
+            min_limit: DEFAULT_MIN_LIMIT,
+            max_limit: DEFAULT_MAX_LIMIT,
+            backoff_ratio: DEFAULT_BACKOFF_RATIO,
+        }
+    }
+}
+
+impl AimdConfig {
+    /// Sets the limit to start with.
+    pub fn with_initial_limit(mut self, initial_limit: usize) -> Self {
+        self.initial_limit = initial_limit;
+        self
+    }
+
+    /// Sets the lowest the limit will ever go.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is zero, since no request could ever be sent.
+    pub fn with_min_limit(mut self, min_limit: usize) -> Self {
+        assert!(min_limit > 0, "min limit must be at least 1");
+        self.min_limit = min_limit;
+        self
+    }
+
+    /// Sets the highest the limit will ever go.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is zero, since no request could ever be sent.
+    pub fn with_max_limit(mut self, max_limit: usize) -> Self {
+        assert!(max_limit > 0, "max limit must be at least 1");
+        self.max_limit = max_limit;
+        self
+    }
+
+    /// Sets the ratio the limit is multiplied by when a request is throttled or dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ratio isn't between `0.5` and `1.0`.
+    pub fn with_backoff_ratio(mut self, backoff_ratio: f64) -> Self {
+        assert!(
+            (0.5..1.0).contains(&backoff_ratio),
+            "backoff ratio must be in the range [0.5, 1.0)"
+        );
+        self.backoff_ratio = backoff_ratio;
+        self
+    }
+}
+
+/// Configuration for the latency-based Vegas concurrency limit.
+///
+/// The limiter tracks the lowest round-trip time it has seen, and uses it to estimate how many
+/// requests are queued at the service. The limit grows by one while the estimated queue is
+/// shorter than `alpha`, and shrinks by one once it is longer than `beta`. Throttled or dropped
+/// requests shrink the limit by the backoff ratio.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub struct VegasConfig {
+    initial_limit: usize,
+    min_limit: usize,
+    max_limit: usize,
+    alpha: usize,
+    beta: usize,
+    backoff_ratio: f64,
+}
+
+impl Default for VegasConfig {
+    fn default() -> Self {
+        Self {
+            initial_limit: DEFAULT_INITIAL_LIMIT,
+            min_limit: DEFAULT_MIN_LIMIT,
+            max_limit: DEFAULT_MAX_LIMIT,
+            alpha: DEFAULT_VEGAS_ALPHA,
+            beta: DEFAULT_VEGAS_BETA,
+            backoff_ratio: DEFAULT_BACKOFF_RATIO,
+        }
+    }
+}
+
+impl VegasConfig {
+    /// Sets the limit to start with.
+    pub fn with_initial_limit(mut self, initial_limit: usize) -> Self {
+        self.initial_limit = initial_limit;
+        self
+    }
+
+    /// Sets the lowest the limit will ever go.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is zero, since no request could ever be sent.
+    pub fn with_min_limit(mut self, min_limit: usize) -> Self {
+        assert!(min_limit > 0, "min limit must be at least 1");
+        self.min_limit = min_limit;
+        self
+    }
+
+    /// Sets the highest the limit will ever go.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is zero, since no request could ever be sent.
+    pub fn with_max_limit(mut self, max_limit: usize) -> Self {
+        assert!(max_limit > 0, "max limit must be at least 1");
+        self.max_limit = max_limit;
+        self
+    }
+
+    /// Sets the estimated queue length below which the limit is increased, and the estimated
+    /// queue length above which the limit is decreased.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is greater than `beta`.
+    pub fn with_thresholds(mut self, alpha: usize, beta: usize) -> Self {
+        assert!(alpha <= beta, "alpha must not be greater than beta");
+        self.alpha = alpha;
+        self.beta = beta;
+        self
+    }
+
+    /// Sets the ratio the limit is multiplied by when a request is throttled or dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ratio isn't between `0.5` and `1.0`.
+    pub fn with_backoff_ratio(mut self, backoff_ratio: f64) -> Self {
+        assert!(
+            (0.5..1.0).contains(&backoff_ratio),
+            "backoff ratio must be in the range [0.5, 1.0)"
+        );
+        self.backoff_ratio = backoff_ratio;
+        self
+    }
+}
+
+impl AimdConfig {
+    fn next_limit(&self, limit: usize, in_flight: usize, outcome: PermitOutcome) -> usize {
+        let next = match outcome {
+            PermitOutcome::Success if is_app_limited(limit, in_flight) => limit,
+            PermitOutcome::Success => limit + 1,
+            _ => backoff(limit, self.backoff_ratio),
+        };
+        next.clamp(self.min_limit, self.max_limit)
+    }
+
+    fn initial_limit(&self) -> usize {
+        self.initial_limit.clamp(self.min_limit, self.max_limit)
+    }
+
+    fn validate(&self) {
+        assert!(
+            self.min_limit <= self.max_limit,
+            "min limit must not be greater than max limit"
+        );
+    }
+}
+
+#[derive(Clone, Debug)]
+struct VegasState {
+    config: VegasConfig,
+    min_rtt: Option<Duration>,
+}
+
+impl VegasState {
+    fn next_limit(
+        &mut self,
+        limit: usize,
+        in_flight: usize,
+        rtt: Option<Duration>,
+        outcome: PermitOutcome,
+    ) -> usize {
+        let next = match (outcome, rtt) {
+            (PermitOutcome::Success, Some(rtt)) if !rtt.is_zero() => {
+                let min_rtt = self.min_rtt.map_or(rtt, |min_rtt| min_rtt.min(rtt));
+                self.min_rtt = Some(min_rtt);
+                // Estimate how many requests are waiting in a queue at the service
+                let queue = limit as f64 * (1.0 - min_rtt.as_secs_f64() / rtt.as_secs_f64());
+                if queue < self.config.alpha as f64 && !is_app_limited(limit, in_flight) {
+                    limit + 1
+                } else if queue > self.config.beta as f64 {
+                    limit.saturating_sub(1)
+                } else {
+                    limit
+                }
+            }
+            (PermitOutcome::Success, _) => limit,
+            _ => backoff(limit, self.config.backoff_ratio),
+        };
+        next.clamp(self.config.min_limit, self.config.max_limit)
+    }
+}
+
+impl VegasConfig {
+    fn initial_limit(&self) -> usize {
+        self.initial_limit.clamp(self.min_limit, self.max_limit)
+    }
+
+    fn validate(&self) {
+        assert!(
+            self.min_limit <= self.max_limit,
+            "min limit must not be greater than max limit"
+        );
+    }
+}
+
+/// Returns true when the client isn't using most of the capacity it already has, in which case
+/// a successful request says nothing about whether the limit could be higher.
+fn is_app_limited(limit: usize, in_flight: usize) -> bool {
+    in_flight * 2 < limit
+}
+
+fn backoff(limit: usize, ratio: f64) -> usize {
+    (limit as f64 * ratio) as usize
+}
+
+#[derive(Clone, Debug)]
+enum Algorithm {
+    Fixed(usize),
+    Aimd(AimdConfig),
+    Vegas(VegasState),
+}
+
+impl Algorithm {
+    fn initial_limit(&self) -> usize {
+        match self {
+            Algorithm::Fixed(limit) => *limit,
+            Algorithm::Aimd(config) => config.initial_limit(),
+            Algorithm::Vegas(state) => state.config.initial_limit(),
+        }
+    }
+
+    fn next_limit(
+        &mut self,
+        limit: usize,
+        in_flight: usize,
+        rtt: Option<Duration>,
+        outcome: PermitOutcome,
+    ) -> usize {
+        match self {
+            Algorithm::Fixed(fixed_limit) => *fixed_limit,
+            Algorithm::Aimd(config) => config.next_limit(limit, in_flight, outcome),
+            Algorithm::Vegas(state) => state.next_limit(limit, in_flight, rtt, outcome),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    algorithm: Algorithm,
+    limit: usize,
+    in_flight: usize,
+}
+
+/// The mutable state of a concurrency limit, shared by every permit that it issues.
+#[derive(Clone, Debug)]
+struct LimitState {
+    state: Arc<Mutex<State>>,
+    notify: Arc<Notify>,
+}
+
+impl LimitState {
+    fn new(algorithm: Algorithm) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                limit: algorithm.initial_limit(),
+                algorithm,
+                in_flight: 0,
+            })),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    fn try_acquire(&self) -> Option<(usize, usize)> {
+        let mut state = self.state.lock().unwrap();
+        if state.in_flight < state.limit {
+            state.in_flight += 1;
+            Some((state.in_flight, state.limit))
+        } else {
+            None
+        }
+    }
+
+    /// Waits until the number of in-flight requests is below the limit, and takes a slot.
+    ///
+    /// Returns the number of in-flight requests (including this one) and the current limit.
+    async fn acquire(&self) -> (usize, usize) {
+        loop {
+            // `Notified` is guaranteed to see any `notify_waiters` call made after it is created,
+            // so creating it before checking the state prevents missed wakeups.
+            let notified = self.notify.notified();
+            if let Some(acquired) = self.try_acquire() {
+                return acquired;
+            }
+            notified.await;
+        }
+    }
+
+    fn record_outcome(&self, rtt: Option<Duration>, outcome: PermitOutcome) {
+        let mut state = self.state.lock().unwrap();
+        let (limit, in_flight) = (state.limit, state.in_flight);
+        let next_limit = state.algorithm.next_limit(limit, in_flight, rtt, outcome);
+        if next_limit != limit {
+            debug!(
+                previous_limit = limit,
+                limit = next_limit,
+                ?outcome,
+                ?rtt,
+                "concurrency limit has been updated"
+            );
+            state.limit = next_limit;
+        }
+        let grew = next_limit > limit;
+        drop(state);
+        if grew {
+            self.notify.notify_waiters();
+        }
+    }
+
+    fn release(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.in_flight = state.in_flight.saturating_sub(1);
+        drop(state);
+        self.notify.notify_waiters();
+    }
+
+    #[cfg(test)]
+    fn snapshot(&self) -> (usize, usize) {
+        let state = self.state.lock().unwrap();
+        (state.in_flight, state.limit)
+    }
+}
+
+/// Limits the number of requests that can be in flight at the same time.
+///
+/// A permit is acquired right before a request is transmitted, and released once the response
+/// body has been read to the end or dropped. Requests that can't get a permit wait in a queue,
+/// and the time spent waiting is reported with a `debug` level tracing event.
+///
+/// By default, the limit is shared by every request made with the client this limiter was
+/// configured on (and any clients that clone its config). Use [`ConcurrencyLimiter::partitioned`]
+/// to instead share the limit across all clients using the same [`RetryPartition`].
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::concurrency_limit::{AimdConfig, ConcurrencyLimiter};
+/// use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+///
+/// // At most 50 requests in flight at once
+/// let fixed = ConcurrencyLimiter::fixed(50);
+///
+/// // Start at 10 in-flight requests, and adjust between 2 and 100 depending on throttling
+/// let adaptive = ConcurrencyLimiter::aimd(
+///     AimdConfig::default()
+///         .with_initial_limit(10)
+///         .with_min_limit(2)
+///         .with_max_limit(100),
+/// );
+///
+/// let components = RuntimeComponentsBuilder::new("example")
+///     .with_concurrency_limiter(Some(adaptive));
+/// ```
+#[derive(Clone)]
+pub struct ConcurrencyLimiter {
+    algorithm: Algorithm,
+    scope: Scope,
+}
+
+#[derive(Clone, Debug)]
+enum Scope {
+    Client(LimitState),
+    RetryPartition,
+}
+
+impl fmt::Debug for ConcurrencyLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConcurrencyLimiter")
+            .field("algorithm", &self.algorithm)
+            .field("partitioned", &matches!(self.scope, Scope::RetryPartition))
+            .finish()
+    }
+}
+
+impl ConcurrencyLimiter {
+    fn new(algorithm: Algorithm) -> Self {
+        Self {
+            scope: Scope::Client(LimitState::new(algorithm.clone())),
+            algorithm,
+        }
+    }
+
+    /// Creates a semaphore-based limiter that allows at most `limit` requests in flight.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the limit is zero, since no request could ever be sent.
+    pub fn fixed(limit: usize) -> Self {
+        assert!(limit > 0, "concurrency limit must be at least 1");
+        Self::new(Algorithm::Fixed(limit))
+    }
+
+    /// Creates an adaptive limiter that uses additive-increase/multiplicative-decrease.
+    ///
+    /// The initial limit is clamped to the configured min and max limits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the min limit is greater than the max limit.
+    pub fn aimd(config: AimdConfig) -> Self {
+        config.validate();
+        Self::new(Algorithm::Aimd(config))
+    }
+
+    /// Creates an adaptive limiter that adjusts the limit based on observed latency.
+    ///
+    /// The initial limit is clamped to the configured min and max limits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the min limit is greater than the max limit.
+    pub fn vegas(config: VegasConfig) -> Self {
+        config.validate();
+        Self::new(Algorithm::Vegas(VegasState {
+            config,
+            min_rtt: None,
+        }))
+    }
+
+    /// Shares the limit with every client that uses the same [`RetryPartition`].
+    ///
+    /// The limit for a partition is created by the first request made in that partition, so
+    /// clients sharing a partition should be configured with the same limiter settings.
+    pub fn partitioned(mut self) -> Self {
+        self.scope = Scope::RetryPartition;
+        self
+    }
+
+    fn limit_state(&self, cfg: &ConfigBag) -> LimitState {
+        match &self.scope {
+            Scope::Client(state) => state.clone(),
+            Scope::RetryPartition => {
+                let retry_partition = cfg
+                    .load::<RetryPartition>()
+                    .cloned()
+                    .unwrap_or_else(|| RetryPartition::new("default"));
+                CONCURRENCY_LIMITS
+                    .get_or_init(ConcurrencyLimiterPartition { retry_partition }, || {
+                        LimitState::new(self.algorithm.clone())
+                    })
+            }
+        }
+    }
+}
+
+impl LimitConcurrency for ConcurrencyLimiter {
+    fn acquire_permit<'a>(
+        &'a self,
+        runtime_components: &'a RuntimeComponents,
+        cfg: &'a ConfigBag,
+    ) -> ConcurrencyPermitFuture<'a> {
+        let limit_state = self.limit_state(cfg);
+        let time_source = runtime_components.time_source().unwrap_or_default();
+        ConcurrencyPermitFuture::new(async move {
+            let queued_at = time_source.now();
+            let (in_flight, limit) = limit_state.acquire().await;
+            let acquired_at = time_source.now();
+            let queue_wait = acquired_at.duration_since(queued_at).unwrap_or_default();
+            debug!(
+                ?queue_wait,
+                in_flight, limit, "acquired a concurrency permit"
+            );
+            Ok(ConcurrencyPermit::new(LimiterPermit {
+                limit_state,
+                time_source,
+                acquired_at,
+                outcome_recorded: false,
+            }))
+        })
+    }
+}
+
+#[derive(Debug)]
+struct LimiterPermit {
+    limit_state: LimitState,
+    time_source: SharedTimeSource,
+    acquired_at: SystemTime,
+    outcome_recorded: bool,
+}
+
+impl ReleasePermit for LimiterPermit {
+    fn record_outcome(&mut self, outcome: PermitOutcome) {
+        if self.outcome_recorded {
+            return;
+        }
+        self.outcome_recorded = true;
+        let rtt = self.time_source.now().duration_since(self.acquired_at).ok();
+        self.limit_state.record_outcome(rtt, outcome);
+    }
+}
+
+impl Drop for LimiterPermit {
+    fn drop(&mut self) {
+        // A permit that never saw a response belonged to an attempt that failed or timed out
+        if !self.outcome_recorded {
+            self.record_outcome(PermitOutcome::Dropped);
+        }
+        self.limit_state.release();
+    }
+}
+
+/// Records the outcome of the request attempt on the permit, and makes the response body hold
+/// onto the permit until the body has been fully read or dropped.
+pub(crate) fn hold_permit_until_body_completes(
+    response: &mut HttpResponse,
+    mut permit: ConcurrencyPermit,
+) {
+    let outcome = match response.status().as_u16() {
+        429 | 503 => PermitOutcome::Overloaded,
+        _ => PermitOutcome::Success,
+    };
+    permit.record_outcome(outcome);
+
+    // The mapping closure is dropped right away for streaming bodies, and kept around to rebuild
+    // retryable ones, so the permit must be owned by the bodies rather than by the closure.
+    let slot = PermitSlot(Arc::new(Mutex::new(Some(permit))));
+    let body = response.take_body();
+    *response.body_mut() = body.map_preserve_contents(move |body| {
+        SdkBody::from_body_1_x(PermitHoldingBody {
+            body,
+            slot: slot.clone(),
+        })
+    });
+}
+
+/// Shares a permit between a response body and any copies made of it.
+///
+/// The permit is released as soon as one of the bodies is read to the end, or once every body
+/// (and the closure that rebuilds them) has been dropped.
+#[derive(Clone, Debug)]
+struct PermitSlot(Arc<Mutex<Option<ConcurrencyPermit>>>);
+
+impl PermitSlot {
+    fn release(&self) {
+        drop(self.0.lock().unwrap().take());
+    }
+}
+
+pin_project! {
+    /// A body-wrapper that releases a concurrency permit once the body completes or is dropped.
+    struct PermitHoldingBody<InnerBody> {
+        #[pin]
+        body: InnerBody,
+        slot: PermitSlot,
+    }
+}
+
+impl<InnerBody> http_body_1x::Body for PermitHoldingBody<InnerBody>
+where
+    InnerBody: http_body_1x::Body,
+{
+    type Data = InnerBody::Data;
+    type Error = InnerBody::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.project();
+        let frame = ready!(this.body.poll_frame(cx));
+        if frame.is_none() {
+            this.slot.release();
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.body.size_hint()
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_runtime_api::http::StatusCode;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use bytes::Bytes;
+    use std::collections::VecDeque;
+    use std::future::Future;
+    use std::time::UNIX_EPOCH;
+
+    fn components(time_source: ManualTimeSource) -> RuntimeComponents {
+        RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(time_source))
+            .build()
+            .unwrap()
+    }
+
+    /// A non-retryable body that yields one chunk per poll.
+    struct StreamingBody(VecDeque<Bytes>);
+
+    impl http_body_1x::Body for StreamingBody {
+        type Data = Bytes;
+        type Error = std::convert::Infallible;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            Poll::Ready(self.0.pop_front().map(|chunk| Ok(Frame::data(chunk))))
+        }
+    }
+
+    fn streaming_body(chunks: &[&'static str]) -> SdkBody {
+        SdkBody::from_body_1_x(StreamingBody(
+            chunks
+                .iter()
+                .map(|chunk| Bytes::from_static(chunk.as_bytes()))
+                .collect(),
+        ))
+    }
+
+    fn poll_once<F: Future>(future: Pin<&mut F>) -> Poll<F::Output> {
+        let waker = futures_util::task::noop_waker();
+        future.poll(&mut Context::from_waker(&waker))
+    }
+
+    #[tokio::test]
+    async fn fixed_limit_queues_requests_until_a_permit_is_released() {
+        let rc = components(ManualTimeSource::new(UNIX_EPOCH));
+        let cfg = ConfigBag::base();
+        let limiter = ConcurrencyLimiter::fixed(2);
+
+        let first = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        let _second = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+
+        let third = limiter.acquire_permit(&rc, &cfg);
+        tokio::pin!(third);
+        assert!(poll_once(third.as_mut()).is_pending());
+
+        drop(first);
+        assert!(matches!(poll_once(third.as_mut()), Poll::Ready(Ok(_))));
+    }
+
+    #[tokio::test]
+    async fn aimd_backs_off_when_overloaded_and_grows_when_saturated() {
+        let rc = components(ManualTimeSource::new(UNIX_EPOCH));
+        let cfg = ConfigBag::base();
+        let limiter = ConcurrencyLimiter::aimd(
+            AimdConfig::default()
+                .with_initial_limit(10)
+                .with_min_limit(2)
+                .with_backoff_ratio(0.5),
+        );
+        let state = limiter.limit_state(&cfg);
+
+        let mut permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        permit.record_outcome(PermitOutcome::Overloaded);
+        drop(permit);
+        assert_eq!((0, 5), state.snapshot());
+
+        // A dropped permit with no recorded outcome counts as a dropped request
+        drop(limiter.acquire_permit(&rc, &cfg).await.unwrap());
+        assert_eq!((0, 2), state.snapshot());
+
+        // Only using half the limit doesn't grow it, but saturating it does
+        let mut permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        permit.record_outcome(PermitOutcome::Success);
+        assert_eq!((1, 3), state.snapshot());
+        drop(permit);
+        let mut permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        permit.record_outcome(PermitOutcome::Success);
+        assert_eq!((1, 3), state.snapshot());
+    }
+
+    #[test]
+    fn initial_limit_is_clamped_to_the_min_and_max_limits() {
+        let cfg = ConfigBag::base();
+        let limiter = ConcurrencyLimiter::aimd(
+            AimdConfig::default()
+                .with_initial_limit(0)
+                .with_min_limit(2),
+        );
+        assert_eq!((0, 2), limiter.limit_state(&cfg).snapshot());
+
+        let limiter = ConcurrencyLimiter::vegas(
+            VegasConfig::default()
+                .with_initial_limit(50)
+                .with_max_limit(10),
+        );
+        assert_eq!((0, 10), limiter.limit_state(&cfg).snapshot());
+    }
+
+    #[test]
+    #[should_panic(expected = "min limit must be at least 1")]
+    fn a_min_limit_of_zero_is_rejected() {
+        AimdConfig::default().with_min_limit(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "concurrency limit must be at least 1")]
+    fn a_fixed_limit_of_zero_is_rejected() {
+        ConcurrencyLimiter::fixed(0);
+    }
+
+    #[tokio::test]
+    async fn vegas_shrinks_the_limit_when_latency_increases() {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let rc = components(time_source.clone());
+        let cfg = ConfigBag::base();
+        let limiter = ConcurrencyLimiter::vegas(
+            VegasConfig::default()
+                .with_initial_limit(10)
+                .with_thresholds(2, 4),
+        );
+        let state = limiter.limit_state(&cfg);
+
+        let mut permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        time_source.advance(Duration::from_millis(100));
+        permit.record_outcome(PermitOutcome::Success);
+        drop(permit);
+        assert_eq!((0, 10), state.snapshot());
+
+        // With twice the minimum latency, about half the limit is estimated to be queued
+        let mut permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        time_source.advance(Duration::from_millis(200));
+        permit.record_outcome(PermitOutcome::Success);
+        drop(permit);
+        assert_eq!((0, 9), state.snapshot());
+    }
+
+    #[tokio::test]
+    async fn partitioned_limiters_share_a_limit() {
+        let rc = components(ManualTimeSource::new(UNIX_EPOCH));
+        let mut cfg = ConfigBag::base();
+        cfg.interceptor_state()
+            .store_put(RetryPartition::new("partitioned_limiters_share_a_limit"));
+        let first = ConcurrencyLimiter::fixed(1).partitioned();
+        let second = ConcurrencyLimiter::fixed(1).partitioned();
+
+        let _permit = first.acquire_permit(&rc, &cfg).await.unwrap();
+        let blocked = second.acquire_permit(&rc, &cfg);
+        tokio::pin!(blocked);
+        assert!(poll_once(blocked.as_mut()).is_pending());
+    }
+
+    #[tokio::test]
+    async fn response_body_holds_the_permit_until_it_is_read() {
+        let rc = components(ManualTimeSource::new(UNIX_EPOCH));
+        let cfg = ConfigBag::base();
+        let limiter = ConcurrencyLimiter::fixed(1);
+        let state = limiter.limit_state(&cfg);
+
+        let permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        let mut response =
+            HttpResponse::new(StatusCode::try_from(200).unwrap(), SdkBody::from("hello"));
+        hold_permit_until_body_completes(&mut response, permit);
+        assert_eq!((1, 1), state.snapshot());
+
+        let body = ByteStream::new(response.take_body())
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(b"hello", body.into_bytes().as_ref());
+        assert_eq!((0, 1), state.snapshot());
+    }
+
+    #[tokio::test]
+    async fn streaming_response_body_holds_the_permit_until_it_is_read() {
+        let rc = components(ManualTimeSource::new(UNIX_EPOCH));
+        let cfg = ConfigBag::base();
+        let limiter = ConcurrencyLimiter::fixed(1);
+        let state = limiter.limit_state(&cfg);
+
+        let permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        let mut response = HttpResponse::new(
+            StatusCode::try_from(200).unwrap(),
+            streaming_body(&["hello", " ", "world"]),
+        );
+        hold_permit_until_body_completes(&mut response, permit);
+        assert_eq!((1, 1), state.snapshot());
+
+        let mut body = response.take_body();
+        let frame =
+            std::future::poll_fn(|cx| http_body_1x::Body::poll_frame(Pin::new(&mut body), cx))
+                .await;
+        let chunk = frame.unwrap().unwrap().into_data().unwrap();
+        assert_eq!(b"hello", chunk.as_ref());
+        assert_eq!((1, 1), state.snapshot());
+
+        let rest = ByteStream::new(body).collect().await.unwrap();
+        assert_eq!(b" world", rest.into_bytes().as_ref());
+        assert_eq!((0, 1), state.snapshot());
+
+        // Dropping a streaming body part way through also releases the permit
+        let permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        let mut response = HttpResponse::new(
+            StatusCode::try_from(200).unwrap(),
+            streaming_body(&["hello"]),
+        );
+        hold_permit_until_body_completes(&mut response, permit);
+        assert_eq!((1, 1), state.snapshot());
+        drop(response);
+        assert_eq!((0, 1), state.snapshot());
+    }
+
+    #[tokio::test]
+    async fn dropping_a_copy_of_the_response_body_keeps_the_permit() {
+        let rc = components(ManualTimeSource::new(UNIX_EPOCH));
+        let cfg = ConfigBag::base();
+        let limiter = ConcurrencyLimiter::fixed(1);
+        let state = limiter.limit_state(&cfg);
+
+        let permit = limiter.acquire_permit(&rc, &cfg).await.unwrap();
+        let mut response =
+            HttpResponse::new(StatusCode::try_from(200).unwrap(), SdkBody::from("hello"));
+        hold_permit_until_body_completes(&mut response, permit);
+
+        drop(response.body().try_clone().expect("retryable"));
+        assert_eq!((1, 1), state.snapshot());
+
+        drop(response);
+        assert_eq!((0, 1), state.snapshot());
+    }
+}
@@ -3,11 +3,25 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use aws_smithy_async::rt::sleep::SharedAsyncSleep;
-use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::time::TimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::{AuthScheme, AuthSchemeId};
+use aws_smithy_runtime_api::client::concurrency_limit::LimitConcurrency;
+use aws_smithy_runtime_api::client::endpoint::ResolveEndpoint;
+use aws_smithy_runtime_api::client::http::HttpClient;
+use aws_smithy_runtime_api::client::identity::{ResolveCachedIdentity, ResolveIdentity};
+use aws_smithy_runtime_api::client::interceptors::{AsyncIntercept, Intercept};
+use aws_smithy_runtime_api::client::retries::classifiers::ClassifyRetry;
+use aws_smithy_runtime_api::client::retries::RetryStrategy;
+use aws_smithy_runtime_api::client::runtime_components::{
+    GetIdentityResolver, RuntimeComponents, RuntimeComponentsBuilder, SharedConfigValidator,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
 use aws_smithy_types::config_bag::{
-    CloneableLayer, FrozenLayer, Layer, Storable, Store, StoreReplace,
+    CloneableLayer, ConfigBag, FrozenLayer, Layer, Storable, Store, StoreReplace,
 };
+use std::borrow::Cow;
 
 macro_rules! component {
     ($typ:ty, $accessor:ident, $latest_accessor:ident, $doc:tt) => {
@@ -179,6 +193,178 @@ impl<'a> Resolver<'a> {
     }
 }
 
+/// Overrides runtime components for a single operation invocation.
+///
+/// Unlike `config_override`, which can only override what a generated client's config builder exposes,
+/// this can swap any runtime component, such as the HTTP client, an identity resolver, the retry
+/// strategy, or the endpoint resolver, and add interceptors. Components set here take precedence
+/// over the ones configured on the client. Interceptors and retry classifiers are added to the
+/// ones configured on the client.
+///
+/// This is a [`RuntimePlugin`] that should be registered as an operation runtime plugin (generated
+/// clients do this with `customize().runtime_components_override(...)`). When the operation is sent,
+/// the final runtime components are checked with [`RuntimeComponents::validate_final_config`],
+/// which includes making sure every overridden auth scheme and identity resolver has a counterpart.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::config_override::RuntimeComponentsOverride;
+/// use aws_smithy_runtime::client::http::test_util::NeverClient;
+///
+/// let components = RuntimeComponentsOverride::new().http_client(NeverClient::new());
+/// ```
+#[derive(Clone, Debug)]
+pub struct RuntimeComponentsOverride {
+    components: RuntimeComponentsBuilder,
+    identity_resolver_schemes: Vec<AuthSchemeId>,
+}
+
+impl Default for RuntimeComponentsOverride {
+    fn default() -> Self {
+        Self {
+            components: RuntimeComponentsBuilder::new("RuntimeComponentsOverride"),
+            identity_resolver_schemes: Vec::new(),
+        }
+    }
+}
+
+impl RuntimeComponentsOverride {
+    /// Creates a new `RuntimeComponentsOverride` that doesn't override anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the HTTP client.
+    pub fn http_client(mut self, http_client: impl HttpClient + 'static) -> Self {
+        self.components.set_http_client(Some(http_client));
+        self
+    }
+
+    /// Overrides the endpoint resolver.
+    pub fn endpoint_resolver(mut self, endpoint_resolver: impl ResolveEndpoint + 'static) -> Self {
+        self.components
+            .set_endpoint_resolver(Some(endpoint_resolver));
+        self
+    }
+
+    /// Overrides the retry strategy.
+    pub fn retry_strategy(mut self, retry_strategy: impl RetryStrategy + 'static) -> Self {
+        self.components.set_retry_strategy(Some(retry_strategy));
+        self
+    }
+
+    /// Adds a retry classifier.
+    pub fn retry_classifier(mut self, retry_classifier: impl ClassifyRetry + 'static) -> Self {
+        self.components.push_retry_classifier(retry_classifier);
+        self
+    }
+
+    /// Adds an auth scheme, or replaces the one with the same scheme ID.
+    ///
+    /// An identity resolver for the scheme must also be available, either from the client or
+    /// from [`identity_resolver`](Self::identity_resolver).
+    pub fn auth_scheme(mut self, auth_scheme: impl AuthScheme + 'static) -> Self {
+        self.components.push_auth_scheme(auth_scheme);
+        self
+    }
+
+    /// Overrides the identity resolver for the given auth scheme.
+    pub fn identity_resolver(
+        mut self,
+        scheme_id: AuthSchemeId,
+        identity_resolver: impl ResolveIdentity + 'static,
+    ) -> Self {
+        self.components
+            .set_identity_resolver(scheme_id, identity_resolver);
+        self.identity_resolver_schemes.push(scheme_id);
+        self
+    }
+
+    /// Overrides the identity cache.
+    pub fn identity_cache(mut self, identity_cache: impl ResolveCachedIdentity + 'static) -> Self {
+        self.components.set_identity_cache(Some(identity_cache));
+        self
+    }
+
+    /// Adds an interceptor.
+    pub fn interceptor(mut self, interceptor: impl Intercept + 'static) -> Self {
+        self.components.push_interceptor(interceptor);
+        self
+    }
+
+    /// Adds an async interceptor.
+    pub fn async_interceptor(mut self, interceptor: impl AsyncIntercept + 'static) -> Self {
+        self.components.push_async_interceptor(interceptor);
+        self
+    }
+
+    /// Overrides the concurrency limiter.
+    pub fn concurrency_limiter(mut self, limiter: impl LimitConcurrency + 'static) -> Self {
+        self.components.set_concurrency_limiter(Some(limiter));
+        self
+    }
+
+    /// Overrides the async sleep implementation.
+    pub fn sleep_impl(mut self, sleep_impl: impl AsyncSleep + 'static) -> Self {
+        self.components = self.components.with_sleep_impl(Some(sleep_impl));
+        self
+    }
+
+    /// Overrides the time source.
+    pub fn time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.components = self.components.with_time_source(Some(time_source));
+        self
+    }
+}
+
+fn validate_override(
+    auth_schemes: &[AuthSchemeId],
+    identity_resolver_schemes: &[AuthSchemeId],
+    components: &RuntimeComponents,
+) -> Result<(), BoxError> {
+    for &scheme_id in auth_schemes {
+        if components.identity_resolver(scheme_id).is_none() {
+            return Err(format!(
+                "the runtime components override added the `{}` auth scheme, but no identity \
+                 resolver is configured for it",
+                scheme_id.as_str()
+            )
+            .into());
+        }
+    }
+    for &scheme_id in identity_resolver_schemes {
+        if components.auth_scheme(scheme_id).is_none() {
+            return Err(format!(
+                "the runtime components override set an identity resolver for the `{}` auth \
+                 scheme, but that auth scheme isn't configured",
+                scheme_id.as_str()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+impl RuntimePlugin for RuntimeComponentsOverride {
+    fn runtime_components(
+        &self,
+        _current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        let identity_resolver_schemes = self.identity_resolver_schemes.clone();
+        let auth_schemes: Vec<_> = self
+            .components
+            .auth_schemes()
+            .map(|scheme| scheme.scheme_id())
+            .collect();
+        Cow::Owned(self.components.clone().with_config_validator(
+            SharedConfigValidator::final_config_fn(move |components, _cfg: &ConfigBag| {
+                validate_override(&auth_schemes, &identity_resolver_schemes, components)
+            }),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -263,4 +449,47 @@ mod tests {
             resolver.resolve_config::<TestStorable>().unwrap().0
         );
     }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn runtime_components_override_validation() {
+        use crate::client::auth::no_auth::{NoAuthScheme, NO_AUTH_SCHEME_ID};
+        use crate::client::identity::no_auth::NoAuthIdentityResolver;
+
+        fn final_components(overrid: &RuntimeComponentsOverride) -> RuntimeComponents {
+            let base = RuntimeComponentsBuilder::for_tests();
+            base.clone()
+                .merge_from(&overrid.runtime_components(&base))
+                .build()
+                .unwrap()
+        }
+        let cfg = ConfigBag::base();
+
+        let overrid = RuntimeComponentsOverride::new().auth_scheme(NoAuthScheme::new());
+        let err = final_components(&overrid)
+            .validate_final_config(&cfg)
+            .expect_err("no identity resolver for no_auth");
+        assert!(
+            err.to_string().contains(
+                "added the `no_auth` auth scheme, but no identity resolver is configured for it"
+            ),
+            "{err}"
+        );
+
+        let overrid = overrid.identity_resolver(NO_AUTH_SCHEME_ID, NoAuthIdentityResolver::new());
+        final_components(&overrid)
+            .validate_final_config(&cfg)
+            .expect("valid");
+
+        let overrid = RuntimeComponentsOverride::new()
+            .identity_resolver(AuthSchemeId::new("other"), NoAuthIdentityResolver::new());
+        let err = final_components(&overrid)
+            .validate_final_config(&cfg)
+            .expect_err("no auth scheme for the identity resolver");
+        assert!(
+            err.to_string()
+                .contains("identity resolver for the `other` auth scheme"),
+            "{err}"
+        );
+    }
 }
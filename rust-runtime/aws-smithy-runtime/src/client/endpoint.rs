@@ -10,6 +10,9 @@ use std::borrow::Cow;
 use std::result::Result as StdResult;
 use std::str::FromStr;
 
+/// Endpoint resolver that memoizes resolved endpoints.
+pub mod cache;
+
 /// Apply `endpoint` to `uri`
 ///
 /// This method mutates `uri` by setting the `endpoint` on it
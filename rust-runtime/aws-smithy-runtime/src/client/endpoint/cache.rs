@@ -0,0 +1,260 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_runtime_api::client::endpoint::{
+    EndpointFuture, EndpointResolverParams, ResolveEndpoint, SharedEndpointResolver,
+};
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::endpoint::Endpoint;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex};
+
+/// Default number of endpoints a [`CachingEndpointResolver`] will remember.
+pub const DEFAULT_CAPACITY: usize = 64;
+
+/// Endpoint resolver that memoizes the endpoints resolved by an inner resolver.
+///
+/// Resolved endpoints are keyed on the concrete endpoint parameters type `P`, which is
+/// usually the code generated `Params` struct of a service. Endpoint rules are pure functions
+/// of their parameters, so resolving the same parameters twice always yields the same endpoint.
+///
+/// The cache is bounded. Once it holds `capacity` endpoints, the least recently used endpoint
+/// is evicted to make room for a new one. Resolution failures are never cached, and parameters
+/// that aren't of type `P` are passed straight through to the inner resolver.
+pub struct CachingEndpointResolver<P> {
+    inner: SharedEndpointResolver,
+    cache: Arc<Mutex<Lru<P>>>,
+    _params: PhantomData<fn(P)>,
+}
+
+impl<P> CachingEndpointResolver<P>
+where
+    P: Clone + Eq + Hash + fmt::Debug + Send + Sync + 'static,
+{
+    /// Creates a new caching resolver around `inner` that holds the [default number](DEFAULT_CAPACITY)
+    /// of endpoints.
+    pub fn new(inner: impl ResolveEndpoint + 'static) -> Self {
+        Self::with_capacity(inner, DEFAULT_CAPACITY)
+    }
+
+    /// Creates a new caching resolver around `inner` that holds at most `capacity` endpoints.
+    ///
+    /// A capacity of zero disables caching.
+    pub fn with_capacity(inner: impl ResolveEndpoint + 'static, capacity: usize) -> Self {
+        Self {
+            inner: inner.into_shared(),
+            cache: Arc::new(Mutex::new(Lru::new(capacity))),
+            _params: PhantomData,
+        }
+    }
+
+    /// Returns the number of endpoints currently cached.
+    pub fn len(&self) -> usize {
+        self.cache.lock().unwrap().entries.len()
+    }
+
+    /// Returns true if no endpoints are currently cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes all cached endpoints.
+    pub fn clear(&self) {
+        self.cache.lock().unwrap().clear();
+    }
+}
+
+impl<P> Clone for CachingEndpointResolver<P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            cache: self.cache.clone(),
+            _params: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for CachingEndpointResolver<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachingEndpointResolver")
+            .field("inner", &self.inner)
+            .field("capacity", &self.cache.lock().unwrap().capacity)
+            .finish()
+    }
+}
+
+impl<P> ResolveEndpoint for CachingEndpointResolver<P>
+where
+    P: Clone + Eq + Hash + fmt::Debug + Send + Sync + 'static,
+{
+    fn resolve_endpoint<'a>(&'a self, params: &'a EndpointResolverParams) -> EndpointFuture<'a> {
+        let key = match params.get::<P>() {
+            Some(key) => key,
+            None => return self.inner.resolve_endpoint(params),
+        };
+        if let Some(endpoint) = self.cache.lock().unwrap().get(key) {
+            tracing::trace!(params = ?key, "using cached endpoint");
+            return EndpointFuture::ready(Ok(endpoint));
+        }
+        EndpointFuture::new(async move {
+            let endpoint = self.inner.resolve_endpoint(params).await?;
+            self.cache
+                .lock()
+                .unwrap()
+                .insert(key.clone(), endpoint.clone());
+            Ok(endpoint)
+        })
+    }
+}
+
+/// A bounded map that evicts its least recently used entry when full.
+struct Lru<P> {
+    capacity: usize,
+    // Each entry remembers the tick it was last used at, which is its key in `order`
+    entries: HashMap<P, (Endpoint, u64)>,
+    // Keys ordered from least to most recently used
+    order: BTreeMap<u64, P>,
+    tick: u64,
+}
+
+impl<P: Clone + Eq + Hash> Lru<P> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+            tick: 0,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, key: &P) -> Option<Endpoint> {
+        let tick = self.next_tick();
+        let (endpoint, last_used) = self.entries.get_mut(key)?;
+        let previous = std::mem::replace(last_used, tick);
+        let endpoint = endpoint.clone();
+        if let Some(key) = self.order.remove(&previous) {
+            self.order.insert(tick, key);
+        }
+        Some(endpoint)
+    }
+
+    fn insert(&mut self, key: P, endpoint: Endpoint) {
+        if self.capacity == 0 {
+            return;
+        }
+        let tick = self.next_tick();
+        if let Some((_, previous)) = self.entries.insert(key.clone(), (endpoint, tick)) {
+            self.order.remove(&previous);
+        } else if self.entries.len() > self.capacity {
+            if let Some((_, evicted)) = self.order.pop_first() {
+                self.entries.remove(&evicted);
+            }
+        }
+        self.order.insert(tick, key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Clone, Debug, Eq, Hash, PartialEq)]
+    struct Params {
+        region: String,
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct CountingResolver {
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl ResolveEndpoint for CountingResolver {
+        fn resolve_endpoint<'a>(
+            &'a self,
+            params: &'a EndpointResolverParams,
+        ) -> EndpointFuture<'a> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let region = &params.get::<Params>().expect("params").region;
+            if region == "invalid" {
+                return EndpointFuture::ready(Err("invalid region".into()));
+            }
+            EndpointFuture::ready(Ok(Endpoint::builder()
+                .url(format!("https://{region}.example.com"))
+                .build()))
+        }
+    }
+
+    fn params(region: &str) -> EndpointResolverParams {
+        EndpointResolverParams::new(Params {
+            region: region.into(),
+        })
+    }
+
+    #[tokio::test]
+    async fn caches_endpoints_by_params() {
+        let inner = CountingResolver::default();
+        let resolver = CachingEndpointResolver::<Params>::new(inner.clone());
+
+        let first = resolver.resolve_endpoint(&params("a")).await.unwrap();
+        let second = resolver.resolve_endpoint(&params("a")).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!("https://a.example.com", second.url());
+        assert_eq!(1, inner.calls.load(Ordering::SeqCst));
+
+        resolver.resolve_endpoint(&params("b")).await.unwrap();
+        assert_eq!(2, inner.calls.load(Ordering::SeqCst));
+        assert_eq!(2, resolver.len());
+    }
+
+    #[tokio::test]
+    async fn evicts_least_recently_used() {
+        let inner = CountingResolver::default();
+        let resolver = CachingEndpointResolver::<Params>::with_capacity(inner.clone(), 2);
+
+        resolver.resolve_endpoint(&params("a")).await.unwrap();
+        resolver.resolve_endpoint(&params("b")).await.unwrap();
+        // `a` becomes the most recently used, so `b` is evicted for `c`
+        resolver.resolve_endpoint(&params("a")).await.unwrap();
+        resolver.resolve_endpoint(&params("c")).await.unwrap();
+        assert_eq!(3, inner.calls.load(Ordering::SeqCst));
+        assert_eq!(2, resolver.len());
+
+        resolver.resolve_endpoint(&params("a")).await.unwrap();
+        assert_eq!(3, inner.calls.load(Ordering::SeqCst));
+        resolver.resolve_endpoint(&params("b")).await.unwrap();
+        assert_eq!(4, inner.calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn does_not_cache_errors() {
+        let inner = CountingResolver::default();
+        let resolver = CachingEndpointResolver::<Params>::new(inner.clone());
+
+        resolver
+            .resolve_endpoint(&params("invalid"))
+            .await
+            .expect_err("invalid region");
+        resolver
+            .resolve_endpoint(&params("invalid"))
+            .await
+            .expect_err("invalid region");
+        assert_eq!(2, inner.calls.load(Ordering::SeqCst));
+        assert!(resolver.is_empty());
+    }
+}
@@ -0,0 +1,356 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Structured, machine-readable records of [`SdkError`]s.
+//!
+//! An [`ErrorRecord`] captures everything about a failed request that is useful for error
+//! analytics: the kind of failure, the modeled error code and message, the HTTP status, request
+//! IDs, the number of attempts and the raw response headers, along with the messages of every
+//! error in the source chain. [`ErrorRecord::to_json`] renders it with a stable JSON shape.
+
+use aws_smithy_json::serialize::JsonObjectWriter;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_runtime_api::client::retries::RequestAttempts;
+use aws_smithy_types::error::metadata::ProvideErrorMetadata;
+use aws_smithy_types::Number;
+use std::error::Error;
+use std::fmt;
+
+/// Response headers that carry request IDs.
+const REQUEST_ID_HEADERS: &[&str] = &["x-amzn-requestid", "x-amz-request-id", "x-amz-id-2"];
+
+/// The kind of failure an [`ErrorRecord`] describes; see the variants of [`SdkError`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorRecordKind {
+    /// The request failed during construction and was never sent.
+    Construction,
+    /// The request timed out.
+    Timeout,
+    /// The request failed during dispatch and no response was received.
+    Dispatch,
+    /// A response was received but couldn't be parsed.
+    Response,
+    /// The service returned an error response.
+    Service,
+    /// A kind of failure this version doesn't know about.
+    Unknown,
+}
+
+impl ErrorRecordKind {
+    /// Returns the name of this kind, as used in the JSON rendering.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Construction => "construction",
+            Self::Timeout => "timeout",
+            Self::Dispatch => "dispatch",
+            Self::Response => "response",
+            Self::Service => "service",
+            Self::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for ErrorRecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A structured record of an [`SdkError`] and its sources.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::error_record::ErrorRecord;
+/// # use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+/// # use aws_smithy_runtime_api::client::result::SdkError;
+/// # use aws_smithy_types::error::ErrorMetadata;
+/// # fn example(err: SdkError<ErrorMetadata, HttpResponse>) {
+/// let record = ErrorRecord::from_sdk_error(&err);
+/// println!("{}", record.to_json());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ErrorRecord {
+    kind: ErrorRecordKind,
+    message: String,
+    code: Option<String>,
+    error_message: Option<String>,
+    extras: Vec<(String, String)>,
+    http_status: Option<u16>,
+    request_ids: Vec<(String, String)>,
+    attempts: Option<u32>,
+    headers: Vec<(String, String)>,
+    sources: Vec<String>,
+}
+
+impl ErrorRecord {
+    /// Creates a record of `err` and its sources.
+    pub fn from_sdk_error<E>(err: &SdkError<E, HttpResponse>) -> Self
+    where
+        E: ProvideErrorMetadata + Error + 'static,
+    {
+        let kind = match err {
+            SdkError::ConstructionFailure(_) => ErrorRecordKind::Construction,
+            SdkError::TimeoutError(_) => ErrorRecordKind::Timeout,
+            SdkError::DispatchFailure(_) => ErrorRecordKind::Dispatch,
+            SdkError::ResponseError(_) => ErrorRecordKind::Response,
+            SdkError::ServiceError(_) => ErrorRecordKind::Service,
+            _ => ErrorRecordKind::Unknown,
+        };
+        let meta = err.meta();
+        let mut extras: Vec<_> = meta
+            .extras()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        extras.sort();
+
+        let response = err.raw_response();
+        let mut headers: Vec<(String, String)> = response
+            .map(|response| {
+                response
+                    .headers()
+                    .iter()
+                    .map(|(name, value)| (name.to_ascii_lowercase(), value.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        headers.sort();
+        let mut request_ids: Vec<_> = headers
+            .iter()
+            .filter(|(name, _)| REQUEST_ID_HEADERS.contains(&name.as_str()))
+            .cloned()
+            .collect();
+        request_ids.extend(
+            extras
+                .iter()
+                .filter(|(key, _)| key.ends_with("request_id"))
+                .cloned(),
+        );
+
+        let mut sources = Vec::new();
+        let mut source = err.source();
+        while let Some(err) = source {
+            sources.push(err.to_string());
+            source = err.source();
+        }
+
+        Self {
+            kind,
+            message: err.to_string(),
+            code: meta.code().map(str::to_string),
+            error_message: meta.message().map(str::to_string),
+            extras,
+            http_status: response.map(|response| response.status().as_u16()),
+            request_ids,
+            attempts: None,
+            headers,
+            sources,
+        }
+    }
+
+    /// Sets the number of attempts made before the request failed.
+    ///
+    /// The orchestrator tracks this as [`RequestAttempts`] in the config bag, where an
+    /// interceptor can read it.
+    pub fn with_attempts(mut self, attempts: impl Into<RequestAttempts>) -> Self {
+        self.attempts = Some(attempts.into().attempts());
+        self
+    }
+
+    /// Returns the kind of failure.
+    pub fn kind(&self) -> ErrorRecordKind {
+        self.kind
+    }
+
+    /// Returns the message of the top-level error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the modeled error code, if any.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Returns the modeled error message, if any.
+    pub fn error_message(&self) -> Option<&str> {
+        self.error_message.as_deref()
+    }
+
+    /// Returns the additional error metadata, sorted by key.
+    pub fn extras(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.extras.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the HTTP status of the response, if one was received.
+    pub fn http_status(&self) -> Option<u16> {
+        self.http_status
+    }
+
+    /// Returns the request IDs from the response headers and error metadata.
+    pub fn request_ids(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.request_ids
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the number of attempts made, if known.
+    pub fn attempts(&self) -> Option<u32> {
+        self.attempts
+    }
+
+    /// Returns the raw response headers, with lowercase names, sorted by name.
+    pub fn headers(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.headers.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns the messages of the errors in the source chain, outermost first.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Renders this record as a JSON object.
+    ///
+    /// Every key is always present, with `null` (or an empty array) for missing values:
+    ///
+    /// ```json
+    /// {
+    ///   "kind": "service",
+    ///   "message": "service error",
+    ///   "code": "NoSuchKey",
+    ///   "error_message": "The specified key does not exist.",
+    ///   "extras": [{"key": "aws_request_id", "value": "..."}],
+    ///   "http_status": 404,
+    ///   "request_ids": [{"name": "x-amz-request-id", "value": "..."}],
+    ///   "attempts": 1,
+    ///   "headers": [{"name": "content-type", "value": "application/xml"}],
+    ///   "sources": ["NoSuchKey: The specified key does not exist."]
+    /// }
+    /// ```
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        let mut object = JsonObjectWriter::new(&mut out);
+        object.key("kind").string(self.kind.as_str());
+        object.key("message").string(&self.message);
+        match &self.code {
+            Some(code) => object.key("code").string(code),
+            None => object.key("code").null(),
+        }
+        match &self.error_message {
+            Some(message) => object.key("error_message").string(message),
+            None => object.key("error_message").null(),
+        }
+        write_pairs(&mut object, "extras", "key", &self.extras);
+        match self.http_status {
+            Some(status) => object
+                .key("http_status")
+                .number(Number::PosInt(status.into())),
+            None => object.key("http_status").null(),
+        }
+        write_pairs(&mut object, "request_ids", "name", &self.request_ids);
+        match self.attempts {
+            Some(attempts) => object
+                .key("attempts")
+                .number(Number::PosInt(attempts.into())),
+            None => object.key("attempts").null(),
+        }
+        write_pairs(&mut object, "headers", "name", &self.headers);
+        let mut sources = object.key("sources").start_array();
+        for source in &self.sources {
+            sources.value().string(source);
+        }
+        sources.finish();
+        object.finish();
+        out
+    }
+}
+
+fn write_pairs(
+    object: &mut JsonObjectWriter<'_>,
+    key: &str,
+    name_key: &str,
+    pairs: &[(String, String)],
+) {
+    let mut array = object.key(key).start_array();
+    for (name, value) in pairs {
+        let mut pair = array.value().start_object();
+        pair.key(name_key).string(name);
+        pair.key("value").string(value);
+        pair.finish();
+    }
+    array.finish();
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ErrorRecord, ErrorRecordKind};
+    use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::error::ErrorMetadata;
+
+    type Error = SdkError<ErrorMetadata, HttpResponse>;
+
+    #[test]
+    fn records_service_errors() {
+        let mut response = HttpResponse::new(404.try_into().unwrap(), SdkBody::empty());
+        response
+            .headers_mut()
+            .insert("Content-Type", "application/xml");
+        response.headers_mut().insert("x-amz-request-id", "ABC123");
+        let meta = ErrorMetadata::builder()
+            .code("NoSuchKey")
+            .message("The specified key does not exist.")
+            .custom("s3_extended_request_id", "XYZ")
+            .build();
+        let err: Error = SdkError::service_error(meta, response);
+
+        let record = ErrorRecord::from_sdk_error(&err).with_attempts(2);
+        assert_eq!(ErrorRecordKind::Service, record.kind());
+        assert_eq!(Some("NoSuchKey"), record.code());
+        assert_eq!(Some(404), record.http_status());
+        assert_eq!(
+            vec![
+                ("x-amz-request-id", "ABC123"),
+                ("s3_extended_request_id", "XYZ")
+            ],
+            record.request_ids().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            concat!(
+                r#"{"kind":"service","message":"service error","code":"NoSuchKey","#,
+                r#""error_message":"The specified key does not exist.","#,
+                r#""extras":[{"key":"s3_extended_request_id","value":"XYZ"}],"http_status":404,"#,
+                r#""request_ids":[{"name":"x-amz-request-id","value":"ABC123"},"#,
+                r#"{"name":"s3_extended_request_id","value":"XYZ"}],"attempts":2,"#,
+                r#""headers":[{"name":"content-type","value":"application/xml"},"#,
+                r#"{"name":"x-amz-request-id","value":"ABC123"}],"#,
+                r#""sources":["Error { code: \"NoSuchKey\", message: \"The specified key does not exist.\", s3_extended_request_id: \"XYZ\" }"]}"#,
+            ),
+            record.to_json()
+        );
+    }
+
+    #[test]
+    fn records_dispatch_failures_with_their_sources() {
+        let err: Error = SdkError::dispatch_failure(ConnectorError::io("connection reset".into()));
+
+        let record = ErrorRecord::from_sdk_error(&err);
+        assert_eq!(ErrorRecordKind::Dispatch, record.kind());
+        assert_eq!(None, record.http_status());
+        assert_eq!(["io error", "connection reset"], record.sources());
+        assert_eq!(
+            concat!(
+                r#"{"kind":"dispatch","message":"dispatch failure","code":null,"error_message":null,"#,
+                r#""extras":[],"http_status":null,"request_ids":[],"attempts":null,"headers":[],"#,
+                r#""sources":["io error","connection reset"]}"#,
+            ),
+            record.to_json()
+        );
+    }
+}
@@ -0,0 +1,699 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Reconnecting event stream receivers.
+//!
+//! When the connection carrying an event stream drops, [`Receiver::recv`] returns an error and the
+//! rest of the stream is lost. A [`ResumableReceiver`] wraps a receiver so that retryable errors
+//! are followed by a call to a caller-supplied resume function, which re-establishes the stream,
+//! typically by re-issuing the operation with a continuation token taken from the last event
+//! received.
+//!
+//! Errors are classified with the same [retry classifiers](ClassifyRetry) that are used for
+//! requests, and reconnect attempts are delayed with the exponential backoff of the
+//! [`StandardRetryStrategy`](crate::client::retries::strategy::StandardRetryStrategy), configured
+//! with a [`RetryConfig`].
+
+use crate::client::retries::classifiers::{run_classifiers_on_ctx, TransientErrorClassifier};
+use crate::client::retries::strategy::standard::calculate_exponential_backoff;
+use aws_smithy_async::future::BoxFuture;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_http::event_stream::Receiver;
+use aws_smithy_runtime_api::client::interceptors::context::{Error, Input, InterceptorContext};
+use aws_smithy_runtime_api::client::orchestrator::{HttpResponse, OrchestratorError};
+use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
+use aws_smithy_runtime_api::client::retries::classifiers::{
+    ClassifyRetry, RetryAction, RetryReason, SharedRetryClassifier,
+};
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::event_stream::RawMessage;
+use aws_smithy_types::retry::RetryConfig;
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::Future;
+use std::time::Duration;
+use tracing::debug;
+
+type CheckpointFn<T, C> = Box<dyn Fn(&T) -> Option<C> + Send + Sync>;
+type ResumeFn<T, E, RE, C> = Box<
+    dyn Fn(Option<C>) -> BoxFuture<'static, Receiver<T, E>, SdkError<RE, HttpResponse>>
+        + Send
+        + Sync,
+>;
+
+/// An error returned by [`ResumableReceiver::recv`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ResumeError<E, RE> {
+    /// The event stream failed with an error that isn't retryable.
+    Stream(SdkError<E, RawMessage>),
+    /// The event stream failed, and every attempt to resume it failed. This is the error from the
+    /// last attempt.
+    Resume(SdkError<RE, HttpResponse>),
+}
+
+impl<E, RE> fmt::Display for ResumeError<E, RE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResumeError::Stream(_) => write!(f, "the event stream failed"),
+            ResumeError::Resume(_) => write!(f, "failed to resume the event stream"),
+        }
+    }
+}
+
+impl<E, RE> StdError for ResumeError<E, RE>
+where
+    E: StdError + 'static,
+    RE: StdError + 'static,
+{
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ResumeError::Stream(err) => Some(err),
+            ResumeError::Resume(err) => Some(err),
+        }
+    }
+}
+
+/// An event stream [`Receiver`] that resumes the stream after retryable errors.
+///
+/// ```no_run
+/// # use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
+/// # use aws_smithy_http::event_stream::Receiver;
+/// # use aws_smithy_runtime::client::event_stream::ResumableReceiver;
+/// # use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+/// # use aws_smithy_runtime_api::client::result::SdkError;
+/// # use aws_smithy_types::retry::RetryConfig;
+/// # #[derive(Debug)] struct Event { token: Option<String> }
+/// # #[derive(Debug)] struct EventError;
+/// # impl std::fmt::Display for EventError {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { write!(f, "error") }
+/// # }
+/// # impl std::error::Error for EventError {}
+/// # type OperationError = EventError;
+/// # async fn start_stream(
+/// #     token: Option<String>,
+/// # ) -> Result<Receiver<Event, EventError>, SdkError<OperationError, HttpResponse>> {
+/// #     todo!()
+/// # }
+/// # async fn example() {
+/// let initial = start_stream(None).await.unwrap();
+/// let mut receiver = ResumableReceiver::builder()
+///     .receiver(initial)
+///     // Remember the continuation token of the last event that had one
+///     .checkpoint(|event: &Event| event.token.clone())
+///     // Re-issue the operation, continuing from the last token
+///     .resume(|token| Box::pin(start_stream(token)))
+///     .retry_config(RetryConfig::standard().with_max_attempts(5))
+///     .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+///     .build();
+/// while let Some(event) = receiver.recv().await.unwrap() {
+///     println!("{event:?}");
+/// }
+/// # }
+/// ```
+pub struct ResumableReceiver<T, E, RE, C> {
+    receiver: Option<Receiver<T, E>>,
+    checkpoint: Option<C>,
+    checkpoint_fn: CheckpointFn<T, C>,
+    resume_fn: ResumeFn<T, E, RE, C>,
+    retry_classifiers: Vec<SharedRetryClassifier>,
+    retry_config: RetryConfig,
+    sleep_impl: SharedAsyncSleep,
+}
+
+impl<T, E, RE, C> fmt::Debug for ResumableReceiver<T, E, RE, C>
+where
+    C: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumableReceiver")
+            .field(
+                "receiver",
+                &self.receiver.as_ref().map(|_| "** receiver **"),
+            )
+            .field("checkpoint", &self.checkpoint)
+            .field("retry_classifiers", &self.retry_classifiers)
+            .field("retry_config", &self.retry_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ResumableReceiver<(), (), (), ()> {
+    /// Returns a builder for a resumable receiver.
+    pub fn builder<T, E, RE, C>() -> ResumableReceiverBuilder<T, E, RE, C> {
+        ResumableReceiverBuilder::default()
+    }
+}
+
+impl<T, E, RE, C> ResumableReceiver<T, E, RE, C>
+where
+    E: StdError + Send + Sync + 'static,
+    RE: StdError + Send + Sync + 'static,
+    C: Clone,
+{
+    /// Returns the checkpoint that the stream would be resumed from.
+    pub fn checkpoint(&self) -> Option<&C> {
+        self.checkpoint.as_ref()
+    }
+
+    /// Receives the next event, resuming the stream if it fails with a retryable error.
+    ///
+    /// Returns `Ok(None)` once the stream has ended. After an error is returned, the stream has
+    /// ended and every following call returns `Ok(None)`.
+    pub async fn recv(&mut self) -> Result<Option<T>, ResumeError<E, RE>> {
+        loop {
+            let Some(receiver) = self.receiver.as_mut() else {
+                return Ok(None);
+            };
+            let err = match receiver.recv().await {
+                Ok(Some(event)) => {
+                    if let Some(checkpoint) = (self.checkpoint_fn)(&event) {
+                        self.checkpoint = Some(checkpoint);
+                    }
+                    return Ok(Some(event));
+                }
+                Ok(None) => {
+                    self.receiver = None;
+                    return Ok(None);
+                }
+                Err(err) => err,
+            };
+            self.receiver = None;
+
+            let (action, err) = classify(&self.retry_classifiers, err, None);
+            if !action.should_retry() || self.retry_config.max_attempts() <= 1 {
+                debug!(action = %action, "event stream failed with an error that won't be resumed");
+                return Err(ResumeError::Stream(err));
+            }
+            debug!(action = %action, "event stream failed; attempting to resume it");
+            self.receiver = Some(self.resume(action).await.map_err(ResumeError::Resume)?);
+        }
+    }
+
+    /// Attempts to resume the stream, up to `max_attempts - 1` times.
+    async fn resume(
+        &self,
+        mut action: RetryAction,
+    ) -> Result<Receiver<T, E>, SdkError<RE, HttpResponse>> {
+        let max_resume_attempts = self.retry_config.max_attempts() - 1;
+        let mut attempt = 1;
+        loop {
+            let delay = backoff(&self.retry_config, &action, attempt);
+            debug!("resuming event stream (attempt #{attempt}) in {delay:?}");
+            self.sleep_impl.sleep(delay).await;
+            let err = match (self.resume_fn)(self.checkpoint.clone()).await {
+                Ok(receiver) => return Ok(receiver),
+                Err(err) => err,
+            };
+            let response = err.raw_response().map(|response| {
+                let mut classified = HttpResponse::new(response.status(), SdkBody::empty());
+                *classified.headers_mut() = response.headers().clone();
+                classified
+            });
+            let (next_action, err) = classify(&self.retry_classifiers, err, response);
+            if !next_action.should_retry() || attempt >= max_resume_attempts {
+                debug!(action = %next_action, "giving up on resuming the event stream after {attempt} attempt(s)");
+                return Err(err);
+            }
+            action = next_action;
+            attempt += 1;
+        }
+    }
+}
+
+/// Calculates the delay before a resume attempt the way the standard retry strategy does.
+fn backoff(retry_config: &RetryConfig, action: &RetryAction, attempt: u32) -> Duration {
+    if let RetryAction::RetryIndicated(RetryReason::RetryableError {
+        retry_after: Some(delay),
+        ..
+    }) = action
+    {
+        return (*delay).min(retry_config.max_backoff());
+    }
+    let base = if retry_config.use_static_exponential_base() {
+        1.0
+    } else {
+        fastrand::f64()
+    };
+    calculate_exponential_backoff(
+        base,
+        retry_config.initial_backoff().as_secs_f64(),
+        attempt - 1,
+        retry_config.max_backoff(),
+    )
+}
+
+/// Runs the retry classifiers on an error, returning the error unchanged along with the action.
+///
+/// Classifiers inspect an [`InterceptorContext`], so one is populated with a stand-in for the
+/// error. Modeled errors are moved into the context and back out so that
+/// [`ModeledAsRetryableClassifier`](crate::client::retries::classifiers::ModeledAsRetryableClassifier)
+/// can downcast them.
+fn classify<E, R>(
+    classifiers: &[SharedRetryClassifier],
+    err: SdkError<E, R>,
+    response: Option<HttpResponse>,
+) -> (RetryAction, SdkError<E, R>)
+where
+    E: StdError + Send + Sync + 'static,
+{
+    let mut ctx = InterceptorContext::new(Input::doesnt_matter());
+    if let Some(response) = response {
+        ctx.set_response(response);
+    }
+    let stand_in = match &err {
+        SdkError::ConstructionFailure(_) => Some(OrchestratorError::other(err.to_string())),
+        SdkError::TimeoutError(_) => Some(OrchestratorError::timeout(err.to_string().into())),
+        SdkError::DispatchFailure(failure) => failure.as_connector_error().map(|connector_error| {
+            OrchestratorError::connector(connector_error_like(connector_error))
+        }),
+        SdkError::ResponseError(_) => Some(OrchestratorError::response(err.to_string().into())),
+        _ => None,
+    };
+    if let Some(stand_in) = stand_in {
+        ctx.set_output_or_error(Err(stand_in));
+        return (
+            run_classifiers_on_ctx(classifiers.iter().cloned(), &ctx),
+            err,
+        );
+    }
+
+    let mut action = RetryAction::NoActionIndicated;
+    let err = err.map_service_error(|service_error| {
+        ctx.set_output_or_error(Err(OrchestratorError::operation(Error::erase(
+            service_error,
+        ))));
+        action = run_classifiers_on_ctx(classifiers.iter().cloned(), &ctx);
+        let mut erased = None;
+        if let Some(Err(err)) = ctx.take_output_or_error() {
+            err.map_operation_error(|err| erased = Some(err));
+        }
+        erased
+            .expect("set above")
+            .downcast::<E>()
+            .expect("erased from an E above")
+    });
+    (action, err)
+}
+
+/// Creates a connector error of the same kind as `err`, for classification.
+fn connector_error_like(err: &ConnectorError) -> ConnectorError {
+    let source = err.to_string().into();
+    if err.is_timeout() {
+        ConnectorError::timeout(source)
+    } else if err.is_io() {
+        ConnectorError::io(source)
+    } else if err.is_user() {
+        ConnectorError::user(source)
+    } else {
+        ConnectorError::other(source, err.as_other())
+    }
+}
+
+/// Builder for [`ResumableReceiver`].
+pub struct ResumableReceiverBuilder<T, E, RE, C> {
+    receiver: Option<Receiver<T, E>>,
+    checkpoint_fn: Option<CheckpointFn<T, C>>,
+    resume_fn: Option<ResumeFn<T, E, RE, C>>,
+    retry_classifiers: Vec<SharedRetryClassifier>,
+    retry_config: Option<RetryConfig>,
+    sleep_impl: Option<SharedAsyncSleep>,
+}
+
+impl<T, E, RE, C> Default for ResumableReceiverBuilder<T, E, RE, C> {
+    fn default() -> Self {
+        Self {
+            receiver: None,
+            checkpoint_fn: None,
+            resume_fn: None,
+            retry_classifiers: Vec::new(),
+            retry_config: None,
+            sleep_impl: None,
+        }
+    }
+}
+
+impl<T, E, RE, C> fmt::Debug for ResumableReceiverBuilder<T, E, RE, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumableReceiverBuilder")
+            .field("retry_classifiers", &self.retry_classifiers)
+            .field("retry_config", &self.retry_config)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T, E, RE, C> ResumableReceiverBuilder<T, E, RE, C>
+where
+    E: StdError + Send + Sync + 'static,
+{
+    /// Set the receiver of the event stream that has already been established.
+    pub fn receiver(mut self, receiver: Receiver<T, E>) -> Self {
+        self.receiver = Some(receiver);
+        self
+    }
+
+    /// Set the function that extracts a checkpoint, such as a continuation token, from an event.
+    ///
+    /// The most recent checkpoint is passed to the [resume function](Self::resume). Events that
+    /// return `None` leave the previous checkpoint in place.
+    pub fn checkpoint(
+        mut self,
+        checkpoint: impl Fn(&T) -> Option<C> + Send + Sync + 'static,
+    ) -> Self {
+        self.checkpoint_fn = Some(Box::new(checkpoint));
+        self
+    }
+
+    /// Set the function that re-establishes the event stream, given the most recent checkpoint.
+    ///
+    /// This is typically a call to the operation that started the stream.
+    pub fn resume<F>(mut self, resume: impl Fn(Option<C>) -> F + Send + Sync + 'static) -> Self
+    where
+        F: Future<Output = Result<Receiver<T, E>, SdkError<RE, HttpResponse>>> + Send + 'static,
+    {
+        self.resume_fn = Some(Box::new(move |checkpoint| Box::pin(resume(checkpoint))));
+        self
+    }
+
+    /// Add a retry classifier that decides which errors the stream is resumed after.
+    ///
+    /// If no classifiers are added, the stream is resumed after transient errors, as classified by
+    /// [`TransientErrorClassifier`].
+    pub fn retry_classifier(mut self, retry_classifier: impl ClassifyRetry + 'static) -> Self {
+        self.retry_classifiers
+            .push(SharedRetryClassifier::new(retry_classifier));
+        self
+    }
+
+    /// Set the retry config that limits the number of resume attempts and their backoff.
+    ///
+    /// Each failure of the stream allows up to `max_attempts - 1` attempts to resume it. Defaults
+    /// to [`RetryConfig::standard`].
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = Some(retry_config);
+        self
+    }
+
+    /// Set the async sleep implementation used to back off between resume attempts.
+    pub fn sleep_impl(mut self, sleep_impl: SharedAsyncSleep) -> Self {
+        self.sleep_impl = Some(sleep_impl);
+        self
+    }
+
+    /// Build a resumable receiver.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the receiver, resume function or sleep implementation weren't set.
+    pub fn build(self) -> ResumableReceiver<T, E, RE, C> {
+        let mut retry_classifiers = self.retry_classifiers;
+        if retry_classifiers.is_empty() {
+            retry_classifiers.push(SharedRetryClassifier::new(
+                TransientErrorClassifier::<E>::new(),
+            ));
+        }
+        retry_classifiers.sort_by_key(|classifier| classifier.priority());
+        ResumableReceiver {
+            receiver: Some(self.receiver.expect("receiver required")),
+            checkpoint: None,
+            checkpoint_fn: self.checkpoint_fn.unwrap_or_else(|| Box::new(|_| None)),
+            resume_fn: self.resume_fn.expect("resume fn required"),
+            retry_classifiers,
+            retry_config: self.retry_config.unwrap_or_else(RetryConfig::standard),
+            sleep_impl: self.sleep_impl.expect("sleep impl required"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ResumableReceiver, ResumeError};
+    use crate::client::retries::classifiers::{HttpStatusCodeClassifier, TransientErrorClassifier};
+    use aws_smithy_async::rt::sleep::SharedAsyncSleep;
+    use aws_smithy_async::test_util::InstantSleep;
+    use aws_smithy_eventstream::error::Error as EventStreamError;
+    use aws_smithy_eventstream::frame::{write_message_to, UnmarshallMessage, UnmarshalledMessage};
+    use aws_smithy_http::event_stream::Receiver;
+    use aws_smithy_runtime_api::client::interceptors::context::InterceptorContext;
+    use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+    use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
+    use aws_smithy_runtime_api::client::retries::classifiers::{ClassifyRetry, RetryAction};
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::event_stream::Message;
+    use aws_smithy_types::retry::{ErrorKind, RetryConfig};
+    use bytes::Bytes;
+    use std::collections::VecDeque;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct TestError;
+
+    impl std::fmt::Display for TestError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "test error")
+        }
+    }
+
+    impl std::error::Error for TestError {}
+
+    #[derive(Debug)]
+    struct Unmarshaller;
+
+    impl UnmarshallMessage for Unmarshaller {
+        type Output = String;
+        type Error = TestError;
+
+        fn unmarshall(
+            &self,
+            message: &Message,
+        ) -> Result<UnmarshalledMessage<Self::Output, Self::Error>, EventStreamError> {
+            match std::str::from_utf8(message.payload()).unwrap() {
+                "error" => Ok(UnmarshalledMessage::Error(TestError)),
+                payload => Ok(UnmarshalledMessage::Event(payload.to_string())),
+            }
+        }
+    }
+
+    /// A body that yields the given chunks, and then fails with an IO error if `fail` is set.
+    struct TestBody {
+        chunks: VecDeque<Bytes>,
+        fail: bool,
+    }
+
+    impl http_body_04x::Body for TestBody {
+        type Data = Bytes;
+        type Error = ConnectorError;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+            match self.chunks.pop_front() {
+                Some(chunk) => Poll::Ready(Some(Ok(chunk))),
+                None if self.fail => {
+                    self.fail = false;
+                    Poll::Ready(Some(Err(ConnectorError::io("connection reset".into()))))
+                }
+                None => Poll::Ready(None),
+            }
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http_02x::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn receiver(events: &[&str], fail: bool) -> Receiver<String, TestError> {
+        let chunks = events
+            .iter()
+            .map(|event| {
+                let mut buffer = Vec::new();
+                write_message_to(&Message::new(event.as_bytes().to_vec()), &mut buffer).unwrap();
+                Bytes::from(buffer)
+            })
+            .collect();
+        Receiver::new(
+            Unmarshaller,
+            SdkBody::from_body_0_4(TestBody { chunks, fail }),
+        )
+    }
+
+    type ResumeResult = Result<Receiver<String, TestError>, SdkError<TestError, HttpResponse>>;
+
+    type Calls = Arc<Mutex<Vec<Option<String>>>>;
+
+    /// Returns a resume function that returns the given results in order, and records the
+    /// checkpoints it was called with in `calls`.
+    fn resume_with(
+        calls: &Calls,
+        results: Vec<ResumeResult>,
+    ) -> impl Fn(Option<String>) -> std::future::Ready<ResumeResult> + Send + Sync + 'static {
+        let calls = calls.clone();
+        let results = Mutex::new(VecDeque::from(results));
+        move |checkpoint| {
+            calls.lock().unwrap().push(checkpoint);
+            std::future::ready(
+                results
+                    .lock()
+                    .unwrap()
+                    .pop_front()
+                    .expect("unexpected resume"),
+            )
+        }
+    }
+
+    async fn collect(
+        receiver: &mut ResumableReceiver<String, TestError, TestError, String>,
+    ) -> (Vec<String>, Option<ResumeError<TestError, TestError>>) {
+        let mut events = Vec::new();
+        loop {
+            match receiver.recv().await {
+                Ok(Some(event)) => events.push(event),
+                Ok(None) => return (events, None),
+                Err(err) => return (events, Some(err)),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn resumes_from_the_last_checkpoint() {
+        let calls = Calls::default();
+        let resume = resume_with(&calls, vec![Ok(receiver(&["c", "token:2", "d"], false))]);
+        let sleep = InstantSleep::unlogged();
+        let mut receiver = ResumableReceiver::builder()
+            .receiver(receiver(&["a", "token:1", "b"], true))
+            .checkpoint(|event: &String| event.strip_prefix("token:").map(str::to_string))
+            .resume(resume)
+            .retry_config(RetryConfig::standard().with_use_static_exponential_base(true))
+            .sleep_impl(SharedAsyncSleep::new(sleep.clone()))
+            .build();
+
+        let (events, err) = collect(&mut receiver).await;
+        assert!(err.is_none(), "{err:?}");
+        assert_eq!(vec!["a", "token:1", "b", "c", "token:2", "d"], events);
+        assert_eq!(vec![Some("1".to_string())], *calls.lock().unwrap());
+        assert_eq!(Some(&"2".to_string()), receiver.checkpoint());
+        assert_eq!(vec![Duration::from_secs(1)], sleep.logs());
+    }
+
+    #[tokio::test]
+    async fn modeled_errors_are_not_resumed_by_default() {
+        let calls = Calls::default();
+        let resume = resume_with(&calls, vec![]);
+        let mut receiver = ResumableReceiver::builder()
+            .receiver(receiver(&["a", "error"], false))
+            .checkpoint(|event: &String| event.strip_prefix("token:").map(str::to_string))
+            .resume(resume)
+            .sleep_impl(SharedAsyncSleep::new(InstantSleep::unlogged()))
+            .build();
+
+        let (events, err) = collect(&mut receiver).await;
+        assert_eq!(vec!["a"], events);
+        match err {
+            Some(ResumeError::Stream(SdkError::ServiceError(_))) => {}
+            other => panic!("expected the modeled error, got {other:?}"),
+        }
+        assert!(calls.lock().unwrap().is_empty());
+        assert!(receiver.recv().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let unavailable = || {
+            SdkError::service_error(
+                TestError,
+                HttpResponse::new(503u16.try_into().unwrap(), SdkBody::empty()),
+            )
+        };
+        let calls = Calls::default();
+        let resume = resume_with(&calls, vec![Err(unavailable()), Err(unavailable())]);
+        let sleep = InstantSleep::unlogged();
+        let mut receiver = ResumableReceiver::builder()
+            .receiver(receiver(&["a"], true))
+            .resume(resume)
+            .retry_classifier(TransientErrorClassifier::<TestError>::new())
+            .retry_classifier(HttpStatusCodeClassifier::default())
+            .retry_config(
+                RetryConfig::standard()
+                    .with_max_attempts(3)
+                    .with_use_static_exponential_base(true),
+            )
+            .sleep_impl(SharedAsyncSleep::new(sleep.clone()))
+            .build();
+
+        let (events, err) = collect(&mut receiver).await;
+        assert_eq!(vec!["a"], events);
+        match err {
+            Some(ResumeError::Resume(SdkError::ServiceError(_))) => {}
+            other => panic!("expected the resume error, got {other:?}"),
+        }
+        assert_eq!(vec![None, None], *calls.lock().unwrap());
+        assert_eq!(
+            vec![Duration::from_secs(1), Duration::from_secs(2)],
+            sleep.logs()
+        );
+    }
+
+    /// Retries responses with a `retry-after` header, after the number of seconds it holds.
+    #[derive(Debug)]
+    struct RetryAfterClassifier;
+
+    impl ClassifyRetry for RetryAfterClassifier {
+        fn classify_retry(&self, ctx: &InterceptorContext) -> RetryAction {
+            ctx.response()
+                .and_then(|response| response.headers().get("retry-after"))
+                .and_then(|retry_after| retry_after.parse().ok())
+                .map(|secs| {
+                    RetryAction::retryable_error_with_explicit_delay(
+                        ErrorKind::ThrottlingError,
+                        Duration::from_secs(secs),
+                    )
+                })
+                .unwrap_or(RetryAction::NoActionIndicated)
+        }
+
+        fn name(&self) -> &'static str {
+            "RetryAfter"
+        }
+    }
+
+    #[tokio::test]
+    async fn resume_errors_are_classified_with_their_headers() {
+        let mut throttled = HttpResponse::new(429u16.try_into().unwrap(), SdkBody::empty());
+        throttled.headers_mut().insert("retry-after", "5");
+        let calls = Calls::default();
+        let resume = resume_with(
+            &calls,
+            vec![
+                Err(SdkError::service_error(TestError, throttled)),
+                Ok(receiver(&["b"], false)),
+            ],
+        );
+        let sleep = InstantSleep::unlogged();
+        let mut receiver = ResumableReceiver::builder()
+            .receiver(receiver(&["a"], true))
+            .resume(resume)
+            .retry_classifier(TransientErrorClassifier::<TestError>::new())
+            .retry_classifier(RetryAfterClassifier)
+            .retry_config(RetryConfig::standard().with_use_static_exponential_base(true))
+            .sleep_impl(SharedAsyncSleep::new(sleep.clone()))
+            .build();
+
+        let (events, err) = collect(&mut receiver).await;
+        assert!(err.is_none(), "{err:?}");
+        assert_eq!(vec!["a", "b"], events);
+        assert_eq!(vec![None, None], *calls.lock().unwrap());
+        assert_eq!(
+            vec![Duration::from_secs(1), Duration::from_secs(5)],
+            sleep.logs()
+        );
+    }
+}
@@ -0,0 +1,296 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A body-wrapping type that caps the rate at which data is streamed.
+//!
+//! A [`BandwidthLimiter`] is a token bucket holding bytes. Every chunk of data taken from a
+//! limited body is paid for with tokens; once the bucket runs dry, the next chunk is held back
+//! until enough tokens have been refilled. Cloning a limiter shares its bucket, so one limiter
+//! can cap the combined rate of many uploads and downloads.
+
+use crate::client::http::body::minimum_throughput::Throughput;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use bytes::Bytes;
+use http_body_1x::{Body, Frame, SizeHint};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, SystemTime};
+
+/// A token bucket that limits the bandwidth of the bodies it wraps.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_async::rt::sleep::TokioSleep;
+/// use aws_smithy_async::time::SystemTimeSource;
+/// use aws_smithy_runtime::client::http::body::bandwidth_limit::BandwidthLimiter;
+/// use aws_smithy_runtime::client::http::body::minimum_throughput::Throughput;
+/// use aws_smithy_types::byte_stream::ByteStream;
+///
+/// # async fn example(upload: ByteStream, download: ByteStream) {
+/// // Cap both streams to 1 MB/s combined
+/// let limiter = BandwidthLimiter::new(
+///     Throughput::new_megabytes_per_second(1),
+///     SystemTimeSource::new(),
+///     TokioSleep::new(),
+/// );
+/// let upload = limiter.wrap(upload);
+/// let download = limiter.wrap(download);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct BandwidthLimiter {
+    bucket: Arc<Mutex<TokenBucket>>,
+    time_source: SharedTimeSource,
+    async_sleep: SharedAsyncSleep,
+}
+
+impl BandwidthLimiter {
+    /// Creates a limiter that allows `limit` on average, with bursts of up to one second's
+    /// worth of data.
+    pub fn new(
+        limit: Throughput,
+        time_source: impl TimeSource + 'static,
+        async_sleep: impl AsyncSleep + 'static,
+    ) -> Self {
+        let bytes_per_second = limit.bytes_per_second();
+        assert!(
+            bytes_per_second > 0.0,
+            "bandwidth limit must be greater than zero"
+        );
+        Self {
+            bucket: Arc::new(Mutex::new(TokenBucket {
+                bytes_per_second,
+                capacity: bytes_per_second,
+                tokens: bytes_per_second,
+                last_refill: None,
+            })),
+            time_source: time_source.into_shared(),
+            async_sleep: async_sleep.into_shared(),
+        }
+    }
+
+    /// Sets how many bytes may be streamed at once before the limit kicks in.
+    pub fn with_burst(self, bytes: u64) -> Self {
+        {
+            let mut bucket = self.bucket.lock().unwrap();
+            bucket.capacity = bytes as f64;
+            bucket.tokens = bucket.tokens.min(bucket.capacity);
+        }
+        self
+    }
+
+    /// Wraps a [`ByteStream`] so that it is streamed no faster than this limiter allows.
+    ///
+    /// The returned stream has the same size hint and is retryable if `stream` was.
+    pub fn wrap(&self, stream: ByteStream) -> ByteStream {
+        ByteStream::new(self.wrap_body(stream.into_inner()))
+    }
+
+    /// Wraps an [`SdkBody`] so that it is streamed no faster than this limiter allows.
+    ///
+    /// The returned body has the same size hint and is retryable if `body` was.
+    pub fn wrap_body(&self, body: SdkBody) -> SdkBody {
+        let limiter = self.clone();
+        body.map_preserve_contents(move |body| {
+            SdkBody::from_body_1_x(BandwidthLimitedBody {
+                limiter: limiter.clone(),
+                inner: body,
+                held: None,
+                sleep: None,
+            })
+        })
+    }
+
+    /// Takes `bytes` tokens and returns how long to wait before the bytes may be released.
+    fn take(&self, bytes: u64) -> Duration {
+        self.bucket
+            .lock()
+            .unwrap()
+            .take(bytes, self.time_source.now())
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    bytes_per_second: f64,
+    capacity: f64,
+    // May go negative when a chunk is larger than the available tokens; the debt is paid off
+    // by waiting before the chunk is released.
+    tokens: f64,
+    last_refill: Option<SystemTime>,
+}
+
+impl TokenBucket {
+    fn take(&mut self, bytes: u64, now: SystemTime) -> Duration {
+        if let Some(last_refill) = self.last_refill {
+            let elapsed = now.duration_since(last_refill).unwrap_or_default();
+            self.tokens =
+                (self.tokens + elapsed.as_secs_f64() * self.bytes_per_second).min(self.capacity);
+        }
+        self.last_refill = Some(now);
+        self.tokens -= bytes as f64;
+        if self.tokens >= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(-self.tokens / self.bytes_per_second)
+        }
+    }
+}
+
+struct BandwidthLimitedBody {
+    limiter: BandwidthLimiter,
+    inner: SdkBody,
+    // A frame that has been paid for but is held back until `sleep` completes
+    held: Option<Frame<Bytes>>,
+    sleep: Option<Sleep>,
+}
+
+impl Body for BandwidthLimitedBody {
+    type Data = Bytes;
+    type Error = aws_smithy_types::body::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if let Some(sleep) = this.sleep.as_mut() {
+            ready!(Pin::new(sleep).poll(cx));
+            this.sleep = None;
+            if let Some(frame) = this.held.take() {
+                return Poll::Ready(Some(Ok(frame)));
+            }
+        }
+
+        let frame = match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+            Some(Ok(frame)) => frame,
+            other => return Poll::Ready(other),
+        };
+        let length = frame.data_ref().map(|data| data.len() as u64).unwrap_or(0);
+        let delay = this.limiter.take(length);
+        if delay.is_zero() {
+            return Poll::Ready(Some(Ok(frame)));
+        }
+        let mut sleep = this.limiter.async_sleep.sleep(delay);
+        if Pin::new(&mut sleep).poll(cx).is_ready() {
+            return Poll::Ready(Some(Ok(frame)));
+        }
+        this.held = Some(frame);
+        this.sleep = Some(sleep);
+        Poll::Pending
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.held.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let held = self
+            .held
+            .as_ref()
+            .and_then(Frame::data_ref)
+            .map(|data| data.len() as u64)
+            .unwrap_or(0);
+        let inner = self.inner.size_hint();
+        let mut hint = SizeHint::new();
+        hint.set_lower(inner.lower() + held);
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + held);
+        }
+        hint
+    }
+}
+
+#[cfg(all(feature = "test-util", test))]
+mod test {
+    use super::BandwidthLimiter;
+    use crate::client::http::body::minimum_throughput::Throughput;
+    use aws_smithy_async::test_util::instant_time_and_sleep;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use bytes::Bytes;
+    use http_body_1x::Frame;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    /// Body for tests that yields `count` chunks of `size` bytes
+    struct ChunkedBody {
+        count: usize,
+        size: usize,
+    }
+
+    impl http_body_1x::Body for ChunkedBody {
+        type Data = Bytes;
+        type Error = aws_smithy_types::body::Error;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            if self.count == 0 {
+                return Poll::Ready(None);
+            }
+            self.count -= 1;
+            Poll::Ready(Some(Ok(Frame::data(Bytes::from(vec![b'x'; self.size])))))
+        }
+    }
+
+    fn chunks(count: usize, size: usize) -> ByteStream {
+        ByteStream::new(SdkBody::from_body_1_x(ChunkedBody { count, size }))
+    }
+
+    #[tokio::test]
+    async fn limits_bandwidth() {
+        let (time_source, sleep) = instant_time_and_sleep(UNIX_EPOCH);
+        let limiter = BandwidthLimiter::new(
+            Throughput::new_bytes_per_second(100),
+            time_source,
+            sleep.clone(),
+        );
+
+        // The first 100 bytes fit in the initial burst; the other 400 take 4 seconds
+        let data = limiter.wrap(chunks(10, 50)).collect().await.unwrap();
+        assert_eq!(500, data.into_bytes().len());
+        assert_eq!(Duration::from_secs(4), sleep.total_duration());
+    }
+
+    #[tokio::test]
+    async fn shared_limiters_cap_the_combined_rate() {
+        let (time_source, sleep) = instant_time_and_sleep(UNIX_EPOCH);
+        let limiter = BandwidthLimiter::new(
+            Throughput::new_bytes_per_second(100),
+            time_source,
+            sleep.clone(),
+        )
+        .with_burst(0);
+
+        let first = limiter.wrap(chunks(2, 50));
+        let second = limiter.clone().wrap(chunks(2, 50));
+        first.collect().await.unwrap();
+        second.collect().await.unwrap();
+        assert_eq!(Duration::from_secs(2), sleep.total_duration());
+    }
+
+    #[test]
+    fn preserves_size_hint_and_retryability() {
+        let (time_source, sleep) = instant_time_and_sleep(UNIX_EPOCH);
+        let limiter =
+            BandwidthLimiter::new(Throughput::new_bytes_per_second(100), time_source, sleep);
+
+        let body = limiter.wrap_body(SdkBody::from("hello world"));
+        assert_eq!(Some(11), body.content_length());
+        assert_eq!(Some(b"hello world".as_slice()), body.bytes());
+        let clone = body.try_clone().expect("retryable");
+        assert_eq!(Some(11), clone.content_length());
+    }
+}
@@ -52,7 +52,7 @@ impl Throughput {
         }
     }
 
-    pub(super) fn bytes_per_second(&self) -> f64 {
+    pub(crate) fn bytes_per_second(&self) -> f64 {
         let per_time_elapsed_secs = self.per_time_elapsed.as_secs_f64();
         if per_time_elapsed_secs == 0.0 {
             return 0.0; // Avoid dividing by zero.
@@ -0,0 +1,334 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A body-wrapping type that reports how much of a body has been streamed.
+//!
+//! A [`ProgressObserver`] calls back with a [`Progress`] snapshot as data flows through the
+//! bodies it wraps, e.g. to drive a progress bar for an upload or a download.
+
+use crate::client::http::body::minimum_throughput::Throughput;
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use bytes::Bytes;
+use http_body_1x::{Body, Frame, SizeHint};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, SystemTime};
+
+/// A snapshot of how far a body has been streamed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Progress {
+    bytes_transferred: u64,
+    total_bytes: Option<u64>,
+    elapsed: Duration,
+    complete: bool,
+}
+
+impl Progress {
+    /// Returns the number of bytes streamed so far.
+    pub fn bytes_transferred(&self) -> u64 {
+        self.bytes_transferred
+    }
+
+    /// Returns the total size of the body, if it is known.
+    pub fn total_bytes(&self) -> Option<u64> {
+        self.total_bytes
+    }
+
+    /// Returns the time since the first byte was requested.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns true once the body has been streamed to the end.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    /// Returns the average throughput so far, or `None` if no time has passed yet.
+    pub fn throughput(&self) -> Option<Throughput> {
+        (!self.elapsed.is_zero()).then(|| Throughput::new(self.bytes_transferred, self.elapsed))
+    }
+
+    /// Returns the estimated time until the body has been streamed, based on the average
+    /// throughput so far.
+    ///
+    /// Returns `None` if the total size isn't known or nothing has been streamed yet.
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.total_bytes?.saturating_sub(self.bytes_transferred);
+        if remaining == 0 {
+            return Some(Duration::ZERO);
+        }
+        let bytes_per_second = self.throughput()?.bytes_per_second();
+        (bytes_per_second > 0.0)
+            .then(|| Duration::from_secs_f64(remaining as f64 / bytes_per_second))
+    }
+}
+
+type ProgressCallback = Arc<dyn Fn(&Progress) + Send + Sync>;
+
+/// Reports the [`Progress`] of the bodies it wraps to a callback.
+///
+/// Retrying a request restarts its body, and with it the reported progress.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_async::time::SystemTimeSource;
+/// use aws_smithy_runtime::client::http::body::progress::ProgressObserver;
+/// use aws_smithy_types::byte_stream::ByteStream;
+/// use std::time::Duration;
+///
+/// # fn example(download: ByteStream) {
+/// let observer = ProgressObserver::new(SystemTimeSource::new(), |progress| {
+///     println!(
+///         "{} of {:?} bytes, eta {:?}",
+///         progress.bytes_transferred(),
+///         progress.total_bytes(),
+///         progress.eta()
+///     );
+/// })
+/// .with_interval(Duration::from_millis(250));
+/// let download = observer.wrap(download);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProgressObserver {
+    callback: ProgressCallback,
+    time_source: SharedTimeSource,
+    interval: Duration,
+}
+
+impl fmt::Debug for ProgressObserver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressObserver")
+            .field("time_source", &self.time_source)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl ProgressObserver {
+    /// Creates an observer that calls `callback` every time data is streamed.
+    pub fn new(
+        time_source: impl TimeSource + 'static,
+        callback: impl Fn(&Progress) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            callback: Arc::new(callback),
+            time_source: time_source.into_shared(),
+            interval: Duration::ZERO,
+        }
+    }
+
+    /// Sets the minimum time between two callbacks for the same body.
+    ///
+    /// The callback is always called once the body has been streamed to the end.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Wraps a [`ByteStream`] so that its progress is reported to this observer.
+    ///
+    /// The returned stream has the same size hint and is retryable if `stream` was.
+    pub fn wrap(&self, stream: ByteStream) -> ByteStream {
+        ByteStream::new(self.wrap_body(stream.into_inner()))
+    }
+
+    /// Wraps an [`SdkBody`] so that its progress is reported to this observer.
+    ///
+    /// The returned body has the same size hint and is retryable if `body` was.
+    pub fn wrap_body(&self, body: SdkBody) -> SdkBody {
+        let observer = self.clone();
+        body.map_preserve_contents(move |body| {
+            let hint = body.size_hint();
+            SdkBody::from_body_1_x(ProgressBody {
+                observer: observer.clone(),
+                total_bytes: hint.exact().or(hint.upper()),
+                inner: body,
+                bytes_transferred: 0,
+                started: None,
+                last_report: None,
+                complete: false,
+            })
+        })
+    }
+}
+
+struct ProgressBody {
+    observer: ProgressObserver,
+    inner: SdkBody,
+    bytes_transferred: u64,
+    total_bytes: Option<u64>,
+    started: Option<SystemTime>,
+    last_report: Option<SystemTime>,
+    complete: bool,
+}
+
+impl ProgressBody {
+    fn report(&mut self, complete: bool) {
+        let now = self.observer.time_source.now();
+        if !complete {
+            if let Some(last_report) = self.last_report {
+                let since_last = now.duration_since(last_report).unwrap_or_default();
+                if since_last < self.observer.interval {
+                    return;
+                }
+            }
+        }
+        self.last_report = Some(now);
+        self.complete = complete;
+        let started = self.started.unwrap_or(now);
+        (self.observer.callback)(&Progress {
+            bytes_transferred: self.bytes_transferred,
+            total_bytes: self.total_bytes,
+            elapsed: now.duration_since(started).unwrap_or_default(),
+            complete,
+        });
+    }
+}
+
+impl Body for ProgressBody {
+    type Data = Bytes;
+    type Error = aws_smithy_types::body::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if this.started.is_none() {
+            this.started = Some(this.observer.time_source.now());
+        }
+        let result = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        match &result {
+            Some(Ok(frame)) => {
+                if let Some(data) = frame.data_ref() {
+                    this.bytes_transferred += data.len() as u64;
+                    let complete = this.inner.is_end_stream();
+                    this.report(complete);
+                }
+            }
+            None if !this.complete => this.report(true),
+            _ => {}
+        }
+        Poll::Ready(result)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(all(feature = "test-util", test))]
+mod test {
+    use super::{Progress, ProgressObserver};
+    use aws_smithy_async::test_util::ManualTimeSource;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use bytes::Bytes;
+    use http_body_1x::Frame;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+    use std::time::{Duration, UNIX_EPOCH};
+
+    /// Body for tests that advances time by one second before yielding each chunk
+    struct SlowBody {
+        chunks: Vec<&'static str>,
+        time_source: ManualTimeSource,
+    }
+
+    impl http_body_1x::Body for SlowBody {
+        type Data = Bytes;
+        type Error = aws_smithy_types::body::Error;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            if self.chunks.is_empty() {
+                return Poll::Ready(None);
+            }
+            self.time_source.advance(Duration::from_secs(1));
+            let chunk = self.chunks.remove(0);
+            Poll::Ready(Some(Ok(Frame::data(Bytes::from_static(chunk.as_bytes())))))
+        }
+
+        fn size_hint(&self) -> http_body_1x::SizeHint {
+            http_body_1x::SizeHint::with_exact(self.chunks.iter().map(|c| c.len() as u64).sum())
+        }
+    }
+
+    fn observe(interval: Duration) -> (ByteStream, Arc<Mutex<Vec<Progress>>>) {
+        let time_source = ManualTimeSource::new(UNIX_EPOCH);
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let observer = ProgressObserver::new(time_source.clone(), {
+            let reports = reports.clone();
+            move |progress| reports.lock().unwrap().push(progress.clone())
+        })
+        .with_interval(interval);
+        let body = SdkBody::from_body_1_x(SlowBody {
+            chunks: vec!["abcd", "efgh", "ij"],
+            time_source,
+        });
+        (observer.wrap(ByteStream::new(body)), reports)
+    }
+
+    #[tokio::test]
+    async fn reports_bytes_throughput_and_eta() {
+        let (stream, reports) = observe(Duration::ZERO);
+        assert_eq!((10, Some(10)), stream.size_hint());
+        stream.collect().await.unwrap();
+
+        let reports = reports.lock().unwrap();
+        let transferred: Vec<_> = reports.iter().map(Progress::bytes_transferred).collect();
+        assert_eq!(vec![4, 8, 10, 10], transferred);
+        assert!(reports.iter().all(|p| p.total_bytes() == Some(10)));
+        assert_eq!(Some(Duration::from_millis(1500)), reports[0].eta());
+        assert_eq!("4 B/s", reports[0].throughput().unwrap().to_string());
+        assert_eq!(
+            vec![false, false, false, true],
+            reports
+                .iter()
+                .map(Progress::is_complete)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(Some(Duration::ZERO), reports[3].eta());
+    }
+
+    #[tokio::test]
+    async fn respects_the_interval_but_always_reports_completion() {
+        let (stream, reports) = observe(Duration::from_secs(2));
+        stream.collect().await.unwrap();
+
+        let transferred: Vec<_> = reports
+            .lock()
+            .unwrap()
+            .iter()
+            .map(Progress::bytes_transferred)
+            .collect();
+        assert_eq!(vec![4, 10, 10], transferred);
+    }
+
+    #[test]
+    fn preserves_size_hint_and_retryability() {
+        let observer = ProgressObserver::new(ManualTimeSource::new(UNIX_EPOCH), |_| {});
+        let body = observer.wrap_body(SdkBody::from("hello world"));
+        assert_eq!(Some(11), body.content_length());
+        assert_eq!(Some(b"hello world".as_slice()), body.bytes());
+        let clone = body.try_clone().expect("retryable");
+        assert_eq!(Some(11), clone.content_length());
+    }
+}
@@ -0,0 +1,801 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A download body that resumes from where it left off when the connection fails midway.
+//!
+//! [`ResumableDownload`] wraps the body of a download in a [`ByteStream`] that counts the bytes
+//! it has handed out. When reading the body fails, it calls a resume function provided by the
+//! caller to re-issue the download for the remaining bytes, checks that the resumed response is
+//! for the same object, and continues streaming from the resumed response as if nothing happened.
+//!
+//! Resuming is driven by the caller: the body never sends requests itself, and isn't tied to the
+//! operation that produced the initial response. The resume function should send the resumed
+//! request through the orchestrator like any other request, so that it is signed and retried,
+//! typically by invoking the same operation again with a [`ResumeRangeRuntimePlugin`], which adds
+//! the `Range` and conditional headers to the request before it is signed.
+
+use aws_smithy_async::future::BoxFuture;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::BeforeTransmitInterceptorContextMut;
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugin;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::byte_stream::ByteStream;
+use aws_smithy_types::config_bag::ConfigBag;
+use http_body_1x::{Frame, SizeHint};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+
+const DEFAULT_MAX_RESUMES: u32 = 3;
+
+type ResumeFn =
+    Arc<dyn Fn(ResumeRange) -> BoxFuture<'static, DownloadPart, BoxError> + Send + Sync>;
+type ResumeIfFn = Arc<dyn Fn(&BoxError) -> bool + Send + Sync>;
+
+/// One response of a download: its body and the validators identifying the downloaded object.
+#[derive(Debug)]
+pub struct DownloadPart {
+    body: ByteStream,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_range: Option<String>,
+}
+
+impl DownloadPart {
+    /// Creates a part with the given body and no validators.
+    pub fn new(body: ByteStream) -> Self {
+        Self {
+            body,
+            etag: None,
+            last_modified: None,
+            content_range: None,
+        }
+    }
+
+    /// Creates a part from the body and the `ETag`, `Last-Modified` and `Content-Range` headers
+    /// of an HTTP response.
+    pub fn from_response(mut response: HttpResponse) -> Self {
+        let header = |name: &str| response.headers().get(name).map(str::to_string);
+        let (etag, last_modified, content_range) = (
+            header("etag"),
+            header("last-modified"),
+            header("content-range"),
+        );
+        Self {
+            body: ByteStream::new(response.take_body()),
+            etag,
+            last_modified,
+            content_range,
+        }
+    }
+
+    /// Sets the `ETag` of the downloaded object.
+    pub fn with_etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+
+    /// Sets the `Last-Modified` date of the downloaded object, as sent by the server.
+    pub fn with_last_modified(mut self, last_modified: impl Into<String>) -> Self {
+        self.last_modified = Some(last_modified.into());
+        self
+    }
+
+    /// Sets the `Content-Range` of the response, e.g. `bytes 100-199/200`.
+    ///
+    /// When the initial part has a content range, the download covers only that range, and is
+    /// resumed within it. Resumed parts must have a content range starting at the requested offset.
+    pub fn with_content_range(mut self, content_range: impl Into<String>) -> Self {
+        self.content_range = Some(content_range.into());
+        self
+    }
+
+    /// Returns the `ETag` of the downloaded object, if known.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Returns the `Last-Modified` date of the downloaded object, if known.
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
+    /// Returns the `Content-Range` of the response, if any.
+    pub fn content_range(&self) -> Option<&str> {
+        self.content_range.as_deref()
+    }
+}
+
+/// The range of a download that still needs to be fetched, and the validators the resumed
+/// response must match.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ResumeRange {
+    offset: u64,
+    end: Option<u64>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl ResumeRange {
+    /// Creates a range starting at `offset` and running to the end of the object, with no validators.
+    pub fn new(offset: u64) -> Self {
+        Self {
+            offset,
+            end: None,
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    /// Sets the position of the last byte in the range, inclusive.
+    pub fn with_end(mut self, end: u64) -> Self {
+        self.end = Some(end);
+        self
+    }
+
+    /// Sets the `ETag` the resumed response must match.
+    pub fn with_etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+
+    /// Sets the `Last-Modified` date the resumed response must match.
+    pub fn with_last_modified(mut self, last_modified: impl Into<String>) -> Self {
+        self.last_modified = Some(last_modified.into());
+        self
+    }
+
+    /// Returns the offset of the first byte that still needs to be fetched.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the position of the last byte in the range, inclusive, or `None` if the range runs
+    /// to the end of the object.
+    pub fn end(&self) -> Option<u64> {
+        self.end
+    }
+
+    /// Returns the `ETag` the resumed response must match, if any.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Returns the `Last-Modified` date the resumed response must match, if any.
+    pub fn last_modified(&self) -> Option<&str> {
+        self.last_modified.as_deref()
+    }
+
+    /// Returns the value of the `Range` header requesting the remaining bytes.
+    pub fn range_header(&self) -> String {
+        match self.end {
+            Some(end) => format!("bytes={}-{end}", self.offset),
+            None => format!("bytes={}-", self.offset),
+        }
+    }
+}
+
+/// An error returned by a [`ResumableDownload`] body when a download could not be resumed.
+#[derive(Debug)]
+pub struct ResumeError {
+    offset: u64,
+    kind: ResumeErrorKind,
+}
+
+#[derive(Debug)]
+enum ResumeErrorKind {
+    RequestFailed(BoxError),
+    ValidatorMismatch {
+        validator: &'static str,
+        expected: String,
+        actual: Option<String>,
+    },
+    RangeMismatch {
+        content_range: Option<String>,
+    },
+}
+
+impl ResumeError {
+    /// Returns the offset the download was being resumed from.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to resume download at byte {}: ", self.offset)?;
+        match &self.kind {
+            ResumeErrorKind::RequestFailed(_) => write!(f, "the resumed request failed"),
+            ResumeErrorKind::ValidatorMismatch {
+                validator,
+                expected,
+                actual: Some(actual),
+            } => write!(
+                f,
+                "the object changed ({validator} was `{expected}` but is now `{actual}`)"
+            ),
+            ResumeErrorKind::ValidatorMismatch {
+                validator,
+                expected,
+                actual: None,
+            } => write!(
+                f,
+                "the resumed response has no {validator} (expected `{expected}`)"
+            ),
+            ResumeErrorKind::RangeMismatch {
+                content_range: Some(content_range),
+            } => write!(
+                f,
+                "the resumed response has content range `{content_range}`"
+            ),
+            ResumeErrorKind::RangeMismatch {
+                content_range: None,
+            } => write!(f, "the resumed response has no content range"),
+        }
+    }
+}
+
+impl Error for ResumeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ResumeErrorKind::RequestFailed(source) => Some(source.as_ref() as _),
+            _ => None,
+        }
+    }
+}
+
+/// A download body that resumes after the connection fails midway.
+///
+/// The download is resumed by calling the [`resume`](ResumableDownloadBuilder::resume) function,
+/// which is responsible for sending the request for the remaining bytes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::http::body::resumable_download::{
+///     DownloadPart, ResumableDownload, ResumeRange,
+/// };
+/// use aws_smithy_runtime_api::box_error::BoxError;
+/// use aws_smithy_types::byte_stream::ByteStream;
+///
+/// const OBJECT: &[u8] = b"hello world";
+///
+/// // Stands in for sending the download request. A real implementation would invoke the
+/// // operation, with a `ResumeRangeRuntimePlugin` for `range` when resuming, and create the part
+/// // with `DownloadPart::from_response`.
+/// async fn download(range: Option<ResumeRange>) -> Result<DownloadPart, BoxError> {
+///     let part = match range {
+///         None => DownloadPart::new(ByteStream::from_static(OBJECT)),
+///         Some(range) => {
+///             let offset = range.offset() as usize;
+///             DownloadPart::new(ByteStream::from_static(&OBJECT[offset..])).with_content_range(
+///                 format!("bytes {offset}-{}/{}", OBJECT.len() - 1, OBJECT.len()),
+///             )
+///         }
+///     };
+///     Ok(part.with_etag("\"object-etag\""))
+/// }
+///
+/// # async fn example() -> Result<(), BoxError> {
+/// let body = ResumableDownload::builder()
+///     .initial(download(None).await?)
+///     // Re-issue the download for the remaining bytes
+///     .resume(|range| download(Some(range)))
+///     .max_resumes(5)
+///     .build();
+/// let data = body.collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ResumableDownload;
+
+impl ResumableDownload {
+    /// Returns a builder for a resumable download body.
+    pub fn builder() -> ResumableDownloadBuilder {
+        ResumableDownloadBuilder::default()
+    }
+}
+
+/// Builder for a [`ResumableDownload`] body.
+#[derive(Default)]
+pub struct ResumableDownloadBuilder {
+    initial: Option<DownloadPart>,
+    resume: Option<ResumeFn>,
+    resume_if: Option<ResumeIfFn>,
+    max_resumes: Option<u32>,
+}
+
+impl fmt::Debug for ResumableDownloadBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResumableDownloadBuilder")
+            .field("initial", &self.initial)
+            .field("max_resumes", &self.max_resumes)
+            .finish()
+    }
+}
+
+impl ResumableDownloadBuilder {
+    /// Sets the first response of the download. Required.
+    ///
+    /// The download can only be resumed if this part has an `ETag` or a `Last-Modified` date,
+    /// since otherwise there is no way to tell whether the object changed in between. If it has a
+    /// content range, only the rest of that range is requested when resuming.
+    pub fn initial(mut self, initial: DownloadPart) -> Self {
+        self.initial = Some(initial);
+        self
+    }
+
+    /// Sets the function that re-issues the download for a [`ResumeRange`]. Required.
+    ///
+    /// The function sends the request itself. The request should carry the headers added by
+    /// [`ResumeRangeRuntimePlugin`], and the returned part should have the validators and
+    /// content range of the resumed response.
+    pub fn resume<F, Fut, E>(mut self, resume: F) -> Self
+    where
+        F: Fn(ResumeRange) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<DownloadPart, E>> + Send + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        self.resume = Some(Arc::new(move |range| {
+            let fut = resume(range);
+            Box::pin(async move { fut.await.map_err(Into::into) })
+        }));
+        self
+    }
+
+    /// Sets which body errors the download is resumed after.
+    ///
+    /// By default, the download is resumed after any error reading the body.
+    pub fn resume_if(
+        mut self,
+        resume_if: impl Fn(&BoxError) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.resume_if = Some(Arc::new(resume_if));
+        self
+    }
+
+    /// Sets how many times the download may be resumed. Defaults to 3.
+    pub fn max_resumes(mut self, max_resumes: u32) -> Self {
+        self.max_resumes = Some(max_resumes);
+        self
+    }
+
+    /// Builds the download body.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the initial part or the resume function wasn't set.
+    pub fn build(self) -> ByteStream {
+        let initial = self.initial.expect("an initial part is required");
+        let remaining = initial.body.size_hint();
+        let range = initial
+            .content_range
+            .as_deref()
+            .and_then(parse_content_range);
+        ByteStream::new(SdkBody::from_body_1_x(ResumableDownloadBody {
+            inner: initial.body.into_inner(),
+            start: range.map_or(0, |(start, _)| start),
+            end: range.map(|(_, end)| end),
+            received: 0,
+            total: (Some(remaining.0) == remaining.1).then_some(remaining.0),
+            etag: initial.etag,
+            last_modified: initial.last_modified,
+            resume: self.resume.expect("a resume function is required"),
+            resume_if: self.resume_if.unwrap_or_else(|| Arc::new(|_| true)),
+            resumes_left: self.max_resumes.unwrap_or(DEFAULT_MAX_RESUMES),
+            resuming: Mutex::new(None),
+        }))
+    }
+}
+
+struct ResumableDownloadBody {
+    inner: SdkBody,
+    // The position of the first byte of the initial part in the object
+    start: u64,
+    end: Option<u64>,
+    received: u64,
+    total: Option<u64>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    resume: ResumeFn,
+    resume_if: ResumeIfFn,
+    resumes_left: u32,
+    // The future is `Send` but not `Sync`; it is only ever accessed through `&mut self`.
+    resuming: Mutex<Option<BoxFuture<'static, DownloadPart, BoxError>>>,
+}
+
+impl ResumableDownloadBody {
+    fn can_resume(&self, err: &BoxError) -> bool {
+        self.resumes_left > 0
+            && (self.etag.is_some() || self.last_modified.is_some())
+            && (self.resume_if)(err)
+    }
+
+    /// Returns the position in the object of the next byte to be received.
+    fn offset(&self) -> u64 {
+        self.start + self.received
+    }
+
+    fn resume_range(&self) -> ResumeRange {
+        ResumeRange {
+            offset: self.offset(),
+            end: self.end,
+            etag: self.etag.clone(),
+            last_modified: self.last_modified.clone(),
+        }
+    }
+
+    fn validate(&self, part: &DownloadPart) -> Result<(), ResumeErrorKind> {
+        let validators = [
+            ("ETag", &self.etag, &part.etag),
+            ("Last-Modified", &self.last_modified, &part.last_modified),
+        ];
+        for (validator, expected, actual) in validators {
+            if let Some(expected) = expected {
+                if actual.as_ref() != Some(expected) {
+                    return Err(ResumeErrorKind::ValidatorMismatch {
+                        validator,
+                        expected: expected.clone(),
+                        actual: actual.clone(),
+                    });
+                }
+            }
+        }
+        match part.content_range.as_deref().and_then(parse_content_range) {
+            Some((start, _)) if start == self.offset() => Ok(()),
+            _ => Err(ResumeErrorKind::RangeMismatch {
+                content_range: part.content_range.clone(),
+            }),
+        }
+    }
+}
+
+/// Returns the first and last byte positions of a `Content-Range` header value like
+/// `bytes 100-199/200`.
+fn parse_content_range(content_range: &str) -> Option<(u64, u64)> {
+    let range = content_range.trim().strip_prefix("bytes ")?;
+    let (range, _) = range.split_once('/')?;
+    let (start, end) = range.split_once('-')?;
+    Some((start.trim().parse().ok()?, end.trim().parse().ok()?))
+}
+
+impl http_body_1x::Body for ResumableDownloadBody {
+    type Data = bytes::Bytes;
+    type Error = aws_smithy_types::body::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        loop {
+            let resuming = this.resuming.get_mut().expect("never poisoned");
+            if let Some(fut) = resuming.as_mut() {
+                let result = ready!(fut.as_mut().poll(cx));
+                *resuming = None;
+                let kind = match result {
+                    Ok(part) => match this.validate(&part) {
+                        Ok(()) => {
+                            tracing::debug!(offset = this.offset(), "resumed download");
+                            this.inner = part.body.into_inner();
+                            continue;
+                        }
+                        Err(kind) => kind,
+                    },
+                    Err(err) => ResumeErrorKind::RequestFailed(err),
+                };
+                this.resumes_left = 0;
+                return Poll::Ready(Some(Err(ResumeError {
+                    offset: this.offset(),
+                    kind,
+                }
+                .into())));
+            }
+
+            match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+                Some(Ok(frame)) => {
+                    if let Some(data) = frame.data_ref() {
+                        this.received += data.len() as u64;
+                    }
+                    return Poll::Ready(Some(Ok(frame)));
+                }
+                Some(Err(err)) if this.can_resume(&err) => {
+                    this.resumes_left -= 1;
+                    tracing::debug!(
+                        offset = this.offset(),
+                        err = %err,
+                        "download failed midway; resuming"
+                    );
+                    let fut = (this.resume)(this.resume_range());
+                    *this.resuming.get_mut().expect("never poisoned") = Some(fut);
+                }
+                other => return Poll::Ready(other),
+            }
+        }
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self.total {
+            Some(total) => SizeHint::with_exact(total.saturating_sub(self.received)),
+            None => SizeHint::default(),
+        }
+    }
+}
+
+/// Adds the `Range` and conditional headers of a [`ResumeRange`] to a request before it's signed.
+#[derive(Debug)]
+struct ResumeRangeInterceptor {
+    range: ResumeRange,
+}
+
+impl Intercept for ResumeRangeInterceptor {
+    fn name(&self) -> &'static str {
+        "ResumeRange"
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let headers = context.request_mut().headers_mut();
+        headers.insert("range", self.range.range_header());
+        if let Some(etag) = &self.range.etag {
+            headers.insert("if-match", etag.clone());
+        } else if let Some(last_modified) = &self.range.last_modified {
+            headers.insert("if-unmodified-since", last_modified.clone());
+        }
+        Ok(())
+    }
+}
+
+/// Runtime plugin that requests the remaining bytes of a download described by a [`ResumeRange`]
+///
+/// The headers are added before signing, so they are covered by the request signature.
+#[derive(Debug)]
+pub struct ResumeRangeRuntimePlugin {
+    components: RuntimeComponentsBuilder,
+}
+
+impl ResumeRangeRuntimePlugin {
+    /// Creates a runtime plugin that adds the headers for `range` to the request.
+    pub fn new(range: ResumeRange) -> Self {
+        Self {
+            components: RuntimeComponentsBuilder::new("ResumeRange")
+                .with_interceptor(ResumeRangeInterceptor { range }),
+        }
+    }
+}
+
+impl RuntimePlugin for ResumeRangeRuntimePlugin {
+    fn runtime_components(
+        &self,
+        _current_components: &RuntimeComponentsBuilder,
+    ) -> Cow<'_, RuntimeComponentsBuilder> {
+        Cow::Borrowed(&self.components)
+    }
+}
+
+#[cfg(all(feature = "test-util", test))]
+mod test {
+    use super::{
+        parse_content_range, DownloadPart, ResumableDownload, ResumeRange, ResumeRangeInterceptor,
+    };
+    use crate::assert_str_contains;
+    use aws_smithy_runtime_api::box_error::BoxError;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::interceptors::Intercept;
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::byte_stream::ByteStream;
+    use aws_smithy_types::config_bag::ConfigBag;
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use bytes::Bytes;
+    use http_body_1x::Frame;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    const OBJECT: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+    /// Body that yields one byte at a time and fails after `fail_after` bytes
+    struct FlakyBody {
+        data: Vec<u8>,
+        fail_after: Option<usize>,
+    }
+
+    impl http_body_1x::Body for FlakyBody {
+        type Data = Bytes;
+        type Error = BoxError;
+
+        fn poll_frame(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+            if self.fail_after == Some(0) {
+                return Poll::Ready(Some(Err("connection reset".into())));
+            }
+            self.fail_after = self.fail_after.map(|n| n - 1);
+            match self.data.pop() {
+                Some(next) => Poll::Ready(Some(Ok(Frame::data(Bytes::from(vec![next]))))),
+                None => Poll::Ready(None),
+            }
+        }
+    }
+
+    fn part(offset: usize, fail_after: Option<usize>) -> DownloadPart {
+        ranged_part(offset, 25, fail_after)
+    }
+
+    fn ranged_part(start: usize, end: usize, fail_after: Option<usize>) -> DownloadPart {
+        let mut data = OBJECT[start..=end].to_vec();
+        data.reverse();
+        DownloadPart::new(ByteStream::new(SdkBody::from_body_1_x(FlakyBody {
+            data,
+            fail_after,
+        })))
+        .with_etag("\"v1\"")
+        .with_content_range(format!("bytes {start}-{end}/26"))
+    }
+
+    #[tokio::test]
+    async fn resumes_from_the_bytes_received() {
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let seen = ranges.clone();
+        let body = ResumableDownload::builder()
+            .initial(part(0, Some(5)))
+            .resume(move |range: ResumeRange| {
+                seen.lock().unwrap().push(range.clone());
+                let offset = range.offset() as usize;
+                let fail_after = (offset < 10).then_some(7);
+                async move { Ok::<_, BoxError>(part(offset, fail_after)) }
+            })
+            .build();
+
+        let data = body.collect().await.unwrap().into_bytes();
+        assert_eq!(OBJECT, data.as_ref());
+        assert_eq!(
+            vec![
+                ResumeRange::new(5).with_end(25).with_etag("\"v1\""),
+                ResumeRange::new(12).with_end(25).with_etag("\"v1\""),
+            ],
+            *ranges.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn resumes_within_the_initial_range() {
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let seen = ranges.clone();
+        let body = ResumableDownload::builder()
+            .initial(ranged_part(10, 19, Some(3)))
+            .resume(move |range: ResumeRange| {
+                seen.lock().unwrap().push(range.clone());
+                let (start, end) = (range.offset() as usize, range.end().unwrap() as usize);
+                async move { Ok::<_, BoxError>(ranged_part(start, end, None)) }
+            })
+            .build();
+
+        let data = body.collect().await.unwrap().into_bytes();
+        assert_eq!(&OBJECT[10..20], data.as_ref());
+        let ranges = ranges.lock().unwrap();
+        assert_eq!(
+            vec![ResumeRange::new(13).with_end(19).with_etag("\"v1\"")],
+            *ranges
+        );
+        assert_eq!("bytes=13-19", ranges[0].range_header());
+    }
+
+    #[tokio::test]
+    async fn fails_when_the_object_changed() {
+        let body = ResumableDownload::builder()
+            .initial(part(0, Some(5)))
+            .resume(|range: ResumeRange| async move {
+                Ok::<_, BoxError>(part(range.offset() as usize, None).with_etag("\"v2\""))
+            })
+            .build();
+
+        let err = body.collect().await.expect_err("object changed");
+        assert_str_contains!(
+            format!("{}", DisplayErrorContext(err)),
+            "failed to resume download at byte 5: the object changed (ETag was `\"v1\"` but is now `\"v2\"`)"
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_when_the_range_was_ignored() {
+        let body = ResumableDownload::builder()
+            .initial(part(0, Some(5)))
+            .resume(|_range| async { Ok::<_, BoxError>(part(0, None)) })
+            .build();
+
+        let err = body.collect().await.expect_err("range ignored");
+        assert_str_contains!(
+            format!("{}", DisplayErrorContext(err)),
+            "the resumed response has content range `bytes 0-25/26`"
+        );
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_resumes() {
+        let body = ResumableDownload::builder()
+            .initial(part(0, Some(5)))
+            .resume(|range: ResumeRange| async move {
+                Ok::<_, BoxError>(part(range.offset() as usize, Some(1)))
+            })
+            .max_resumes(2)
+            .build();
+
+        let err = body.collect().await.expect_err("too many failures");
+        assert_str_contains!(format!("{}", DisplayErrorContext(err)), "connection reset");
+    }
+
+    #[tokio::test]
+    async fn does_not_resume_without_validators() {
+        let mut data = OBJECT.to_vec();
+        data.reverse();
+        let initial = DownloadPart::new(ByteStream::new(SdkBody::from_body_1_x(FlakyBody {
+            data,
+            fail_after: Some(5),
+        })));
+        let body = ResumableDownload::builder()
+            .initial(initial)
+            .resume(|_range| async { Err::<DownloadPart, _>("should not be called") })
+            .build();
+
+        let err = body.collect().await.expect_err("not resumable");
+        assert_str_contains!(format!("{}", DisplayErrorContext(err)), "connection reset");
+    }
+
+    #[test]
+    fn parses_content_range() {
+        assert_eq!(Some((100, 199)), parse_content_range("bytes 100-199/200"));
+        assert_eq!(Some((0, 0)), parse_content_range("bytes 0-0/*"));
+        assert_eq!(None, parse_content_range("bytes */200"));
+        assert_eq!(None, parse_content_range("items 1-2/3"));
+    }
+
+    #[test]
+    fn adds_range_headers_before_signing() {
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.enter_serialization_phase();
+        context.set_request(HttpRequest::empty());
+        let _ = context.take_input();
+        context.enter_before_transmit_phase();
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::base();
+
+        let interceptor = ResumeRangeInterceptor {
+            range: ResumeRange::new(1024)
+                .with_etag("\"v1\"")
+                .with_last_modified("Wed, 21 Oct 2015 07:28:00 GMT"),
+        };
+        interceptor
+            .modify_before_signing(&mut (&mut context).into(), &rc, &mut cfg)
+            .unwrap();
+        let headers = context.request().unwrap().headers();
+        assert_eq!(Some("bytes=1024-"), headers.get("range"));
+        assert_eq!(Some("\"v1\""), headers.get("if-match"));
+        assert_eq!(None, headers.get("if-unmodified-since"));
+
+        assert_eq!(
+            "bytes=1024-2047",
+            ResumeRange::new(1024).with_end(2047).range_header()
+        );
+    }
+}
@@ -5,6 +5,7 @@
 
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeDeserializationInterceptorContextMut, BeforeSerializationInterceptorContextMut,
     BeforeSerializationInterceptorContextRef, BeforeTransmitInterceptorContextMut,
     FinalizerInterceptorContextMut, FinalizerInterceptorContextRef,
 };
@@ -12,7 +13,7 @@ use aws_smithy_runtime_api::client::interceptors::context::{
     Error, Input, InterceptorContext, Output,
 };
 use aws_smithy_runtime_api::client::interceptors::{
-    Intercept, InterceptorError, SharedInterceptor,
+    AsyncIntercept, Intercept, InterceptorError, SharedAsyncInterceptor, SharedInterceptor,
 };
 use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
@@ -272,6 +273,82 @@ where
     }
 }
 
+macro_rules! async_interceptor_impl_fn {
+    ($interceptor:ident, $phase:ident) => {
+        pub(crate) async fn $interceptor(
+            self,
+            ctx: &mut InterceptorContext,
+            runtime_components: &RuntimeComponents,
+            cfg: &mut ConfigBag,
+        ) -> Result<(), InterceptorError> {
+            tracing::trace!(concat!(
+                "running async `",
+                stringify!($interceptor),
+                "` interceptors"
+            ));
+            let mut result: Result<(), (&str, BoxError)> = Ok(());
+            let mut ctx: $phase<'_> = ctx.into();
+            for interceptor in self.interceptors {
+                if !interceptor.enabled(cfg) {
+                    continue;
+                }
+                if let Err(new_error) = interceptor
+                    .$interceptor(&mut ctx, runtime_components, cfg)
+                    .await
+                {
+                    if let Err(last_error) = result {
+                        tracing::debug!(
+                            "{}::{}: {}",
+                            last_error.0,
+                            stringify!($interceptor),
+                            DisplayErrorContext(&*last_error.1)
+                        );
+                    }
+                    result = Err((interceptor.name(), new_error));
+                }
+            }
+            result.map_err(|(name, err)| InterceptorError::$interceptor(name, err))
+        }
+    };
+}
+
+/// Runs the hooks of [`AsyncIntercept`] implementations.
+///
+/// The orchestrator runs these right after the synchronous [`Interceptors`] for the same hook.
+#[derive(Debug)]
+pub(crate) struct AsyncInterceptors<I> {
+    interceptors: I,
+}
+
+impl<I> AsyncInterceptors<I>
+where
+    I: Iterator<Item = SharedAsyncInterceptor>,
+{
+    pub(crate) fn new(interceptors: I) -> Self {
+        Self { interceptors }
+    }
+
+    async_interceptor_impl_fn!(
+        modify_before_serialization,
+        BeforeSerializationInterceptorContextMut
+    );
+    async_interceptor_impl_fn!(
+        modify_before_retry_loop,
+        BeforeTransmitInterceptorContextMut
+    );
+    async_interceptor_impl_fn!(modify_before_signing, BeforeTransmitInterceptorContextMut);
+    async_interceptor_impl_fn!(modify_before_transmit, BeforeTransmitInterceptorContextMut);
+    async_interceptor_impl_fn!(
+        modify_before_deserialization,
+        BeforeDeserializationInterceptorContextMut
+    );
+    async_interceptor_impl_fn!(
+        modify_before_attempt_completion,
+        FinalizerInterceptorContextMut
+    );
+    async_interceptor_impl_fn!(modify_before_completion, FinalizerInterceptorContextMut);
+}
+
 /// A interceptor wrapper to conditionally enable the interceptor based on
 /// [`DisableInterceptor`](aws_smithy_runtime_api::client::interceptors::DisableInterceptor)
 struct ConditionallyEnabledInterceptor(SharedInterceptor);
@@ -4,7 +4,8 @@
  */
 
 use self::auth::orchestrate_auth;
-use crate::client::interceptors::Interceptors;
+use crate::client::concurrency_limit::hold_permit_until_body_completes;
+use crate::client::interceptors::{AsyncInterceptors, Interceptors};
 use crate::client::orchestrator::http::{log_response_body, read_body};
 use crate::client::timeout::{MaybeTimeout, MaybeTimeoutConfig, TimeoutKind};
 use crate::client::{
@@ -13,6 +14,7 @@ use crate::client::{
 };
 use aws_smithy_async::rt::sleep::AsyncSleep;
 use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::concurrency_limit::LimitConcurrency;
 use aws_smithy_runtime_api::client::http::{HttpClient, HttpConnector, HttpConnectorSettings};
 use aws_smithy_runtime_api::client::interceptors::context::{
     Error, Input, InterceptorContext, Output, RewindResult,
@@ -40,7 +42,7 @@ mod auth;
 pub mod endpoints;
 
 /// Defines types that work with HTTP types
-mod http;
+pub(crate) mod http;
 
 /// Utility for making one-off unmodeled requests with the orchestrator.
 pub mod operation;
@@ -89,6 +91,22 @@ macro_rules! run_interceptors {
     };
 }
 
+// Async interceptors run right after the synchronous interceptors for the same hook. Since they
+// are awaited inside the operation (and attempt) futures, they count towards those timeouts.
+macro_rules! run_async_interceptors {
+    (continue_on_err: $interceptor:ident($ctx:ident, $rc:ident, $cfg:ident)) => {
+        continue_on_err!([$ctx] => run_async_interceptors!(__private $interceptor($ctx, $rc, $cfg)))
+    };
+    (halt_on_err: $interceptor:ident($ctx:ident, $rc:ident, $cfg:ident)) => {
+        halt_on_err!([$ctx] => run_async_interceptors!(__private $interceptor($ctx, $rc, $cfg)))
+    };
+    (__private $interceptor:ident($ctx:ident, $rc:ident, $cfg:ident)) => {
+        AsyncInterceptors::new($rc.async_interceptors())
+            .$interceptor($ctx, $rc, $cfg)
+            .await
+    };
+}
+
 /// Orchestrates the execution of a request and handling of a response.
 ///
 /// The given `runtime_plugins` will be used to generate a `ConfigBag` for this request,
@@ -216,10 +234,9 @@ async fn try_op(
     stop_point: StopPoint,
 ) {
     // Before serialization
-    run_interceptors!(halt_on_err: {
-        modify_before_serialization(ctx, runtime_components, cfg);
-        read_before_serialization(ctx, runtime_components, cfg);
-    });
+    run_interceptors!(halt_on_err: modify_before_serialization(ctx, runtime_components, cfg));
+    run_async_interceptors!(halt_on_err: modify_before_serialization(ctx, runtime_components, cfg));
+    run_interceptors!(halt_on_err: read_before_serialization(ctx, runtime_components, cfg));
 
     // Serialization
     ctx.enter_serialization_phase();
@@ -255,6 +272,7 @@ async fn try_op(
         read_after_serialization(ctx, runtime_components, cfg);
         modify_before_retry_loop(ctx, runtime_components, cfg);
     });
+    run_async_interceptors!(halt_on_err: modify_before_retry_loop(ctx, runtime_components, cfg));
 
     // If we got a retry strategy from the bag, ask it what to do.
     // Otherwise, assume we should attempt the initial request.
@@ -352,18 +370,18 @@ async fn try_attempt(
 
     halt_on_err!([ctx] => orchestrate_endpoint(ctx, runtime_components, cfg).await.map_err(OrchestratorError::other));
 
-    run_interceptors!(halt_on_err: {
-        modify_before_signing(ctx, runtime_components, cfg);
-        read_before_signing(ctx, runtime_components, cfg);
-    });
+    run_interceptors!(halt_on_err: modify_before_signing(ctx, runtime_components, cfg));
+    run_async_interceptors!(halt_on_err: modify_before_signing(ctx, runtime_components, cfg));
+    run_interceptors!(halt_on_err: read_before_signing(ctx, runtime_components, cfg));
 
     halt_on_err!([ctx] => orchestrate_auth(ctx, runtime_components, cfg).await.map_err(OrchestratorError::other));
 
     run_interceptors!(halt_on_err: {
         read_after_signing(ctx, runtime_components, cfg);
         modify_before_transmit(ctx, runtime_components, cfg);
-        read_before_transmit(ctx, runtime_components, cfg);
     });
+    run_async_interceptors!(halt_on_err: modify_before_transmit(ctx, runtime_components, cfg));
+    run_interceptors!(halt_on_err: read_before_transmit(ctx, runtime_components, cfg));
 
     // Return early if a stop point is set for before transmit
     if let StopPoint::BeforeTransmit = stop_point {
@@ -374,7 +392,17 @@ async fn try_attempt(
     // The connection consumes the request but we need to keep a copy of it
     // within the interceptor context, so we clone it here.
     ctx.enter_transmit_phase();
-    let response = halt_on_err!([ctx] => {
+    // If a concurrency limiter is configured, wait for a permit. The permit is released when the
+    // response body is done, or when it is dropped because this attempt failed.
+    let permit = match runtime_components.concurrency_limiter() {
+        Some(limiter) => Some(halt_on_err!([ctx] => limiter
+            .acquire_permit(runtime_components, cfg)
+            .instrument(debug_span!("acquire_concurrency_permit"))
+            .await
+            .map_err(OrchestratorError::other))),
+        None => None,
+    };
+    let mut response = halt_on_err!([ctx] => {
         let request = ctx.take_request().expect("set during serialization");
         trace!(request = ?request, "transmitting request");
         let http_client = halt_on_err!([ctx] => runtime_components.http_client().ok_or_else(||
@@ -397,14 +425,18 @@ async fn try_attempt(
         response_future.await.map_err(OrchestratorError::connector)
     });
     trace!(response = ?response, "received response from service");
+    if let Some(permit) = permit {
+        hold_permit_until_body_completes(&mut response, permit);
+    }
     ctx.set_response(response);
     ctx.enter_before_deserialization_phase();
 
     run_interceptors!(halt_on_err: {
         read_after_transmit(ctx, runtime_components, cfg);
         modify_before_deserialization(ctx, runtime_components, cfg);
-        read_before_deserialization(ctx, runtime_components, cfg);
     });
+    run_async_interceptors!(halt_on_err: modify_before_deserialization(ctx, runtime_components, cfg));
+    run_interceptors!(halt_on_err: read_before_deserialization(ctx, runtime_components, cfg));
 
     ctx.enter_deserialization_phase();
     let output_or_error = async {
@@ -444,10 +476,9 @@ async fn finally_attempt(
     cfg: &mut ConfigBag,
     runtime_components: &RuntimeComponents,
 ) {
-    run_interceptors!(continue_on_err: {
-        modify_before_attempt_completion(ctx, runtime_components, cfg);
-        read_after_attempt(ctx, runtime_components, cfg);
-    });
+    run_interceptors!(continue_on_err: modify_before_attempt_completion(ctx, runtime_components, cfg));
+    run_async_interceptors!(continue_on_err: modify_before_attempt_completion(ctx, runtime_components, cfg));
+    run_interceptors!(continue_on_err: read_after_attempt(ctx, runtime_components, cfg));
 }
 
 #[instrument(skip_all, level = "debug")]
@@ -456,10 +487,9 @@ async fn finally_op(
     cfg: &mut ConfigBag,
     runtime_components: &RuntimeComponents,
 ) {
-    run_interceptors!(continue_on_err: {
-        modify_before_completion(ctx, runtime_components, cfg);
-        read_after_execution(ctx, runtime_components, cfg);
-    });
+    run_interceptors!(continue_on_err: modify_before_completion(ctx, runtime_components, cfg));
+    run_async_interceptors!(continue_on_err: modify_before_completion(ctx, runtime_components, cfg));
+    run_interceptors!(continue_on_err: read_after_execution(ctx, runtime_components, cfg));
 }
 
 #[cfg(all(test, feature = "test-util"))]
@@ -472,6 +502,7 @@ mod tests {
     use crate::client::test_util::{
         deserializer::CannedResponseDeserializer, serializer::CannedRequestSerializer,
     };
+    use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, TokioSleep};
     use aws_smithy_runtime_api::box_error::BoxError;
     use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
     use aws_smithy_runtime_api::client::auth::{
@@ -490,8 +521,11 @@ mod tests {
         BeforeTransmitInterceptorContextRef, FinalizerInterceptorContextMut,
         FinalizerInterceptorContextRef, Input, Output,
     };
-    use aws_smithy_runtime_api::client::interceptors::{Intercept, SharedInterceptor};
+    use aws_smithy_runtime_api::client::interceptors::{
+        AsyncIntercept, Intercept, InterceptorFuture, SharedInterceptor,
+    };
     use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, OrchestratorError};
+    use aws_smithy_runtime_api::client::result::SdkError;
     use aws_smithy_runtime_api::client::retries::SharedRetryStrategy;
     use aws_smithy_runtime_api::client::runtime_components::{
         RuntimeComponents, RuntimeComponentsBuilder,
@@ -507,7 +541,8 @@ mod tests {
     use http_02x::{Response, StatusCode};
     use std::borrow::Cow;
     use std::sync::atomic::{AtomicBool, Ordering};
-    use std::sync::Arc;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
     use tracing_test::traced_test;
 
     fn new_request_serializer() -> CannedRequestSerializer {
@@ -1345,4 +1380,157 @@ mod tests {
             .read_after_execution_called
             .load(Ordering::Relaxed));
     }
+
+    #[derive(Debug)]
+    struct TestInterceptorRuntimePlugin {
+        builder: RuntimeComponentsBuilder,
+    }
+
+    impl RuntimePlugin for TestInterceptorRuntimePlugin {
+        fn runtime_components(
+            &self,
+            _: &RuntimeComponentsBuilder,
+        ) -> Cow<'_, RuntimeComponentsBuilder> {
+            Cow::Borrowed(&self.builder)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_interceptors_run_after_sync_interceptors() {
+        #[derive(Clone, Debug, Default)]
+        struct SyncInterceptor {
+            seen_in_read_before_transmit: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl Intercept for SyncInterceptor {
+            fn name(&self) -> &'static str {
+                "SyncInterceptor"
+            }
+
+            fn modify_before_transmit(
+                &self,
+                context: &mut BeforeTransmitInterceptorContextMut<'_>,
+                _rc: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                context
+                    .request_mut()
+                    .headers_mut()
+                    .append("x-hooks", "sync");
+                Ok(())
+            }
+
+            fn read_before_transmit(
+                &self,
+                context: &BeforeTransmitInterceptorContextRef<'_>,
+                _rc: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                *self.seen_in_read_before_transmit.lock().unwrap() = context
+                    .request()
+                    .headers()
+                    .get_all("x-hooks")
+                    .map(String::from)
+                    .collect();
+                Ok(())
+            }
+        }
+
+        #[derive(Debug)]
+        struct TestAsyncInterceptor;
+
+        impl AsyncIntercept for TestAsyncInterceptor {
+            fn name(&self) -> &'static str {
+                "TestAsyncInterceptor"
+            }
+
+            fn modify_before_transmit<'a>(
+                &'a self,
+                context: &'a mut BeforeTransmitInterceptorContextMut<'_>,
+                _rc: &'a RuntimeComponents,
+                _cfg: &'a mut ConfigBag,
+            ) -> InterceptorFuture<'a> {
+                InterceptorFuture::new(async move {
+                    tokio::task::yield_now().await;
+                    context
+                        .request_mut()
+                        .headers_mut()
+                        .append("x-hooks", "async");
+                    Ok(())
+                })
+            }
+        }
+
+        let interceptor = SyncInterceptor::default();
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new())
+            .with_operation_plugin(TestInterceptorRuntimePlugin {
+                builder: RuntimeComponentsBuilder::new("test")
+                    .with_interceptor(interceptor.clone())
+                    .with_async_interceptor(TestAsyncInterceptor),
+            });
+
+        invoke("test", "test", Input::doesnt_matter(), &runtime_plugins)
+            .await
+            .expect("success");
+        assert_eq!(
+            vec!["sync", "async"],
+            *interceptor.seen_in_read_before_transmit.lock().unwrap()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_async_interceptors_count_towards_the_attempt_timeout() {
+        #[derive(Debug)]
+        struct SlowInterceptor;
+
+        impl AsyncIntercept for SlowInterceptor {
+            fn name(&self) -> &'static str {
+                "SlowInterceptor"
+            }
+
+            fn modify_before_signing<'a>(
+                &'a self,
+                _context: &'a mut BeforeTransmitInterceptorContextMut<'_>,
+                _rc: &'a RuntimeComponents,
+                _cfg: &'a mut ConfigBag,
+            ) -> InterceptorFuture<'a> {
+                InterceptorFuture::new(async {
+                    TokioSleep::new().sleep(Duration::from_secs(60)).await;
+                    Ok(())
+                })
+            }
+        }
+
+        #[derive(Debug)]
+        struct TimeoutRuntimePlugin;
+
+        impl RuntimePlugin for TimeoutRuntimePlugin {
+            fn config(&self) -> Option<FrozenLayer> {
+                let mut layer = Layer::new("TimeoutRuntimePlugin");
+                layer.store_put(
+                    TimeoutConfig::builder()
+                        .operation_attempt_timeout(Duration::from_secs(1))
+                        .build(),
+                );
+                Some(layer.freeze())
+            }
+        }
+
+        let runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(TestOperationRuntimePlugin::new())
+            .with_operation_plugin(NoAuthRuntimePlugin::new())
+            .with_operation_plugin(TimeoutRuntimePlugin)
+            .with_operation_plugin(TestInterceptorRuntimePlugin {
+                builder: RuntimeComponentsBuilder::new("test")
+                    .with_sleep_impl(Some(SharedAsyncSleep::new(TokioSleep::new())))
+                    .with_async_interceptor(SlowInterceptor),
+            });
+
+        let err = invoke("test", "test", Input::doesnt_matter(), &runtime_plugins)
+            .await
+            .expect_err("the attempt should time out");
+        assert!(matches!(err, SdkError::TimeoutError(_)), "{err:?}");
+    }
 }
@@ -13,6 +13,17 @@ use tracing::trace;
 
 const LOG_SENSITIVE_BODIES: &str = "LOG_SENSITIVE_BODIES";
 
+/// Logged in place of a body that belongs to an operation with `@sensitive` input or output.
+pub(crate) const REDACTED_BODY: &str = "** REDACTED **. To print, set LOG_SENSITIVE_BODIES=true";
+
+/// Returns true if sensitive bodies should be logged anyway, which is opted into by setting the
+/// `LOG_SENSITIVE_BODIES` environment variable to `true`.
+pub(crate) fn log_sensitive_bodies() -> bool {
+    std::env::var(LOG_SENSITIVE_BODIES)
+        .map(|v| v.eq_ignore_ascii_case("true"))
+        .unwrap_or_default()
+}
+
 async fn body_to_bytes(body: SdkBody) -> Result<Bytes, <SdkBody as Body>::Error> {
     let mut output = Vec::new();
     pin_mut!(body);
@@ -39,16 +50,9 @@ pub(crate) async fn read_body(response: &mut HttpResponse) -> Result<(), <SdkBod
 }
 
 pub(crate) fn log_response_body(response: &HttpResponse, cfg: &ConfigBag) {
-    if cfg.load::<SensitiveOutput>().is_none()
-        || std::env::var(LOG_SENSITIVE_BODIES)
-            .map(|v| v.eq_ignore_ascii_case("true"))
-            .unwrap_or_default()
-    {
+    if cfg.load::<SensitiveOutput>().is_none() || log_sensitive_bodies() {
         trace!(response = ?response, "read HTTP response body");
     } else {
-        trace!(
-            response = "** REDACTED **. To print, set LOG_SENSITIVE_BODIES=true",
-            "read HTTP response body"
-        )
+        trace!(response = REDACTED_BODY, "read HTTP response body")
     }
 }
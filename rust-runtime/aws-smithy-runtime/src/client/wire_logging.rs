@@ -0,0 +1,456 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An interceptor that logs serialized requests and received responses as structured events.
+//!
+//! Events are emitted at `DEBUG` level with the `aws_smithy_runtime::wire` target, so they can
+//! be enabled independently of other logging, for example with
+//! `RUST_LOG=aws_smithy_runtime::wire=debug`.
+//!
+//! # Redaction
+//!
+//! Similar to the server's `instrumentation::sensitivity` module, values are redacted so that
+//! enabling wire logging doesn't leak secrets:
+//! - Headers like `Authorization` and `Cookie`, plus any headers listed by
+//!   [`WireLoggingInterceptor::with_redacted_header`], always have their values redacted.
+//! - Headers bound to `@sensitive` members (stored in the config bag as [`SensitiveHeaders`])
+//!   have their values redacted.
+//! - Path segments and query values bound to `@sensitive` members (stored in the config bag as
+//!   [`SensitiveUri`]) are redacted.
+//! - Request and response bodies of operations with `@sensitive` input or output (marked by
+//!   [`SensitiveInput`] and [`SensitiveOutput`]) are redacted, unless the `LOG_SENSITIVE_BODIES`
+//!   environment variable is set to `true`.
+
+use crate::client::orchestrator::http::{log_sensitive_bodies, REDACTED_BODY};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    AfterDeserializationInterceptorContextRef, BeforeTransmitInterceptorContextRef,
+};
+use aws_smithy_runtime_api::client::interceptors::Intercept;
+use aws_smithy_runtime_api::client::orchestrator::{
+    SensitiveHeaders, SensitiveInput, SensitiveOutput, SensitiveUri,
+};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_runtime_api::http::Headers;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::borrow::Cow;
+
+mod pretty;
+
+const REDACTED: &str = "** REDACTED **";
+const DEFAULT_MAX_BODY_SIZE: usize = 4096;
+const DEFAULT_REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "cookie",
+    "set-cookie",
+    "x-amz-security-token",
+];
+
+/// Logs serialized requests and received responses as structured `tracing` events.
+///
+/// See the [module docs](crate::client::wire_logging) for details on what gets redacted.
+///
+/// # Examples
+///
+/// ```no_run
+/// use aws_smithy_runtime::client::wire_logging::WireLoggingInterceptor;
+///
+/// let interceptor = WireLoggingInterceptor::new()
+///     .with_max_body_size(16 * 1024)
+///     .with_redacted_header("x-api-key")
+///     .with_pretty(true);
+/// // Register it with `.interceptor(interceptor)` on a client config builder.
+/// ```
+#[derive(Clone, Debug)]
+pub struct WireLoggingInterceptor {
+    max_body_size: usize,
+    redacted_headers: Vec<Cow<'static, str>>,
+    pretty: bool,
+}
+
+impl Default for WireLoggingInterceptor {
+    fn default() -> Self {
+        Self {
+            max_body_size: DEFAULT_MAX_BODY_SIZE,
+            redacted_headers: DEFAULT_REDACTED_HEADERS
+                .iter()
+                .map(|&name| Cow::Borrowed(name))
+                .collect(),
+            pretty: false,
+        }
+    }
+}
+
+impl WireLoggingInterceptor {
+    /// Creates a new `WireLoggingInterceptor` with a 4 KiB body size cap.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of body bytes that get logged.
+    ///
+    /// Longer bodies are truncated, and the log notes how many bytes were left out.
+    pub fn with_max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = max_body_size;
+        self
+    }
+
+    /// Always redacts the value of the header with the given name.
+    pub fn with_redacted_header(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.redacted_headers.push(name.into());
+        self
+    }
+
+    /// When enabled, JSON, XML, and CBOR bodies are printed as indented, human-readable text.
+    ///
+    /// The format is detected from the `Content-Type` header. CBOR is printed in its
+    /// diagnostic notation.
+    pub fn with_pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    fn render_headers(&self, headers: &Headers, cfg: &ConfigBag) -> String {
+        let sensitive = cfg.load::<SensitiveHeaders>();
+        let mut out = String::new();
+        for (name, value) in headers.iter() {
+            let redact = self
+                .redacted_headers
+                .iter()
+                .any(|h| h.eq_ignore_ascii_case(name))
+                || sensitive.is_some_and(|s| s.is_sensitive(name));
+            if !out.is_empty() {
+                out.push_str(", ");
+            }
+            out.push_str(name);
+            out.push_str(": ");
+            out.push_str(if redact { REDACTED } else { value });
+        }
+        out
+    }
+
+    fn render_body(&self, body: &SdkBody, headers: &Headers, sensitive: bool) -> String {
+        if sensitive && !log_sensitive_bodies() {
+            return REDACTED_BODY.into();
+        }
+        let Some(bytes) = body.bytes() else {
+            return "<streaming body>".into();
+        };
+        let mut length = bytes.len().min(self.max_body_size);
+        if let Ok(text) = std::str::from_utf8(bytes) {
+            // Don't split a character
+            while !text.is_char_boundary(length) {
+                length -= 1;
+            }
+        }
+        let (shown, truncated) = (&bytes[..length], bytes.len() - length);
+        let mut out = if self.pretty {
+            pretty_body(shown, headers.get("content-type"))
+        } else {
+            None
+        }
+        .unwrap_or_else(|| raw_body(shown));
+        if truncated > 0 {
+            out.push_str(&format!("... ({truncated} bytes truncated)"));
+        }
+        out
+    }
+}
+
+/// Redacts the path segments and query values of a URI that are marked as sensitive.
+fn render_uri(uri: &str, sensitive: Option<&SensitiveUri>) -> String {
+    let Some(sensitive) = sensitive else {
+        return uri.to_string();
+    };
+    let path_start = match uri.find("://") {
+        Some(scheme_end) => {
+            let authority_start = scheme_end + 3;
+            uri[authority_start..]
+                .find(['/', '?'])
+                .map_or(uri.len(), |i| authority_start + i)
+        }
+        None => 0,
+    };
+    let (path, query) = match uri[path_start..].find('?') {
+        Some(i) => (
+            &uri[path_start..path_start + i],
+            Some(&uri[path_start + i + 1..]),
+        ),
+        None => (&uri[path_start..], None),
+    };
+
+    let mut out = String::with_capacity(uri.len());
+    out.push_str(&uri[..path_start]);
+    if sensitive.is_whole_path_sensitive() && !path.is_empty() {
+        out.push('/');
+        out.push_str(REDACTED);
+    } else {
+        // A trailing slash doesn't count as a segment of its own
+        let trimmed = path.strip_suffix('/').unwrap_or(path);
+        let segments: Vec<_> = trimmed.split('/').collect();
+        for (i, segment) in segments.iter().enumerate() {
+            if i > 0 {
+                out.push('/');
+            }
+            let from_end = segments.len() - 1 - i;
+            // The first segment is the empty string before the leading `/`
+            if !segment.is_empty() && sensitive.is_path_segment_sensitive(from_end) {
+                out.push_str(REDACTED);
+            } else {
+                out.push_str(segment);
+            }
+        }
+        out.push_str(&path[trimmed.len()..]);
+    }
+    if let Some(query) = query {
+        out.push('?');
+        for (i, pair) in query.split('&').enumerate() {
+            if i > 0 {
+                out.push('&');
+            }
+            let key = pair.split_once('=').map_or(pair, |(key, _)| key);
+            if sensitive.are_all_query_params_sensitive() {
+                out.push_str(REDACTED);
+            } else if sensitive.is_query_value_sensitive(key) {
+                out.push_str(key);
+                out.push('=');
+                out.push_str(REDACTED);
+            } else {
+                out.push_str(pair);
+            }
+        }
+    }
+    out
+}
+
+fn raw_body(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => bytes.escape_ascii().to_string(),
+    }
+}
+
+fn pretty_body(bytes: &[u8], content_type: Option<&str>) -> Option<String> {
+    let content_type = content_type?.to_ascii_lowercase();
+    // Strip parameters, such as `; charset=utf-8`. Media types like `application/x-amz-json-1.0`
+    // carry the format in the middle of the name.
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    if media_type.contains("cbor") {
+        Some(pretty::cbor(bytes))
+    } else if media_type.contains("json") {
+        // The body isn't necessarily valid UTF-8, so don't require it
+        Some(pretty::json(&String::from_utf8_lossy(bytes)))
+    } else if media_type.contains("xml") {
+        Some(pretty::xml(&String::from_utf8_lossy(bytes)))
+    } else {
+        None
+    }
+}
+
+impl Intercept for WireLoggingInterceptor {
+    fn name(&self) -> &'static str {
+        "WireLogging"
+    }
+
+    fn read_before_transmit(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let request = context.request();
+        tracing::debug!(
+            target: "aws_smithy_runtime::wire",
+            method = request.method(),
+            uri = %render_uri(request.uri(), cfg.load::<SensitiveUri>()),
+            headers = %self.render_headers(request.headers(), cfg),
+            body = %self.render_body(
+                request.body(),
+                request.headers(),
+                cfg.load::<SensitiveInput>().is_some()
+            ),
+            "sending request"
+        );
+        Ok(())
+    }
+
+    fn read_after_deserialization(
+        &self,
+        context: &AfterDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let response = context.response();
+        let sensitive = cfg.load::<SensitiveOutput>().is_some();
+        let body = self.render_body(response.body(), response.headers(), sensitive);
+        // The `Debug` impls of generated outputs and errors already redact `@sensitive` members
+        match context.output_or_error() {
+            Ok(output) => tracing::debug!(
+                target: "aws_smithy_runtime::wire",
+                status = response.status().as_u16(),
+                headers = %self.render_headers(response.headers(), cfg),
+                body = %body,
+                output = ?output,
+                "received response"
+            ),
+            Err(error) => tracing::debug!(
+                target: "aws_smithy_runtime::wire",
+                status = response.status().as_u16(),
+                headers = %self.render_headers(response.headers(), cfg),
+                body = %body,
+                error = ?error,
+                "received error response"
+            ),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::Layer;
+    use tracing_test::traced_test;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> Headers {
+        let mut headers = Headers::new();
+        for (name, value) in pairs {
+            headers.insert(*name, *value);
+        }
+        headers
+    }
+
+    #[test]
+    fn redacts_default_configured_and_sensitive_headers() {
+        let interceptor = WireLoggingInterceptor::new().with_redacted_header("X-Api-Key");
+        let mut layer = Layer::new("test");
+        layer.store_put(
+            SensitiveHeaders::new()
+                .with_name("x-secret")
+                .with_prefix("x-meta-"),
+        );
+        let cfg = ConfigBag::of_layers(vec![layer]);
+
+        let rendered = interceptor.render_headers(
+            &headers(&[
+                ("authorization", "AWS4-HMAC-SHA256 ..."),
+                ("x-api-key", "key"),
+                ("x-secret", "hunter2"),
+                ("x-meta-token", "abc"),
+                ("content-type", "application/json"),
+            ]),
+            &cfg,
+        );
+        assert_eq!(
+            "authorization: ** REDACTED **, x-api-key: ** REDACTED **, x-secret: ** REDACTED **, \
+             x-meta-token: ** REDACTED **, content-type: application/json",
+            rendered
+        );
+    }
+
+    #[test]
+    fn redacts_sensitive_uri_parts() {
+        let sensitive = SensitiveUri::new()
+            .with_path_segment_from_end(0)
+            .with_query_key("token");
+        assert_eq!(
+            "https://example.com/prefix/Bucket/** REDACTED **?token=** REDACTED **&x-id=Get",
+            render_uri(
+                "https://example.com/prefix/Bucket/secret?token=hunter2&x-id=Get",
+                Some(&sensitive)
+            )
+        );
+        assert_eq!(
+            "https://example.com/a/b/?token=x",
+            render_uri("https://example.com/a/b/?token=x", None)
+        );
+
+        let sensitive = SensitiveUri::new()
+            .with_whole_path()
+            .with_all_query_params();
+        assert_eq!(
+            "https://example.com/** REDACTED **?** REDACTED **&** REDACTED **",
+            render_uri(
+                "https://example.com/Bucket/a/b?meta=1&flag",
+                Some(&sensitive)
+            )
+        );
+    }
+
+    #[test]
+    fn renders_bodies() {
+        let interceptor = WireLoggingInterceptor::new().with_max_body_size(4);
+        let no_headers = Headers::new();
+        assert_eq!(
+            "abcd... (2 bytes truncated)",
+            interceptor.render_body(&SdkBody::from("abcdef"), &no_headers, false)
+        );
+        assert_eq!(
+            "abc... (2 bytes truncated)",
+            interceptor.render_body(&SdkBody::from("abcé"), &no_headers, false)
+        );
+        assert_eq!(
+            "\\xff\\x00",
+            interceptor.render_body(&SdkBody::from(vec![0xff, 0x00]), &no_headers, false)
+        );
+        assert_eq!(
+            "** REDACTED **. To print, set LOG_SENSITIVE_BODIES=true",
+            interceptor.render_body(&SdkBody::from("secret"), &no_headers, true)
+        );
+
+        let interceptor = WireLoggingInterceptor::new().with_pretty(true);
+        assert_eq!(
+            "{\n  \"a\": 1\n}",
+            interceptor.render_body(
+                &SdkBody::from(r#"{"a":1}"#),
+                &headers(&[("content-type", "application/x-amz-json-1.0; charset=utf-8")]),
+                false
+            )
+        );
+        assert_eq!(
+            "[1, 2]",
+            interceptor.render_body(
+                &SdkBody::from(vec![0x82, 0x01, 0x02]),
+                &headers(&[("content-type", "application/cbor")]),
+                false
+            )
+        );
+    }
+
+    #[test]
+    #[traced_test]
+    fn logs_request() {
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.enter_serialization_phase();
+        context.set_request(
+            HttpRequest::try_from(
+                http_02x::Request::builder()
+                    .uri("https://example.com/foo")
+                    .header("authorization", "secret")
+                    .body(SdkBody::from("hello"))
+                    .unwrap(),
+            )
+            .unwrap(),
+        );
+        let _ = context.take_input();
+        context.enter_before_transmit_phase();
+        let rc = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::base();
+
+        WireLoggingInterceptor::new()
+            .read_before_transmit(&(&context).into(), &rc, &mut cfg)
+            .unwrap();
+        assert!(logs_contain("sending request"));
+        assert!(logs_contain("https://example.com/foo"));
+        assert!(logs_contain("authorization: ** REDACTED **"));
+        assert!(logs_contain("body=hello"));
+        assert!(!logs_contain("secret"));
+    }
+}
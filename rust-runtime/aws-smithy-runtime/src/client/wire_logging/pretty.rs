@@ -0,0 +1,429 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Best-effort pretty printers for logged bodies.
+//!
+//! These never fail: bodies may have been truncated to the logging size cap, so malformed
+//! input is printed as far as it makes sense and the remainder is left as-is.
+
+use std::fmt::Write;
+
+const INDENT: &str = "  ";
+
+fn newline(out: &mut String, depth: usize) {
+    out.push('\n');
+    for _ in 0..depth {
+        out.push_str(INDENT);
+    }
+}
+
+/// Re-indents JSON text.
+pub(super) fn json(input: &str) -> String {
+    let mut out = String::with_capacity(input.len() * 2);
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match (escaped, c) {
+                (true, _) => escaped = false,
+                (false, '\\') => escaped = true,
+                (false, '"') => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+                // Keep empty objects and arrays on a single line
+                if let Some(close) = chars.next_if(|&n| n == '}' || n == ']') {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_ascii_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+enum XmlToken<'a> {
+    Open(&'a str),
+    Close(&'a str),
+    // Self-closing elements, processing instructions, comments, and declarations
+    Standalone(&'a str),
+    Text(&'a str),
+}
+
+fn xml_tokens(input: &str) -> Vec<XmlToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = input;
+    while !rest.is_empty() {
+        if rest.starts_with('<') {
+            let end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+            let tag = &rest[..end];
+            tokens.push(if tag.starts_with("</") {
+                XmlToken::Close(tag)
+            } else if tag.ends_with("/>") || tag.starts_with("<?") || tag.starts_with("<!") {
+                XmlToken::Standalone(tag)
+            } else {
+                XmlToken::Open(tag)
+            });
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = rest[..end].trim();
+            if !text.is_empty() {
+                tokens.push(XmlToken::Text(text));
+            }
+            rest = &rest[end..];
+        }
+    }
+    tokens
+}
+
+/// Re-indents XML text, keeping elements that only contain text on a single line.
+pub(super) fn xml(input: &str) -> String {
+    let tokens = xml_tokens(input);
+    let mut out = String::with_capacity(input.len() * 2);
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < tokens.len() {
+        if !out.is_empty() {
+            if let XmlToken::Close(_) = tokens[i] {
+                depth = depth.saturating_sub(1);
+            }
+            newline(&mut out, depth);
+        }
+        match (&tokens[i], tokens.get(i + 1), tokens.get(i + 2)) {
+            (XmlToken::Open(open), Some(XmlToken::Text(text)), Some(XmlToken::Close(close))) => {
+                out.push_str(open);
+                out.push_str(text);
+                out.push_str(close);
+                i += 3;
+                continue;
+            }
+            (XmlToken::Open(open), Some(XmlToken::Close(close)), _) => {
+                out.push_str(open);
+                out.push_str(close);
+                i += 2;
+                continue;
+            }
+            (XmlToken::Open(open), _, _) => {
+                out.push_str(open);
+                depth += 1;
+            }
+            (XmlToken::Close(tag) | XmlToken::Standalone(tag) | XmlToken::Text(tag), _, _) => {
+                out.push_str(tag)
+            }
+        }
+        i += 1;
+    }
+    out
+}
+
+/// Renders CBOR in the diagnostic notation described in [RFC 8949 §8].
+///
+/// If the input ends early, whatever could be decoded is rendered followed by `<truncated>`.
+/// Items nested more than 64 levels deep are cut off the same way, followed by `<too deep>`.
+///
+/// [RFC 8949 §8]: https://www.rfc-editor.org/rfc/rfc8949.html#section-8
+pub(super) fn cbor(input: &[u8]) -> String {
+    let mut out = String::new();
+    let mut decoder = CborDiagnostic {
+        input,
+        position: 0,
+        depth: 0,
+        out: &mut out,
+    };
+    let mut first = true;
+    while decoder.position < decoder.input.len() {
+        if !first {
+            decoder.out.push_str(", ");
+        }
+        first = false;
+        match decoder.item() {
+            Ok(()) => {}
+            Err(Stop::Truncated) => {
+                decoder.out.push_str("<truncated>");
+                break;
+            }
+            Err(Stop::TooDeep) => {
+                decoder.out.push_str("<too deep>");
+                break;
+            }
+        }
+    }
+    out
+}
+
+// Input nested deeper than this is cut off rather than recursing any further
+const MAX_DEPTH: usize = 64;
+
+enum Stop {
+    Truncated,
+    TooDeep,
+}
+
+struct CborDiagnostic<'a> {
+    input: &'a [u8],
+    position: usize,
+    depth: usize,
+    out: &'a mut String,
+}
+
+const BREAK: u8 = 0xff;
+
+impl CborDiagnostic<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], Stop> {
+        let end = self.position.checked_add(len).ok_or(Stop::Truncated)?;
+        let bytes = self.input.get(self.position..end).ok_or(Stop::Truncated)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn peek_break(&mut self) -> Result<bool, Stop> {
+        match self.input.get(self.position) {
+            Some(&BREAK) => {
+                self.position += 1;
+                Ok(true)
+            }
+            Some(_) => Ok(false),
+            None => Err(Stop::Truncated),
+        }
+    }
+
+    /// Reads the argument of an item header. Returns `None` for indefinite lengths.
+    fn argument(&mut self, info: u8) -> Result<Option<u64>, Stop> {
+        Ok(Some(match info {
+            0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            _ => return Ok(None),
+        }))
+    }
+
+    fn length(&mut self, info: u8) -> Result<Option<usize>, Stop> {
+        self.argument(info)?
+            .map(|len| usize::try_from(len).map_err(|_| Stop::Truncated))
+            .transpose()
+    }
+
+    fn item(&mut self) -> Result<(), Stop> {
+        if self.depth == MAX_DEPTH {
+            return Err(Stop::TooDeep);
+        }
+        self.depth += 1;
+        let result = self.item_at_depth();
+        self.depth -= 1;
+        result
+    }
+
+    fn item_at_depth(&mut self) -> Result<(), Stop> {
+        let header = self.take(1)?[0];
+        let (major, info) = (header >> 5, header & 0x1f);
+        match major {
+            0 => {
+                let value = self.argument(info)?.ok_or(Stop::Truncated)?;
+                write!(self.out, "{value}").unwrap();
+            }
+            1 => {
+                let value = self.argument(info)?.ok_or(Stop::Truncated)?;
+                write!(self.out, "{}", -1 - value as i128).unwrap();
+            }
+            2 | 3 => match self.length(info)? {
+                Some(len) => self.string(major, len)?,
+                None => {
+                    self.out.push_str("(_ ");
+                    let mut first = true;
+                    while !self.peek_break()? {
+                        if !first {
+                            self.out.push_str(", ");
+                        }
+                        first = false;
+                        self.item()?;
+                    }
+                    self.out.push(')');
+                }
+            },
+            4 => self.collection('[', ']', info, 1)?,
+            5 => self.collection('{', '}', info, 2)?,
+            6 => {
+                let tag = self.argument(info)?.ok_or(Stop::Truncated)?;
+                write!(self.out, "{tag}(").unwrap();
+                self.item()?;
+                self.out.push(')');
+            }
+            _ => self.simple(info)?,
+        }
+        Ok(())
+    }
+
+    fn string(&mut self, major: u8, len: usize) -> Result<(), Stop> {
+        let bytes = self.take(len)?;
+        let rendered = if major == 2 {
+            let mut hex = String::with_capacity(len * 2 + 3);
+            hex.push_str("h'");
+            for b in bytes {
+                write!(hex, "{b:02x}").unwrap();
+            }
+            hex.push('\'');
+            hex
+        } else {
+            format!("{:?}", String::from_utf8_lossy(bytes))
+        };
+        self.out.push_str(&rendered);
+        Ok(())
+    }
+
+    fn collection(
+        &mut self,
+        open: char,
+        close: char,
+        info: u8,
+        items_per_entry: usize,
+    ) -> Result<(), Stop> {
+        self.out.push(open);
+        let len = self.length(info)?;
+        if len.is_none() {
+            self.out.push_str("_ ");
+        }
+        let mut index = 0usize;
+        loop {
+            match len {
+                // An overflowing length can't be satisfied, so the input will run out first
+                Some(len) if Some(index) == len.checked_mul(items_per_entry) => break,
+                None if self.peek_break()? => break,
+                _ => {}
+            }
+            if index > 0 {
+                // Map keys and values alternate
+                let is_value = items_per_entry == 2 && index % 2 == 1;
+                self.out.push_str(if is_value { ": " } else { ", " });
+            }
+            self.item()?;
+            index += 1;
+        }
+        self.out.push(close);
+        Ok(())
+    }
+
+    fn simple(&mut self, info: u8) -> Result<(), Stop> {
+        match info {
+            20 => self.out.push_str("false"),
+            21 => self.out.push_str("true"),
+            22 => self.out.push_str("null"),
+            23 => self.out.push_str("undefined"),
+            25 => {
+                let bits = u16::from_be_bytes(self.take(2)?.try_into().unwrap());
+                self.float(f16_to_f64(bits));
+            }
+            26 => {
+                let bits = u32::from_be_bytes(self.take(4)?.try_into().unwrap());
+                self.float(f32::from_bits(bits) as f64);
+            }
+            27 => {
+                let bits = u64::from_be_bytes(self.take(8)?.try_into().unwrap());
+                self.float(f64::from_bits(bits));
+            }
+            24 => {
+                let value = self.take(1)?[0];
+                write!(self.out, "simple({value})").unwrap();
+            }
+            _ => write!(self.out, "simple({info})").unwrap(),
+        }
+        Ok(())
+    }
+
+    fn float(&mut self, value: f64) {
+        if value.is_nan() {
+            self.out.push_str("NaN");
+        } else if value.is_infinite() {
+            self.out
+                .push_str(if value > 0.0 { "Infinity" } else { "-Infinity" });
+        } else {
+            write!(self.out, "{value:?}").unwrap();
+        }
+    }
+}
+
+fn f16_to_f64(bits: u16) -> f64 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f64;
+    sign * match exponent {
+        0 => mantissa * 2f64.powi(-24),
+        31 if mantissa == 0.0 => f64::INFINITY,
+        31 => f64::NAN,
+        _ => (1.0 + mantissa / 1024.0) * 2f64.powi(exponent - 15),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pretty_json() {
+        assert_eq!(
+            "{\n  \"a\": [\n    1,\n    \"x, }\"\n  ],\n  \"b\": {}\n}",
+            json(r#"{"a":[1, "x, }"],"b":{ }}"#)
+        );
+        // Stop::Truncated input is printed as far as it goes
+        assert_eq!("{\n  \"a\": \"tru", json(r#"{"a":"tru"#));
+    }
+
+    #[test]
+    fn pretty_xml() {
+        assert_eq!(
+            "<?xml version=\"1.0\"?>\n<List>\n  <Item>a</Item>\n  <Item/>\n  <Empty></Empty>\n</List>",
+            xml("<?xml version=\"1.0\"?><List><Item>a</Item><Item/><Empty></Empty></List>")
+        );
+    }
+
+    #[test]
+    fn cbor_diagnostic_notation() {
+        // {"a": [1, -2, h'ff'], "b": 1.5, "c": (_ "x", "y"), "d": 1(0)}
+        let input = [
+            0xa4, 0x61, b'a', 0x83, 0x01, 0x21, 0x41, 0xff, 0x61, b'b', 0xf9, 0x3e, 0x00, 0x61,
+            b'c', 0x7f, 0x61, b'x', 0x61, b'y', 0xff, 0x61, b'd', 0xc1, 0x00,
+        ];
+        assert_eq!(
+            r#"{"a": [1, -2, h'ff'], "b": 1.5, "c": (_ "x", "y"), "d": 1(0)}"#,
+            cbor(&input)
+        );
+        assert_eq!("[_ true, null]", cbor(&[0x9f, 0xf5, 0xf6, 0xff]));
+        assert_eq!("[1, <truncated>", cbor(&[0x82, 0x01]));
+        // A map claiming 2^63 entries doesn't overflow the entry count
+        assert_eq!("{<truncated>", cbor(&[0xbb, 0x80, 0, 0, 0, 0, 0, 0, 0]));
+        let nested = [0x81; 1000];
+        let rendered = cbor(&nested);
+        assert!(rendered.ends_with("[<too deep>"), "{rendered}");
+        assert_eq!(MAX_DEPTH, rendered.matches('[').count());
+    }
+}
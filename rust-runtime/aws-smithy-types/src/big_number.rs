@@ -0,0 +1,595 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Arbitrary precision numbers for Smithy's `bigInteger` and `bigDecimal` shapes.
+//!
+//! [`BigInteger`] and [`BigDecimal`] store the decimal text of a number rather than a binary
+//! representation, so that protocols can read and write them without losing precision. They
+//! intentionally don't support arithmetic; use a dedicated arbitrary precision arithmetic crate
+//! for that, converting through the string representation.
+
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::str::FromStr;
+
+/// The number of decimal digits that fit in a base 10^19 limb, the largest power of ten below 2^64
+const DECIMAL_LIMB_DIGITS: usize = 19;
+const DECIMAL_LIMB: u128 = 10u128.pow(DECIMAL_LIMB_DIGITS as u32);
+
+/// An error that occurs when parsing a [`BigInteger`] or [`BigDecimal`] from a string.
+#[derive(Debug)]
+pub struct BigNumberError {
+    kind: BigNumberErrorKind,
+}
+
+#[derive(Debug)]
+enum BigNumberErrorKind {
+    InvalidBigInteger(String),
+    InvalidBigDecimal(String),
+    ExponentOutOfRange(String),
+}
+
+impl fmt::Display for BigNumberError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use BigNumberErrorKind::*;
+        match &self.kind {
+            InvalidBigInteger(input) => write!(f, "`{input}` is not a valid big integer"),
+            InvalidBigDecimal(input) => write!(f, "`{input}` is not a valid big decimal"),
+            ExponentOutOfRange(input) => {
+                write!(f, "the exponent of big decimal `{input}` is out of range")
+            }
+        }
+    }
+}
+
+impl Error for BigNumberError {}
+
+/// An arbitrary precision integer.
+///
+/// The value is stored as its decimal string representation, which uses the JSON integer syntax:
+/// an optional `-` followed by digits without leading zeros. Parsing and then displaying a value
+/// returns the original string, except that `-0` is normalized to `0`.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_types::BigInteger;
+///
+/// let value: BigInteger = "-123456789012345678901234567890".parse().unwrap();
+/// assert!(value.is_negative());
+/// assert_eq!("123456789012345678901234567890", value.magnitude());
+/// assert_eq!(None, value.to_i64());
+/// assert_eq!("-123456789012345678901234567890", value.to_string());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigInteger {
+    value: String,
+}
+
+impl BigInteger {
+    /// Returns the decimal string representation of this integer.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns `true` if this integer is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.value.starts_with('-')
+    }
+
+    /// Returns the decimal digits of the absolute value of this integer.
+    pub fn magnitude(&self) -> &str {
+        self.value.strip_prefix('-').unwrap_or(&self.value)
+    }
+
+    /// Returns this integer as a `u64`, or `None` if it is out of range.
+    pub fn to_u64(&self) -> Option<u64> {
+        self.value.parse().ok()
+    }
+
+    /// Returns this integer as an `i64`, or `None` if it is out of range.
+    pub fn to_i64(&self) -> Option<i64> {
+        self.value.parse().ok()
+    }
+
+    /// Returns the absolute value of this integer as big-endian bytes, without leading zeros.
+    ///
+    /// Zero is represented by no bytes at all.
+    pub fn magnitude_to_be_bytes(&self) -> Vec<u8> {
+        // Little-endian base 2^64 limbs, built up from chunks of up to 19 decimal digits
+        let digits = self.magnitude().as_bytes();
+        let mut limbs: Vec<u64> = Vec::with_capacity(digits.len() / DECIMAL_LIMB_DIGITS + 1);
+        let first_chunk = match digits.len() % DECIMAL_LIMB_DIGITS {
+            0 => DECIMAL_LIMB_DIGITS,
+            len => len,
+        };
+        let (first, rest) = digits.split_at(first_chunk.min(digits.len()));
+        for chunk in std::iter::once(first).chain(rest.chunks(DECIMAL_LIMB_DIGITS)) {
+            let mut carry = chunk
+                .iter()
+                .fold(0u128, |value, digit| value * 10 + u128::from(digit - b'0'));
+            let scale = 10u128.pow(chunk.len() as u32);
+            for limb in limbs.iter_mut() {
+                let value = u128::from(*limb) * scale + carry;
+                *limb = value as u64;
+                carry = value >> 64;
+            }
+            if carry > 0 {
+                limbs.push(carry as u64);
+            }
+        }
+        let mut bytes: Vec<u8> = limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .collect();
+        let leading_zeros = bytes.iter().take_while(|&&byte| byte == 0).count();
+        bytes.drain(..leading_zeros);
+        bytes
+    }
+
+    /// Creates an integer from the big-endian bytes of its absolute value and its sign.
+    pub fn from_magnitude_be_bytes(negative: bool, magnitude: &[u8]) -> Self {
+        // Little-endian base 10^19 limbs, built up from chunks of up to 8 bytes
+        let mut limbs: Vec<u64> = Vec::with_capacity(magnitude.len() / 8 + 1);
+        let first_chunk = match magnitude.len() % 8 {
+            0 => 8,
+            len => len,
+        };
+        let (first, rest) = magnitude.split_at(first_chunk.min(magnitude.len()));
+        for chunk in std::iter::once(first).chain(rest.chunks(8)) {
+            let mut carry = chunk
+                .iter()
+                .fold(0u128, |value, byte| (value << 8) | u128::from(*byte));
+            let shift = 8 * chunk.len() as u32;
+            for limb in limbs.iter_mut() {
+                let value = (u128::from(*limb) << shift) + carry;
+                *limb = (value % DECIMAL_LIMB) as u64;
+                carry = value / DECIMAL_LIMB;
+            }
+            while carry > 0 {
+                limbs.push((carry % DECIMAL_LIMB) as u64);
+                carry /= DECIMAL_LIMB;
+            }
+        }
+        let Some((most_significant, rest)) = limbs.split_last() else {
+            return Self::from(0u64);
+        };
+        let mut value = String::with_capacity(limbs.len() * DECIMAL_LIMB_DIGITS + 1);
+        if negative {
+            value.push('-');
+        }
+        write!(value, "{most_significant}").expect("writing to a String is infallible");
+        for limb in rest.iter().rev() {
+            write!(value, "{limb:019}").expect("writing to a String is infallible");
+        }
+        Self { value }
+    }
+}
+
+impl FromStr for BigInteger {
+    type Err = BigNumberError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('-').unwrap_or(s);
+        if integer_digits_len(digits) != Some(digits.len()) {
+            return Err(BigNumberError {
+                kind: BigNumberErrorKind::InvalidBigInteger(s.into()),
+            });
+        }
+        let value = if digits == "0" { digits } else { s };
+        Ok(Self {
+            value: value.into(),
+        })
+    }
+}
+
+impl fmt::Display for BigInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl AsRef<str> for BigInteger {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+macro_rules! big_integer_from {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for BigInteger {
+                fn from(value: $t) -> Self {
+                    Self {
+                        value: value.to_string(),
+                    }
+                }
+            }
+        )+
+    };
+}
+
+big_integer_from!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl Default for BigInteger {
+    /// Returns zero.
+    fn default() -> Self {
+        Self::from(0u8)
+    }
+}
+
+/// An arbitrary precision decimal number.
+///
+/// The value is stored as its decimal string representation, which uses the JSON number syntax:
+/// an optional `-`, an integer part without leading zeros, an optional fraction, and an optional
+/// exponent. Parsing and then displaying a value returns the original string.
+///
+/// Equality compares representations, so `1.0` and `1.00` are not equal. Every value can also be
+/// viewed as a [mantissa and exponent](BigDecimal::mantissa_and_exponent), which is how binary
+/// protocols represent decimals.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_types::{BigDecimal, BigInteger};
+///
+/// let value: BigDecimal = "-1.50e-3".parse().unwrap();
+/// let (mantissa, exponent) = value.mantissa_and_exponent();
+/// assert_eq!("-150", mantissa.as_str());
+/// assert_eq!(-5, exponent);
+/// let rebuilt = BigDecimal::from_mantissa_and_exponent(&mantissa, exponent).unwrap();
+/// assert_eq!("-0.00150", rebuilt.as_str());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigDecimal {
+    value: String,
+}
+
+/// The parts of a valid big decimal string
+struct DecimalParts<'a> {
+    negative: bool,
+    integer: &'a str,
+    fraction: &'a str,
+    exponent: i64,
+}
+
+impl<'a> DecimalParts<'a> {
+    fn parse(s: &'a str) -> Result<Self, BigNumberError> {
+        let invalid = || BigNumberError {
+            kind: BigNumberErrorKind::InvalidBigDecimal(s.into()),
+        };
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let integer_len = integer_digits_len(rest).ok_or_else(invalid)?;
+        let (integer, rest) = rest.split_at(integer_len);
+        let (fraction, rest) = match rest.strip_prefix('.') {
+            Some(rest) => {
+                let len = rest.bytes().take_while(u8::is_ascii_digit).count();
+                if len == 0 {
+                    return Err(invalid());
+                }
+                rest.split_at(len)
+            }
+            None => ("", rest),
+        };
+        let exponent = match rest.strip_prefix(['e', 'E']) {
+            Some(exponent) => {
+                let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+                if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid());
+                }
+                exponent
+                    .strip_prefix('+')
+                    .unwrap_or(exponent)
+                    .parse()
+                    .map_err(|_| BigNumberError {
+                        kind: BigNumberErrorKind::ExponentOutOfRange(s.into()),
+                    })?
+            }
+            None if rest.is_empty() => 0,
+            None => return Err(invalid()),
+        };
+        Ok(Self {
+            negative,
+            integer,
+            fraction,
+            exponent,
+        })
+    }
+
+    /// The exponent of the mantissa formed by the integer and fraction digits
+    fn mantissa_exponent(&self) -> Option<i64> {
+        self.exponent
+            .checked_sub(i64::try_from(self.fraction.len()).ok()?)
+    }
+}
+
+impl BigDecimal {
+    /// Returns the decimal string representation of this number.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns `true` if this number is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.value.starts_with('-') && self.mantissa_and_exponent().0.is_negative()
+    }
+
+    /// Returns this number as an `f64`, rounding it to the nearest representable value.
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.value.parse().expect("big decimals are valid floats")
+    }
+
+    /// Splits this number into an integer mantissa and a base 10 exponent, such that the value
+    /// is `mantissa * 10^exponent`.
+    ///
+    /// The mantissa keeps trailing zeros, so `1.50` is split into `150` and `-2`.
+    pub fn mantissa_and_exponent(&self) -> (BigInteger, i64) {
+        let parts = DecimalParts::parse(&self.value).expect("big decimals are always valid");
+        let exponent = parts
+            .mantissa_exponent()
+            .expect("checked when the big decimal was created");
+        let digits = format!("{}{}", parts.integer, parts.fraction);
+        let digits = digits.trim_start_matches('0');
+        let mantissa = match (digits.is_empty(), parts.negative) {
+            (true, _) => BigInteger::from(0u64),
+            (false, true) => BigInteger {
+                value: format!("-{digits}"),
+            },
+            (false, false) => BigInteger {
+                value: digits.into(),
+            },
+        };
+        (mantissa, exponent)
+    }
+
+    /// Creates a number with the value `mantissa * 10^exponent`.
+    ///
+    /// Like Java's `BigDecimal.toString()`, the result uses plain notation for non-positive
+    /// exponents unless the number is very small, and scientific notation otherwise.
+    ///
+    /// Returns an error if the exponent of the result, when written with a single integer digit,
+    /// doesn't fit in an `i64`.
+    pub fn from_mantissa_and_exponent(
+        mantissa: &BigInteger,
+        exponent: i64,
+    ) -> Result<Self, BigNumberError> {
+        let digits = mantissa.magnitude();
+        let sign = if mantissa.is_negative() { "-" } else { "" };
+        // The exponent of the number when written with a single integer digit
+        let adjusted = i64::try_from(digits.len() - 1)
+            .ok()
+            .and_then(|shift| exponent.checked_add(shift))
+            .ok_or_else(|| BigNumberError {
+                kind: BigNumberErrorKind::ExponentOutOfRange(format!("{mantissa}E{exponent}")),
+            })?;
+        let value = if exponent == 0 {
+            mantissa.value.clone()
+        } else if exponent < 0 && adjusted >= -6 {
+            let scale = exponent.unsigned_abs() as usize;
+            if digits.len() > scale {
+                let (integer, fraction) = digits.split_at(digits.len() - scale);
+                format!("{sign}{integer}.{fraction}")
+            } else {
+                let zeros = "0".repeat(scale - digits.len());
+                format!("{sign}0.{zeros}{digits}")
+            }
+        } else {
+            let (first, rest) = digits.split_at(1);
+            let point = if rest.is_empty() { "" } else { "." };
+            format!("{sign}{first}{point}{rest}E{adjusted:+}")
+        };
+        Ok(Self { value })
+    }
+}
+
+impl FromStr for BigDecimal {
+    type Err = BigNumberError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = DecimalParts::parse(s)?;
+        if parts.mantissa_exponent().is_none() {
+            return Err(BigNumberError {
+                kind: BigNumberErrorKind::ExponentOutOfRange(s.into()),
+            });
+        }
+        Ok(Self { value: s.into() })
+    }
+}
+
+impl fmt::Display for BigDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.value)
+    }
+}
+
+impl AsRef<str> for BigDecimal {
+    fn as_ref(&self) -> &str {
+        &self.value
+    }
+}
+
+impl From<BigInteger> for BigDecimal {
+    fn from(value: BigInteger) -> Self {
+        Self { value: value.value }
+    }
+}
+
+macro_rules! big_decimal_from_integer {
+    ($($t:ty),+) => {
+        $(
+            impl From<$t> for BigDecimal {
+                fn from(value: $t) -> Self {
+                    BigInteger::from(value).into()
+                }
+            }
+        )+
+    };
+}
+
+big_decimal_from_integer!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+impl Default for BigDecimal {
+    /// Returns zero.
+    fn default() -> Self {
+        Self::from(0u8)
+    }
+}
+
+/// Returns the length of the JSON integer digits (`0` or a non-zero digit followed by digits)
+/// at the start of `s`, or `None` if `s` doesn't start with them.
+fn integer_digits_len(s: &str) -> Option<usize> {
+    match s.as_bytes().first()? {
+        b'0' => Some(1),
+        b'1'..=b'9' => Some(s.bytes().take_while(u8::is_ascii_digit).count()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{BigDecimal, BigInteger};
+
+    #[test]
+    fn big_integer_parsing() {
+        for valid in [
+            "0",
+            "7",
+            "-7",
+            "18446744073709551616",
+            "-99999999999999999999999",
+        ] {
+            let value: BigInteger = valid.parse().expect(valid);
+            assert_eq!(valid, value.as_str());
+        }
+        assert_eq!("0", "-0".parse::<BigInteger>().unwrap().as_str());
+        assert!(!"-0".parse::<BigInteger>().unwrap().is_negative());
+        for invalid in ["", "-", "+1", "01", "1.0", "1e3", " 1", "0x10", "١"] {
+            assert!(invalid.parse::<BigInteger>().is_err(), "{invalid}");
+        }
+        assert_eq!(Some(u64::MAX), BigInteger::from(u64::MAX).to_u64());
+        assert_eq!(None, BigInteger::from(u64::MAX).to_i64());
+        assert_eq!(Some(i64::MIN), BigInteger::from(i64::MIN).to_i64());
+        assert_eq!("0", BigInteger::default().as_str());
+    }
+
+    #[test]
+    fn big_integer_bytes_round_trip() {
+        let cases: &[(&str, &[u8])] = &[
+            ("0", &[]),
+            ("255", &[0xff]),
+            ("256", &[0x01, 0x00]),
+            ("18446744073709551615", &[0xff; 8]),
+            ("18446744073709551616", &[1, 0, 0, 0, 0, 0, 0, 0, 0]),
+            (
+                "9999999999999999999",
+                &[0x8a, 0xc7, 0x23, 0x04, 0x89, 0xe7, 0xff, 0xff],
+            ),
+            (
+                "10000000000000000000",
+                &[0x8a, 0xc7, 0x23, 0x04, 0x89, 0xe8, 0x00, 0x00],
+            ),
+            (
+                "340282366920938463463374607431768211456",
+                &[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+            ),
+        ];
+        for (value, bytes) in cases {
+            let value: BigInteger = value.parse().unwrap();
+            assert_eq!(*bytes, value.magnitude_to_be_bytes().as_slice());
+            assert_eq!(value, BigInteger::from_magnitude_be_bytes(false, bytes));
+        }
+        let huge = format!("-{}", "9".repeat(100));
+        let value: BigInteger = huge.parse().unwrap();
+        let bytes = value.magnitude_to_be_bytes();
+        assert_eq!(value, BigInteger::from_magnitude_be_bytes(true, &bytes));
+        assert_eq!(
+            BigInteger::from(0u64),
+            BigInteger::from_magnitude_be_bytes(true, &[0, 0])
+        );
+        assert_eq!(
+            BigInteger::from(5u64),
+            BigInteger::from_magnitude_be_bytes(
+                false,
+                &[0; 20].iter().chain(&[5]).copied().collect::<Vec<_>>()
+            )
+        );
+
+        // 2^(8 * 9999) has 24080 digits
+        let mut bytes = vec![0u8; 10_000];
+        bytes[0] = 1;
+        let value = BigInteger::from_magnitude_be_bytes(false, &bytes);
+        assert_eq!(24080, value.as_str().len());
+        assert_eq!(bytes, value.magnitude_to_be_bytes());
+    }
+
+    #[test]
+    fn big_decimal_parsing() {
+        for valid in [
+            "0",
+            "-0.0",
+            "1.50",
+            "123456789.123456789123456789",
+            "1e400",
+            "1E+3",
+            "-2.5e-10",
+        ] {
+            let value: BigDecimal = valid.parse().expect(valid);
+            assert_eq!(valid, value.to_string());
+        }
+        for invalid in [
+            "", "-", ".5", "5.", "01.5", "1e", "1e+", "1.5.5", "NaN", "Infinity",
+        ] {
+            assert!(invalid.parse::<BigDecimal>().is_err(), "{invalid}");
+        }
+        assert!(format!("1e{}", i64::MAX).parse::<BigDecimal>().is_ok());
+        assert!(format!("1.5e{}", i64::MIN).parse::<BigDecimal>().is_err());
+        assert!("1e99999999999999999999".parse::<BigDecimal>().is_err());
+        assert!(!"-0.0".parse::<BigDecimal>().unwrap().is_negative());
+        assert!("-0.1".parse::<BigDecimal>().unwrap().is_negative());
+        assert_eq!(
+            0.015,
+            "1.5e-2".parse::<BigDecimal>().unwrap().to_f64_lossy()
+        );
+        assert_eq!("0", BigDecimal::default().as_str());
+    }
+
+    #[test]
+    fn big_decimal_mantissa_and_exponent() {
+        let cases = [
+            ("0", "0", 0, "0"),
+            ("-0.0", "0", -1, "0.0"),
+            ("1.50", "150", -2, "1.50"),
+            ("-0.0015", "-15", -4, "-0.0015"),
+            ("12e3", "12", 3, "1.2E+4"),
+            ("1.5e-10", "15", -11, "1.5E-10"),
+            ("123.45e1", "12345", -1, "1234.5"),
+        ];
+        for (input, mantissa, exponent, formatted) in cases {
+            let value: BigDecimal = input.parse().unwrap();
+            let (actual_mantissa, actual_exponent) = value.mantissa_and_exponent();
+            assert_eq!(mantissa, actual_mantissa.as_str(), "{input}");
+            assert_eq!(exponent, actual_exponent, "{input}");
+            let rebuilt =
+                BigDecimal::from_mantissa_and_exponent(&actual_mantissa, exponent).unwrap();
+            assert_eq!(formatted, rebuilt.as_str(), "{input}");
+            assert!(formatted.parse::<BigDecimal>().is_ok());
+        }
+
+        let mantissa = BigInteger::from(12u64);
+        assert_eq!(
+            "1.2E+9223372036854775807",
+            BigDecimal::from_mantissa_and_exponent(&mantissa, i64::MAX - 1)
+                .unwrap()
+                .as_str()
+        );
+        let err = BigDecimal::from_mantissa_and_exponent(&mantissa, i64::MAX).unwrap_err();
+        assert_eq!(
+            "the exponent of big decimal `12E9223372036854775807` is out of range",
+            err.to_string()
+        );
+    }
+}
@@ -143,6 +143,8 @@ pub mod error;
 
 #[cfg(feature = "rt-tokio")]
 pub use self::bytestream_util::FsBuilder;
+#[cfg(feature = "rt-tokio")]
+pub use self::bytestream_util::{FileChunker, FilePart, PartChecksum};
 
 /// This module is named after the `http-body` version number since we anticipate
 /// needing to provide equivalent functionality for 1.x of that crate in the future.
@@ -414,6 +416,34 @@ impl ByteStream {
             .await
     }
 
+    /// Write this `ByteStream` into `file`, starting at `offset`
+    ///
+    /// Returns the number of bytes written. Writes are positional (`pwrite`) and don't use the
+    /// file's cursor on Unix, so several ranges of the same file can be written at once, e.g. when
+    /// downloading an object in parts. On Windows, the cursor of the file is moved by each write.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use aws_smithy_types::byte_stream::ByteStream;
+    ///
+    /// async fn write_part(part: ByteStream, offset: u64) -> Result<u64, Box<dyn std::error::Error>> {
+    ///     let file = tokio::fs::OpenOptions::new()
+    ///         .write(true)
+    ///         .create(true)
+    ///         .open("docs/some-large-file.csv")
+    ///         .await?;
+    ///     Ok(part.write_to_file_at(&file, offset).await?)
+    /// }
+    /// ```
+    #[cfg(feature = "rt-tokio")]
+    pub async fn write_to_file_at(
+        self,
+        file: &tokio::fs::File,
+        offset: u64,
+    ) -> Result<u64, crate::byte_stream::error::Error> {
+        bytestream_util::write_to_file_at(self, file, offset).await
+    }
+
     #[cfg(feature = "rt-tokio")]
     /// Convert this `ByteStream` into a struct that implements [`AsyncBufRead`](tokio::io::AsyncBufRead).
     ///
@@ -9,6 +9,7 @@ use std::cmp::min;
 use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
+use std::sync::Arc;
 use tokio::fs::File;
 use tokio::io::{self, AsyncReadExt, AsyncSeekExt};
 use tokio_util::io::ReaderStream;
@@ -22,6 +23,9 @@ mod http_body_0_4_x;
 #[cfg(feature = "http-body-1-x")]
 mod http_body_1_x;
 
+mod file_chunker;
+pub use file_chunker::{FileChunker, FilePart, PartChecksum};
+
 // 4KB corresponds to the default buffer size used by Tokio's ReaderStream
 const DEFAULT_BUFFER_SIZE: usize = 4096;
 // By default, read files from their start
@@ -231,6 +235,16 @@ impl FsBuilder {
         }
     }
 
+    /// Splits the file (or the window of it set with [`offset`](FsBuilder::offset) and
+    /// [`length`](FsBuilder::length)) into parts of `part_size` bytes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part_size` is zero or if this builder wasn't given a [`path`](FsBuilder::path).
+    pub fn chunked(self, part_size: u64) -> FileChunker {
+        FileChunker::new(self, part_size)
+    }
+
     async fn get_file_size(&self) -> Result<u64, Error> {
         Ok(match self.path.as_ref() {
             Some(path) => tokio::fs::metadata(path).await,
@@ -241,6 +255,58 @@ impl FsBuilder {
     }
 }
 
+/// Writes all of `stream` into `file` starting at `offset`, returning the number of bytes written.
+///
+/// Each write is positional, so other streams may write other ranges of the same file at once.
+pub(super) async fn write_to_file_at(
+    mut stream: ByteStream,
+    file: &File,
+    offset: u64,
+) -> Result<u64, Error> {
+    let file = Arc::new(file.try_clone().await?.into_std().await);
+    let mut position = offset;
+    while let Some(bytes) = stream.try_next().await? {
+        let length = bytes.len() as u64;
+        let file = file.clone();
+        tokio::task::spawn_blocking(move || write_all_at(&file, &bytes, position))
+            .await
+            .map_err(std::io::Error::other)??;
+        position += length;
+    }
+    Ok(position - offset)
+}
+
+#[cfg(unix)]
+fn write_all_at(file: &std::fs::File, buf: &[u8], offset: u64) -> std::io::Result<()> {
+    use std::os::unix::fs::FileExt;
+    file.write_all_at(buf, offset)
+}
+
+#[cfg(windows)]
+fn write_all_at(file: &std::fs::File, mut buf: &[u8], mut offset: u64) -> std::io::Result<()> {
+    use std::os::windows::fs::FileExt;
+    while !buf.is_empty() {
+        match file.seek_write(buf, offset) {
+            Ok(0) => return Err(std::io::ErrorKind::WriteZero.into()),
+            Ok(written) => {
+                buf = &buf[written..];
+                offset += written as u64;
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(any(unix, windows)))]
+fn write_all_at(_file: &std::fs::File, _buf: &[u8], _offset: u64) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "positional writes are not supported on this platform",
+    ))
+}
+
 enum State {
     Unloaded(PathBuf),
     Loading(Pin<Box<dyn Future<Output = io::Result<File>> + Send + Sync + 'static>>),
@@ -302,4 +368,81 @@ mod tests {
             assert_eq!(FILE_LEN as u64, lower);
         }
     }
+
+    struct Sum(u64);
+
+    impl PartChecksum for Sum {
+        fn update(&mut self, bytes: &[u8]) {
+            self.0 += bytes.iter().map(|b| *b as u64).sum::<u64>();
+        }
+
+        fn finalize(self: Box<Self>) -> bytes::Bytes {
+            self.0.to_be_bytes().to_vec().into()
+        }
+    }
+
+    #[tokio::test]
+    async fn chunked_parts_have_exact_lengths() {
+        let mut file = NamedTempFile::new().unwrap();
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        file.write_all(&data).unwrap();
+
+        let parts = FsBuilder::new()
+            .path(file.path())
+            .offset(100)
+            .length(Length::Exact(850))
+            .chunked(300)
+            .checksum(|| Box::new(Sum(0)))
+            .parts()
+            .await
+            .unwrap();
+        let windows: Vec<_> = parts
+            .iter()
+            .map(|part| (part.part_number(), part.offset(), part.length()))
+            .collect();
+        assert_eq!(vec![(1, 100, 300), (2, 400, 300), (3, 700, 250)], windows);
+
+        for part in &parts {
+            let range = part.offset() as usize..(part.offset() + part.length()) as usize;
+            let stream = part.stream().await.unwrap();
+            assert_eq!((part.length(), Some(part.length())), stream.size_hint());
+            let bytes = stream.collect().await.unwrap().into_bytes();
+            assert_eq!(&data[range.clone()], bytes.as_ref());
+
+            let sum: u64 = data[range].iter().map(|b| *b as u64).sum();
+            let checksum = part.checksum().await.unwrap().unwrap();
+            assert_eq!(sum.to_be_bytes(), checksum.as_ref());
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_file_has_one_empty_part() {
+        let file = NamedTempFile::new().unwrap();
+        let parts = FsBuilder::new()
+            .path(file.path())
+            .chunked(300)
+            .parts()
+            .await
+            .unwrap();
+        assert_eq!(1, parts.len());
+        assert_eq!(0, parts[0].length());
+        assert_eq!(None, parts[0].checksum().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn ranges_are_written_concurrently_at_their_offsets() {
+        let file = NamedTempFile::new().unwrap();
+        let target = File::from_std(file.reopen().unwrap());
+        // Write the ranges out of order, all at the same time
+        let written = tokio::try_join!(
+            ByteStream::from_static(b"ABCDEFGHIJ").write_to_file_at(&target, 20),
+            ByteStream::from_static(b"abcdefghij").write_to_file_at(&target, 10),
+            ByteStream::from_static(b"0123456789").write_to_file_at(&target, 0),
+        )
+        .unwrap();
+        assert_eq!((10, 10, 10), written);
+
+        let contents = std::fs::read(file.path()).unwrap();
+        assert_eq!(b"0123456789abcdefghijABCDEFGHIJ".as_slice(), contents);
+    }
 }
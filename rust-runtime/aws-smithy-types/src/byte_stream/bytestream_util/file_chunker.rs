@@ -0,0 +1,204 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::byte_stream::{error::Error, error::ErrorKind, ByteStream, FsBuilder, Length};
+use bytes::Bytes;
+use std::cmp::min;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// A checksum calculated over the bytes of a single [`FilePart`].
+///
+/// This has the same shape as the checksums in `aws-smithy-checksums`, so those can be adapted
+/// with a small wrapper.
+pub trait PartChecksum: Send {
+    /// Adds the given bytes to the checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of all bytes passed to [`update`](PartChecksum::update).
+    fn finalize(self: Box<Self>) -> Bytes;
+}
+
+type NewChecksum = Arc<dyn Fn() -> Box<dyn PartChecksum> + Send + Sync>;
+
+/// Splits a file into parts that can be read concurrently, e.g. for a multipart upload.
+///
+/// Created with [`FsBuilder::chunked`]. Every part except the last has exactly the configured
+/// part size. Each part opens its own file handle, so parts can be streamed from different tasks
+/// at the same time.
+///
+/// ```no_run
+/// # #[cfg(feature = "rt-tokio")]
+/// # {
+/// use aws_smithy_types::byte_stream::ByteStream;
+///
+/// async fn upload_in_parts() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+///     let parts = ByteStream::read_from()
+///         .path("docs/some-large-file.csv")
+///         .chunked(8 * 1024 * 1024)
+///         .parts()
+///         .await?;
+///     for part in parts {
+///         let body = part.stream().await?;
+///         // upload part `part.part_number()` with `body`
+///     }
+///     Ok(())
+/// }
+/// # }
+/// ```
+pub struct FileChunker {
+    path: PathBuf,
+    offset: u64,
+    length: Option<Length>,
+    buffer_size: usize,
+    part_size: u64,
+    checksum: Option<NewChecksum>,
+}
+
+impl fmt::Debug for FileChunker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileChunker")
+            .field("path", &self.path)
+            .field("offset", &self.offset)
+            .field("buffer_size", &self.buffer_size)
+            .field("part_size", &self.part_size)
+            .field("checksum", &self.checksum.is_some())
+            .finish()
+    }
+}
+
+impl FileChunker {
+    pub(super) fn new(builder: FsBuilder, part_size: u64) -> Self {
+        assert!(part_size > 0, "part size must be greater than zero");
+        let path = match (builder.path, builder.file) {
+            (Some(path), None) => path,
+            (None, Some(_)) => panic!(
+                "A FileChunker must be created from a path so that each part can open its own file handle"
+            ),
+            (Some(_), Some(_)) => panic!("The 'file' and 'path' options on an FsBuilder are mutually exclusive but both were set. Please set only one"),
+            (None, None) => panic!("FsBuilder constructed without a file or a path"),
+        };
+        Self {
+            path,
+            offset: builder.offset.unwrap_or_default(),
+            length: builder.length,
+            buffer_size: builder.buffer_size,
+            part_size,
+            checksum: None,
+        }
+    }
+
+    /// Calculate a checksum for each part with checksums created by `new_checksum`.
+    ///
+    /// The checksum of a part is calculated when [`FilePart::checksum`] is called, which reads
+    /// the part from disk.
+    pub fn checksum(
+        mut self,
+        new_checksum: impl Fn() -> Box<dyn PartChecksum> + Send + Sync + 'static,
+    ) -> Self {
+        self.checksum = Some(Arc::new(new_checksum));
+        self
+    }
+
+    /// Returns the parts of the file, in order.
+    ///
+    /// An empty file (or window of a file) has a single empty part.
+    pub async fn parts(self) -> Result<Vec<FilePart>, Error> {
+        let file_length = tokio::fs::metadata(&self.path).await?.len();
+        if self.offset > file_length {
+            return Err(ErrorKind::OffsetLargerThanFileSize.into());
+        }
+        let remaining_file_length = file_length - self.offset;
+        let length = match self.length {
+            Some(Length::Exact(length)) => {
+                if length > remaining_file_length {
+                    return Err(ErrorKind::LengthLargerThanFileSizeMinusReadOffset.into());
+                }
+                length
+            }
+            Some(Length::UpTo(length)) => min(length, remaining_file_length),
+            None => remaining_file_length,
+        };
+
+        let part_count = length.div_ceil(self.part_size).max(1);
+        Ok((0..part_count)
+            .map(|index| {
+                let start = index * self.part_size;
+                FilePart {
+                    path: self.path.clone(),
+                    part_number: index + 1,
+                    offset: self.offset + start,
+                    length: min(self.part_size, length - start),
+                    buffer_size: self.buffer_size,
+                    checksum: self.checksum.clone(),
+                }
+            })
+            .collect())
+    }
+}
+
+/// A window of a file produced by a [`FileChunker`].
+#[derive(Clone)]
+pub struct FilePart {
+    path: PathBuf,
+    part_number: u64,
+    offset: u64,
+    length: u64,
+    buffer_size: usize,
+    checksum: Option<NewChecksum>,
+}
+
+impl fmt::Debug for FilePart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilePart")
+            .field("path", &self.path)
+            .field("part_number", &self.part_number)
+            .field("offset", &self.offset)
+            .field("length", &self.length)
+            .finish()
+    }
+}
+
+impl FilePart {
+    /// Returns the 1-based number of this part.
+    pub fn part_number(&self) -> u64 {
+        self.part_number
+    }
+
+    /// Returns the offset of this part in the file.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the length of this part in bytes.
+    pub fn length(&self) -> u64 {
+        self.length
+    }
+
+    /// Returns a retryable [`ByteStream`] of exactly the bytes of this part.
+    pub async fn stream(&self) -> Result<ByteStream, Error> {
+        FsBuilder::new()
+            .path(&self.path)
+            .offset(self.offset)
+            .length(Length::Exact(self.length))
+            .buffer_size(self.buffer_size)
+            .build()
+            .await
+    }
+
+    /// Reads this part and returns its checksum, or `None` if the chunker has no checksum.
+    pub async fn checksum(&self) -> Result<Option<Bytes>, Error> {
+        let Some(new_checksum) = &self.checksum else {
+            return Ok(None);
+        };
+        let mut checksum = new_checksum();
+        let mut stream = self.stream().await?;
+        while let Some(bytes) = stream.try_next().await? {
+            checksum.update(&bytes);
+        }
+        Ok(Some(checksum.finalize()))
+    }
+}
@@ -130,9 +130,11 @@
 //! }
 //! ```
 //!
+mod dump;
 mod storable;
 mod typeid_map;
 
+use crate::config_bag::dump::Dump;
 use crate::config_bag::typeid_map::TypeIdMap;
 use crate::type_erasure::TypeErasedBox;
 use std::any::{type_name, TypeId};
@@ -144,6 +146,7 @@ use std::ops::Deref;
 use std::slice::Iter;
 use std::sync::Arc;
 
+pub use dump::{ConfigBagDump, ItemDump, LayerDump, StoreMode};
 pub use storable::{AppendItemIter, Storable, Store, StoreAppend, StoreReplace};
 
 /// [`FrozenLayer`] is the immutable and shareable form of [`Layer`].
@@ -260,13 +263,13 @@ impl CloneableLayer {
         self
     }
 
-    fn put_directly_cloneable<T: Store>(&mut self, value: T::StoredType) -> &mut Self
+    fn put_directly_cloneable<T: Dump>(&mut self, value: T::StoredType) -> &mut Self
     where
         T::StoredType: Clone,
     {
         self.0.props.insert(
             TypeId::of::<T::StoredType>(),
-            TypeErasedBox::new_with_clone(value),
+            Item::new::<T>(TypeErasedBox::new_with_clone(value)),
         );
         self
     }
@@ -315,26 +318,44 @@ impl CloneableLayer {
         self.put_directly_cloneable::<StoreAppend<T>>(Value::ExplicitlyUnset(type_name::<T>()));
     }
 
-    fn get_mut_or_default<T: Send + Sync + Store + 'static>(&mut self) -> &mut T::StoredType
+    fn get_mut_or_default<T: Dump>(&mut self) -> &mut T::StoredType
     where
         T::StoredType: Default + Clone,
     {
         self.0
             .props
             .entry(TypeId::of::<T::StoredType>())
-            .or_insert_with(|| TypeErasedBox::new_with_clone(T::StoredType::default()))
+            .or_insert_with(|| {
+                Item::new::<T>(TypeErasedBox::new_with_clone(T::StoredType::default()))
+            })
+            .value
             .downcast_mut()
             .expect("typechecked")
     }
 }
 
+/// An item stored in a [`Layer`], along with how to describe it in a [`LayerDump`]
+struct Item {
+    value: TypeErasedBox,
+    dump: fn(&TypeErasedBox) -> ItemDump,
+}
+
+impl Item {
+    fn new<T: Dump>(value: TypeErasedBox) -> Self {
+        Self {
+            value,
+            dump: T::dump,
+        }
+    }
+}
+
 /// A named layer comprising a config bag
 ///
 /// See the [module docs](crate::config_bag) for more documentation.
 #[derive(Default)]
 pub struct Layer {
     name: Cow<'static, str>,
-    props: TypeIdMap<TypeErasedBox>,
+    props: TypeIdMap<Item>,
 }
 
 impl Debug for Layer {
@@ -342,7 +363,9 @@ impl Debug for Layer {
         struct Items<'a>(&'a Layer);
         impl Debug for Items<'_> {
             fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-                f.debug_list().entries(self.0.props.values()).finish()
+                f.debug_list()
+                    .entries(self.0.props.values().map(|item| &item.value))
+                    .finish()
             }
         }
         f.debug_struct("Layer")
@@ -357,7 +380,17 @@ impl Layer {
         let new_props = self
             .props
             .iter()
-            .flat_map(|(tyid, erased)| erased.try_clone().map(|e| (*tyid, e)))
+            .flat_map(|(tyid, item)| {
+                item.value.try_clone().map(|value| {
+                    (
+                        *tyid,
+                        Item {
+                            value,
+                            dump: item.dump,
+                        },
+                    )
+                })
+            })
             .collect::<TypeIdMap<_>>();
         if new_props.len() == self.props.len() {
             Some(Layer {
@@ -370,9 +403,11 @@ impl Layer {
     }
 
     /// Inserts `value` into the layer directly
-    fn put_directly<T: Store>(&mut self, value: T::StoredType) -> &mut Self {
-        self.props
-            .insert(TypeId::of::<T::StoredType>(), TypeErasedBox::new(value));
+    fn put_directly<T: Dump>(&mut self, value: T::StoredType) -> &mut Self {
+        self.props.insert(
+            TypeId::of::<T::StoredType>(),
+            Item::new::<T>(TypeErasedBox::new(value)),
+        );
         self
     }
 
@@ -381,6 +416,14 @@ impl Layer {
         self.props.is_empty()
     }
 
+    /// Returns a snapshot of every type stored in this layer, for debugging.
+    pub fn dump(&self) -> LayerDump {
+        LayerDump::new(
+            &self.name,
+            self.props.values().map(|item| (item.dump)(&item.value)),
+        )
+    }
+
     /// Converts this layer into a frozen layer that can no longer be mutated.
     pub fn freeze(self) -> FrozenLayer {
         self.into()
@@ -492,25 +535,26 @@ impl Layer {
     fn get<T: Send + Sync + Store + 'static>(&self) -> Option<&T::StoredType> {
         self.props
             .get(&TypeId::of::<T::StoredType>())
-            .map(|t| t.downcast_ref().expect("typechecked"))
+            .map(|t| t.value.downcast_ref().expect("typechecked"))
     }
 
     /// Returns a mutable reference to `T` if it is stored in this layer
     fn get_mut<T: Send + Sync + Store + 'static>(&mut self) -> Option<&mut T::StoredType> {
         self.props
             .get_mut(&TypeId::of::<T::StoredType>())
-            .map(|t| t.downcast_mut().expect("typechecked"))
+            .map(|t| t.value.downcast_mut().expect("typechecked"))
     }
 
     /// Returns a mutable reference to `T` if it is stored in this layer, otherwise returns the
     /// [`Default`] implementation of `T`
-    fn get_mut_or_default<T: Send + Sync + Store + 'static>(&mut self) -> &mut T::StoredType
+    fn get_mut_or_default<T: Dump>(&mut self) -> &mut T::StoredType
     where
         T::StoredType: Default,
     {
         self.props
             .entry(TypeId::of::<T::StoredType>())
-            .or_insert_with(|| TypeErasedBox::new(T::StoredType::default()))
+            .or_insert_with(|| Item::new::<T>(TypeErasedBox::new(T::StoredType::default())))
+            .value
             .downcast_mut()
             .expect("typechecked")
     }
@@ -698,6 +742,36 @@ impl ConfigBag {
         }
     }
 
+    /// Returns a snapshot of every layer in this bag, for debugging.
+    ///
+    /// The dump shows which layer each value was stored in, and which layer overrides it. This
+    /// can be called from an interceptor to report the configuration a request was sent with.
+    ///
+    /// ```
+    /// use aws_smithy_types::config_bag::{ConfigBag, Layer, Storable, StoreReplace};
+    ///
+    /// #[derive(Debug)]
+    /// struct Region(&'static str);
+    /// impl Storable for Region {
+    ///     type Storer = StoreReplace<Self>;
+    /// }
+    ///
+    /// let mut client = Layer::new("client");
+    /// client.store_put(Region("us-east-1"));
+    /// let mut operation = Layer::new("operation");
+    /// operation.store_put(Region("eu-west-1"));
+    /// let bag = ConfigBag::of_layers(vec![client, operation]);
+    ///
+    /// let dump = bag.dump();
+    /// let client = &dump.layers()[2];
+    /// assert_eq!(client.name(), "client");
+    /// assert_eq!(client.items()[0].overridden_by(), Some("operation"));
+    /// println!("{dump}");
+    /// ```
+    pub fn dump(&self) -> ConfigBagDump {
+        ConfigBagDump::new(self.layers().map(Layer::dump).collect())
+    }
+
     /// Add a new layer with `name` after freezing the top layer so far
     pub fn add_layer(self, name: impl Into<Cow<'static, str>>) -> ConfigBag {
         self.with_fn(name, |_| {})
@@ -0,0 +1,384 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Inspectable snapshots of what is stored in a config bag, for debugging.
+
+use crate::config_bag::value::Value;
+use crate::config_bag::{Store, StoreAppend, StoreReplace};
+use crate::type_erasure::TypeErasedBox;
+use std::any::{type_name, TypeId};
+use std::collections::HashMap;
+use std::fmt::{self, Debug, Display, Formatter, Write};
+
+/// Describes a stored item of a [`Store`] for a [`LayerDump`]
+pub(super) trait Dump: Store {
+    fn dump(value: &TypeErasedBox) -> ItemDump;
+}
+
+impl<U: Send + Sync + Debug + 'static> Dump for StoreReplace<U> {
+    fn dump(value: &TypeErasedBox) -> ItemDump {
+        let value = value.downcast_ref::<Value<U>>().expect("typechecked");
+        ItemDump {
+            type_id: TypeId::of::<Value<U>>(),
+            type_name: type_name::<U>(),
+            mode: StoreMode::Replace,
+            values: match value {
+                Value::Set(value) => vec![format!("{value:?}")],
+                Value::ExplicitlyUnset(_) => vec![],
+            },
+            unset: matches!(value, Value::ExplicitlyUnset(_)),
+            overridden_by: None,
+        }
+    }
+}
+
+impl<U: Send + Sync + Debug + 'static> Dump for StoreAppend<U> {
+    fn dump(value: &TypeErasedBox) -> ItemDump {
+        let value = value.downcast_ref::<Value<Vec<U>>>().expect("typechecked");
+        ItemDump {
+            type_id: TypeId::of::<Value<Vec<U>>>(),
+            type_name: type_name::<U>(),
+            mode: StoreMode::Append,
+            values: match value {
+                Value::Set(values) => values.iter().map(|value| format!("{value:?}")).collect(),
+                Value::ExplicitlyUnset(_) => vec![],
+            },
+            unset: matches!(value, Value::ExplicitlyUnset(_)),
+            overridden_by: None,
+        }
+    }
+}
+
+/// How an item is stored in a bag; see [`StoreReplace`] and [`StoreAppend`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StoreMode {
+    /// The item was stored with [`StoreReplace`]
+    Replace,
+    /// The item was stored with [`StoreAppend`]
+    Append,
+}
+
+impl Display for StoreMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Replace => f.pad("replace"),
+            Self::Append => f.pad("append"),
+        }
+    }
+}
+
+/// A snapshot of one type stored in a layer.
+#[derive(Clone, Debug)]
+pub struct ItemDump {
+    type_id: TypeId,
+    type_name: &'static str,
+    mode: StoreMode,
+    values: Vec<String>,
+    unset: bool,
+    overridden_by: Option<String>,
+}
+
+impl ItemDump {
+    /// Returns the name of the stored type.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns how the type is stored.
+    pub fn mode(&self) -> StoreMode {
+        self.mode
+    }
+
+    /// Returns the `Debug` rendering of each stored value.
+    ///
+    /// A [`StoreReplace`] item has at most one value, and an unset item has none.
+    pub fn values(&self) -> &[String] {
+        &self.values
+    }
+
+    /// Returns true if the type was explicitly unset (or cleared) in this layer.
+    pub fn is_unset(&self) -> bool {
+        self.unset
+    }
+
+    /// Returns the name of the layer that hides this item, if any.
+    ///
+    /// Only set in a [`ConfigBagDump`]. A [`StoreReplace`] item is hidden by any layer above it
+    /// that stores or unsets the same type; a [`StoreAppend`] item is only hidden by a layer above
+    /// it that clears the type.
+    pub fn overridden_by(&self) -> Option<&str> {
+        self.overridden_by.as_deref()
+    }
+
+    fn value(&self) -> String {
+        match (self.unset, self.mode) {
+            (true, _) => "<unset>".into(),
+            (false, StoreMode::Replace) => self.values.concat(),
+            (false, StoreMode::Append) => format!("[{}]", self.values.join(", ")),
+        }
+    }
+}
+
+/// A snapshot of everything stored in a [`Layer`](super::Layer).
+#[derive(Clone, Debug)]
+pub struct LayerDump {
+    name: String,
+    items: Vec<ItemDump>,
+}
+
+impl LayerDump {
+    pub(super) fn new(name: &str, items: impl Iterator<Item = ItemDump>) -> Self {
+        let mut items: Vec<_> = items.collect();
+        items.sort_by_key(|item| item.type_name);
+        Self {
+            name: name.into(),
+            items,
+        }
+    }
+
+    /// Returns the name of the layer.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the items stored in the layer, sorted by type name.
+    pub fn items(&self) -> &[ItemDump] {
+        &self.items
+    }
+}
+
+/// A snapshot of every layer of a [`ConfigBag`](super::ConfigBag), from the interceptor state down.
+///
+/// The [`Display`] implementation renders the same table as [`to_table`](ConfigBagDump::to_table).
+#[derive(Clone, Debug)]
+pub struct ConfigBagDump {
+    layers: Vec<LayerDump>,
+}
+
+impl ConfigBagDump {
+    /// Creates a dump from layers ordered from the top of the bag down, marking which items are
+    /// overridden by layers above them.
+    pub(super) fn new(mut layers: Vec<LayerDump>) -> Self {
+        let mut resolved_by: HashMap<TypeId, String> = HashMap::new();
+        for layer in &mut layers {
+            for item in &mut layer.items {
+                if let Some(name) = resolved_by.get(&item.type_id) {
+                    item.overridden_by = Some(name.clone());
+                } else if item.mode == StoreMode::Replace || item.unset {
+                    resolved_by.insert(item.type_id, layer.name.clone());
+                }
+            }
+        }
+        Self { layers }
+    }
+
+    /// Returns the layers of the bag, from the top of the bag down.
+    pub fn layers(&self) -> &[LayerDump] {
+        &self.layers
+    }
+
+    /// Renders the dump as a plain text table with one section per layer.
+    pub fn to_table(&self) -> String {
+        let width = self
+            .layers
+            .iter()
+            .flat_map(|layer| &layer.items)
+            .map(|item| item.type_name.len())
+            .max()
+            .unwrap_or_default();
+        let mut out = String::new();
+        for layer in &self.layers {
+            let _ = writeln!(out, "layer {:?}", layer.name);
+            if layer.items.is_empty() {
+                out.push_str("  (empty)\n");
+            }
+            for item in &layer.items {
+                let _ = write!(
+                    out,
+                    "  {:width$}  {:7}  {}",
+                    item.type_name,
+                    item.mode,
+                    item.value()
+                );
+                if let Some(name) = &item.overridden_by {
+                    let _ = write!(out, "  (overridden by {name:?})");
+                }
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    /// Renders the dump as a JSON document.
+    pub fn to_json(&self) -> String {
+        let mut out = String::from("{\"layers\":[");
+        for (index, layer) in self.layers.iter().enumerate() {
+            if index > 0 {
+                out.push(',');
+            }
+            out.push_str("{\"name\":");
+            push_json_string(&mut out, &layer.name);
+            out.push_str(",\"items\":[");
+            for (index, item) in layer.items.iter().enumerate() {
+                if index > 0 {
+                    out.push(',');
+                }
+                out.push_str("{\"type\":");
+                push_json_string(&mut out, item.type_name);
+                let _ = write!(
+                    out,
+                    ",\"mode\":\"{}\",\"unset\":{},\"values\":[",
+                    item.mode, item.unset
+                );
+                for (index, value) in item.values.iter().enumerate() {
+                    if index > 0 {
+                        out.push(',');
+                    }
+                    push_json_string(&mut out, value);
+                }
+                out.push_str("],\"overridden_by\":");
+                match &item.overridden_by {
+                    Some(name) => push_json_string(&mut out, name),
+                    None => out.push_str("null"),
+                }
+                out.push('}');
+            }
+            out.push_str("]}");
+        }
+        out.push_str("]}");
+        out
+    }
+}
+
+impl Display for ConfigBagDump {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_table())
+    }
+}
+
+fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod test {
+    use crate::config_bag::{ConfigBag, Layer, Storable, StoreAppend, StoreMode, StoreReplace};
+
+    #[allow(dead_code)]
+    #[derive(Clone, Debug)]
+    struct Region(&'static str);
+    impl Storable for Region {
+        type Storer = StoreReplace<Self>;
+    }
+
+    #[allow(dead_code)]
+    #[derive(Clone, Debug)]
+    struct Retries(u32);
+    impl Storable for Retries {
+        type Storer = StoreReplace<Self>;
+    }
+
+    #[allow(dead_code)]
+    #[derive(Clone, Debug)]
+    struct Plugin(&'static str);
+    impl Storable for Plugin {
+        type Storer = StoreAppend<Self>;
+    }
+
+    fn bag() -> ConfigBag {
+        let mut base = Layer::new("base");
+        base.store_put(Region("us-east-1"));
+        base.store_put(Retries(3));
+        base.store_append(Plugin("a"));
+        base.store_append(Plugin("b"));
+
+        let mut client = Layer::new("client");
+        client.store_put(Region("eu-west-1"));
+        client.store_append(Plugin("c"));
+
+        let mut operation = Layer::new("operation");
+        operation.unset::<Retries>();
+
+        ConfigBag::of_layers(vec![base, client, operation])
+    }
+
+    #[test]
+    fn dumps_layers_with_provenance() {
+        let dump = bag().dump();
+        let names: Vec<_> = dump.layers().iter().map(|layer| layer.name()).collect();
+        assert_eq!(
+            vec!["interceptor_state", "operation", "client", "base"],
+            names
+        );
+
+        let operation = &dump.layers()[1].items()[0];
+        assert!(operation.type_name().ends_with("Retries"));
+        assert!(operation.is_unset());
+        assert!(operation.values().is_empty());
+
+        let client = dump.layers()[2].items();
+        assert_eq!(StoreMode::Append, client[0].mode());
+        assert_eq!(["Plugin(\"c\")"], client[0].values());
+        assert_eq!(None, client[0].overridden_by());
+        assert_eq!(["Region(\"eu-west-1\")"], client[1].values());
+
+        let base = dump.layers()[3].items();
+        assert_eq!(["Plugin(\"a\")", "Plugin(\"b\")"], base[0].values());
+        assert_eq!(None, base[0].overridden_by());
+        assert_eq!(Some("client"), base[1].overridden_by());
+        assert_eq!(Some("operation"), base[2].overridden_by());
+    }
+
+    #[test]
+    fn renders_a_table() {
+        let table = bag()
+            .dump()
+            .to_table()
+            .replace("aws_smithy_types::config_bag::dump::test::", "");
+        assert_eq!(
+            "layer \"interceptor_state\"\n\
+             \x20 (empty)\n\
+             layer \"operation\"\n\
+             \x20 Retries  replace  <unset>\n\
+             layer \"client\"\n\
+             \x20 Plugin   append   [Plugin(\"c\")]\n\
+             \x20 Region   replace  Region(\"eu-west-1\")\n\
+             layer \"base\"\n\
+             \x20 Plugin   append   [Plugin(\"a\"), Plugin(\"b\")]\n\
+             \x20 Region   replace  Region(\"us-east-1\")  (overridden by \"client\")\n\
+             \x20 Retries  replace  Retries(3)  (overridden by \"operation\")\n",
+            table
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let mut layer = Layer::new("only");
+        layer.store_put(Region("quote\"d"));
+        let json = ConfigBag::of_layers(vec![layer])
+            .dump()
+            .to_json()
+            .replace("aws_smithy_types::config_bag::dump::test::", "");
+        assert_eq!(
+            r#"{"layers":[{"name":"interceptor_state","items":[]},{"name":"only","items":[{"type":"Region","mode":"replace","unset":false,"values":["Region(\"quote\\\"d\")"],"overridden_by":null}]}]}"#,
+            json
+        );
+    }
+}
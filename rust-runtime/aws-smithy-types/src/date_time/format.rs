@@ -163,29 +163,8 @@ pub(crate) mod http_date {
         }
         let structured = OffsetDateTime::from_unix_timestamp_nanos(date_time.as_nanos())
             .map_err(out_of_range)?;
-        let weekday = match structured.weekday() {
-            Weekday::Monday => "Mon",
-            Weekday::Tuesday => "Tue",
-            Weekday::Wednesday => "Wed",
-            Weekday::Thursday => "Thu",
-            Weekday::Friday => "Fri",
-            Weekday::Saturday => "Sat",
-            Weekday::Sunday => "Sun",
-        };
-        let month = match structured.month() {
-            Month::January => "Jan",
-            Month::February => "Feb",
-            Month::March => "Mar",
-            Month::April => "Apr",
-            Month::May => "May",
-            Month::June => "Jun",
-            Month::July => "Jul",
-            Month::August => "Aug",
-            Month::September => "Sep",
-            Month::October => "Oct",
-            Month::November => "Nov",
-            Month::December => "Dec",
-        };
+        let weekday = &weekday_name(structured.weekday())[..3];
+        let month = month_abbreviation(structured.month());
         let mut out = String::with_capacity(32);
         fn push_digit(out: &mut String, digit: u8) {
             debug_assert!(digit < 10);
@@ -245,7 +224,8 @@ pub(crate) mod http_date {
     /// Parse an IMF-fixdate formatted date into a DateTime
     ///
     /// This function has a few caveats:
-    /// 1. It DOES NOT support the "deprecated" formats supported by HTTP date
+    /// 1. It DOES NOT support the "deprecated" formats supported by HTTP date. See
+    ///    [`parse_rfc850`] and [`parse_asctime`] for those.
     /// 2. It supports up to 3 digits of subsecond precision
     ///
     /// Ok: "Mon, 16 Dec 2019 23:48:18 GMT"
@@ -328,32 +308,189 @@ pub(crate) mod http_date {
             )
         })?;
 
-        let month = match &s[7..12] {
-            b" Jan " => Month::January,
-            b" Feb " => Month::February,
-            b" Mar " => Month::March,
-            b" Apr " => Month::April,
-            b" May " => Month::May,
-            b" Jun " => Month::June,
-            b" Jul " => Month::July,
-            b" Aug " => Month::August,
-            b" Sep " => Month::September,
-            b" Oct " => Month::October,
-            b" Nov " => Month::November,
-            b" Dec " => Month::December,
-            month => {
-                return Err(DateTimeParseErrorKind::Invalid(
+        if s[7] != b' ' || s[11] != b' ' {
+            return Err(DateTimeParseErrorKind::Invalid("incorrectly shaped string".into()).into());
+        }
+        let month = parse_month(&s[8..11])?;
+        let year = parse_slice(&s[12..16])?;
+        let day = parse_slice(&s[5..7])?;
+        to_date_time(year, month, day, time)
+    }
+
+    /// Format a `DateTime` in the obsolete RFC-850 HTTP date format
+    ///
+    /// Example: "Monday, 16-Dec-19 23:48:18 GMT"
+    ///
+    /// Since the year only has two digits, the century is lost. Subsecond nanos are not emitted.
+    pub(crate) fn format_rfc850(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        let structured = structured(date_time)?;
+        Ok(format!(
+            "{}, {:02}-{}-{:02} {:02}:{:02}:{:02} GMT",
+            weekday_name(structured.weekday()),
+            structured.day(),
+            month_abbreviation(structured.month()),
+            structured.year().rem_euclid(100),
+            structured.hour(),
+            structured.minute(),
+            structured.second(),
+        ))
+    }
+
+    /// Parse a date in the obsolete RFC-850 HTTP date format
+    ///
+    /// A two digit year is interpreted as the year with the same last two digits that is more than
+    /// 50 years before, and no more than 50 years after, `pivot_year`.
+    ///
+    /// Ok: "Sunday, 06-Nov-94 08:49:37 GMT"
+    pub(crate) fn parse_rfc850(s: &str, pivot_year: i32) -> Result<DateTime, DateTimeParseError> {
+        if !s.is_ascii() {
+            return Err(DateTimeParseErrorKind::Invalid("date-time must be ASCII".into()).into());
+        }
+        let (weekday, s) = s
+            .trim()
+            .split_once(", ")
+            .ok_or_else(|| DateTimeParseErrorKind::Invalid("incorrectly shaped string".into()))?;
+        let s = s.as_bytes();
+        // Example: `06-Nov-94 08:49:37 GMT`
+        if !WEEKDAYS.contains(&weekday)
+            || s.len() != 22
+            || !s.ends_with(b" GMT")
+            || s[2] != b'-'
+            || s[6] != b'-'
+            || s[9] != b' '
+            || s[12] != b':'
+            || s[15] != b':'
+        {
+            return Err(DateTimeParseErrorKind::Invalid("incorrectly shaped string".into()).into());
+        }
+        let day = parse_slice(&s[0..2])?;
+        let month = parse_month(&s[3..6])?;
+        let two_digit_year: i32 = parse_slice(&s[7..9])?;
+        let time = parse_time(&s[10..12], &s[13..15], &s[16..18])?;
+
+        let mut year = pivot_year - pivot_year.rem_euclid(100) + two_digit_year;
+        if year > pivot_year + 50 {
+            year -= 100;
+        } else if year <= pivot_year - 50 {
+            year += 100;
+        }
+        to_date_time(year, month, day, time)
+    }
+
+    /// Format a `DateTime` in the obsolete ANSI C `asctime()` HTTP date format
+    ///
+    /// Example: "Mon Dec 16 23:48:18 2019"
+    ///
+    /// Subsecond nanos are not emitted
+    pub(crate) fn format_asctime(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        let structured = structured(date_time)?;
+        Ok(format!(
+            "{} {} {:>2} {:02}:{:02}:{:02} {:04}",
+            &weekday_name(structured.weekday())[..3],
+            month_abbreviation(structured.month()),
+            structured.day(),
+            structured.hour(),
+            structured.minute(),
+            structured.second(),
+            structured.year(),
+        ))
+    }
+
+    /// Parse a date in the obsolete ANSI C `asctime()` HTTP date format
+    ///
+    /// Ok: "Sun Nov  6 08:49:37 1994"
+    pub(crate) fn parse_asctime(s: &str) -> Result<DateTime, DateTimeParseError> {
+        if !s.is_ascii() {
+            return Err(DateTimeParseErrorKind::Invalid("date-time must be ASCII".into()).into());
+        }
+        let s = s.trim().as_bytes();
+        if s.len() != ASCTIME_LEN
+            || !WEEKDAYS.iter().any(|name| name.as_bytes()[..3] == s[0..3])
+            || s[3] != b' '
+            || s[7] != b' '
+            || s[10] != b' '
+            || s[13] != b':'
+            || s[16] != b':'
+            || s[19] != b' '
+        {
+            return Err(DateTimeParseErrorKind::Invalid("incorrectly shaped string".into()).into());
+        }
+        let month = parse_month(&s[4..7])?;
+        // Single digit days are padded with a space
+        let day = match s[8] {
+            b' ' => parse_slice(&s[9..10])?,
+            _ => parse_slice(&s[8..10])?,
+        };
+        let time = parse_time(&s[11..13], &s[14..16], &s[17..19])?;
+        let year = parse_slice(&s[20..24])?;
+        to_date_time(year, month, day, time)
+    }
+
+    /// The length of an `asctime()` date
+    const ASCTIME_LEN: usize = 24;
+
+    const WEEKDAYS: [&str; 7] = [
+        "Monday",
+        "Tuesday",
+        "Wednesday",
+        "Thursday",
+        "Friday",
+        "Saturday",
+        "Sunday",
+    ];
+
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+
+    fn weekday_name(weekday: Weekday) -> &'static str {
+        WEEKDAYS[weekday.number_days_from_monday() as usize]
+    }
+
+    fn month_abbreviation(month: Month) -> &'static str {
+        MONTHS[u8::from(month) as usize - 1]
+    }
+
+    fn parse_month(abbreviation: &[u8]) -> Result<Month, DateTimeParseError> {
+        let index = MONTHS
+            .iter()
+            .position(|month| month.as_bytes() == abbreviation)
+            .ok_or_else(|| {
+                DateTimeParseErrorKind::Invalid(
                     format!(
                         "invalid month: {}",
-                        std::str::from_utf8(month).unwrap_or_default()
+                        std::str::from_utf8(abbreviation).unwrap_or_default()
                     )
                     .into(),
                 )
-                .into())
-            }
-        };
-        let year = parse_slice(&s[12..16])?;
-        let day = parse_slice(&s[5..7])?;
+            })?;
+        Ok(Month::try_from(index as u8 + 1).expect("valid month number"))
+    }
+
+    fn parse_time(
+        hours: &[u8],
+        minutes: &[u8],
+        seconds: &[u8],
+    ) -> Result<Time, DateTimeParseError> {
+        Time::from_hms(
+            parse_slice(hours)?,
+            parse_slice(minutes)?,
+            parse_slice(seconds)?,
+        )
+        .map_err(|err| {
+            DateTimeParseErrorKind::Invalid(
+                format!("time components are out of range: {}", err).into(),
+            )
+            .into()
+        })
+    }
+
+    fn to_date_time(
+        year: i32,
+        month: Month,
+        day: u8,
+        time: Time,
+    ) -> Result<DateTime, DateTimeParseError> {
         let date = Date::from_calendar_date(year, month, day).map_err(|err| {
             DateTimeParseErrorKind::Invalid(
                 format!("date components are out of range: {}", err).into(),
@@ -365,6 +502,22 @@ pub(crate) mod http_date {
             .expect("this date format cannot produce out of range date-times"))
     }
 
+    /// Breaks a `DateTime` into calendar components, supporting years 0001 through 9999
+    fn structured(date_time: &DateTime) -> Result<OffsetDateTime, DateTimeFormatError> {
+        let out_of_range = || -> DateTimeFormatError {
+            DateTimeFormatErrorKind::OutOfRange(
+                "HTTP dates support dates between the years 0001 and 9999".into(),
+            )
+            .into()
+        };
+        let structured = OffsetDateTime::from_unix_timestamp_nanos(date_time.as_nanos())
+            .map_err(|_| out_of_range())?;
+        if !(1..=9_999).contains(&structured.year()) {
+            return Err(out_of_range());
+        }
+        Ok(structured)
+    }
+
     fn parse_slice<T>(ascii_slice: &[u8]) -> Result<T, DateTimeParseError>
     where
         T: FromStr,
@@ -432,6 +585,47 @@ pub(crate) mod rfc3339 {
 
     /// Format a [DateTime] in the RFC-3339 date format
     pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        let mut out = format_local(date_time.as_nanos())?;
+        out.push('Z');
+        Ok(out)
+    }
+
+    /// Format a [DateTime] in the RFC-3339 date format, as the local time at the given UTC offset
+    ///
+    /// The offset must be a whole number of minutes less than 24 hours in either direction.
+    /// A zero offset is written as `+00:00` rather than `Z`.
+    pub(crate) fn format_with_offset(
+        date_time: &DateTime,
+        offset_seconds: i32,
+    ) -> Result<String, DateTimeFormatError> {
+        use std::fmt::Write;
+        if offset_seconds % 60 != 0 || offset_seconds.unsigned_abs() >= 24 * 60 * 60 {
+            return Err(DateTimeFormatErrorKind::OutOfRange(
+                format!(
+                    "UTC offsets must be a whole number of minutes less than 24 hours, \
+                    but {} seconds was given",
+                    offset_seconds
+                )
+                .into(),
+            )
+            .into());
+        }
+        let local = date_time.as_nanos() + i128::from(offset_seconds) * 1_000_000_000;
+        let mut out = format_local(local)?;
+        let offset_minutes = offset_seconds.unsigned_abs() / 60;
+        write!(
+            out,
+            "{}{:02}:{:02}",
+            if offset_seconds < 0 { '-' } else { '+' },
+            offset_minutes / 60,
+            offset_minutes % 60
+        )
+        .unwrap();
+        Ok(out)
+    }
+
+    /// Formats the date-time `nanos` after the Unix epoch without a timezone designator
+    fn format_local(nanos: i128) -> Result<String, DateTimeFormatError> {
         use std::fmt::Write;
         fn out_of_range<E: std::fmt::Display>(cause: E) -> DateTimeFormatError {
             DateTimeFormatErrorKind::OutOfRange(
@@ -445,8 +639,7 @@ pub(crate) mod rfc3339 {
             .into()
         }
         let (year, month, day, hour, minute, second, micros) = {
-            let s = OffsetDateTime::from_unix_timestamp_nanos(date_time.as_nanos())
-                .map_err(out_of_range)?;
+            let s = OffsetDateTime::from_unix_timestamp_nanos(nanos).map_err(out_of_range)?;
             (
                 s.year(),
                 u8::from(s.month()),
@@ -472,13 +665,12 @@ pub(crate) mod rfc3339 {
         )
         .unwrap();
         format_subsecond_fraction(&mut out, micros);
-        out.push('Z');
         Ok(out)
     }
 
     /// Formats sub-second fraction for RFC-3339 (including the '.').
     /// Expects to be called with a number of `micros` between 0 and 999_999 inclusive.
-    fn format_subsecond_fraction(into: &mut String, micros: u32) {
+    pub(super) fn format_subsecond_fraction(into: &mut String, micros: u32) {
         debug_assert!(micros < 1_000_000);
         if micros > 0 {
             into.push('.');
@@ -493,6 +685,90 @@ pub(crate) mod rfc3339 {
     }
 }
 
+pub(crate) mod iso8601_basic {
+    use crate::date_time::format::rfc3339::{self, AllowOffsets};
+    use crate::date_time::format::{
+        DateTimeFormatError, DateTimeParseError, DateTimeParseErrorKind,
+    };
+    use crate::DateTime;
+
+    // OK: 20191216T234818Z
+    // OK: 20191216T234818.52Z
+    //
+    // Timezones not supported:
+    // Not OK: 20191216T234818-0200
+    pub(crate) fn parse(s: &str) -> Result<DateTime, DateTimeParseError> {
+        let b = s.as_bytes();
+        let digits = |range: std::ops::Range<usize>| b[range].iter().all(u8::is_ascii_digit);
+        let well_shaped = b.len() >= 16
+            && digits(0..8)
+            && b[8] == b'T'
+            && digits(9..15)
+            && b[b.len() - 1] == b'Z'
+            && (b.len() == 16 || (b[15] == b'.' && b.len() > 17 && digits(16..b.len() - 1)));
+        if !well_shaped {
+            return Err(DateTimeParseErrorKind::Invalid(
+                "ISO-8601 basic format date-times must look like `20191216T234818Z`".into(),
+            )
+            .into());
+        }
+        // The shape was validated above, so converting to the extended format only adds separators
+        let extended = format!(
+            "{}-{}-{}T{}:{}:{}",
+            &s[0..4],
+            &s[4..6],
+            &s[6..8],
+            &s[9..11],
+            &s[11..13],
+            &s[13..]
+        );
+        rfc3339::parse(&extended, AllowOffsets::OffsetsForbidden)
+    }
+
+    /// Format a [DateTime] in the ISO-8601 basic date format
+    pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        let mut out = rfc3339::format(date_time)?;
+        out.retain(|c| c != '-' && c != ':');
+        Ok(out)
+    }
+}
+
+pub(crate) mod epoch_millis {
+    use super::{DateTimeFormatError, DateTimeFormatErrorKind};
+    use super::{DateTimeParseError, DateTimeParseErrorKind};
+    use crate::DateTime;
+
+    /// Formats a `DateTime` into an integer number of milliseconds since the Unix epoch.
+    ///
+    /// Sub-millisecond precision is truncated toward negative infinity.
+    pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        date_time
+            .to_millis()
+            .map(|millis| millis.to_string())
+            .map_err(|_| {
+                DateTimeFormatErrorKind::OutOfRange(
+                    "epoch milliseconds must fit in a 64-bit integer".into(),
+                )
+                .into()
+            })
+    }
+
+    /// Parses an integer number of milliseconds since the Unix epoch.
+    pub(crate) fn parse(value: &str) -> Result<DateTime, DateTimeParseError> {
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(DateTimeParseErrorKind::Invalid(
+                "epoch milliseconds must be an integer".into(),
+            )
+            .into());
+        }
+        let millis: i64 = value
+            .parse()
+            .map_err(|_| DateTimeParseErrorKind::IntParseError)?;
+        Ok(DateTime::from_millis(millis))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -816,6 +1092,134 @@ mod tests {
         }
     }
 
+    #[test]
+    fn iso8601_basic() {
+        let date_time = DateTime::from_secs_and_nanos(1576540098, 520_000_000);
+        assert_eq!(
+            "20191216T234818.52Z",
+            iso8601_basic::format(&date_time).unwrap()
+        );
+        assert_eq!(
+            date_time,
+            iso8601_basic::parse("20191216T234818.52Z").unwrap()
+        );
+        assert_eq!(
+            DateTime::from_secs(1576540098),
+            iso8601_basic::parse("20191216T234818Z").unwrap()
+        );
+        for invalid in [
+            "2019-12-16T23:48:18Z",
+            "20191216T234818",
+            "20191216T234818.Z",
+            "20191216T234818-0200",
+            "20191216 234818Z",
+            "20191316T234818Z",
+        ] {
+            iso8601_basic::parse(invalid).expect_err(invalid);
+        }
+    }
+
+    #[test]
+    fn rfc850_date() {
+        let date_time = DateTime::from_secs(784111777);
+        assert_eq!(
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            http_date::format_rfc850(&date_time).unwrap()
+        );
+        assert_eq!(
+            date_time,
+            http_date::parse_rfc850("Sunday, 06-Nov-94 08:49:37 GMT", 2024).unwrap()
+        );
+        // 74 is more than 50 years after 2023, so it's in the previous century
+        assert_eq!(
+            rfc3339::parse("1974-01-01T00:00:00Z", AllowOffsets::OffsetsForbidden).unwrap(),
+            http_date::parse_rfc850("Tuesday, 01-Jan-74 00:00:00 GMT", 2023).unwrap()
+        );
+        assert_eq!(
+            rfc3339::parse("2073-01-01T00:00:00Z", AllowOffsets::OffsetsForbidden).unwrap(),
+            http_date::parse_rfc850("Sunday, 01-Jan-73 00:00:00 GMT", 2023).unwrap()
+        );
+        // 10 is 70 years before 2080, so it's in the next century
+        assert_eq!(
+            rfc3339::parse("2110-01-01T00:00:00Z", AllowOffsets::OffsetsForbidden).unwrap(),
+            http_date::parse_rfc850("Wednesday, 01-Jan-10 00:00:00 GMT", 2080).unwrap()
+        );
+        for invalid in [
+            "Sun, 06-Nov-94 08:49:37 GMT",
+            "Sunday, 06-Nov-1994 08:49:37 GMT",
+            "Sunday, 06 Nov 94 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37",
+            "Sunday, 06-Foo-94 08:49:37 GMT",
+        ] {
+            http_date::parse_rfc850(invalid, 2024).expect_err(invalid);
+        }
+    }
+
+    #[test]
+    fn asctime_date() {
+        let date_time = DateTime::from_secs(784111777);
+        assert_eq!(
+            "Sun Nov  6 08:49:37 1994",
+            http_date::format_asctime(&date_time).unwrap()
+        );
+        assert_eq!(
+            date_time,
+            http_date::parse_asctime("Sun Nov  6 08:49:37 1994").unwrap()
+        );
+        assert_eq!(
+            DateTime::from_secs(1576540098),
+            http_date::parse_asctime("Mon Dec 16 23:48:18 2019").unwrap()
+        );
+        for invalid in [
+            "Sun Nov 6 08:49:37 1994",
+            "Sun Nov 06 08:49:37 94",
+            "Sunday Nov  6 08:49:37 1994",
+            "Sun Nov  6 08:49:37 1994 GMT",
+        ] {
+            http_date::parse_asctime(invalid).expect_err(invalid);
+        }
+        http_date::format_asctime(&DateTime::from_secs(-62135596801)).expect_err("year 0");
+    }
+
+    #[test]
+    fn epoch_millis() {
+        assert_eq!(
+            "-1001",
+            epoch_millis::format(&DateTime::from_secs_and_nanos(-2, 999_000_001)).unwrap()
+        );
+        assert_eq!(
+            DateTime::from_secs_and_nanos(1576540098, 520_000_000),
+            epoch_millis::parse("1576540098520").unwrap()
+        );
+        assert_eq!(
+            DateTime::from_secs_and_nanos(-2, 999_000_000),
+            epoch_millis::parse("-1001").unwrap()
+        );
+        for invalid in ["", "-", "1.5", "1e3", "+1", "99999999999999999999"] {
+            epoch_millis::parse(invalid).expect_err(invalid);
+        }
+        epoch_millis::format(&DateTime::from_secs(i64::MAX)).expect_err("out of range");
+    }
+
+    #[test]
+    fn rfc3339_with_offset() {
+        let date_time = DateTime::from_secs_and_nanos(1576540098, 520_000_000);
+        assert_eq!(
+            "2019-12-16T23:48:18.52+00:00",
+            rfc3339::format_with_offset(&date_time, 0).unwrap()
+        );
+        assert_eq!(
+            "2019-12-17T05:18:18.52+05:30",
+            rfc3339::format_with_offset(&date_time, 5 * 3600 + 30 * 60).unwrap()
+        );
+        assert_eq!(
+            "2019-12-16T14:48:18.52-09:00",
+            rfc3339::format_with_offset(&date_time, -9 * 3600).unwrap()
+        );
+        rfc3339::format_with_offset(&date_time, 30).expect_err("not whole minutes");
+        rfc3339::format_with_offset(&date_time, 24 * 3600).expect_err("too large");
+    }
+
     proptest! {
         #![proptest_config(ProptestConfig::with_cases(10000))]
 
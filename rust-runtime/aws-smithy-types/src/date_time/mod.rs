@@ -175,6 +175,50 @@ impl DateTime {
         }
     }
 
+    /// Parses a `DateTime` from an ISO-8601 basic format date-time, which has no separators.
+    /// Offsets are not supported.
+    ///
+    /// # Example
+    /// ```
+    /// # use aws_smithy_types::DateTime;
+    /// assert_eq!(
+    ///     DateTime::from_secs(1576540098),
+    ///     DateTime::from_iso8601_basic("20191216T234818Z").unwrap()
+    /// );
+    /// ```
+    pub fn from_iso8601_basic(s: &str) -> Result<Self, DateTimeParseError> {
+        format::iso8601_basic::parse(s)
+    }
+
+    /// Parses a `DateTime` from the obsolete RFC-850 date format, which RFC-7231 requires HTTP
+    /// date parsers to accept, e.g. `Sunday, 06-Nov-94 08:49:37 GMT`.
+    ///
+    /// Since the year only has two digits, it is interpreted as the latest year with those
+    /// digits that is no more than 50 years after `pivot_year`. RFC-7231 asks for the pivot
+    /// to be the current year.
+    ///
+    /// # Example
+    /// ```
+    /// # use aws_smithy_types::DateTime;
+    /// let date_time = DateTime::from_rfc850_date("Sunday, 06-Nov-94 08:49:37 GMT", 2024).unwrap();
+    /// assert_eq!(DateTime::from_secs(784111777), date_time);
+    /// ```
+    pub fn from_rfc850_date(s: &str, pivot_year: i32) -> Result<Self, DateTimeParseError> {
+        format::http_date::parse_rfc850(s, pivot_year)
+    }
+
+    /// Parses a `DateTime` from the obsolete ANSI C `asctime()` date format, which RFC-7231
+    /// requires HTTP date parsers to accept, e.g. `Sun Nov  6 08:49:37 1994`.
+    pub fn from_asctime_date(s: &str) -> Result<Self, DateTimeParseError> {
+        format::http_date::parse_asctime(s)
+    }
+
+    /// Parses a `DateTime` from an integer number of milliseconds since the Unix epoch,
+    /// e.g. `1576540098520`.
+    pub fn from_epoch_millis_str(s: &str) -> Result<Self, DateTimeParseError> {
+        format::epoch_millis::parse(s)
+    }
+
     /// Returns true if sub-second nanos is greater than zero.
     pub fn has_subsec_nanos(&self) -> bool {
         self.subsecond_nanos != 0
@@ -261,6 +305,145 @@ impl DateTime {
             Format::HttpDate => format::http_date::format(self),
         }
     }
+
+    /// Formats the `DateTime` as an ISO-8601 basic format date-time, e.g. `20191216T234818Z`.
+    pub fn fmt_iso8601_basic(&self) -> Result<String, DateTimeFormatError> {
+        format::iso8601_basic::format(self)
+    }
+
+    /// Formats the `DateTime` in the obsolete RFC-850 date format,
+    /// e.g. `Monday, 16-Dec-19 23:48:18 GMT`.
+    ///
+    /// The century and subsecond nanos are not included.
+    pub fn fmt_rfc850_date(&self) -> Result<String, DateTimeFormatError> {
+        format::http_date::format_rfc850(self)
+    }
+
+    /// Formats the `DateTime` in the obsolete ANSI C `asctime()` date format,
+    /// e.g. `Mon Dec 16 23:48:18 2019`.
+    ///
+    /// Subsecond nanos are not included.
+    pub fn fmt_asctime_date(&self) -> Result<String, DateTimeFormatError> {
+        format::http_date::format_asctime(self)
+    }
+
+    /// Formats the `DateTime` as an integer number of milliseconds since the Unix epoch,
+    /// e.g. `1576540098520`.
+    ///
+    /// Sub-millisecond precision is truncated toward negative infinity.
+    pub fn fmt_epoch_millis(&self) -> Result<String, DateTimeFormatError> {
+        format::epoch_millis::format(self)
+    }
+
+    /// Formats the `DateTime` as an RFC-3339 date-time in the local time of the given UTC offset.
+    ///
+    /// The offset is given in seconds east of UTC, and must be a whole number of minutes
+    /// less than 24 hours in either direction. The result always includes a numeric offset,
+    /// so a zero offset is written as `+00:00`.
+    ///
+    /// # Example
+    /// ```
+    /// # use aws_smithy_types::DateTime;
+    /// let date_time = DateTime::from_secs(1576540098);
+    /// assert_eq!(
+    ///     date_time.to_offset_string(-5 * 60 * 60).unwrap(),
+    ///     "2019-12-16T18:48:18-05:00"
+    /// );
+    /// ```
+    pub fn to_offset_string(&self, offset_seconds: i32) -> Result<String, DateTimeFormatError> {
+        format::rfc3339::format_with_offset(self, offset_seconds)
+    }
+
+    /// Adds a `duration` to this `DateTime`, returning `None` if the result can't be represented.
+    pub fn checked_add(self, duration: Duration) -> Option<DateTime> {
+        let nanos = self
+            .as_nanos()
+            .checked_add(i128::try_from(duration.as_nanos()).ok()?)?;
+        DateTime::from_nanos(nanos).ok()
+    }
+
+    /// Subtracts a `duration` from this `DateTime`, returning `None` if the result can't be represented.
+    pub fn checked_sub(self, duration: Duration) -> Option<DateTime> {
+        let nanos = self
+            .as_nanos()
+            .checked_sub(i128::try_from(duration.as_nanos()).ok()?)?;
+        DateTime::from_nanos(nanos).ok()
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to this `DateTime`.
+    ///
+    /// Returns a `ConversionError` if `earlier` is later than this `DateTime`.
+    pub fn duration_since(&self, earlier: DateTime) -> Result<Duration, ConversionError> {
+        let nanos = u128::try_from(self.as_nanos() - earlier.as_nanos())
+            .map_err(|_| ConversionError("`earlier` is later than this DateTime"))?;
+        let (seconds, subsecond_nanos) = nanos.div_mod_floor(&(NANOS_PER_SECOND as u128));
+        Ok(Duration::new(
+            u64::try_from(seconds).expect("the difference of two DateTimes fits in u64 seconds"),
+            subsecond_nanos as u32, // safe cast because of the modulus
+        ))
+    }
+
+    /// Truncates the `DateTime` to a multiple of the given `unit` since the Unix epoch.
+    ///
+    /// Times before the epoch are rounded toward negative infinity, so truncation always moves
+    /// a `DateTime` backwards in time.
+    ///
+    /// # Panics
+    /// This function will panic if truncating would move the `DateTime` before the earliest
+    /// representable value, which can only happen within a day of `i64::MIN` epoch seconds.
+    /// Use [`DateTime::checked_truncate`] to handle that case instead.
+    ///
+    /// # Example
+    /// ```
+    /// # use aws_smithy_types::date_time::{DateTime, TimeUnit};
+    /// let date_time = DateTime::from_secs_and_nanos(1576540098, 520_000_000);
+    /// assert_eq!(DateTime::from_secs(1576540080), date_time.truncate(TimeUnit::Minute));
+    /// ```
+    pub fn truncate(&self, unit: TimeUnit) -> DateTime {
+        self.checked_truncate(unit)
+            .expect("truncated DateTime is out of range")
+    }
+
+    /// Truncates the `DateTime` to a multiple of the given `unit` since the Unix epoch, like
+    /// [`DateTime::truncate`].
+    ///
+    /// Returns `None` if truncating would move the `DateTime` before the earliest representable
+    /// value.
+    pub fn checked_truncate(&self, unit: TimeUnit) -> Option<DateTime> {
+        let nanos = self.as_nanos();
+        DateTime::from_nanos(nanos - nanos.mod_floor(&unit.nanos())).ok()
+    }
+}
+
+/// Units of time that a [`DateTime`] can be truncated to.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum TimeUnit {
+    /// 24 hours. Leap seconds are not taken into account.
+    Day,
+    /// 60 minutes
+    Hour,
+    /// 60 seconds
+    Minute,
+    /// 1 second
+    Second,
+    /// 1/1,000th of a second
+    Millisecond,
+    /// 1/1,000,000th of a second
+    Microsecond,
+}
+
+impl TimeUnit {
+    fn nanos(self) -> i128 {
+        match self {
+            TimeUnit::Day => 24 * 60 * 60 * NANOS_PER_SECOND,
+            TimeUnit::Hour => 60 * 60 * NANOS_PER_SECOND,
+            TimeUnit::Minute => 60 * NANOS_PER_SECOND,
+            TimeUnit::Second => NANOS_PER_SECOND,
+            TimeUnit::Millisecond => NANOS_PER_MILLI as i128,
+            TimeUnit::Microsecond => 1_000,
+        }
+    }
 }
 
 /// Tries to convert a [`DateTime`] into a [`SystemTime`].
@@ -389,10 +572,10 @@ pub enum Format {
 
 #[cfg(test)]
 mod test {
-    use crate::date_time::Format;
+    use crate::date_time::{Format, TimeUnit};
     use crate::DateTime;
     use proptest::proptest;
-    use std::time::SystemTime;
+    use std::time::{Duration, SystemTime};
     use time::format_description::well_known::Rfc3339;
     use time::OffsetDateTime;
 
@@ -454,6 +637,117 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_fmt_extended_formats() {
+        let date_time = DateTime::from_secs_and_nanos(1576540098, 520_000_000);
+        let formatted = date_time.fmt_iso8601_basic().unwrap();
+        assert_eq!("20191216T234818.52Z", formatted);
+        assert_eq!(date_time, DateTime::from_iso8601_basic(&formatted).unwrap());
+
+        let formatted = date_time.fmt_epoch_millis().unwrap();
+        assert_eq!("1576540098520", formatted);
+        assert_eq!(
+            date_time,
+            DateTime::from_epoch_millis_str(&formatted).unwrap()
+        );
+
+        let date_time = DateTime::from_secs(1576540098);
+        let formatted = date_time.fmt_rfc850_date().unwrap();
+        assert_eq!("Monday, 16-Dec-19 23:48:18 GMT", formatted);
+        assert_eq!(
+            date_time,
+            DateTime::from_rfc850_date(&formatted, 2024).unwrap()
+        );
+        // The same two digit year is a century earlier when it's more than 50 years after the pivot
+        assert_eq!(
+            DateTime::from_secs(1576540098 - 36525 * 24 * 60 * 60),
+            DateTime::from_rfc850_date(&formatted, 1968).unwrap()
+        );
+
+        let formatted = date_time.fmt_asctime_date().unwrap();
+        assert_eq!("Mon Dec 16 23:48:18 2019", formatted);
+        assert_eq!(date_time, DateTime::from_asctime_date(&formatted).unwrap());
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let date_time = DateTime::from_secs_and_nanos(-1, 500_000_000);
+        let later = date_time.checked_add(Duration::from_millis(1_750)).unwrap();
+        assert_eq!(DateTime::from_secs_and_nanos(1, 250_000_000), later);
+        assert_eq!(
+            date_time,
+            later.checked_sub(Duration::from_millis(1_750)).unwrap()
+        );
+        assert_eq!(
+            Duration::from_millis(1_750),
+            later.duration_since(date_time).unwrap()
+        );
+        assert!(date_time.duration_since(later).is_err());
+        assert_eq!(Duration::ZERO, later.duration_since(later).unwrap());
+
+        let max = DateTime::from_secs_and_nanos(i64::MAX, 999_999_999);
+        assert_eq!(None, max.checked_add(Duration::from_nanos(1)));
+        let min = DateTime::from_secs(i64::MIN);
+        assert_eq!(None, min.checked_sub(Duration::from_nanos(1)));
+        assert_eq!(Some(max), min.checked_add(Duration::MAX));
+        assert_eq!(
+            Duration::new(u64::MAX, 999_999_999),
+            max.duration_since(min).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        let date_time = DateTime::from_secs_and_nanos(1576540098, 520_123_456);
+        let cases = [
+            (TimeUnit::Day, DateTime::from_secs(1576454400)),
+            (TimeUnit::Hour, DateTime::from_secs(1576537200)),
+            (TimeUnit::Minute, DateTime::from_secs(1576540080)),
+            (TimeUnit::Second, DateTime::from_secs(1576540098)),
+            (
+                TimeUnit::Millisecond,
+                DateTime::from_secs_and_nanos(1576540098, 520_000_000),
+            ),
+            (
+                TimeUnit::Microsecond,
+                DateTime::from_secs_and_nanos(1576540098, 520_123_000),
+            ),
+        ];
+        for (unit, expected) in cases {
+            assert_eq!(expected, date_time.truncate(unit), "{:?}", unit);
+        }
+        // Times before the epoch are truncated toward negative infinity
+        assert_eq!(
+            DateTime::from_secs(-60),
+            DateTime::from_secs_and_nanos(-1, 1).truncate(TimeUnit::Minute)
+        );
+        // Truncating the earliest representable value would move it out of range
+        assert_eq!(
+            None,
+            DateTime::from_secs(i64::MIN).checked_truncate(TimeUnit::Day)
+        );
+        assert_eq!(
+            Some(DateTime::from_secs(i64::MIN)),
+            DateTime::from_secs(i64::MIN).checked_truncate(TimeUnit::Second)
+        );
+    }
+
+    #[test]
+    fn test_to_offset_string() {
+        let date_time = DateTime::from_secs(1576540098);
+        assert_eq!(
+            "2019-12-17T00:48:18+01:00",
+            date_time.to_offset_string(3600).unwrap()
+        );
+        assert!(date_time.to_offset_string(86_400).is_err());
+        let round_trip = DateTime::from_str(
+            &date_time.to_offset_string(-7 * 3600).unwrap(),
+            Format::DateTimeWithOffset,
+        )
+        .unwrap();
+        assert_eq!(date_time, round_trip);
+    }
+
     #[test]
     fn test_fmt_zero_seconds() {
         let date_time = DateTime::from_secs(1576540080);
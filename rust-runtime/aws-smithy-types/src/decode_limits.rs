@@ -0,0 +1,223 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Resource limits that can be applied when decoding JSON, XML or CBOR payloads.
+//!
+//! The decoders in `aws-smithy-json`, `aws-smithy-xml` and `aws-smithy-cbor` all accept a
+//! [`DecodeLimits`]. When a payload exceeds one of the limits, decoding stops with an error that
+//! carries a [`LimitExceeded`] describing which limit was hit, so that servers can reject
+//! untrusted input before allocating for it.
+
+use crate::config_bag::{Storable, StoreReplace};
+use std::error::Error;
+use std::fmt;
+
+/// Limits on the size and shape of a payload being decoded.
+///
+/// All limits are disabled by default.
+///
+/// ```rust
+/// use aws_smithy_types::decode_limits::DecodeLimits;
+///
+/// let limits = DecodeLimits::new()
+///     .with_max_depth(32)
+///     .with_max_string_length(64 * 1024)
+///     .with_max_collection_size(1_000)
+///     .with_max_tokens(100_000);
+/// assert_eq!(Some(32), limits.max_depth());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DecodeLimits {
+    max_depth: Option<usize>,
+    max_string_length: Option<usize>,
+    max_collection_size: Option<usize>,
+    max_tokens: Option<usize>,
+}
+
+impl DecodeLimits {
+    /// Creates a `DecodeLimits` with every limit disabled.
+    pub const fn new() -> Self {
+        Self {
+            max_depth: None,
+            max_string_length: None,
+            max_collection_size: None,
+            max_tokens: None,
+        }
+    }
+
+    /// Limits how deeply arrays, objects, maps and elements can be nested.
+    ///
+    /// A top-level collection has a depth of 1.
+    pub const fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = Some(max_depth);
+        self
+    }
+
+    /// Limits the length, in bytes, of any single string, key, attribute value or blob.
+    ///
+    /// The length is measured on the encoded payload, so JSON and XML escape sequences count
+    /// towards it.
+    pub const fn with_max_string_length(mut self, max_string_length: usize) -> Self {
+        self.max_string_length = Some(max_string_length);
+        self
+    }
+
+    /// Limits the number of items in any single array, or members in any single object or map.
+    ///
+    /// For XML, this limits the number of child elements of any single element.
+    pub const fn with_max_collection_size(mut self, max_collection_size: usize) -> Self {
+        self.max_collection_size = Some(max_collection_size);
+        self
+    }
+
+    /// Limits the total number of tokens read from the payload.
+    pub const fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Returns the maximum nesting depth, if limited.
+    pub const fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns the maximum string length, if limited.
+    pub const fn max_string_length(&self) -> Option<usize> {
+        self.max_string_length
+    }
+
+    /// Returns the maximum collection size, if limited.
+    pub const fn max_collection_size(&self) -> Option<usize> {
+        self.max_collection_size
+    }
+
+    /// Returns the maximum number of tokens, if limited.
+    pub const fn max_tokens(&self) -> Option<usize> {
+        self.max_tokens
+    }
+
+    /// Returns true if none of the limits are enabled.
+    pub const fn is_unlimited(&self) -> bool {
+        self.max_depth.is_none()
+            && self.max_string_length.is_none()
+            && self.max_collection_size.is_none()
+            && self.max_tokens.is_none()
+    }
+
+    /// Checks that `depth` doesn't exceed the maximum nesting depth.
+    pub fn check_depth(&self, depth: usize) -> Result<(), LimitExceeded> {
+        check(DecodeLimit::Depth, self.max_depth, depth)
+    }
+
+    /// Checks that `length` doesn't exceed the maximum string length.
+    pub fn check_string_length(&self, length: usize) -> Result<(), LimitExceeded> {
+        check(DecodeLimit::StringLength, self.max_string_length, length)
+    }
+
+    /// Checks that `size` doesn't exceed the maximum collection size.
+    pub fn check_collection_size(&self, size: usize) -> Result<(), LimitExceeded> {
+        check(DecodeLimit::CollectionSize, self.max_collection_size, size)
+    }
+
+    /// Checks that `tokens` doesn't exceed the maximum number of tokens.
+    pub fn check_tokens(&self, tokens: usize) -> Result<(), LimitExceeded> {
+        check(DecodeLimit::Tokens, self.max_tokens, tokens)
+    }
+}
+
+impl Storable for DecodeLimits {
+    type Storer = StoreReplace<DecodeLimits>;
+}
+
+fn check(limit: DecodeLimit, max: Option<usize>, actual: usize) -> Result<(), LimitExceeded> {
+    match max {
+        Some(max) if actual > max => Err(LimitExceeded { limit, max }),
+        _ => Ok(()),
+    }
+}
+
+/// One of the limits in [`DecodeLimits`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DecodeLimit {
+    /// See [`DecodeLimits::with_max_depth`].
+    Depth,
+    /// See [`DecodeLimits::with_max_string_length`].
+    StringLength,
+    /// See [`DecodeLimits::with_max_collection_size`].
+    CollectionSize,
+    /// See [`DecodeLimits::with_max_tokens`].
+    Tokens,
+}
+
+impl fmt::Display for DecodeLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeLimit::Depth => write!(f, "nesting depth"),
+            DecodeLimit::StringLength => write!(f, "string length"),
+            DecodeLimit::CollectionSize => write!(f, "collection size"),
+            DecodeLimit::Tokens => write!(f, "number of tokens"),
+        }
+    }
+}
+
+/// A payload exceeded one of the configured [`DecodeLimits`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct LimitExceeded {
+    limit: DecodeLimit,
+    max: usize,
+}
+
+impl LimitExceeded {
+    /// Returns the limit that was exceeded.
+    pub fn limit(&self) -> DecodeLimit {
+        self.limit
+    }
+
+    /// Returns the configured maximum for the exceeded limit.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exceeded the maximum {} of {}", self.limit, self.max)
+    }
+}
+
+impl Error for LimitExceeded {}
+
+#[cfg(test)]
+mod tests {
+    use super::{DecodeLimit, DecodeLimits};
+
+    #[test]
+    fn unlimited_by_default() {
+        let limits = DecodeLimits::default();
+        assert!(limits.is_unlimited());
+        assert!(limits.check_depth(usize::MAX).is_ok());
+        assert!(limits.check_tokens(usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn limits_are_inclusive() {
+        let limits = DecodeLimits::new()
+            .with_max_depth(2)
+            .with_max_collection_size(3);
+        assert!(!limits.is_unlimited());
+        assert!(limits.check_depth(2).is_ok());
+        let err = limits.check_depth(3).unwrap_err();
+        assert_eq!(DecodeLimit::Depth, err.limit());
+        assert_eq!(2, err.max());
+        assert_eq!("exceeded the maximum nesting depth of 2", err.to_string());
+        assert!(limits.check_collection_size(3).is_ok());
+        assert_eq!(
+            "exceeded the maximum collection size of 3",
+            limits.check_collection_size(4).unwrap_err().to_string()
+        );
+        assert!(limits.check_string_length(usize::MAX).is_ok());
+    }
+}
@@ -3,6 +3,24 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+//! Protocol-agnostic open content.
+//!
+//! Besides the [`Document`] type itself, this module provides support for querying and patching
+//! documents with [JSON Pointers](Document::pointer), [JSON Patch](Document::apply_patch),
+//! [JSON Merge Patch](Document::apply_merge_patch), and [deep merging](Document::merge).
+//!
+//! Documents can be written as literals with the [`document!`](crate::document!) macro.
+//!
+//! # Preserving the order of object members
+//!
+//! [`Document::Object`] is backed by a `HashMap`, so the order of its members is unspecified.
+//! [`Document::OrderedObject`] is backed by an insertion-ordered [`OrderedMap`] instead. Documents
+//! built by [`document!`](crate::document!) or collected from key-value pairs use this variant, as
+//! do documents deserialized by `aws-smithy-json`'s `expect_ordered_document`.
+//!
+//! An object is equal to another object with the same members regardless of which representation
+//! each of them uses.
+
 use crate::Number;
 use std::borrow::Cow;
 use std::collections::HashMap;
@@ -13,6 +31,13 @@ use std::collections::HashMap;
 ))]
 use serde;
 
+mod ordered;
+mod patch;
+mod pointer;
+
+pub use self::ordered::OrderedMap;
+pub use self::patch::{ArrayMergeStrategy, PatchError};
+
 /* ANCHOR: document */
 
 /// Document Type
@@ -21,7 +46,7 @@ use serde;
 /// Open content is useful for modeling unstructured data that has no schema, data that can't be
 /// modeled using rigid types, or data that has a schema that evolves outside of the purview of a model.
 /// The serialization format of a document is an implementation detail of a protocol.
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug)]
 #[cfg_attr(
     all(aws_sdk_unstable, feature = "serde-serialize"),
     derive(serde::Serialize)
@@ -50,6 +75,31 @@ pub enum Document {
     Bool(bool),
     /// JSON null
     Null,
+    /// JSON object that preserves the insertion order of its members
+    #[cfg_attr(
+        all(aws_sdk_unstable, feature = "serde-deserialize"),
+        serde(skip_deserializing)
+    )]
+    OrderedObject(OrderedMap),
+}
+
+impl PartialEq for Document {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Object(a), Self::Object(b)) => a == b,
+            (Self::OrderedObject(a), Self::OrderedObject(b)) => a == b,
+            (Self::Object(a), Self::OrderedObject(b))
+            | (Self::OrderedObject(b), Self::Object(a)) => {
+                a.len() == b.len() && b.iter().all(|(key, value)| a.get(key) == Some(value))
+            }
+            (Self::Array(a), Self::Array(b)) => a == b,
+            (Self::Number(a), Self::Number(b)) => a == b,
+            (Self::String(a), Self::String(b)) => a == b,
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Null, Self::Null) => true,
+            _ => false,
+        }
+    }
 }
 
 impl Document {
@@ -71,6 +121,24 @@ impl Document {
         }
     }
 
+    /// Returns the inner map value if this `Document` is an ordered object.
+    pub fn as_ordered_object(&self) -> Option<&OrderedMap> {
+        if let Self::OrderedObject(object) = self {
+            Some(object)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the mutable inner map value if this `Document` is an ordered object.
+    pub fn as_ordered_object_mut(&mut self) -> Option<&mut OrderedMap> {
+        if let Self::OrderedObject(object) = self {
+            Some(object)
+        } else {
+            None
+        }
+    }
+
     /// Returns the inner array value if this `Document` is an array.
     pub fn as_array(&self) -> Option<&Vec<Document>> {
         if let Self::Array(array) = self {
@@ -130,6 +198,11 @@ impl Document {
         matches!(self, Self::Object(_))
     }
 
+    /// Returns `true` if this `Document` is an ordered object.
+    pub fn is_ordered_object(&self) -> bool {
+        matches!(self, Self::OrderedObject(_))
+    }
+
     /// Returns `true` if this `Document` is an array.
     pub fn is_array(&self) -> bool {
         matches!(self, Self::Array(_))
@@ -154,6 +227,75 @@ impl Document {
     pub fn is_null(&self) -> bool {
         matches!(self, Self::Null)
     }
+
+    /// Returns `true` if this `Document` is an object of either representation.
+    pub(crate) fn is_any_object(&self) -> bool {
+        matches!(self, Self::Object(_) | Self::OrderedObject(_))
+    }
+
+    /// Returns an iterator over the members of this `Document` if it is an object.
+    ///
+    /// Unlike [`Document::as_object`], this supports both object representations. Members of a
+    /// [`Document::Object`] are returned in an unspecified order.
+    pub fn members(&self) -> Option<Box<dyn Iterator<Item = (&String, &Document)> + '_>> {
+        match self {
+            Self::Object(object) => Some(Box::new(object.iter())),
+            Self::OrderedObject(object) => Some(Box::new(object.iter())),
+            _ => None,
+        }
+    }
+
+    /// Returns the member named `key` if this `Document` is an object of either representation.
+    pub(crate) fn member(&self, key: &str) -> Option<&Document> {
+        match self {
+            Self::Object(object) => object.get(key),
+            Self::OrderedObject(object) => object.get(key),
+            _ => None,
+        }
+    }
+
+    /// Returns the mutable member named `key` if this `Document` is an object of either representation.
+    pub(crate) fn member_mut(&mut self, key: &str) -> Option<&mut Document> {
+        match self {
+            Self::Object(object) => object.get_mut(key),
+            Self::OrderedObject(object) => object.get_mut(key),
+            _ => None,
+        }
+    }
+
+    /// Inserts a member if this `Document` is an object of either representation.
+    ///
+    /// Returns `value` back if this `Document` isn't an object.
+    pub(crate) fn insert_member(&mut self, key: String, value: Document) -> Result<(), Document> {
+        match self {
+            Self::Object(object) => {
+                object.insert(key, value);
+            }
+            Self::OrderedObject(object) => {
+                object.insert(key, value);
+            }
+            _ => return Err(value),
+        }
+        Ok(())
+    }
+
+    /// Removes the member named `key` if this `Document` is an object of either representation.
+    pub(crate) fn remove_member(&mut self, key: &str) -> Option<Document> {
+        match self {
+            Self::Object(object) => object.remove(key),
+            Self::OrderedObject(object) => object.remove(key),
+            _ => None,
+        }
+    }
+
+    /// Converts this `Document` into its members if it is an object of either representation.
+    pub(crate) fn into_members(self) -> Result<Vec<(String, Document)>, Document> {
+        match self {
+            Self::Object(object) => Ok(object.into_iter().collect()),
+            Self::OrderedObject(object) => Ok(object.into_iter().collect()),
+            other => Err(other),
+        }
+    }
 }
 
 /// The default value is `Document::Null`.
@@ -199,6 +341,19 @@ impl From<HashMap<String, Document>> for Document {
     }
 }
 
+impl From<OrderedMap> for Document {
+    fn from(values: OrderedMap) -> Self {
+        Document::OrderedObject(values)
+    }
+}
+
+/// Collects key-value pairs into a [`Document::OrderedObject`] with the members in iteration order.
+impl<K: Into<String>> FromIterator<(K, Document)> for Document {
+    fn from_iter<T: IntoIterator<Item = (K, Document)>>(iter: T) -> Self {
+        Document::OrderedObject(iter.into_iter().collect())
+    }
+}
+
 impl From<u64> for Document {
     fn from(value: u64) -> Self {
         Document::Number(Number::PosInt(value))
@@ -231,8 +386,128 @@ impl From<Number> for Document {
 
 /* ANCHOR END: document */
 
+/// Creates a [`Document`] from a JSON-like literal.
+///
+/// Objects are written with string literal keys, or with parenthesized expressions for computed keys.
+/// `null`, arrays, and objects nest, and any other value is converted with [`Document::from`], so it
+/// can be a literal, a variable, or any other expression.
+///
+/// Objects are created as [`Document::OrderedObject`]s, so their members keep the order they are written in.
+///
+/// ```rust
+/// use aws_smithy_types::{document, Document};
+///
+/// let name = "example";
+/// let doc = document!({
+///     "name": name,
+///     "enabled": true,
+///     "limits": { "min": 1u64, "max": 10 * 10u64 },
+///     "tags": ["a", "b", null],
+///     ("computed-".to_string() + "key"): -1.5,
+/// });
+/// assert_eq!(Some(&Document::from(100u64)), doc.pointer("/limits/max"));
+/// assert_eq!(Some(&Document::Null), doc.pointer("/tags/2"));
+///
+/// // The outer braces can also be the macro's delimiters
+/// assert_eq!(document!({ "a": 1u64 }), document! { "a": 1u64 });
+/// ```
+#[macro_export]
+macro_rules! document {
+    () => {
+        $crate::document!({})
+    };
+    (null) => {
+        $crate::Document::Null
+    };
+    ([ $($tt:tt)* ]) => {
+        $crate::Document::Array($crate::__document_array!([] () $($tt)*))
+    };
+    ({ $($tt:tt)* }) => {
+        $crate::__document_object!([] $($tt)*)
+            .into_iter()
+            .collect::<$crate::Document>()
+    };
+    ($key:literal : $($tt:tt)*) => {
+        $crate::document!({ $key : $($tt)* })
+    };
+    (($key:expr) : $($tt:tt)*) => {
+        $crate::document!({ ($key) : $($tt)* })
+    };
+    ($other:expr) => {
+        $crate::Document::from($other)
+    };
+}
+
+// Munches the tokens of an array one at a time, accumulating the tokens of the current element
+// until a comma is found, and the finished elements in brackets.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __document_array {
+    ([$($elems:expr,)*] ()) => {
+        ::std::vec![$($elems,)*]
+    };
+    ([$($elems:expr,)*] ($($value:tt)+)) => {
+        ::std::vec![$($elems,)* $crate::document!($($value)+)]
+    };
+    ([$($elems:expr,)*] ($($value:tt)+) , $($rest:tt)*) => {
+        $crate::__document_array!([$($elems,)* $crate::document!($($value)+),] () $($rest)*)
+    };
+    ([$($elems:expr,)*] ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::__document_array!([$($elems,)*] ($($value)* $next) $($rest)*)
+    };
+}
+
+// Like `__document_array`, but munches `key: value` members.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __document_object {
+    (@value [$($members:expr,)*] ($key:expr) ($($value:tt)+)) => {
+        $crate::__document_object!([$($members,)* $crate::__document_object!(@member $key, $($value)+),])
+    };
+    (@value [$($members:expr,)*] ($key:expr) ($($value:tt)+) , $($rest:tt)*) => {
+        $crate::__document_object!([$($members,)* $crate::__document_object!(@member $key, $($value)+),] $($rest)*)
+    };
+    (@value [$($members:expr,)*] ($key:expr) ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::__document_object!(@value [$($members,)*] ($key) ($($value)* $next) $($rest)*)
+    };
+    (@member $key:expr, $($value:tt)+) => {
+        (::std::string::String::from($key), $crate::document!($($value)+))
+    };
+    ([$($members:expr,)*]) => {
+        ::std::vec![$($members,)*]
+    };
+    ([$($members:expr,)*] $key:literal : $($rest:tt)*) => {
+        $crate::__document_object!(@value [$($members,)*] ($key) () $($rest)*)
+    };
+    ([$($members:expr,)*] ($key:expr) : $($rest:tt)*) => {
+        $crate::__document_object!(@value [$($members,)*] ($key) () $($rest)*)
+    };
+}
+
 #[cfg(test)]
 mod test {
+    #[test]
+    fn document_macro_preserves_order() {
+        use crate::Document;
+
+        let doc = crate::document!({ "z": 1u64, "a": [true, null], "m": { "y": "x" } });
+        let object = doc.as_ordered_object().expect("ordered object");
+        assert_eq!(vec!["z", "a", "m"], object.keys().collect::<Vec<_>>());
+        assert_eq!(
+            vec!["y"],
+            doc.pointer("/m")
+                .and_then(Document::as_ordered_object)
+                .expect("ordered object")
+                .keys()
+                .collect::<Vec<_>>()
+        );
+
+        // Objects are equal regardless of their representation
+        let unordered = Document::Object(object.clone().into());
+        assert_eq!(unordered, doc);
+        assert_ne!(Document::Object(Default::default()), doc);
+    }
+
     /// checks if a) serialization of json suceeds and b) it is compatible with serde_json
     #[test]
     #[cfg(all(
@@ -0,0 +1,345 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! An insertion-ordered map for document objects.
+
+use crate::Document;
+use std::collections::HashMap;
+use std::fmt;
+
+/// A map from strings to documents that remembers the order in which keys were inserted.
+///
+/// This backs [`Document::OrderedObject`](crate::Document::OrderedObject). Iteration yields members
+/// in insertion order. Re-inserting an existing key replaces its value but keeps its position, and
+/// removing a key preserves the order of the remaining members.
+///
+/// Like JSON objects, two maps are equal if they have the same members, regardless of order.
+#[derive(Clone, Default)]
+pub struct OrderedMap {
+    entries: Vec<(String, Document)>,
+    index: HashMap<String, usize>,
+}
+
+impl OrderedMap {
+    /// Creates an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty map with space for at least `capacity` members.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: Vec::with_capacity(capacity),
+            index: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of members in the map.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map has no members.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns `true` if the map has a member named `key`.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.index.contains_key(key)
+    }
+
+    /// Returns the value of the member named `key`.
+    pub fn get(&self, key: &str) -> Option<&Document> {
+        self.index.get(key).map(|&i| &self.entries[i].1)
+    }
+
+    /// Returns the mutable value of the member named `key`.
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut Document> {
+        self.index.get(key).map(|&i| &mut self.entries[i].1)
+    }
+
+    /// Inserts a member, returning the previous value if the key was already present.
+    ///
+    /// A new key is added at the end of the map, and an existing key keeps its position.
+    pub fn insert(&mut self, key: impl Into<String>, value: Document) -> Option<Document> {
+        let key = key.into();
+        match self.index.get(&key) {
+            Some(&i) => Some(std::mem::replace(&mut self.entries[i].1, value)),
+            None => {
+                self.index.insert(key.clone(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the member named `key`, preserving the order of the remaining members.
+    pub fn remove(&mut self, key: &str) -> Option<Document> {
+        let i = self.index.remove(key)?;
+        let (_, value) = self.entries.remove(i);
+        for (key, _) in &self.entries[i..] {
+            *self.index.get_mut(key).expect("every entry is indexed") -= 1;
+        }
+        Some(value)
+    }
+
+    /// Removes every member.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.index.clear();
+    }
+
+    /// Keeps only the members for which `keep` returns `true`, preserving their order.
+    pub fn retain(&mut self, mut keep: impl FnMut(&String, &mut Document) -> bool) {
+        self.entries.retain_mut(|(k, v)| keep(k, v));
+        self.index.clear();
+        for (i, (key, _)) in self.entries.iter().enumerate() {
+            self.index.insert(key.clone(), i);
+        }
+    }
+
+    /// Returns an iterator over the members in insertion order.
+    pub fn iter(&self) -> OrderedMapIter<'_> {
+        OrderedMapIter(self.entries.iter())
+    }
+
+    /// Returns an iterator over the members in insertion order, with mutable values.
+    pub fn iter_mut(&mut self) -> OrderedMapIterMut<'_> {
+        OrderedMapIterMut(self.entries.iter_mut())
+    }
+
+    /// Returns an iterator over the keys in insertion order.
+    pub fn keys(&self) -> impl DoubleEndedIterator<Item = &String> + ExactSizeIterator {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Returns an iterator over the values in insertion order.
+    pub fn values(&self) -> impl DoubleEndedIterator<Item = &Document> + ExactSizeIterator {
+        self.entries.iter().map(|(_, v)| v)
+    }
+
+    /// Returns an iterator over the mutable values in insertion order.
+    pub fn values_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = &mut Document> + ExactSizeIterator {
+        self.entries.iter_mut().map(|(_, v)| v)
+    }
+}
+
+/// An iterator over the members of an [`OrderedMap`], in insertion order.
+#[derive(Clone, Debug)]
+pub struct OrderedMapIter<'a>(std::slice::Iter<'a, (String, Document)>);
+
+impl<'a> Iterator for OrderedMapIter<'a> {
+    type Item = (&'a String, &'a Document);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for OrderedMapIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, v)| (k, v))
+    }
+}
+
+impl ExactSizeIterator for OrderedMapIter<'_> {}
+
+/// An iterator over the members of an [`OrderedMap`] with mutable values, in insertion order.
+#[derive(Debug)]
+pub struct OrderedMapIterMut<'a>(std::slice::IterMut<'a, (String, Document)>);
+
+impl<'a> Iterator for OrderedMapIterMut<'a> {
+    type Item = (&'a String, &'a mut Document);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(k, v)| (&*k, v))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for OrderedMapIterMut<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next_back().map(|(k, v)| (&*k, v))
+    }
+}
+
+impl ExactSizeIterator for OrderedMapIterMut<'_> {}
+
+impl<'a> IntoIterator for &'a OrderedMap {
+    type Item = (&'a String, &'a Document);
+    type IntoIter = OrderedMapIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut OrderedMap {
+    type Item = (&'a String, &'a mut Document);
+    type IntoIter = OrderedMapIterMut<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl std::ops::Index<&str> for OrderedMap {
+    type Output = Document;
+
+    /// Returns the value of the member named `key`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the map has no member named `key`.
+    fn index(&self, key: &str) -> &Document {
+        self.get(key).expect("no member with the given key")
+    }
+}
+
+impl fmt::Debug for OrderedMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq for OrderedMap {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .all(|(key, value)| other.get(key) == Some(value))
+    }
+}
+
+impl IntoIterator for OrderedMap {
+    type Item = (String, Document);
+    type IntoIter = std::vec::IntoIter<(String, Document)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, Document)> for OrderedMap {
+    fn from_iter<T: IntoIterator<Item = (K, Document)>>(iter: T) -> Self {
+        let mut map = OrderedMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K: Into<String>> Extend<(K, Document)> for OrderedMap {
+    fn extend<T: IntoIterator<Item = (K, Document)>>(&mut self, iter: T) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl From<HashMap<String, Document>> for OrderedMap {
+    fn from(map: HashMap<String, Document>) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl From<OrderedMap> for HashMap<String, Document> {
+    fn from(map: OrderedMap) -> Self {
+        map.into_iter().collect()
+    }
+}
+
+impl<K: Into<String>, const N: usize> From<[(K, Document); N]> for OrderedMap {
+    fn from(members: [(K, Document); N]) -> Self {
+        members.into_iter().collect()
+    }
+}
+
+#[cfg(all(aws_sdk_unstable, feature = "serde-serialize"))]
+impl serde::Serialize for OrderedMap {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(all(aws_sdk_unstable, feature = "serde-deserialize"))]
+impl<'de> serde::Deserialize<'de> for OrderedMap {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = OrderedMap;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut access: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut map = OrderedMap::with_capacity(access.size_hint().unwrap_or(0).min(4096));
+                while let Some((key, value)) = access.next_entry::<String, Document>()? {
+                    map.insert(key, value);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::OrderedMap;
+    use crate::Document;
+
+    #[test]
+    fn preserves_insertion_order() {
+        let mut map: OrderedMap = [("z", Document::from(1u64)), ("a", 2u64.into())]
+            .into_iter()
+            .collect();
+        map.insert("m", 3u64.into());
+        assert_eq!(Some(Document::from(1u64)), map.insert("z", 4u64.into()));
+        assert_eq!(vec!["z", "a", "m"], map.keys().collect::<Vec<_>>());
+
+        assert_eq!(Some(Document::from(2u64)), map.remove("a"));
+        assert_eq!(None, map.remove("a"));
+        assert_eq!(vec!["z", "m"], map.keys().collect::<Vec<_>>());
+        assert_eq!(Some(&Document::from(3u64)), map.get("m"));
+        assert_eq!(Some(&Document::from(4u64)), map.get("z"));
+        assert_eq!(
+            vec!["m", "z"],
+            map.iter().rev().map(|(k, _)| k).collect::<Vec<_>>()
+        );
+
+        map.insert("b", 5u64.into());
+        map.retain(|key, _| key != "z");
+        assert_eq!(vec!["m", "b"], map.keys().collect::<Vec<_>>());
+        assert_eq!(Document::from(5u64), map["b"]);
+    }
+
+    #[test]
+    fn equality_ignores_order() {
+        let a: OrderedMap = [("a", Document::Null), ("b", true.into())]
+            .into_iter()
+            .collect();
+        let b: OrderedMap = [("b", Document::from(true)), ("a", Document::Null)]
+            .into_iter()
+            .collect();
+        assert_eq!(a, b);
+        assert_eq!(r#"{"a": Null, "b": Bool(true)}"#, format!("{a:?}"));
+    }
+}
@@ -0,0 +1,623 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902) JSON Patch,
+//! [RFC 7386](https://datatracker.ietf.org/doc/html/rfc7386) JSON Merge Patch,
+//! and deep merging for [`Document`].
+
+use super::pointer::{parse_index, tokens};
+use crate::{Document, Number};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+enum PatchErrorKind {
+    /// The patch isn't a valid JSON Patch document.
+    InvalidPatch(Cow<'static, str>),
+    /// A path doesn't refer to an existing value, or to a location where a value can be added.
+    PathNotFound(String),
+    /// A `move` operation attempted to move a value into one of its own children.
+    MoveIntoChild { from: String, path: String },
+    /// A `test` operation found a different value than expected.
+    TestFailed(String),
+}
+
+/// Error returned when a JSON Patch can't be applied to a [`Document`].
+#[derive(Debug)]
+pub struct PatchError {
+    operation: Option<usize>,
+    kind: PatchErrorKind,
+}
+
+impl PatchError {
+    fn new(operation: usize, kind: PatchErrorKind) -> Self {
+        Self {
+            operation: Some(operation),
+            kind,
+        }
+    }
+
+    /// The index of the patch operation that failed, if the failure is specific to an operation.
+    pub fn operation(&self) -> Option<usize> {
+        self.operation
+    }
+}
+
+impl fmt::Display for PatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use PatchErrorKind::*;
+        if let Some(operation) = self.operation {
+            write!(f, "patch operation {operation} failed: ")?;
+        }
+        match &self.kind {
+            InvalidPatch(msg) => write!(f, "invalid patch: {msg}"),
+            PathNotFound(path) => write!(f, "path `{path}` does not exist"),
+            MoveIntoChild { from, path } => {
+                write!(f, "cannot move `{from}` into its own child `{path}`")
+            }
+            TestFailed(path) => write!(f, "value at `{path}` does not match"),
+        }
+    }
+}
+
+impl Error for PatchError {}
+
+/// How [`Document::merge`] combines two arrays.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ArrayMergeStrategy {
+    /// The incoming array replaces the existing array.
+    #[default]
+    Replace,
+    /// The incoming elements are appended to the existing array.
+    Append,
+    /// The incoming elements that aren't already in the existing array are appended to it.
+    AppendUnique,
+    /// Elements at the same index are merged, and any additional incoming elements are appended.
+    MergeByIndex,
+}
+
+impl Document {
+    /// Applies an [RFC 6902](https://datatracker.ietf.org/doc/html/rfc6902) JSON Patch to this document.
+    ///
+    /// The patch must be an array of operation objects. The `add`, `remove`, `replace`, `move`, `copy`,
+    /// and `test` operations are supported. The patch is applied atomically: if any operation fails,
+    /// this document is left unchanged.
+    ///
+    /// ```rust
+    /// use aws_smithy_types::Document;
+    /// use std::collections::HashMap;
+    ///
+    /// let op = |op: &str, path: &str, value: Document| {
+    ///     Document::Object(HashMap::from([
+    ///         ("op".to_string(), op.into()),
+    ///         ("path".to_string(), path.into()),
+    ///         ("value".to_string(), value),
+    ///     ]))
+    /// };
+    /// let mut doc = Document::Object(HashMap::new());
+    /// doc.apply_patch(&Document::Array(vec![
+    ///     op("add", "/tags", Document::Array(vec![])),
+    ///     op("add", "/tags/-", "blue".into()),
+    /// ]))
+    /// .unwrap();
+    /// assert_eq!(Some(&Document::from("blue")), doc.pointer("/tags/0"));
+    /// ```
+    pub fn apply_patch(&mut self, patch: &Document) -> Result<(), PatchError> {
+        let operations = patch.as_array().ok_or(PatchError {
+            operation: None,
+            kind: PatchErrorKind::InvalidPatch("a patch must be an array of operations".into()),
+        })?;
+        let mut patched = self.clone();
+        for (index, operation) in operations.iter().enumerate() {
+            apply_operation(&mut patched, operation)
+                .map_err(|kind| PatchError::new(index, kind))?;
+        }
+        *self = patched;
+        Ok(())
+    }
+
+    /// Applies an [RFC 7386](https://datatracker.ietf.org/doc/html/rfc7386) JSON Merge Patch to this document.
+    ///
+    /// Objects in the patch are merged into this document recursively, and `null` members in the patch
+    /// remove the corresponding member from this document. Any other patch value, including an array,
+    /// replaces the target value.
+    pub fn apply_merge_patch(&mut self, patch: Document) {
+        match patch.into_members() {
+            Ok(members) => {
+                if !self.is_any_object() {
+                    *self = Document::Object(HashMap::new());
+                }
+                for (key, value) in members {
+                    if value.is_null() {
+                        self.remove_member(&key);
+                    } else if let Some(existing) = self.member_mut(&key) {
+                        existing.apply_merge_patch(value);
+                    } else {
+                        let mut member = Document::Null;
+                        member.apply_merge_patch(value);
+                        let _ = self.insert_member(key, member);
+                    }
+                }
+            }
+            Err(patch) => *self = patch,
+        }
+    }
+
+    /// Deeply merges `other` into this document.
+    ///
+    /// Members of two objects are merged recursively, and arrays are combined according to `arrays`.
+    /// In every other case, including when `other` is `null`, the value from `other` replaces the
+    /// existing value. Use [`Document::apply_merge_patch`] if `null` should remove values instead.
+    pub fn merge(&mut self, other: Document, arrays: ArrayMergeStrategy) {
+        match (self, other) {
+            (target, other) if target.is_any_object() && other.is_any_object() => {
+                for (key, value) in other.into_members().expect("checked above") {
+                    match target.member_mut(&key) {
+                        Some(existing) => existing.merge(value, arrays),
+                        None => {
+                            let _ = target.insert_member(key, value);
+                        }
+                    }
+                }
+            }
+            (Document::Array(target), Document::Array(other)) => match arrays {
+                ArrayMergeStrategy::Replace => *target = other,
+                ArrayMergeStrategy::Append => target.extend(other),
+                ArrayMergeStrategy::AppendUnique => {
+                    for value in other {
+                        if !target.iter().any(|existing| json_eq(existing, &value)) {
+                            target.push(value);
+                        }
+                    }
+                }
+                ArrayMergeStrategy::MergeByIndex => {
+                    let mut other = other.into_iter();
+                    for (existing, value) in target.iter_mut().zip(other.by_ref()) {
+                        existing.merge(value, arrays);
+                    }
+                    target.extend(other);
+                }
+            },
+            (target, other) => *target = other,
+        }
+    }
+}
+
+fn apply_operation(doc: &mut Document, operation: &Document) -> Result<(), PatchErrorKind> {
+    let member = |name: &'static str| {
+        operation
+            .as_object()
+            .ok_or(PatchErrorKind::InvalidPatch(
+                "an operation must be an object".into(),
+            ))?
+            .get(name)
+            .ok_or_else(|| PatchErrorKind::InvalidPatch(format!("missing `{name}`").into()))
+    };
+    let string_member = |name: &'static str| {
+        member(name)?.as_string().ok_or_else(|| {
+            PatchErrorKind::InvalidPatch(format!("`{name}` must be a string").into())
+        })
+    };
+
+    let path = string_member("path")?;
+    match string_member("op")? {
+        "add" => add(doc, path, member("value")?.clone()),
+        "remove" => remove(doc, path).map(|_| ()),
+        "replace" => {
+            let target = doc
+                .pointer_mut(path)
+                .ok_or_else(|| PatchErrorKind::PathNotFound(path.into()))?;
+            *target = member("value")?.clone();
+            Ok(())
+        }
+        "move" => {
+            let from = string_member("from")?;
+            if from == path {
+                return Ok(());
+            }
+            if path
+                .strip_prefix(from)
+                .is_some_and(|rest| rest.starts_with('/'))
+            {
+                return Err(PatchErrorKind::MoveIntoChild {
+                    from: from.into(),
+                    path: path.into(),
+                });
+            }
+            let value = remove(doc, from)?;
+            add(doc, path, value)
+        }
+        "copy" => {
+            let from = string_member("from")?;
+            let value = doc
+                .pointer(from)
+                .ok_or_else(|| PatchErrorKind::PathNotFound(from.into()))?
+                .clone();
+            add(doc, path, value)
+        }
+        "test" => {
+            let actual = doc
+                .pointer(path)
+                .ok_or_else(|| PatchErrorKind::PathNotFound(path.into()))?;
+            if json_eq(actual, member("value")?) {
+                Ok(())
+            } else {
+                Err(PatchErrorKind::TestFailed(path.into()))
+            }
+        }
+        op => Err(PatchErrorKind::InvalidPatch(
+            format!("unknown operation `{op}`").into(),
+        )),
+    }
+}
+
+/// Splits a pointer into the pointer to its parent and its last (unescaped) reference token.
+fn split_last(path: &str) -> Result<(&str, Cow<'_, str>), PatchErrorKind> {
+    let not_found = || PatchErrorKind::PathNotFound(path.into());
+    let index = path.rfind('/').ok_or_else(not_found)?;
+    let last = tokens(&path[index..])
+        .and_then(|mut tokens| tokens.next())
+        .ok_or_else(not_found)?;
+    Ok((&path[..index], last))
+}
+
+fn add(doc: &mut Document, path: &str, value: Document) -> Result<(), PatchErrorKind> {
+    if path.is_empty() {
+        *doc = value;
+        return Ok(());
+    }
+    let not_found = || PatchErrorKind::PathNotFound(path.into());
+    let (parent, last) = split_last(path)?;
+    match doc.pointer_mut(parent).ok_or_else(not_found)? {
+        Document::Array(array) => {
+            let index = if last == "-" {
+                array.len()
+            } else {
+                parse_index(&last)
+                    .filter(|index| *index <= array.len())
+                    .ok_or_else(not_found)?
+            };
+            array.insert(index, value);
+            Ok(())
+        }
+        parent => parent
+            .insert_member(last.into_owned(), value)
+            .map_err(|_| not_found()),
+    }
+}
+
+fn remove(doc: &mut Document, path: &str) -> Result<Document, PatchErrorKind> {
+    let not_found = || PatchErrorKind::PathNotFound(path.into());
+    if path.is_empty() {
+        return Ok(std::mem::take(doc));
+    }
+    let (parent, last) = split_last(path)?;
+    match doc.pointer_mut(parent).ok_or_else(not_found)? {
+        Document::Array(array) => {
+            let index = parse_index(&last)
+                .filter(|index| *index < array.len())
+                .ok_or_else(not_found)?;
+            Ok(array.remove(index))
+        }
+        parent => parent.remove_member(&last).ok_or_else(not_found),
+    }
+}
+
+/// Compares two documents as JSON values, so that numbers are equal if they are numerically equal.
+fn json_eq(a: &Document, b: &Document) -> bool {
+    match (a, b) {
+        (Document::Number(a), Document::Number(b)) => number_eq(*a, *b),
+        (Document::Array(a), Document::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| json_eq(a, b))
+        }
+        (a, b) if a.is_any_object() && b.is_any_object() => {
+            let len = |doc: &Document| doc.members().map_or(0, Iterator::count);
+            len(a) == len(b)
+                && a.members()
+                    .into_iter()
+                    .flatten()
+                    .all(|(key, a)| b.member(key).is_some_and(|b| json_eq(a, b)))
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn number_eq(a: Number, b: Number) -> bool {
+    let as_int = |n: Number| match n {
+        Number::PosInt(v) => Some(v as i128),
+        Number::NegInt(v) => Some(v as i128),
+        Number::Float(_) => None,
+    };
+    match (as_int(a), as_int(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => a.to_f64_lossy() == b.to_f64_lossy(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ArrayMergeStrategy;
+    use crate::{Document, Number};
+    use std::collections::HashMap;
+
+    fn obj<const N: usize>(members: [(&str, Document); N]) -> Document {
+        Document::Object(
+            members
+                .into_iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        )
+    }
+
+    fn arr<const N: usize>(values: [Document; N]) -> Document {
+        Document::Array(values.into())
+    }
+
+    fn op(op: &str, path: &str, value: Option<Document>, from: Option<&str>) -> Document {
+        let mut operation = HashMap::from([
+            ("op".to_string(), op.into()),
+            ("path".to_string(), path.into()),
+        ]);
+        if let Some(value) = value {
+            operation.insert("value".into(), value);
+        }
+        if let Some(from) = from {
+            operation.insert("from".into(), from.into());
+        }
+        Document::Object(operation)
+    }
+
+    #[test]
+    fn json_patch_rfc_examples() {
+        // RFC 6902, appendix A.1, A.2, A.3, A.6, A.7, and A.16
+        let mut doc = obj([("foo", "bar".into())]);
+        doc.apply_patch(&arr([
+            op("add", "/baz", Some("qux".into()), None),
+            op("add", "/list", Some(arr(["a".into(), "c".into()])), None),
+            op("add", "/list/1", Some("b".into()), None),
+            op("add", "/list/-", Some(arr(["d".into()])), None),
+            op("remove", "/baz", None, None),
+            op("replace", "/foo", Some("baz".into()), None),
+            op("move", "/moved", None, Some("/foo")),
+            op("copy", "/copied", None, Some("/list/1")),
+            op("test", "/list/3/0", Some("d".into()), None),
+        ]))
+        .unwrap();
+        assert_eq!(
+            obj([
+                ("moved", "baz".into()),
+                ("copied", "b".into()),
+                (
+                    "list",
+                    arr(["a".into(), "b".into(), "c".into(), arr(["d".into()])])
+                ),
+            ]),
+            doc
+        );
+    }
+
+    #[test]
+    fn json_patch_is_atomic() {
+        let original = obj([("a", 1u64.into())]);
+        let mut doc = original.clone();
+        let err = doc
+            .apply_patch(&arr([
+                op("remove", "/a", None, None),
+                op("test", "/a", Some(1u64.into()), None),
+            ]))
+            .expect_err("`/a` was removed");
+        assert_eq!(Some(1), err.operation());
+        assert_eq!(
+            "patch operation 1 failed: path `/a` does not exist",
+            err.to_string()
+        );
+        assert_eq!(original, doc);
+    }
+
+    #[test]
+    fn json_patch_errors() {
+        let mut doc = obj([("a", obj([("b", 1u64.into())])), ("list", arr([]))]);
+        let cases = [
+            (
+                op("add", "/x/y", Some(1u64.into()), None),
+                "path `/x/y` does not exist",
+            ),
+            (
+                op("add", "/list/1", Some(1u64.into()), None),
+                "path `/list/1` does not exist",
+            ),
+            (
+                op("remove", "/list/0", None, None),
+                "path `/list/0` does not exist",
+            ),
+            (
+                op("test", "/a/b", Some(2u64.into()), None),
+                "value at `/a/b` does not match",
+            ),
+            (
+                op("move", "/a/b/c", None, Some("/a")),
+                "cannot move `/a` into its own child `/a/b/c`",
+            ),
+            (
+                op("frobnicate", "/a", None, None),
+                "invalid patch: unknown operation `frobnicate`",
+            ),
+            (
+                op("add", "/a", None, None),
+                "invalid patch: missing `value`",
+            ),
+        ];
+        for (operation, expected) in cases {
+            let err = doc.apply_patch(&arr([operation])).expect_err(expected);
+            assert_eq!(
+                format!("patch operation 0 failed: {expected}"),
+                err.to_string()
+            );
+        }
+        let err = doc.apply_patch(&obj([])).expect_err("not an array");
+        assert_eq!(None, err.operation());
+    }
+
+    #[test]
+    fn json_patch_test_compares_numbers_numerically() {
+        let mut doc = obj([("n", Document::Number(Number::PosInt(1)))]);
+        doc.apply_patch(&arr([
+            op("test", "/n", Some(Number::Float(1.0).into()), None),
+            op("test", "/n", Some(Number::NegInt(1).into()), None),
+        ]))
+        .unwrap();
+    }
+
+    #[test]
+    fn merge_patch_rfc_examples() {
+        // RFC 7386, appendix A
+        let cases = [
+            (
+                obj([("a", "b".into())]),
+                obj([("a", "c".into())]),
+                obj([("a", "c".into())]),
+            ),
+            (
+                obj([("a", "b".into())]),
+                obj([("b", "c".into())]),
+                obj([("a", "b".into()), ("b", "c".into())]),
+            ),
+            (
+                obj([("a", "b".into())]),
+                obj([("a", Document::Null)]),
+                obj([]),
+            ),
+            (
+                obj([("a", "b".into()), ("b", "c".into())]),
+                obj([("a", Document::Null)]),
+                obj([("b", "c".into())]),
+            ),
+            (
+                obj([("a", arr(["b".into()]))]),
+                obj([("a", "c".into())]),
+                obj([("a", "c".into())]),
+            ),
+            (
+                obj([("a", "c".into())]),
+                obj([("a", arr(["b".into()]))]),
+                obj([("a", arr(["b".into()]))]),
+            ),
+            (
+                obj([("a", obj([("b", "c".into())]))]),
+                obj([("a", obj([("b", "d".into()), ("c", Document::Null)]))]),
+                obj([("a", obj([("b", "d".into())]))]),
+            ),
+            (
+                obj([("a", arr([obj([("b", "c".into())])]))]),
+                obj([("a", arr([1u64.into()]))]),
+                obj([("a", arr([1u64.into()]))]),
+            ),
+            (
+                arr(["a".into(), "b".into()]),
+                arr(["c".into(), "d".into()]),
+                arr(["c".into(), "d".into()]),
+            ),
+            (
+                obj([("a", "b".into())]),
+                arr(["c".into()]),
+                arr(["c".into()]),
+            ),
+            (obj([("a", "foo".into())]), Document::Null, Document::Null),
+            (obj([("a", "foo".into())]), "bar".into(), "bar".into()),
+            (
+                obj([("e", Document::Null)]),
+                obj([("a", 1u64.into())]),
+                obj([("e", Document::Null), ("a", 1u64.into())]),
+            ),
+            (
+                arr([1u64.into(), 2u64.into()]),
+                obj([("a", "b".into()), ("c", Document::Null)]),
+                obj([("a", "b".into())]),
+            ),
+            (
+                obj([]),
+                obj([("a", obj([("bb", obj([("ccc", Document::Null)]))]))]),
+                obj([("a", obj([("bb", obj([]))]))]),
+            ),
+        ];
+        for (original, patch, expected) in cases {
+            let mut doc = original.clone();
+            doc.apply_merge_patch(patch.clone());
+            assert_eq!(expected, doc, "{original:?} patched with {patch:?}");
+        }
+    }
+
+    #[test]
+    fn merge_with_array_strategies() {
+        let base = || {
+            obj([
+                ("name", "base".into()),
+                ("list", arr([1u64.into(), obj([("a", 1u64.into())])])),
+                ("nested", obj([("keep", true.into())])),
+            ])
+        };
+        let other = || {
+            obj([
+                ("name", Document::Null),
+                (
+                    "list",
+                    arr([1u64.into(), obj([("b", 2u64.into())]), 3u64.into()]),
+                ),
+                ("nested", obj([("added", true.into())])),
+            ])
+        };
+        let expected = |list: Document| {
+            obj([
+                ("name", Document::Null),
+                ("list", list),
+                (
+                    "nested",
+                    obj([("keep", true.into()), ("added", true.into())]),
+                ),
+            ])
+        };
+        let cases = [
+            (
+                ArrayMergeStrategy::Replace,
+                arr([1u64.into(), obj([("b", 2u64.into())]), 3u64.into()]),
+            ),
+            (
+                ArrayMergeStrategy::Append,
+                arr([
+                    1u64.into(),
+                    obj([("a", 1u64.into())]),
+                    1u64.into(),
+                    obj([("b", 2u64.into())]),
+                    3u64.into(),
+                ]),
+            ),
+            (
+                ArrayMergeStrategy::AppendUnique,
+                arr([
+                    1u64.into(),
+                    obj([("a", 1u64.into())]),
+                    obj([("b", 2u64.into())]),
+                    3u64.into(),
+                ]),
+            ),
+            (
+                ArrayMergeStrategy::MergeByIndex,
+                arr([
+                    1u64.into(),
+                    obj([("a", 1u64.into()), ("b", 2u64.into())]),
+                    3u64.into(),
+                ]),
+            ),
+        ];
+        for (strategy, list) in cases {
+            let mut doc = base();
+            doc.merge(other(), strategy);
+            assert_eq!(expected(list), doc, "{strategy:?}");
+        }
+    }
+}
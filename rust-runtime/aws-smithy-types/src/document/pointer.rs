@@ -0,0 +1,156 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON Pointer support for [`Document`].
+
+use crate::Document;
+use std::borrow::Cow;
+
+impl Document {
+    /// Looks up a value by an [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON Pointer.
+    ///
+    /// The empty pointer `""` refers to the whole document. Any other pointer must start with `/`.
+    /// Returns `None` if the pointer is malformed or doesn't refer to an existing value.
+    ///
+    /// ```rust
+    /// use aws_smithy_types::Document;
+    /// use std::collections::HashMap;
+    ///
+    /// let doc = Document::Object(HashMap::from([(
+    ///     "tags".to_string(),
+    ///     Document::Array(vec!["a".into(), "b".into()]),
+    /// )]));
+    /// assert_eq!(Some(&Document::from("b")), doc.pointer("/tags/1"));
+    /// assert_eq!(None, doc.pointer("/tags/2"));
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Document> {
+        let mut target = self;
+        for token in tokens(pointer)? {
+            target = match target {
+                Document::Array(array) => array.get(parse_index(&token)?)?,
+                other => other.member(&token)?,
+            };
+        }
+        Some(target)
+    }
+
+    /// Looks up a value by an [RFC 6901](https://datatracker.ietf.org/doc/html/rfc6901) JSON Pointer
+    /// and returns it mutably.
+    ///
+    /// See [`Document::pointer`] for details.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Document> {
+        let mut target = self;
+        for token in tokens(pointer)? {
+            target = match target {
+                Document::Array(array) => array.get_mut(parse_index(&token)?)?,
+                other => other.member_mut(&token)?,
+            };
+        }
+        Some(target)
+    }
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+///
+/// Returns `None` if the pointer is neither empty nor starts with `/`.
+pub(super) fn tokens(pointer: &str) -> Option<impl Iterator<Item = Cow<'_, str>>> {
+    let rest = match pointer {
+        "" => None,
+        _ => Some(pointer.strip_prefix('/')?),
+    };
+    Some(
+        rest.into_iter()
+            .flat_map(|rest| rest.split('/').map(unescape)),
+    )
+}
+
+fn unescape(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        // `~1` must be unescaped before `~0` so that `~01` becomes `~1` rather than `/`
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Parses an array index, rejecting leading zeros, signs, and the `-` (past the end) token.
+pub(super) fn parse_index(token: &str) -> Option<usize> {
+    let valid = !token.is_empty()
+        && token.bytes().all(|b| b.is_ascii_digit())
+        && (token == "0" || !token.starts_with('0'));
+    if valid {
+        token.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::Document;
+    use std::collections::HashMap;
+
+    // The example document from RFC 6901, section 5
+    fn rfc_example() -> Document {
+        Document::Object(HashMap::from([
+            (
+                "foo".to_string(),
+                Document::Array(vec!["bar".into(), "baz".into()]),
+            ),
+            ("".to_string(), 0u64.into()),
+            ("a/b".to_string(), 1u64.into()),
+            ("c%d".to_string(), 2u64.into()),
+            ("e^f".to_string(), 3u64.into()),
+            ("g|h".to_string(), 4u64.into()),
+            ("i\\j".to_string(), 5u64.into()),
+            ("k\"l".to_string(), 6u64.into()),
+            (" ".to_string(), 7u64.into()),
+            ("m~n".to_string(), 8u64.into()),
+        ]))
+    }
+
+    #[test]
+    fn rfc_examples() {
+        let doc = rfc_example();
+        assert_eq!(Some(&doc), doc.pointer(""));
+        assert_eq!(
+            Some(&Document::Array(vec!["bar".into(), "baz".into()])),
+            doc.pointer("/foo")
+        );
+        assert_eq!(Some(&"bar".into()), doc.pointer("/foo/0"));
+        let expected = [
+            ("/", 0u64),
+            ("/a~1b", 1),
+            ("/c%d", 2),
+            ("/e^f", 3),
+            ("/g|h", 4),
+            ("/i\\j", 5),
+            ("/k\"l", 6),
+            ("/ ", 7),
+            ("/m~0n", 8),
+        ];
+        for (pointer, value) in expected {
+            assert_eq!(Some(&value.into()), doc.pointer(pointer), "{pointer}");
+        }
+    }
+
+    #[test]
+    fn invalid_pointers() {
+        let doc = rfc_example();
+        for pointer in [
+            "foo", "/foo/2", "/foo/-", "/foo/01", "/foo/+1", "/foo/0/x", "/nope",
+        ] {
+            assert_eq!(None, doc.pointer(pointer), "{pointer}");
+        }
+    }
+
+    #[test]
+    fn pointer_mut() {
+        let mut doc = rfc_example();
+        *doc.pointer_mut("/foo/1").unwrap() = "qux".into();
+        assert_eq!(Some(&"qux".into()), doc.pointer("/foo/1"));
+        assert!(doc.pointer_mut("/foo/2").is_none());
+    }
+}
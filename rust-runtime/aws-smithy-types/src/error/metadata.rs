@@ -128,6 +128,13 @@ impl ErrorMetadata {
             .as_ref()
             .and_then(|extras| extras.get(key).map(|k| k.as_str()))
     }
+    /// Returns all additional information about the error, in no particular order.
+    pub fn extras(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        self.extras
+            .iter()
+            .flatten()
+            .map(|(key, value)| (*key, value.as_str()))
+    }
 
     /// Creates an `Error` builder.
     pub fn builder() -> Builder {
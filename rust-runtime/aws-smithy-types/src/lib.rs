@@ -18,11 +18,14 @@
 )]
 
 pub mod base64;
+pub mod big_number;
 pub mod body;
 pub mod byte_stream;
 /// A typemap for storing configuration.
 pub mod config_bag;
 pub mod date_time;
+pub mod decode_limits;
+pub mod document;
 pub mod endpoint;
 pub mod error;
 pub mod event_stream;
@@ -34,10 +37,10 @@ pub mod timeout;
 pub mod type_erasure;
 
 mod blob;
-mod document;
 mod number;
 pub mod str_bytes;
 
+pub use big_number::{BigDecimal, BigInteger};
 pub use blob::Blob;
 pub use date_time::DateTime;
 pub use document::Document;
@@ -31,6 +31,7 @@
 //! assert_eq!("true", Encoder::from(true).encode());
 //! ```
 use crate::primitive::private::Sealed;
+use crate::{BigDecimal, BigInteger};
 use std::error::Error;
 use std::fmt;
 use std::str::FromStr;
@@ -65,6 +66,8 @@ mod private {
     impl Sealed for f64 {}
     impl Sealed for u64 {}
     impl Sealed for bool {}
+    impl Sealed for crate::BigInteger {}
+    impl Sealed for crate::BigDecimal {}
 }
 
 macro_rules! parse_from_str {
@@ -82,6 +85,8 @@ parse_from_str!(i8);
 parse_from_str!(i16);
 parse_from_str!(i32);
 parse_from_str!(i64);
+parse_from_str!(BigInteger);
+parse_from_str!(BigDecimal);
 
 impl Parse for f32 {
     fn parse_smithy_primitive(input: &str) -> Result<Self, PrimitiveParseError> {
@@ -112,6 +117,10 @@ enum Inner {
     F32(f32, ryu::Buffer),
     /// 64-bit IEEE 754 double-precision floating-point number
     F64(f64, ryu::Buffer),
+    /// Arbitrary precision integer
+    BigInteger(BigInteger),
+    /// Arbitrary precision decimal number
+    BigDecimal(BigDecimal),
 }
 
 impl fmt::Debug for Inner {
@@ -125,6 +134,8 @@ impl fmt::Debug for Inner {
             Self::U64(v, _) => write!(f, "U64({})", v),
             Self::F32(v, _) => write!(f, "F32({})", v),
             Self::F64(v, _) => write!(f, "F64({})", v),
+            Self::BigInteger(v) => write!(f, "BigInteger({})", v),
+            Self::BigDecimal(v) => write!(f, "BigDecimal({})", v),
         }
     }
 }
@@ -172,6 +183,8 @@ impl Encoder {
                     buf.format_finite(*v)
                 }
             }
+            Inner::BigInteger(v) => v.as_str(),
+            Inner::BigDecimal(v) => v.as_str(),
         }
     }
 }
@@ -224,6 +237,30 @@ impl From<f64> for Encoder {
     }
 }
 
+impl From<BigInteger> for Encoder {
+    fn from(input: BigInteger) -> Self {
+        Self(Inner::BigInteger(input))
+    }
+}
+
+impl From<&BigInteger> for Encoder {
+    fn from(input: &BigInteger) -> Self {
+        Self(Inner::BigInteger(input.clone()))
+    }
+}
+
+impl From<BigDecimal> for Encoder {
+    fn from(input: BigDecimal) -> Self {
+        Self(Inner::BigDecimal(input))
+    }
+}
+
+impl From<&BigDecimal> for Encoder {
+    fn from(input: &BigDecimal) -> Self {
+        Self(Inner::BigDecimal(input.clone()))
+    }
+}
+
 mod float {
     use std::num::ParseFloatError;
 
@@ -271,6 +308,25 @@ mod test {
         assert!(!bool::parse_smithy_primitive("false").unwrap());
     }
 
+    #[test]
+    fn big_number_format() {
+        let integer: crate::BigInteger = "-18446744073709551616".parse().unwrap();
+        assert_eq!(Encoder::from(&integer).encode(), "-18446744073709551616");
+        let decimal: crate::BigDecimal = "1.000000000000000000001E+30".parse().unwrap();
+        assert_eq!(
+            Encoder::from(decimal).encode(),
+            "1.000000000000000000001E+30"
+        );
+    }
+
+    #[test]
+    fn big_number_parse() {
+        let parsed = crate::BigInteger::parse_smithy_primitive("18446744073709551616").unwrap();
+        assert_eq!("18446744073709551616", parsed.as_str());
+        let err = crate::BigDecimal::parse_smithy_primitive("1.").expect_err("should fail");
+        assert_eq!(err.0, "BigDecimal");
+    }
+
     #[test]
     fn float_format() {
         assert_eq!(Encoder::from(55_f64).encode(), "55.0");
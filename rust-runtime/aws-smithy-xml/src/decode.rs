@@ -4,11 +4,14 @@
  */
 
 use crate::unescape::unescape;
+use aws_smithy_types::decode_limits::{DecodeLimits, LimitExceeded};
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use xmlparser::{ElementEnd, Token, Tokenizer};
 
+pub mod pull;
+
 pub type Depth = usize;
 
 // in general, these errors are just for reporting what happened, there isn't
@@ -20,6 +23,7 @@ enum XmlDecodeErrorKind {
     InvalidEscape { esc: String },
     Custom(Cow<'static, str>),
     Unhandled(Box<dyn std::error::Error + Send + Sync + 'static>),
+    LimitExceeded { limit: LimitExceeded, offset: usize },
 }
 
 #[derive(Debug)]
@@ -34,6 +38,9 @@ impl Display for XmlDecodeError {
             XmlDecodeErrorKind::InvalidEscape { esc } => write!(f, "invalid XML escape: {}", esc),
             XmlDecodeErrorKind::Custom(msg) => write!(f, "error parsing XML: {}", msg),
             XmlDecodeErrorKind::Unhandled(_) => write!(f, "error parsing XML"),
+            XmlDecodeErrorKind::LimitExceeded { limit, offset } => {
+                write!(f, "error parsing XML: input {limit} at offset {offset}")
+            }
         }
     }
 }
@@ -43,6 +50,7 @@ impl Error for XmlDecodeError {
         match &self.kind {
             XmlDecodeErrorKind::InvalidXml(source) => Some(source as _),
             XmlDecodeErrorKind::Unhandled(source) => Some(source.as_ref() as _),
+            XmlDecodeErrorKind::LimitExceeded { limit, .. } => Some(limit as _),
             XmlDecodeErrorKind::InvalidEscape { .. } | XmlDecodeErrorKind::Custom(..) => None,
         }
     }
@@ -72,6 +80,21 @@ impl XmlDecodeError {
             kind: XmlDecodeErrorKind::Unhandled(error.into()),
         }
     }
+
+    pub(crate) fn from_limit(limit: LimitExceeded, offset: usize) -> Self {
+        Self {
+            kind: XmlDecodeErrorKind::LimitExceeded { limit, offset },
+        }
+    }
+
+    /// Returns the limit that was exceeded if this error was caused by the document exceeding the
+    /// configured [`DecodeLimits`].
+    pub fn limit_exceeded(&self) -> Option<&LimitExceeded> {
+        match &self.kind {
+            XmlDecodeErrorKind::LimitExceeded { limit, .. } => Some(limit),
+            _ => None,
+        }
+    }
 }
 
 #[derive(PartialEq, Debug)]
@@ -185,9 +208,16 @@ impl<'a> StartEl<'a> {
 ///
 /// This document wraps a lazy tokenizer with depth tracking.
 /// Constructing a document is essentially free.
+#[derive(Clone)]
 pub struct Document<'a> {
     tokenizer: Tokenizer<'a>,
     depth: Depth,
+    limits: DecodeLimits,
+    token_count: usize,
+    /// Number of child elements read so far for each open element
+    child_counts: Vec<usize>,
+    /// The limit that stopped reading, and the offset of the token that exceeded it
+    limit_exceeded: Option<(LimitExceeded, usize)>,
 }
 
 impl<'a> TryFrom<&'a [u8]> for Document<'a> {
@@ -205,7 +235,61 @@ impl<'inp> Document<'inp> {
         Document {
             tokenizer: Tokenizer::from(doc),
             depth: 0,
+            limits: DecodeLimits::new(),
+            token_count: 0,
+            child_counts: Vec::new(),
+            limit_exceeded: None,
+        }
+    }
+
+    /// Enforces the given [`DecodeLimits`] while reading this document
+    ///
+    /// Nesting depth counts elements (the root element has a depth of 1), collection size counts
+    /// the child elements of a single element, and string length applies to text, CDATA and
+    /// attribute values before unescaping.
+    ///
+    /// The limits are checked as the document is read. The token that exceeds a limit yields an
+    /// error and reading stops there. From then on, [`next_start_element`](Document::next_start_element)
+    /// and [`ScopedDecoder::next_tag`] return that error instead of reporting the end of the
+    /// document.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Returns the error for the limit that stopped reading, if any.
+    fn limit_error(&self) -> Option<XmlDecodeError> {
+        self.limit_exceeded
+            .map(|(limit, offset)| XmlDecodeError::from_limit(limit, offset))
+    }
+
+    /// Checks a token against the configured limits.
+    fn check_token(&mut self, token: &Token<'_>) -> Result<(), LimitExceeded> {
+        self.token_count += 1;
+        self.limits.check_tokens(self.token_count)?;
+        match token {
+            Token::ElementStart { .. } => {
+                if let Some(children) = self.child_counts.last_mut() {
+                    *children += 1;
+                    self.limits.check_collection_size(*children)?;
+                }
+                self.child_counts.push(0);
+                self.limits.check_depth(self.child_counts.len())?;
+            }
+            Token::ElementEnd {
+                end: ElementEnd::Close(..) | ElementEnd::Empty,
+                ..
+            } => {
+                self.child_counts.pop();
+            }
+            Token::Attribute { value: text, .. }
+            | Token::Text { text }
+            | Token::Cdata { text, .. } => {
+                self.limits.check_string_length(text.as_str().len())?;
+            }
+            _ => {}
         }
+        Ok(())
     }
 
     /// "Depth first" iterator
@@ -223,14 +307,19 @@ impl<'inp> Document<'inp> {
     ///    <B/> <-- second call to next_tag returns this
     /// </Response>
     /// ```
-    pub fn next_start_element<'a>(&'a mut self) -> Option<StartEl<'inp>> {
-        next_start_element(self)
+    ///
+    /// Returns an error if the document exceeded one of its [limits](Document::with_limits).
+    pub fn next_start_element<'a>(&'a mut self) -> Result<Option<StartEl<'inp>>, XmlDecodeError> {
+        match next_start_element(self) {
+            Some(start_el) => Ok(Some(start_el)),
+            None => self.limit_error().map_or(Ok(None), Err),
+        }
     }
 
     /// A scoped reader for the entire document
     pub fn root_element<'a>(&'a mut self) -> Result<ScopedDecoder<'inp, 'a>, XmlDecodeError> {
         let start_el = self
-            .next_start_element()
+            .next_start_element()?
             .ok_or_else(|| XmlDecodeError::custom("no root element"))?;
         Ok(ScopedDecoder {
             doc: self,
@@ -271,11 +360,21 @@ pub struct XmlToken<'inp>(Token<'inp>);
 impl<'inp> Iterator for Document<'inp> {
     type Item = Result<(XmlToken<'inp>, Depth), XmlDecodeError>;
     fn next<'a>(&'a mut self) -> Option<Result<(XmlToken<'inp>, Depth), XmlDecodeError>> {
+        if self.limit_exceeded.is_some() {
+            return None;
+        }
         let tok = self.tokenizer.next()?;
         let tok = match tok {
             Err(e) => return Some(Err(XmlDecodeError::invalid_xml(e))),
             Ok(tok) => tok,
         };
+        if !self.limits.is_unlimited() {
+            if let Err(limit) = self.check_token(&tok) {
+                let offset = tok.span().start();
+                self.limit_exceeded = Some((limit, offset));
+                return Some(Err(XmlDecodeError::from_limit(limit, offset)));
+            }
+        }
         // depth bookkeeping
         match tok {
             Token::ElementEnd {
@@ -338,9 +437,39 @@ impl<'inp> ScopedDecoder<'inp, '_> {
     ///    <B/> <-- second call to next_tag returns this
     /// </Response>
     /// ```
-    pub fn next_tag<'a>(&'a mut self) -> Option<ScopedDecoder<'inp, 'a>> {
-        let next_tag = next_start_element(self)?;
-        Some(self.nested_decoder(next_tag))
+    ///
+    /// Returns an error if the document exceeded one of its [limits](Document::with_limits).
+    pub fn next_tag<'a>(&'a mut self) -> Result<Option<ScopedDecoder<'inp, 'a>>, XmlDecodeError> {
+        match next_start_element(self) {
+            Some(start_el) => Ok(Some(self.nested_decoder(start_el))),
+            None => self.doc.limit_error().map_or(Ok(None), Err),
+        }
+    }
+
+    /// Returns the next top-level text or tag in this scope
+    ///
+    /// Unlike [`next_tag()`](ScopedDecoder::next_tag), text between tags is returned rather
+    /// than skipped.
+    pub(crate) fn next_node<'a>(&'a mut self) -> Option<Result<Node<'inp, 'a>, XmlDecodeError>> {
+        loop {
+            let (tok, depth) = match self.next() {
+                Some(Ok(tok)) => tok,
+                Some(Err(e)) => return Some(Err(e)),
+                None => return self.doc.limit_error().map(Err),
+            };
+            match tok.0 {
+                Token::Text { text } => return Some(unescape(text.as_str()).map(Node::Text)),
+                Token::Cdata { text, .. } => {
+                    return Some(Ok(Node::Text(Cow::Borrowed(text.as_str()))))
+                }
+                Token::ElementStart { local, prefix, .. } => {
+                    let start_el = StartEl::new(local.as_str(), prefix.as_str(), depth);
+                    let start_el = finish_start_element(start_el, self)?;
+                    return Some(Ok(Node::Tag(self.nested_decoder(start_el))));
+                }
+                _ => {}
+            }
+        }
     }
 
     fn nested_decoder<'a>(&'a mut self, start_el: StartEl<'inp>) -> ScopedDecoder<'inp, 'a> {
@@ -378,18 +507,31 @@ impl<'inp, 'a> Iterator for ScopedDecoder<'inp, 'a> {
     }
 }
 
+/// A top-level node in a [`ScopedDecoder`]
+pub(crate) enum Node<'inp, 'a> {
+    Text(Cow<'inp, str>),
+    Tag(ScopedDecoder<'inp, 'a>),
+}
+
 /// Load the next start element out of a depth-tagged token iterator
 fn next_start_element<'a, 'inp>(
     tokens: &'a mut impl Iterator<Item = Result<(XmlToken<'inp>, Depth), XmlDecodeError>>,
 ) -> Option<StartEl<'inp>> {
-    let mut out = StartEl::new("", "", 0);
+    loop {
+        if let Ok((XmlToken(Token::ElementStart { local, prefix, .. }), depth)) = tokens.next()? {
+            let start_el = StartEl::new(local.as_str(), prefix.as_str(), depth);
+            return finish_start_element(start_el, tokens);
+        }
+    }
+}
+
+/// Load the attributes of a start element whose opening token has already been read
+fn finish_start_element<'a, 'inp>(
+    mut out: StartEl<'inp>,
+    tokens: &'a mut impl Iterator<Item = Result<(XmlToken<'inp>, Depth), XmlDecodeError>>,
+) -> Option<StartEl<'inp>> {
     loop {
         match tokens.next()? {
-            Ok((XmlToken(Token::ElementStart { local, prefix, .. }), depth)) => {
-                out.name.local = local.as_str();
-                out.name.prefix = prefix.as_str();
-                out.depth = depth;
-            }
             Ok((
                 XmlToken(Token::Attribute {
                     prefix,
@@ -453,7 +595,10 @@ pub fn try_data<'a, 'inp>(
 
 #[cfg(test)]
 mod test {
-    use crate::decode::{try_data, Attr, Depth, Document, Name, StartEl};
+    use crate::decode::{
+        try_data, Attr, Depth, Document, Name, ScopedDecoder, StartEl, XmlDecodeError,
+    };
+    use aws_smithy_types::decode_limits::{DecodeLimit, DecodeLimits};
 
     // test helper to create a closed startel
     fn closed<'a>(local: &'a str, prefix: &'a str, depth: Depth) -> StartEl<'a> {
@@ -468,8 +613,15 @@ mod test {
         let mut doc = Document::new(xml);
         let mut root = doc.root_element().expect("valid document");
         assert_eq!(root.start_el().local(), "Response");
-        assert_eq!(root.next_tag().expect("tag exists").start_el().local(), "A");
-        assert!(root.next_tag().is_none());
+        assert_eq!(
+            root.next_tag()
+                .unwrap()
+                .expect("tag exists")
+                .start_el()
+                .local(),
+            "A"
+        );
+        assert!(root.next_tag().unwrap().is_none());
     }
 
     #[test]
@@ -478,12 +630,12 @@ mod test {
         let mut doc = Document::new(xml);
         let mut scoped = doc.root_element().expect("valid document");
         assert_eq!(
-            scoped.next_tag().unwrap().start_el(),
+            scoped.next_tag().unwrap().unwrap().start_el(),
             &StartEl::new("Response", "", 1)
         );
         let closed_a = closed("A", "", 1);
-        assert_eq!(scoped.next_tag().unwrap().start_el(), &closed_a);
-        assert!(scoped.next_tag().is_none())
+        assert_eq!(scoped.next_tag().unwrap().unwrap().start_el(), &closed_a);
+        assert!(scoped.next_tag().unwrap().is_none())
     }
 
     #[test]
@@ -492,7 +644,7 @@ mod test {
         let mut doc = Document::new(xml);
         let mut scoped = doc.root_element().expect("valid doc");
         assert!(scoped.start_el.closed);
-        assert!(scoped.next_tag().is_none())
+        assert!(scoped.next_tag().unwrap().is_none())
     }
 
     #[test]
@@ -500,16 +652,20 @@ mod test {
         let xml = r#"<Response><Struct><A></A><Also/></Struct><More/></Response>"#;
         let mut doc = Document::new(xml);
         let mut response_iter = doc.root_element().expect("valid doc");
-        let mut struct_iter = response_iter.next_tag().unwrap();
+        let mut struct_iter = response_iter.next_tag().unwrap().unwrap();
         assert_eq!(
-            struct_iter.next_tag().as_ref().map(|t| t.start_el()),
+            struct_iter
+                .next_tag()
+                .unwrap()
+                .as_ref()
+                .map(|t| t.start_el()),
             Some(&StartEl::new("A", "", 2))
         );
         // When the inner iter is dropped, it will read to the end of its scope
         // prevent accidental behavior where we didn't read a full node
         drop(struct_iter);
         assert_eq!(
-            response_iter.next_tag().unwrap().start_el(),
+            response_iter.next_tag().unwrap().unwrap().start_el(),
             &closed("More", "", 1)
         );
     }
@@ -544,7 +700,7 @@ mod test {
         let xml = r#"<Response>   <A>  </A>    </Response>"#;
         let mut doc = Document::new(xml);
         let mut resp = doc.root_element().unwrap();
-        let mut a = resp.next_tag().expect("should be a");
+        let mut a = resp.next_tag().unwrap().expect("should be a");
         let data = try_data(&mut a).expect("valid");
         assert_eq!(data, "  ");
     }
@@ -584,12 +740,12 @@ mod test {
         </XmlListsInputOutput>"#;
         let mut doc = Document::new(xml);
         let mut root = doc.root_element().unwrap();
-        let mut string_list = root.next_tag().unwrap();
+        let mut string_list = root.next_tag().unwrap().unwrap();
         assert_eq!(string_list.start_el(), &closed("stringList", "", 1));
-        assert!(string_list.next_tag().is_none());
+        assert!(string_list.next_tag().unwrap().is_none());
         drop(string_list);
         assert_eq!(
-            root.next_tag().unwrap().start_el(),
+            root.next_tag().unwrap().unwrap().start_el(),
             &StartEl::new("stringSet", "", 1)
         );
     }
@@ -612,9 +768,79 @@ mod test {
         let mut doc = Document::new(xml);
         let mut root = doc.root_element().unwrap();
         let mut cmp = vec![];
-        while let Some(tag) = root.next_tag() {
+        while let Some(tag) = root.next_tag().unwrap() {
             cmp.push(tag.start_el().local().to_owned());
         }
         assert_eq!(root_tags, cmp.as_slice());
     }
+
+    #[test]
+    fn limits() {
+        let xml = r#"<a><b attr="xyz">hello</b><c><d/></c><e/></a>"#;
+        #[track_caller]
+        fn expect_limit(xml: &str, limits: DecodeLimits, limit: DecodeLimit, offset: usize) {
+            fn read_all(decoder: &mut ScopedDecoder<'_, '_>) -> Result<(), XmlDecodeError> {
+                while let Some(mut tag) = decoder.next_tag()? {
+                    read_all(&mut tag)?;
+                }
+                Ok(())
+            }
+
+            // Reading through scoped decoders stops with the limit error, and the error keeps
+            // being returned instead of the end of the document
+            let mut doc = Document::new(xml).with_limits(limits);
+            let err = doc
+                .root_element()
+                .and_then(|mut root| read_all(&mut root))
+                .expect_err("limit should be exceeded");
+            assert_eq!(Some(limit), err.limit_exceeded().map(|l| l.limit()));
+            assert_eq!(
+                Some(limit),
+                doc.next_start_element()
+                    .err()
+                    .and_then(|e| e.limit_exceeded().map(|l| l.limit()))
+            );
+            assert!(
+                err.to_string().ends_with(&format!("at offset {offset}")),
+                "{err}"
+            );
+
+            // Iterating directly yields the error and then stops
+            let mut doc = Document::new(xml).with_limits(limits);
+            let err = doc.find_map(|token| token.err()).unwrap();
+            assert_eq!(Some(limit), err.limit_exceeded().map(|l| l.limit()));
+            assert!(doc.next().is_none());
+        }
+
+        let limits = DecodeLimits::new()
+            .with_max_depth(3)
+            .with_max_string_length(5)
+            .with_max_collection_size(3)
+            .with_max_tokens(15);
+        let mut doc = Document::new(xml).with_limits(limits);
+        let mut root = doc.root_element().unwrap();
+        let mut b = root.next_tag().unwrap().unwrap();
+        assert_eq!(Some("xyz"), b.start_el().attr("attr"));
+        assert_eq!("hello", try_data(&mut b).unwrap());
+        drop(b);
+        assert!(root.next_tag().unwrap().is_some());
+
+        // The root element itself can exceed a limit
+        let limits = DecodeLimits::new().with_max_tokens(1);
+        expect_limit(xml, limits, DecodeLimit::Tokens, 2);
+
+        let limits = DecodeLimits::new().with_max_depth(2);
+        expect_limit(xml, limits, DecodeLimit::Depth, 29);
+        let limits = DecodeLimits::new().with_max_string_length(4);
+        expect_limit(xml, limits, DecodeLimit::StringLength, 17);
+        let limits = DecodeLimits::new().with_max_collection_size(2);
+        expect_limit(xml, limits, DecodeLimit::CollectionSize, 37);
+        let limits = DecodeLimits::new().with_max_tokens(14);
+        expect_limit(xml, limits, DecodeLimit::Tokens, 41);
+
+        // Invalid XML is still reported where it is encountered
+        let mut doc = Document::new("<a><b></c></a>").with_limits(limits);
+        let mut root = doc.root_element().unwrap();
+        assert!(root.next_tag().unwrap().is_some());
+    }
 }
@@ -0,0 +1,848 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Pull parser for XML documents that arrive in chunks.
+//!
+//! [`Document`](crate::decode::Document) needs the whole XML document as a single `&str`.
+//! [`PullParser`] instead accepts the document's bytes chunk by chunk, and yields an
+//! [`Event`] as soon as each element start, element end, or run of text is complete. Only the
+//! markup currently being read is buffered, so large listings can be processed while the rest
+//! of the document is still being received.
+//!
+//! Elements have the same [`Depth`] they would have when read through a
+//! [`ScopedDecoder`](crate::decode::ScopedDecoder), and their names are split into a prefix and
+//! local part in the same way. Namespace prefixes are not resolved.
+
+use crate::decode::{Attr, Depth, Name, StartEl, XmlDecodeError};
+use crate::unescape::unescape;
+use aws_smithy_types::decode_limits::DecodeLimits;
+use std::borrow::Cow;
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+/// An event read from an XML document by a [`PullParser`].
+#[derive(Debug, PartialEq)]
+pub enum Event<'a> {
+    /// The start of an element.
+    ///
+    /// Self-closing elements such as `<a/>` are immediately followed by an
+    /// [`EndElement`](Event::EndElement) event.
+    StartElement(StartEl<'a>),
+    /// The end of an element.
+    EndElement {
+        /// Name of the element
+        name: Name<'a>,
+        /// Depth of the element, which is the same as the depth of its start element
+        depth: Depth,
+    },
+    /// Unescaped text or CDATA within an element.
+    ///
+    /// Text is only split where it is interrupted by markup such as an element, a comment,
+    /// or CDATA, and never at chunk boundaries.
+    Text(Cow<'a, str>),
+}
+
+/// Result of reading the next event from a [`PullParser`].
+#[derive(Debug, PartialEq)]
+pub enum NextEvent<'a> {
+    /// There wasn't enough input buffered to read a full event.
+    ///
+    /// Call [`PullParser::feed`] with more input, or [`PullParser::finish`] if there is none
+    /// left, and try again.
+    Incomplete,
+    /// A complete event was read.
+    Complete(Event<'a>),
+    /// The whole document has been read.
+    End,
+}
+
+/// The default maximum size of a single piece of markup or text, see
+/// [`PullParser::with_max_unit_size`].
+pub const DEFAULT_MAX_UNIT_SIZE: usize = 16 * 1024 * 1024;
+
+/// XML pull parser that accepts its input in chunks.
+///
+/// Input is validated as UTF-8 one piece of markup or text at a time, so multi-byte characters
+/// may be split across chunks. Events borrow from the parser's buffer, so each event must be
+/// dropped before more input is fed in.
+///
+/// A piece of markup or text has to be buffered until it is complete, so its size is limited
+/// by [`with_max_unit_size`](PullParser::with_max_unit_size).
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_xml::decode::pull::{Event, NextEvent, PullParser};
+///
+/// let mut parser = PullParser::new();
+/// parser.feed(b"<Response><Name>hel");
+/// match parser.next_event().unwrap() {
+///     NextEvent::Complete(Event::StartElement(start_el)) => assert!(start_el.matches("Response")),
+///     other => panic!("unexpected: {:?}", other),
+/// }
+/// assert!(matches!(parser.next_event(), Ok(NextEvent::Complete(Event::StartElement(_)))));
+/// assert!(matches!(parser.next_event(), Ok(NextEvent::Incomplete)));
+///
+/// parser.feed(b"lo</Name></Response>");
+/// parser.finish();
+/// assert_eq!(
+///     NextEvent::Complete(Event::Text("hello".into())),
+///     parser.next_event().unwrap()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct PullParser {
+    /// Input that hasn't been discarded yet
+    buffer: Vec<u8>,
+    /// Index into `buffer` of the first byte that hasn't been parsed
+    index: usize,
+    /// Offset of `buffer[0]` in the overall input
+    base_offset: usize,
+    /// How far the incomplete unit at `index` has been scanned, so that each chunk of a long
+    /// unit is only scanned once
+    scan: Scan,
+    max_unit_size: usize,
+    limits: DecodeLimits,
+    token_count: usize,
+    /// Number of child elements read so far for each open element
+    child_counts: Vec<usize>,
+    /// Qualified names of the open elements, concatenated
+    open_names: String,
+    /// Start index in `open_names` of each open element's name
+    open_name_starts: Vec<usize>,
+    /// The element at the top of `open_names` ended, and must be removed before the next event
+    pop_pending: bool,
+    /// The last start element was self-closing, and its end hasn't been returned yet
+    self_close_pending: bool,
+    root_read: bool,
+    finished: bool,
+    failed: bool,
+}
+
+/// Progress made scanning for the end of an incomplete unit
+#[derive(Clone, Copy, Debug, Default)]
+struct Scan {
+    /// Number of bytes of the unit that have been scanned
+    scanned: usize,
+    /// The quote character of the attribute value being scanned, if in one
+    quote: Option<u8>,
+    /// Number of unclosed `[` brackets scanned in a tag
+    brackets: usize,
+}
+
+/// A complete piece of markup or text
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Unit {
+    Text,
+    StartTag,
+    EndTag,
+    Cdata,
+    Comment,
+    Declaration,
+    ProcessingInstruction,
+    Doctype,
+}
+
+impl Default for PullParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PullParser {
+    /// Creates a new parser without any input.
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            index: 0,
+            base_offset: 0,
+            scan: Scan::default(),
+            max_unit_size: DEFAULT_MAX_UNIT_SIZE,
+            limits: DecodeLimits::new(),
+            token_count: 0,
+            child_counts: Vec::new(),
+            open_names: String::new(),
+            open_name_starts: Vec::new(),
+            pop_pending: false,
+            self_close_pending: false,
+            root_read: false,
+            finished: false,
+            failed: false,
+        }
+    }
+
+    /// Sets the maximum size, in bytes, of a single piece of markup or text.
+    ///
+    /// This bounds how much input is buffered while waiting for the end of a tag, comment,
+    /// CDATA section or run of text. Defaults to [`DEFAULT_MAX_UNIT_SIZE`].
+    pub fn with_max_unit_size(mut self, max_unit_size: usize) -> Self {
+        self.max_unit_size = max_unit_size;
+        self
+    }
+
+    /// Enforces the given [`DecodeLimits`] while parsing.
+    ///
+    /// The limits apply in the same way as with
+    /// [`Document::with_limits`](crate::decode::Document::with_limits). Text and CDATA are
+    /// checked against the maximum string length while they are still being buffered, so
+    /// they are rejected as soon as they are too long rather than once they are complete.
+    pub fn with_limits(mut self, limits: DecodeLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Appends the next chunk of input.
+    ///
+    /// # Panics
+    /// This function will panic if called after [`finish`](PullParser::finish).
+    pub fn feed(&mut self, chunk: &[u8]) {
+        assert!(
+            !self.finished,
+            "input can't be fed after `finish` was called"
+        );
+        // Discard input that has already been parsed so the buffer only ever holds
+        // the partial markup at the end of the input
+        self.buffer.drain(..self.index);
+        self.base_offset += self.index;
+        self.index = 0;
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Indicates that there is no more input.
+    ///
+    /// Once finished, text at the end of the input is yielded, and incomplete markup or
+    /// unclosed elements are reported as errors.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// The depth of the next element to start
+    fn depth(&self) -> Depth {
+        self.open_name_starts.len()
+    }
+
+    /// Reads the next event.
+    ///
+    /// Once an error is returned, all subsequent calls return [`NextEvent::End`].
+    pub fn next_event(&mut self) -> Result<NextEvent<'_>, XmlDecodeError> {
+        if self.failed {
+            return Ok(NextEvent::End);
+        }
+        // Parsing stops at the first error, so assume there will be one until an event is read
+        self.failed = true;
+
+        if self.pop_pending {
+            let start = self.open_name_starts.pop().expect("an element was open");
+            self.open_names.truncate(start);
+            self.child_counts.pop();
+            self.pop_pending = false;
+        }
+        if self.self_close_pending {
+            self.self_close_pending = false;
+            self.pop_pending = true;
+            self.failed = false;
+            let depth = self.depth() - 1;
+            let name = split_name(&self.open_names[self.open_name_starts[depth]..]);
+            return Ok(NextEvent::Complete(Event::EndElement { name, depth }));
+        }
+
+        loop {
+            let rest = &self.buffer[self.index..];
+            let (unit, len) = match split_unit(rest, self.finished, &mut self.scan)? {
+                Some(unit) => unit,
+                None if !self.finished => {
+                    self.check_incomplete()?;
+                    self.failed = false;
+                    return Ok(NextEvent::Incomplete);
+                }
+                None if self.depth() > 0 => {
+                    return Err(XmlDecodeError::custom(format!(
+                        "unexpected end of document: `{}` was never closed",
+                        &self.open_names[self.open_name_starts[self.depth() - 1]..]
+                    )));
+                }
+                None => {
+                    self.failed = false;
+                    return Ok(NextEvent::End);
+                }
+            };
+            let (start, offset) = (self.index, self.base_offset + self.index);
+            if len > self.max_unit_size {
+                return Err(self.unit_too_large());
+            }
+            self.index += len;
+            self.scan = Scan::default();
+            self.token_count += 1;
+            let limit_err = |limit| XmlDecodeError::from_limit(limit, offset);
+            self.limits
+                .check_tokens(self.token_count)
+                .map_err(limit_err)?;
+            let text = std::str::from_utf8(&self.buffer[start..self.index])
+                .map_err(XmlDecodeError::unhandled)?;
+
+            match unit {
+                Unit::Text if self.depth() == 0 => {
+                    let text = match offset {
+                        0 => text.trim_start_matches('\u{FEFF}'),
+                        _ => text,
+                    };
+                    if !text
+                        .bytes()
+                        .all(|b| matches!(b, b' ' | b'\t' | b'\r' | b'\n'))
+                    {
+                        return Err(XmlDecodeError::custom(
+                            "text is not allowed outside of the root element",
+                        ));
+                    }
+                }
+                Unit::Text => {
+                    self.limits
+                        .check_string_length(text.len())
+                        .map_err(limit_err)?;
+                    let text = unescape(text)?;
+                    self.failed = false;
+                    return Ok(NextEvent::Complete(Event::Text(text)));
+                }
+                Unit::Cdata if self.depth() == 0 => {
+                    return Err(XmlDecodeError::custom(
+                        "CDATA is not allowed outside of the root element",
+                    ));
+                }
+                Unit::Cdata => {
+                    let data = &text["<![CDATA[".len()..text.len() - "]]>".len()];
+                    self.limits
+                        .check_string_length(data.len())
+                        .map_err(limit_err)?;
+                    self.failed = false;
+                    return Ok(NextEvent::Complete(Event::Text(Cow::Borrowed(data))));
+                }
+                Unit::Comment | Unit::ProcessingInstruction => {}
+                Unit::Declaration if offset == 0 => {}
+                Unit::Declaration => {
+                    return Err(XmlDecodeError::custom(
+                        "the XML declaration must be at the start of the document",
+                    ));
+                }
+                Unit::Doctype if self.root_read || self.depth() > 0 => {
+                    return Err(XmlDecodeError::custom(
+                        "the document type declaration must come before the root element",
+                    ));
+                }
+                Unit::Doctype => {}
+                Unit::StartTag if self.root_read && self.depth() == 0 => {
+                    return Err(XmlDecodeError::custom("only one root element is allowed"));
+                }
+                Unit::StartTag => {
+                    if let Some(children) = self.child_counts.last_mut() {
+                        *children += 1;
+                        self.limits
+                            .check_collection_size(*children)
+                            .map_err(limit_err)?;
+                    }
+                    self.limits
+                        .check_depth(self.depth() + 1)
+                        .map_err(limit_err)?;
+                    let start_el = parse_start_tag(text, self.depth(), &self.limits, offset)?;
+                    self.token_count += start_el.attributes.len();
+                    self.limits
+                        .check_tokens(self.token_count)
+                        .map_err(limit_err)?;
+                    self.child_counts.push(0);
+                    self.open_name_starts.push(self.open_names.len());
+                    if !start_el.prefix().is_empty() {
+                        self.open_names.push_str(start_el.prefix());
+                        self.open_names.push(':');
+                    }
+                    self.open_names.push_str(start_el.local());
+                    self.root_read = true;
+                    self.self_close_pending = start_el.closed;
+                    self.failed = false;
+                    return Ok(NextEvent::Complete(Event::StartElement(start_el)));
+                }
+                Unit::EndTag => {
+                    let name = parse_end_tag(text)?;
+                    let depth = match self.open_name_starts.last() {
+                        Some(&start) if split_name(&self.open_names[start..]) == name => {
+                            self.depth() - 1
+                        }
+                        _ => {
+                            return Err(XmlDecodeError::custom(format!(
+                                "unexpected end element: {}",
+                                text
+                            )));
+                        }
+                    };
+                    self.pop_pending = true;
+                    self.failed = false;
+                    return Ok(NextEvent::Complete(Event::EndElement { name, depth }));
+                }
+            }
+        }
+    }
+
+    /// Checks that the incomplete unit at the end of the buffer is allowed to keep growing
+    fn check_incomplete(&self) -> Result<(), XmlDecodeError> {
+        let rest = &self.buffer[self.index..];
+        if rest.len() > self.max_unit_size {
+            return Err(self.unit_too_large());
+        }
+        let string_length = if rest.first().is_some_and(|&b| b != b'<') {
+            Some(rest.len())
+        } else if rest.starts_with(b"<![CDATA[") {
+            // The terminating `]]>` may already be partially buffered
+            Some((rest.len() - "<![CDATA[".len()).saturating_sub("]]".len()))
+        } else {
+            None
+        };
+        match string_length.map(|len| self.limits.check_string_length(len)) {
+            Some(Err(limit)) => Err(XmlDecodeError::from_limit(
+                limit,
+                self.base_offset + self.index,
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn unit_too_large(&self) -> XmlDecodeError {
+        XmlDecodeError::custom(format!(
+            "markup or text at offset {} is longer than the maximum of {} bytes",
+            self.base_offset + self.index,
+            self.max_unit_size
+        ))
+    }
+}
+
+/// Splits a `prefix:local` or `local` element name
+fn split_name(qualified: &str) -> Name<'_> {
+    match qualified.split_once(':') {
+        Some((prefix, local)) => Name { prefix, local },
+        None => Name {
+            prefix: "",
+            local: qualified,
+        },
+    }
+}
+
+/// Finds the first complete unit of markup or text in `input`
+///
+/// Returns `None` if more input is needed, in which case `scan` records how far the unit was
+/// scanned so that the next call with more input can resume from there. Markup characters are
+/// all ASCII, so they can be found in the input before it has been validated as UTF-8.
+fn split_unit(
+    input: &[u8],
+    finished: bool,
+    scan: &mut Scan,
+) -> Result<Option<(Unit, usize)>, XmlDecodeError> {
+    /// Returns the index just after the first `terminator` at or after `from`
+    fn find_end(input: &[u8], from: usize, terminator: &[u8], scan: &mut Scan) -> Option<usize> {
+        // A terminator split across chunks starts before the end of the scanned input
+        let from = from.max(scan.scanned.saturating_sub(terminator.len() - 1));
+        let found = input
+            .get(from..)?
+            .windows(terminator.len())
+            .position(|window| window == terminator)
+            .map(|idx| from + idx + terminator.len());
+        scan.scanned = input.len();
+        found
+    }
+
+    /// Returns the index just after the first `>` that isn't quoted or in brackets
+    fn find_tag_end(input: &[u8], scan: &mut Scan) -> Option<usize> {
+        let from = scan.scanned.max(1);
+        for (idx, &b) in input.iter().enumerate().skip(from) {
+            match (scan.quote, b) {
+                (Some(q), _) if q == b => scan.quote = None,
+                (Some(_), _) => {}
+                (None, b'"' | b'\'') => scan.quote = Some(b),
+                (None, b'[') => scan.brackets += 1,
+                (None, b']') => scan.brackets = scan.brackets.saturating_sub(1),
+                (None, b'>') if scan.brackets == 0 => return Some(idx + 1),
+                _ => {}
+            }
+        }
+        scan.scanned = input.len();
+        None
+    }
+
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let found = if input[0] != b'<' {
+        let from = scan.scanned;
+        match input[from..].iter().position(|&b| b == b'<') {
+            Some(len) => Some((Unit::Text, from + len)),
+            None if finished => Some((Unit::Text, input.len())),
+            None => {
+                scan.scanned = input.len();
+                None
+            }
+        }
+    } else if input.starts_with(b"<!--") {
+        find_end(input, 4, b"-->", scan).map(|len| (Unit::Comment, len))
+    } else if input.starts_with(b"<![CDATA[") {
+        find_end(input, 9, b"]]>", scan).map(|len| (Unit::Cdata, len))
+    } else if input.starts_with(b"<?") {
+        let unit = if input.starts_with(b"<?xml ") {
+            Unit::Declaration
+        } else {
+            Unit::ProcessingInstruction
+        };
+        find_end(input, 2, b"?>", scan).map(|len| (unit, len))
+    } else if input.starts_with(b"<!") {
+        // Wait until it's clear this isn't a comment or CDATA
+        if b"<!--".starts_with(input) || b"<![CDATA[".starts_with(input) {
+            None
+        } else {
+            find_tag_end(input, scan).map(|len| (Unit::Doctype, len))
+        }
+    } else if input.starts_with(b"</") {
+        find_tag_end(input, scan).map(|len| (Unit::EndTag, len))
+    } else {
+        find_tag_end(input, scan).map(|len| (Unit::StartTag, len))
+    };
+    match found {
+        None if finished => Err(XmlDecodeError::custom(
+            "unexpected end of document in the middle of markup",
+        )),
+        found => Ok(found),
+    }
+}
+
+fn parse_start_tag<'a>(
+    tag: &'a str,
+    depth: Depth,
+    limits: &DecodeLimits,
+    offset: usize,
+) -> Result<StartEl<'a>, XmlDecodeError> {
+    let mut tokens = Tokenizer::from_fragment(tag, 0..tag.len());
+    let mut start_el = match tokens.next() {
+        Some(Ok(Token::ElementStart { prefix, local, .. })) => {
+            StartEl::new(local.as_str(), prefix.as_str(), depth)
+        }
+        Some(Err(err)) => return Err(XmlDecodeError::invalid_xml(err)),
+        _ => return Err(XmlDecodeError::custom("expected a start element")),
+    };
+    for token in tokens {
+        match token.map_err(XmlDecodeError::invalid_xml)? {
+            Token::Attribute {
+                prefix,
+                local,
+                value,
+                ..
+            } => {
+                limits
+                    .check_string_length(value.as_str().len())
+                    .map_err(|limit| XmlDecodeError::from_limit(limit, offset))?;
+                start_el.attributes.push(Attr {
+                    name: Name {
+                        prefix: prefix.as_str(),
+                        local: local.as_str(),
+                    },
+                    value: unescape(value.as_str())?,
+                })
+            }
+            Token::ElementEnd {
+                end: ElementEnd::Empty,
+                ..
+            } => start_el.closed = true,
+            _ => {}
+        }
+    }
+    Ok(start_el)
+}
+
+fn parse_end_tag(tag: &str) -> Result<Name<'_>, XmlDecodeError> {
+    match Tokenizer::from_fragment(tag, 0..tag.len()).next() {
+        Some(Ok(Token::ElementEnd {
+            end: ElementEnd::Close(prefix, local),
+            ..
+        })) => Ok(Name {
+            prefix: prefix.as_str(),
+            local: local.as_str(),
+        }),
+        Some(Err(err)) => Err(XmlDecodeError::invalid_xml(err)),
+        _ => Err(XmlDecodeError::custom("expected an end element")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decode::pull::{Event, NextEvent, PullParser};
+    use crate::decode::{Document, XmlDecodeError};
+    use aws_smithy_types::decode_limits::{DecodeLimit, DecodeLimits};
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- a listing -->
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    <Name>bücket</Name>
+    <Contents><Key>a &amp; b</Key><Size>12</Size></Contents>
+    <Contents><Key><![CDATA[<c>]]></Key><Owner xsi:type="Canonical&quot;User" id='x>y'/></Contents>
+    <?pi ignored?>
+</ListBucketResult>
+"#;
+
+    /// Parses `chunks` incrementally, returning a description of each event
+    fn parse_chunks<'a>(
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Result<Vec<String>, XmlDecodeError> {
+        let mut parser = PullParser::new();
+        let mut chunks = chunks.into_iter();
+        let mut out = Vec::new();
+        loop {
+            match parser.next_event()? {
+                NextEvent::Complete(Event::StartElement(start_el)) => out.push(format!(
+                    "start {}:{} {} {:?}",
+                    start_el.prefix(),
+                    start_el.local(),
+                    start_el.depth(),
+                    start_el.attributes,
+                )),
+                NextEvent::Complete(Event::EndElement { name, depth }) => {
+                    out.push(format!("end {}:{} {}", name.prefix, name.local, depth))
+                }
+                NextEvent::Complete(Event::Text(text)) => {
+                    if !text.trim().is_empty() {
+                        out.push(format!("text {}", text))
+                    }
+                }
+                NextEvent::Incomplete => match chunks.next() {
+                    Some(chunk) => parser.feed(chunk),
+                    None => parser.finish(),
+                },
+                NextEvent::End => return Ok(out),
+            }
+        }
+    }
+
+    #[test]
+    fn events() {
+        let events = parse_chunks([DOCUMENT.as_bytes()]).unwrap();
+        let start_attrs = r#"[Attr { name: Name { prefix: "", local: "xmlns" }, value: "http://s3.amazonaws.com/doc/2006-03-01/" }]"#;
+        let owner_attrs = r#"[Attr { name: Name { prefix: "xsi", local: "type" }, value: "Canonical\"User" }, Attr { name: Name { prefix: "", local: "id" }, value: "x>y" }]"#;
+        assert_eq!(
+            vec![
+                format!("start :ListBucketResult 0 {start_attrs}"),
+                "start :Name 1 []".to_string(),
+                "text bücket".to_string(),
+                "end :Name 1".to_string(),
+                "start :Contents 1 []".to_string(),
+                "start :Key 2 []".to_string(),
+                "text a & b".to_string(),
+                "end :Key 2".to_string(),
+                "start :Size 2 []".to_string(),
+                "text 12".to_string(),
+                "end :Size 2".to_string(),
+                "end :Contents 1".to_string(),
+                "start :Contents 1 []".to_string(),
+                "start :Key 2 []".to_string(),
+                "text <c>".to_string(),
+                "end :Key 2".to_string(),
+                format!("start :Owner 2 {owner_attrs}"),
+                "end :Owner 2".to_string(),
+                "end :Contents 1".to_string(),
+                "end :ListBucketResult 0".to_string(),
+            ],
+            events
+        );
+    }
+
+    #[test]
+    fn every_split_point() {
+        let input = DOCUMENT.as_bytes();
+        let expected = parse_chunks([input]).unwrap();
+        for split in 0..=input.len() {
+            let (head, tail) = input.split_at(split);
+            assert_eq!(
+                expected,
+                parse_chunks([head, tail]).unwrap(),
+                "split at {split}"
+            );
+        }
+        assert_eq!(expected, parse_chunks(input.chunks(1)).unwrap());
+    }
+
+    #[test]
+    fn depth_matches_scoped_decoder() {
+        let xml = "<a><b><c/><b></b></b><d>x</d></a>";
+        let mut doc = Document::new(xml);
+        let mut expected = Vec::new();
+        while let Some(start_el) = doc.next_start_element().unwrap() {
+            expected.push(format!("{} {}", start_el.local(), start_el.depth()));
+        }
+
+        let mut parser = PullParser::new();
+        parser.feed(xml.as_bytes());
+        parser.finish();
+        let mut actual = Vec::new();
+        while let NextEvent::Complete(event) = parser.next_event().unwrap() {
+            if let Event::StartElement(start_el) = event {
+                actual.push(format!("{} {}", start_el.local(), start_el.depth()));
+            }
+        }
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn invalid_documents() {
+        for invalid in [
+            &b"<a>"[..],
+            b"<a></b>",
+            b"<a:b></b>",
+            b"<a></a><b></b>",
+            b"text<a></a>",
+            b"<a></a>text",
+            b"<a><!DOCTYPE a></a>",
+            b"<a>&bogus;</a>",
+            b"<a x=1></a>",
+            b"<a><b></a>",
+            b"<a>\xff</a>",
+            b"<a><!-- unterminated</a>",
+            b" <?xml version=\"1.0\"?><a/>",
+        ] {
+            assert!(
+                parse_chunks([invalid]).is_err(),
+                "{:?}",
+                String::from_utf8_lossy(invalid)
+            );
+            assert!(parse_chunks(invalid.chunks(1)).is_err());
+        }
+    }
+
+    #[test]
+    fn split_utf8_characters() {
+        let input = "<a>🦀</a>".as_bytes();
+        assert_eq!(
+            vec!["start :a 0 []", "text 🦀", "end :a 0"],
+            parse_chunks(input.chunks(1)).unwrap()
+        );
+    }
+
+    #[test]
+    fn long_units_are_scanned_once() {
+        for (open, close) in [
+            ("<a>", "</a>"),
+            ("<a><!--", "--></a>"),
+            ("<a><![CDATA[", "]]></a>"),
+            ("<a x='", "'></a>"),
+        ] {
+            let mut parser = PullParser::new();
+            parser.feed(open.as_bytes());
+            while let NextEvent::Complete(_) = parser.next_event().unwrap() {}
+            for _ in 0..3 {
+                parser.feed(b"]]--x");
+                assert_eq!(NextEvent::Incomplete, parser.next_event().unwrap());
+                let unit_len = parser.buffer.len() - parser.index;
+                assert_eq!(unit_len, parser.scan.scanned, "{open}");
+            }
+            parser.feed(close.as_bytes());
+            parser.finish();
+            while let NextEvent::Complete(_) = parser.next_event().unwrap() {}
+        }
+    }
+
+    #[test]
+    fn unit_size_is_limited() {
+        for input in ["<a>abcdefghi</a>", "<a><!-- abc --></a>", "<abcdefghi/>"] {
+            let mut parser = PullParser::new().with_max_unit_size(8);
+            let mut result = Ok(());
+            for chunk in input.as_bytes().chunks(1) {
+                parser.feed(chunk);
+                result = loop {
+                    match parser.next_event() {
+                        Ok(NextEvent::Complete(_)) => {}
+                        other => break other.map(|_| ()),
+                    }
+                };
+                if result.is_err() {
+                    break;
+                }
+            }
+            assert!(result.is_err(), "{input}");
+        }
+        assert!(parse_chunks([&b"<a>abcdefghi</a>"[..]]).is_ok());
+    }
+
+    #[test]
+    fn limits() {
+        fn expect_limit(xml: &str, limits: DecodeLimits, limit: DecodeLimit) {
+            for size in [1, xml.len()] {
+                let mut parser = PullParser::new().with_limits(limits);
+                let mut chunks = xml.as_bytes().chunks(size);
+                let err = loop {
+                    match parser.next_event() {
+                        Ok(NextEvent::Complete(_)) => {}
+                        Ok(NextEvent::Incomplete) => match chunks.next() {
+                            Some(chunk) => parser.feed(chunk),
+                            None => parser.finish(),
+                        },
+                        Ok(NextEvent::End) => panic!("no limit was exceeded in {xml}"),
+                        Err(err) => break err,
+                    }
+                };
+                assert_eq!(
+                    Some(limit),
+                    err.limit_exceeded().map(|err| err.limit()),
+                    "{err}"
+                );
+            }
+        }
+
+        let xml = r#"<a><b x="abc">abcd</b><b><![CDATA[ab]]><c/></b><d/></a>"#;
+        expect_limit(
+            xml,
+            DecodeLimits::new().with_max_tokens(3),
+            DecodeLimit::Tokens,
+        );
+        expect_limit(
+            xml,
+            DecodeLimits::new().with_max_depth(2),
+            DecodeLimit::Depth,
+        );
+        expect_limit(
+            xml,
+            DecodeLimits::new().with_max_string_length(3),
+            DecodeLimit::StringLength,
+        );
+        expect_limit(
+            r#"<a x="abcd"/>"#,
+            DecodeLimits::new().with_max_string_length(3),
+            DecodeLimit::StringLength,
+        );
+        expect_limit(
+            xml,
+            DecodeLimits::new().with_max_collection_size(2),
+            DecodeLimit::CollectionSize,
+        );
+
+        let limits = DecodeLimits::new()
+            .with_max_depth(3)
+            .with_max_string_length(4)
+            .with_max_collection_size(3)
+            .with_max_tokens(13);
+        let mut parser = PullParser::new().with_limits(limits);
+        parser.feed(xml.as_bytes());
+        parser.finish();
+        while let NextEvent::Complete(_) = parser.next_event().unwrap() {}
+    }
+
+    #[test]
+    fn text_is_limited_while_buffered() {
+        let limits = DecodeLimits::new().with_max_string_length(4);
+        let mut parser = PullParser::new().with_limits(limits);
+        parser.feed(b"<a>abcd");
+        assert!(matches!(parser.next_event(), Ok(NextEvent::Complete(_))));
+        assert_eq!(NextEvent::Incomplete, parser.next_event().unwrap());
+        parser.feed(b"e");
+        let err = parser.next_event().unwrap_err();
+        assert!(err.limit_exceeded().is_some());
+    }
+
+    #[test]
+    fn no_events_after_error() {
+        let mut parser = PullParser::new();
+        parser.feed(b"<a></b>");
+        assert!(matches!(parser.next_event(), Ok(NextEvent::Complete(_))));
+        assert!(parser.next_event().is_err());
+        assert_eq!(NextEvent::End, parser.next_event().unwrap());
+    }
+}
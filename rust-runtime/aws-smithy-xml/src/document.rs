@@ -0,0 +1,324 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Lossy mapping between [`Document`](aws_smithy_types::Document)s and XML
+//!
+//! XML has no notion of types, arrays or null, so documents can't be represented in XML without
+//! losing information. This module uses the following mapping, which is modeled after the default
+//! Smithy XML bindings:
+//!
+//! | Document | XML                                                       |
+//! |----------|-----------------------------------------------------------|
+//! | object   | one child element per member, named after the member key |
+//! | array    | one `<member>` child element per item                     |
+//! | string   | text                                                      |
+//! | number   | text, formatted like a Smithy XML number                  |
+//! | boolean  | the text `true` or `false`                                |
+//! | null     | no content                                                |
+//!
+//! Reading XML back into a document is the reverse, with the following caveats:
+//! - Text is always read as a string, since it can't be told apart from a number or boolean.
+//! - Elements without any content are read as null, including empty strings, arrays and objects.
+//! - An element is read as an array if all of its child elements are named `member`, so an
+//!   object whose only keys are `member` is read as an array.
+//! - Repeated child elements of an object are read as an array, like flattened lists.
+//! - Attributes, namespace prefixes, and text mixed with child elements are ignored.
+//!
+//! Object keys are written as element names, so writing a document fails if one of its keys is
+//! not a valid XML name without a namespace prefix.
+
+use crate::decode::{Node, ScopedDecoder, XmlDecodeError};
+use crate::encode::{ScopeWriter, XmlEncodeError};
+use aws_smithy_types::primitive::Encoder;
+use aws_smithy_types::{Document, Number};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The maximum nesting depth of elements when reading a [`Document`].
+const MAX_DOCUMENT_RECURSION: usize = 256;
+
+/// The element name used for array items
+const ARRAY_MEMBER: &str = "member";
+
+/// Writes `value` as the content of the element in `scope`
+///
+/// Returns an error if an object key is not a valid XML name, in which case the members written
+/// before it are left in `scope`.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_types::Document;
+/// use aws_smithy_xml::document::write_document;
+/// use aws_smithy_xml::encode::XmlWriter;
+///
+/// let mut out = String::new();
+/// let mut writer = XmlWriter::new(&mut out);
+/// let mut root = writer.start_el("Root").finish();
+/// write_document(&mut root, &Document::Array(vec![1u64.into(), "two".into()])).unwrap();
+/// root.finish();
+/// assert_eq!("<Root><member>1</member><member>two</member></Root>", out);
+/// ```
+pub fn write_document<W: Write + ?Sized>(
+    scope: &mut ScopeWriter<'_, '_, W>,
+    value: &Document,
+) -> Result<(), XmlEncodeError> {
+    match value {
+        Document::Array(items) => {
+            for item in items {
+                let mut member = scope.start_el(ARRAY_MEMBER).finish();
+                write_document(&mut member, item)?;
+            }
+        }
+        Document::Number(number) => {
+            let mut encoder = match *number {
+                Number::PosInt(n) => Encoder::from(n),
+                Number::NegInt(n) => Encoder::from(n),
+                Number::Float(n) => Encoder::from(n),
+            };
+            scope.data(encoder.encode());
+        }
+        Document::String(s) => scope.data(s),
+        Document::Bool(b) => scope.data(if *b { "true" } else { "false" }),
+        Document::Null => {}
+        object => {
+            for (key, value) in object.members().expect("all other variants are objects") {
+                if !is_valid_name(key) {
+                    return Err(XmlEncodeError::invalid_name(key.as_str()));
+                }
+                let mut member = scope.start_el(key).finish();
+                write_document(&mut member, value)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if `name` is an XML name without a namespace prefix (an `NCName`)
+fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(is_name_start_char) && chars.all(is_name_char)
+}
+
+// See https://www.w3.org/TR/xml/#NT-NameStartChar, minus `:`
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z'
+        | '_'
+        | 'a'..='z'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}'
+    )
+}
+
+// See https://www.w3.org/TR/xml/#NT-NameChar, minus `:`
+fn is_name_char(c: char) -> bool {
+    is_name_start_char(c)
+        || matches!(c,
+            '-' | '.' | '0'..='9' | '\u{B7}' | '\u{300}'..='\u{36F}' | '\u{203F}'..='\u{2040}'
+        )
+}
+
+/// Reads the content of the element in `scope` as a [`Document`]
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_types::Document;
+/// use aws_smithy_xml::decode::Document as XmlDocument;
+/// use aws_smithy_xml::document::read_document;
+///
+/// let mut xml = XmlDocument::new("<Root><name>a</name><tags><member>b</member></tags></Root>");
+/// let mut root = xml.root_element().unwrap();
+/// let document = read_document(&mut root).unwrap();
+/// assert_eq!(Some(&Document::from("a")), document.pointer("/name"));
+/// assert_eq!(Some(&Document::from("b")), document.pointer("/tags/0"));
+/// ```
+pub fn read_document(scope: &mut ScopedDecoder<'_, '_>) -> Result<Document, XmlDecodeError> {
+    read_element(scope, 0)
+}
+
+fn read_element(
+    scope: &mut ScopedDecoder<'_, '_>,
+    depth: usize,
+) -> Result<Document, XmlDecodeError> {
+    if depth >= MAX_DOCUMENT_RECURSION {
+        return Err(XmlDecodeError::custom(
+            "exceeded max recursion depth while reading a document",
+        ));
+    }
+    let mut text = String::new();
+    // Members are grouped by name, in the order each name first appears
+    let mut members: Vec<(String, Vec<Document>)> = Vec::new();
+    let mut member_indices: HashMap<String, usize> = HashMap::new();
+    while let Some(node) = scope.next_node() {
+        match node? {
+            Node::Text(data) => text.push_str(&data),
+            Node::Tag(mut child) => {
+                let name = child.start_el().local().to_string();
+                let value = read_element(&mut child, depth + 1)?;
+                match member_indices.get(&name) {
+                    Some(&index) => members[index].1.push(value),
+                    None => {
+                        member_indices.insert(name.clone(), members.len());
+                        members.push((name, vec![value]));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(if members.is_empty() {
+        if text.is_empty() {
+            Document::Null
+        } else {
+            Document::String(text)
+        }
+    } else if members.len() == 1 && members[0].0 == ARRAY_MEMBER {
+        let (_, items) = members.pop().expect("checked above");
+        Document::Array(items)
+    } else {
+        members
+            .into_iter()
+            .map(|(key, mut values)| {
+                let value = if values.len() == 1 {
+                    values.pop().expect("checked above")
+                } else {
+                    Document::Array(values)
+                };
+                (key, value)
+            })
+            .collect()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decode::Document as XmlDocument;
+    use crate::document::{read_document, write_document};
+    use crate::encode::XmlWriter;
+    use aws_smithy_types::{Document, Number};
+    use std::collections::HashMap;
+
+    fn write(value: &Document) -> String {
+        let mut out = String::new();
+        let mut writer = XmlWriter::new(&mut out);
+        let mut root = writer.start_el("Root").finish();
+        write_document(&mut root, value).expect("valid document");
+        root.finish();
+        out
+    }
+
+    fn read(xml: &str) -> Document {
+        let mut doc = XmlDocument::new(xml);
+        let mut root = doc.root_element().expect("valid document");
+        read_document(&mut root).expect("valid document")
+    }
+
+    #[test]
+    fn write_scalars() {
+        assert_eq!("<Root>a &lt; b</Root>", write(&"a < b".into()));
+        assert_eq!("<Root>-5</Root>", write(&Number::NegInt(-5).into()));
+        assert_eq!("<Root>NaN</Root>", write(&Number::Float(f64::NAN).into()));
+        assert_eq!("<Root>true</Root>", write(&true.into()));
+        assert_eq!("<Root></Root>", write(&Document::Null));
+    }
+
+    #[test]
+    fn round_trip_is_lossy() {
+        let document = Document::Object(HashMap::from([
+            ("name".to_string(), Document::from("widget")),
+            ("count".to_string(), Document::from(3u64)),
+            ("parent".to_string(), Document::Null),
+            (
+                "tags".to_string(),
+                Document::Array(vec!["a".into(), "b".into()]),
+            ),
+        ]));
+        let expected = Document::Object(HashMap::from([
+            ("name".to_string(), Document::from("widget")),
+            ("count".to_string(), Document::from("3")),
+            ("parent".to_string(), Document::Null),
+            (
+                "tags".to_string(),
+                Document::Array(vec!["a".into(), "b".into()]),
+            ),
+        ]));
+        assert_eq!(expected, read(&write(&document)));
+    }
+
+    #[test]
+    fn read_flattened_and_mixed_content() {
+        let xml = r#"<Root a="ignored">
+            <item>1</item>
+            <name><![CDATA[x & y]]></name>
+            <item>2</item>
+            <empty/>
+        </Root>"#;
+        assert_eq!(
+            Document::Object(HashMap::from([
+                (
+                    "item".to_string(),
+                    Document::Array(vec!["1".into(), "2".into()])
+                ),
+                ("name".to_string(), Document::from("x & y")),
+                ("empty".to_string(), Document::Null),
+            ])),
+            read(xml)
+        );
+    }
+
+    #[test]
+    fn write_rejects_invalid_names() {
+        for key in ["a><b", "", "1a", "a b", "ns:a", "a&b"] {
+            let mut out = String::new();
+            let mut writer = XmlWriter::new(&mut out);
+            let mut root = writer.start_el("Root").finish();
+            let document = Document::from(HashMap::from([(key.to_string(), Document::Null)]));
+            assert!(
+                write_document(&mut root, &document).is_err(),
+                "`{key}` should be rejected"
+            );
+        }
+        let document = Document::from(HashMap::from([("_a-1.\u{e9}".to_string(), Document::Null)]));
+        assert_eq!("<Root><_a-1.\u{e9}></_a-1.\u{e9}></Root>", write(&document));
+    }
+
+    #[test]
+    fn read_many_repeated_members() {
+        let xml = format!("<Root>{}</Root>", "<a>1</a><b>2</b>".repeat(1000));
+        let document = read(&xml);
+        assert_eq!(
+            Some(1000),
+            document
+                .pointer("/a")
+                .and_then(|a| a.as_array())
+                .map(Vec::len)
+        );
+        assert_eq!(
+            Some(1000),
+            document
+                .pointer("/b")
+                .and_then(|b| b.as_array())
+                .map(Vec::len)
+        );
+    }
+
+    #[test]
+    fn recursion_limit() {
+        let xml = format!("{}{}", "<a>".repeat(300), "</a>".repeat(300));
+        let mut doc = XmlDocument::new(&xml);
+        let mut root = doc.root_element().expect("valid document");
+        assert!(read_document(&mut root).is_err());
+    }
+}
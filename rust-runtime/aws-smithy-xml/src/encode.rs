@@ -7,17 +7,33 @@
 //! generating malformed XML a compile error
 
 use crate::escape::escape;
+use bytes::BufMut;
 use std::error::Error as StdError;
 use std::fmt::{self, Display, Formatter, Write};
 
-// currently there's actually no way that encoding can fail but give it time :-)
 #[non_exhaustive]
 #[derive(Debug)]
-pub struct XmlEncodeError {}
+pub struct XmlEncodeError {
+    invalid_name: Option<String>,
+}
+
+impl XmlEncodeError {
+    pub(crate) fn invalid_name(name: impl Into<String>) -> Self {
+        Self {
+            invalid_name: Some(name.into()),
+        }
+    }
+}
 
 impl Display for XmlEncodeError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "error encoding XML")
+        match &self.invalid_name {
+            Some(name) => write!(
+                f,
+                "error encoding XML: `{name}` is not a valid element name"
+            ),
+            None => write!(f, "error encoding XML"),
+        }
     }
 }
 
@@ -49,31 +65,35 @@ impl StdError for XmlEncodeError {}
 /// assert_eq!(s, "<Root xmlns=\"http://example.com\">hello</Root>");
 /// ```
 ///
+/// The document can be written to any [`fmt::Write`](std::fmt::Write) implementation rather than
+/// a `String`. To write into a [`BufMut`], such as a `BytesMut` that is sent in chunks while
+/// the document is being written, wrap it in a [`BufMutWriter`].
+///
 /// See `tests/handwritten_serializers.rs` for more usage examples.
-pub struct XmlWriter<'a> {
-    doc: &'a mut String,
+pub struct XmlWriter<'a, W: Write + ?Sized = String> {
+    doc: &'a mut W,
 }
 
-impl<'a> XmlWriter<'a> {
-    pub fn new(doc: &'a mut String) -> Self {
+impl<'a, W: Write + ?Sized> XmlWriter<'a, W> {
+    pub fn new(doc: &'a mut W) -> Self {
         Self { doc }
     }
 }
 
-impl<'a> XmlWriter<'a> {
-    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b> {
+impl<W: Write + ?Sized> XmlWriter<'_, W> {
+    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b, W> {
         write!(self.doc, "<{}", tag).unwrap();
         ElWriter::new(self.doc, tag)
     }
 }
 
-pub struct ElWriter<'a, 'b> {
+pub struct ElWriter<'a, 'b, W: Write + ?Sized = String> {
     start: &'b str,
-    doc: Option<&'a mut String>,
+    doc: Option<&'a mut W>,
 }
 
-impl<'a, 'b> ElWriter<'a, 'b> {
-    fn new(doc: &'a mut String, start: &'b str) -> ElWriter<'a, 'b> {
+impl<'a, 'b, W: Write + ?Sized> ElWriter<'a, 'b, W> {
+    fn new(doc: &'a mut W, start: &'b str) -> ElWriter<'a, 'b, W> {
         ElWriter {
             start,
             doc: Some(doc),
@@ -95,11 +115,11 @@ impl<'a, 'b> ElWriter<'a, 'b> {
         self
     }
 
-    fn write_end(doc: &mut String) {
+    fn write_end(doc: &mut W) {
         write!(doc, ">").unwrap();
     }
 
-    fn doc<'c>(&'c mut self) -> &'c mut String
+    fn doc<'c>(&'c mut self) -> &'c mut W
     where
         'a: 'c,
     {
@@ -116,7 +136,7 @@ impl<'a, 'b> ElWriter<'a, 'b> {
         self.doc.as_mut().unwrap()
     }
 
-    pub fn finish(mut self) -> ScopeWriter<'a, 'b> {
+    pub fn finish(mut self) -> ScopeWriter<'a, 'b, W> {
         let doc = self.doc.take().unwrap();
         Self::write_end(doc);
         ScopeWriter {
@@ -126,7 +146,7 @@ impl<'a, 'b> ElWriter<'a, 'b> {
     }
 }
 
-impl Drop for ElWriter<'_, '_> {
+impl<W: Write + ?Sized> Drop for ElWriter<'_, '_, W> {
     fn drop(&mut self) {
         if let Some(doc) = self.doc.take() {
             // Calls to write_end() are always preceded by self.doc.take(). The value in self.doc
@@ -138,18 +158,18 @@ impl Drop for ElWriter<'_, '_> {
 }
 
 /// Wrap the construction of a tag pair `<a></a>`
-pub struct ScopeWriter<'a, 'b> {
-    doc: &'a mut String,
+pub struct ScopeWriter<'a, 'b, W: Write + ?Sized = String> {
+    doc: &'a mut W,
     start: &'b str,
 }
 
-impl Drop for ScopeWriter<'_, '_> {
+impl<W: Write + ?Sized> Drop for ScopeWriter<'_, '_, W> {
     fn drop(&mut self) {
         write!(self.doc, "</{}>", self.start).unwrap();
     }
 }
 
-impl ScopeWriter<'_, '_> {
+impl<W: Write + ?Sized> ScopeWriter<'_, '_, W> {
     pub fn data(&mut self, data: &str) {
         self.doc.write_str(escape(data).as_ref()).unwrap();
     }
@@ -158,15 +178,84 @@ impl ScopeWriter<'_, '_> {
         // drop will be called which writes the closer to the document
     }
 
-    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b> {
+    pub fn start_el<'b, 'c>(&'c mut self, tag: &'b str) -> ElWriter<'c, 'b, W> {
         write!(self.doc, "<{}", tag).unwrap();
         ElWriter::new(self.doc, tag)
     }
+
+    /// Returns the output that the document is being written to
+    ///
+    /// This allows the part of the document that has already been written to be sent
+    /// before the rest of it is complete. Only data that was already written should be removed;
+    /// writing to the output directly can result in invalid XML.
+    pub fn output_mut(&mut self) -> &mut W {
+        self.doc
+    }
+}
+
+/// Adapts a [`BufMut`] so that an [`XmlWriter`] can write to it
+///
+/// # Panics
+/// Writing panics if the buffer runs out of capacity. Growable buffers such as `BytesMut`
+/// and `Vec<u8>` never run out.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_xml::encode::{BufMutWriter, XmlWriter};
+/// use bytes::BytesMut;
+///
+/// let mut out = BufMutWriter::new(BytesMut::new());
+/// let mut doc = XmlWriter::new(&mut out);
+/// let mut root = doc.start_el("Root").finish();
+/// for i in 0..3 {
+///     root.start_el("Item").finish().data(&i.to_string());
+///     // Send the items written so far
+///     let chunk = root.output_mut().get_mut().split();
+///     # let _ = chunk;
+/// }
+/// root.finish();
+/// assert_eq!(&b"</Root>"[..], &out.into_inner()[..]);
+/// ```
+#[derive(Debug, Default)]
+pub struct BufMutWriter<B> {
+    buf: B,
+}
+
+impl<B: BufMut> BufMutWriter<B> {
+    /// Creates a writer that appends to `buf`
+    pub fn new(buf: B) -> Self {
+        Self { buf }
+    }
+
+    /// Returns a reference to the underlying buffer
+    pub fn get_ref(&self) -> &B {
+        &self.buf
+    }
+
+    /// Returns a mutable reference to the underlying buffer
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.buf
+    }
+
+    /// Returns the underlying buffer
+    pub fn into_inner(self) -> B {
+        self.buf
+    }
+}
+
+impl<B: BufMut> Write for BufMutWriter<B> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.buf.remaining_mut() < s.len() {
+            return Err(fmt::Error);
+        }
+        self.buf.put_slice(s.as_bytes());
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::encode::XmlWriter;
+    use crate::encode::{BufMutWriter, XmlWriter};
     use aws_smithy_protocol_test::{assert_ok, validate_body, MediaType};
 
     #[test]
@@ -203,6 +292,42 @@ mod test {
         ));
     }
 
+    #[test]
+    fn big_numbers_round_trip() {
+        use crate::decode::{try_data, Document};
+        use aws_smithy_types::primitive::{Encoder, Parse};
+        use aws_smithy_types::{BigDecimal, BigInteger};
+
+        let integer: BigInteger = "-123456789012345678901234567890".parse().unwrap();
+        let decimal: BigDecimal = "3.14159265358979323846264338327950288".parse().unwrap();
+        let mut out = String::new();
+        let mut doc_writer = XmlWriter::new(&mut out);
+        let mut root = doc_writer.start_el("Root").finish();
+        root.start_el("Integer")
+            .finish()
+            .data(Encoder::from(&integer).encode());
+        root.start_el("Decimal")
+            .finish()
+            .data(Encoder::from(&decimal).encode());
+        root.finish();
+        assert_eq!(
+            "<Root><Integer>-123456789012345678901234567890</Integer>\
+            <Decimal>3.14159265358979323846264338327950288</Decimal></Root>",
+            out
+        );
+
+        let mut doc = Document::new(&out);
+        let mut root = doc.root_element().unwrap();
+        let parsed = BigInteger::parse_smithy_primitive(
+            &try_data(&mut root.next_tag().unwrap().unwrap()).unwrap(),
+        );
+        assert_eq!(integer, parsed.unwrap());
+        let parsed = BigDecimal::parse_smithy_primitive(
+            &try_data(&mut root.next_tag().unwrap().unwrap()).unwrap(),
+        );
+        assert_eq!(decimal, parsed.unwrap());
+    }
+
     #[test]
     fn basic_document_encoding() {
         let mut out = String::new();
@@ -229,6 +354,42 @@ mod test {
         ));
     }
 
+    #[test]
+    fn write_to_buf_mut() {
+        let mut out = BufMutWriter::new(Vec::new());
+        let mut chunks = Vec::new();
+        {
+            let mut doc_writer = XmlWriter::new(&mut out);
+            let mut root = doc_writer
+                .start_el("Root")
+                .write_ns("http://example.com", None)
+                .finish();
+            for item in ["a", "b&c"] {
+                root.start_el("Item").finish().data(item);
+                chunks.push(std::mem::take(root.output_mut().get_mut()));
+            }
+        }
+        chunks.push(out.into_inner());
+        assert_eq!(
+            vec![
+                &br#"<Root xmlns="http://example.com"><Item>a</Item>"#[..],
+                b"<Item>b&amp;c</Item>",
+                b"</Root>",
+            ],
+            chunks
+        );
+    }
+
+    #[test]
+    fn buf_mut_out_of_capacity() {
+        let mut buf = [0u8; 8];
+        let mut out = BufMutWriter::new(&mut buf[..]);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            XmlWriter::new(&mut out).start_el("TooLong").finish();
+        }));
+        assert!(result.is_err());
+    }
+
     #[test]
     fn escape_data() {
         let mut s = String::new();
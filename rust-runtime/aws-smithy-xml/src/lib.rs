@@ -18,6 +18,7 @@
 //! [XML Binding Traits](https://smithy.io/2.0/spec/protocol-traits.html#xml-bindings)
 
 pub mod decode;
+pub mod document;
 pub mod encode;
 mod escape;
 mod unescape;
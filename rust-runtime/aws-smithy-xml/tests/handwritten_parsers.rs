@@ -48,7 +48,7 @@ fn deserialize_xml_attribute(inp: &str) -> Result<XmlAttribute, XmlDecodeError>
     let mut foo: Option<String> = None;
     let mut bar: Option<String> = None;
     foo = root.start_el().attr("foo").map(|attr| attr.to_string());
-    while let Some(mut tag) = root.next_tag() {
+    while let Some(mut tag) = root.next_tag()? {
         if tag.start_el().matches("bar") {
             bar = Some(try_data(&mut tag)?.to_string());
         }
@@ -63,7 +63,7 @@ fn deserialize_flat_xml_map(inp: &str) -> Result<FlatXmlMap, XmlDecodeError> {
     let mut doc = Document::new(inp);
     let mut root = doc.root_element()?;
     let mut my_map: Option<HashMap<String, FooEnum>> = None;
-    while let Some(mut tag) = root.next_tag() {
+    while let Some(mut tag) = root.next_tag()? {
         if tag.start_el().matches("myMap") {
             let mut _my_map = my_map.unwrap_or_default();
             deserialize_foo_enum_map_entry(&mut tag, &mut _my_map)?;
@@ -79,7 +79,7 @@ fn deserialize_xml_map(inp: &str) -> Result<XmlMap, XmlDecodeError> {
     let mut doc = Document::new(inp);
     let mut root = doc.root_element()?;
     let mut my_map: Option<HashMap<String, FooEnum>> = None;
-    while let Some(mut tag) = root.next_tag() {
+    while let Some(mut tag) = root.next_tag()? {
         if tag.start_el().matches("values") {
             my_map = Some(deserialize_foo_enum_map(&mut tag)?);
         }
@@ -93,7 +93,7 @@ fn deserialize_foo_enum_map(
     decoder: &mut ScopedDecoder,
 ) -> Result<HashMap<String, FooEnum>, XmlDecodeError> {
     let mut out: HashMap<String, FooEnum> = HashMap::new();
-    while let Some(mut tag) = decoder.next_tag() {
+    while let Some(mut tag) = decoder.next_tag()? {
         if tag.start_el().matches("entry") {
             deserialize_foo_enum_map_entry(&mut tag, &mut out)?;
         }
@@ -107,7 +107,7 @@ fn deserialize_foo_enum_map_entry(
 ) -> Result<(), XmlDecodeError> {
     let mut k: Option<String> = None;
     let mut v: Option<FooEnum> = None;
-    while let Some(mut tag) = decoder.next_tag() {
+    while let Some(mut tag) = decoder.next_tag()? {
         match tag.start_el() {
             s if s.matches("key") => k = Some(try_data(&mut tag)?.to_string()),
             s if s.matches("value") => v = Some(FooEnum::from(try_data(&mut tag)?.as_ref())),
@@ -152,7 +152,7 @@ pub fn deserialize_nested_string_list(
     decoder: &mut ScopedDecoder,
 ) -> Result<std::vec::Vec<std::vec::Vec<std::string::String>>, XmlDecodeError> {
     let mut out = std::vec::Vec::new();
-    while let Some(mut tag) = decoder.next_tag() {
+    while let Some(mut tag) = decoder.next_tag()? {
         match tag.start_el() {
             s if s.matches("member") => {
                 out.push(deserialize_string_list(&mut tag)?);
@@ -167,7 +167,7 @@ pub fn deserialize_string_list(
     decoder: &mut ScopedDecoder,
 ) -> Result<std::vec::Vec<std::string::String>, XmlDecodeError> {
     let mut out = std::vec::Vec::new();
-    while let Some(mut tag) = decoder.next_tag() {
+    while let Some(mut tag) = decoder.next_tag()? {
         match dbg!(tag.start_el()) {
             s if s.matches("member") => {
                 out.push(dbg!({
@@ -4,14 +4,19 @@
  */
 
 use std::error::Error;
+use std::fmt;
 
 /// Diagnostic collector for endpoint resolution
 ///
 /// Endpoint functions return `Option<T>`—to enable diagnostic information to flow, we capture the
 /// last error that occurred.
+///
+/// When created with [`DiagnosticCollector::with_trace`], the collector additionally records
+/// whether each rule condition matched or failed, in evaluation order.
 #[derive(Debug, Default)]
 pub(crate) struct DiagnosticCollector {
     last_error: Option<Box<dyn Error + Send + Sync>>,
+    trace: Option<EvaluationTrace>,
 }
 
 impl DiagnosticCollector {
@@ -40,8 +45,133 @@ impl DiagnosticCollector {
         self.last_error.take()
     }
 
+    #[allow(unused)]
+    /// Record whether the rule condition at `index` in the condition table matched. This is a
+    /// no-op unless tracing is enabled.
+    pub(crate) fn record_condition(&mut self, index: usize, matched: bool) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.outcomes.push((index, matched));
+        }
+    }
+
+    #[allow(unused)]
+    /// Take the trace of evaluated conditions, if tracing is enabled
+    pub(crate) fn take_trace(&mut self) -> Option<EvaluationTrace> {
+        self.trace.take()
+    }
+
     /// Create a new diagnostic collector
     pub(crate) fn new() -> Self {
-        Self { last_error: None }
+        Self {
+            last_error: None,
+            trace: None,
+        }
+    }
+
+    #[allow(unused)]
+    /// Create a new diagnostic collector that records the outcome of every evaluated condition
+    ///
+    /// Conditions are recorded by their index in `conditions`, which holds every condition of the
+    /// endpoint rules as written in the rule set.
+    pub(crate) fn with_trace(conditions: &'static [&'static str]) -> Self {
+        Self {
+            last_error: None,
+            trace: Some(EvaluationTrace {
+                conditions,
+                outcomes: Vec::new(),
+            }),
+        }
+    }
+}
+
+/// The outcome of evaluating a single rule condition
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ConditionOutcome {
+    condition: &'static str,
+    matched: bool,
+}
+
+impl ConditionOutcome {
+    #[allow(unused)]
+    /// The condition, as written in the endpoint rules
+    pub(crate) fn condition(&self) -> &'static str {
+        self.condition
+    }
+
+    #[allow(unused)]
+    /// Whether the condition matched
+    pub(crate) fn matched(&self) -> bool {
+        self.matched
+    }
+}
+
+/// The conditions evaluated while resolving an endpoint, in evaluation order
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct EvaluationTrace {
+    conditions: &'static [&'static str],
+    outcomes: Vec<(usize, bool)>,
+}
+
+impl EvaluationTrace {
+    #[allow(unused)]
+    /// The evaluated conditions
+    pub(crate) fn conditions(&self) -> impl Iterator<Item = ConditionOutcome> + '_ {
+        self.outcomes
+            .iter()
+            .map(|&(index, matched)| ConditionOutcome {
+                condition: self.conditions.get(index).copied().unwrap_or("<unknown>"),
+                matched,
+            })
+    }
+
+    #[allow(unused)]
+    /// Emit this trace as a `tracing` event
+    pub(crate) fn emit(&self, params: &dyn fmt::Debug) {
+        tracing::debug!(
+            target: "aws_smithy_endpoint::diagnostic",
+            params = ?params,
+            trace = %self,
+            "evaluated endpoint rules"
+        );
+    }
+}
+
+impl fmt::Display for EvaluationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, outcome) in self.conditions().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            let result = if outcome.matched { "matched" } else { "failed" };
+            write!(f, "{result}: {}", outcome.condition)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DiagnosticCollector;
+
+    #[test]
+    fn records_conditions_only_when_tracing() {
+        const CONDITIONS: &[&str] = &["isSet(Region)", "booleanEquals(UseFIPS, true)"];
+
+        let mut collector = DiagnosticCollector::new();
+        collector.record_condition(0, true);
+        assert_eq!(None, collector.take_trace());
+
+        let mut collector = DiagnosticCollector::with_trace(CONDITIONS);
+        collector.record_condition(0, true);
+        collector.record_condition(1, false);
+        let trace = collector.take_trace().expect("tracing was enabled");
+        let conditions: Vec<_> = trace.conditions().collect();
+        assert_eq!(2, conditions.len());
+        assert!(conditions[0].matched());
+        assert_eq!("booleanEquals(UseFIPS, true)", conditions[1].condition());
+        assert_eq!(
+            "matched: isSet(Region); failed: booleanEquals(UseFIPS, true)",
+            trace.to_string()
+        );
     }
 }
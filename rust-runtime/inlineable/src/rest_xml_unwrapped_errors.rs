@@ -30,7 +30,7 @@ pub fn parse_error_metadata(body: &[u8]) -> Result<ErrorMetadataBuilder, XmlDeco
     let mut doc = Document::try_from(body)?;
     let mut root = doc.root_element()?;
     let mut builder = ErrorMetadata::builder();
-    while let Some(mut tag) = root.next_tag() {
+    while let Some(mut tag) = root.next_tag()? {
         match tag.start_el().local() {
             "Code" => {
                 builder = builder.code(try_data(&mut tag)?);
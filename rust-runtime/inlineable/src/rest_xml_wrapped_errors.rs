@@ -18,9 +18,9 @@ pub fn parse_error_metadata(body: &[u8]) -> Result<ErrorMetadataBuilder, XmlDeco
     let mut doc = Document::try_from(body)?;
     let mut root = doc.root_element()?;
     let mut err_builder = ErrorMetadata::builder();
-    while let Some(mut tag) = root.next_tag() {
+    while let Some(mut tag) = root.next_tag()? {
         if tag.start_el().local() == "Error" {
-            while let Some(mut error_field) = tag.next_tag() {
+            while let Some(mut error_field) = tag.next_tag()? {
                 match error_field.start_el().local() {
                     "Code" => {
                         err_builder = err_builder.code(try_data(&mut error_field)?);
@@ -41,13 +41,13 @@ pub fn error_scope<'a, 'b>(
     doc: &'a mut Document<'b>,
 ) -> Result<ScopedDecoder<'b, 'a>, XmlDecodeError> {
     let root = doc
-        .next_start_element()
+        .next_start_element()?
         .ok_or_else(|| XmlDecodeError::custom("no root found searching for an Error"))?;
     if !root.matches("ErrorResponse") {
         return Err(XmlDecodeError::custom("expected ErrorResponse as root"));
     }
 
-    while let Some(el) = doc.next_start_element() {
+    while let Some(el) = doc.next_start_element()? {
         if el.matches("Error") && el.depth() == 1 {
             return Ok(doc.scoped_to(el));
         }
@@ -100,7 +100,7 @@ mod test {
         let mut doc = Document::try_from(xml).expect("valid");
         let mut error = error_scope(&mut doc).expect("contains error");
         let mut keys = vec![];
-        while let Some(tag) = error.next_tag() {
+        while let Some(tag) = error.next_tag().unwrap() {
             keys.push(tag.start_el().local().to_owned());
             // read this the full contents of this element
         }